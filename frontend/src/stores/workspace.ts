@@ -32,14 +32,33 @@ type PoolAccountMinimal = {
   platformFeeClaimed?: boolean
 }
 
-// Minimal type surface for history account
-type HistoryPoint = {
-  timestamp: { toNumber(): number }
-  yesReserve: { toNumber(): number }
-  noReserve: { toNumber(): number }
+// Minimal type surface for history account: a ring buffer of points whose times are offsets
+// from startedAt; `head` is the slot the next point goes into
+type RawHistory = {
+  betId: { toNumber(): number }
+  startedAt: { toNumber(): number }
+  head: number
+  len: number
+  points: Array<{ offsetSecs: number; yesShareBps: number; kind: number }>
 }
+// History points oldest first, as charts read them
+type HistoryPoint = { timestamp: number; yesShareBps: number; kind: number }
 type PoolHistoryMinimal = { betId: { toNumber(): number }; points: HistoryPoint[] }
 
+function unrollHistory(raw: RawHistory): PoolHistoryMinimal {
+  const capacity = raw.points.length
+  const oldest = (raw.head + capacity - raw.len) % capacity
+  const points = Array.from({ length: raw.len }, (_, i) => {
+    const point = raw.points[(oldest + i) % capacity]!
+    return {
+      timestamp: raw.startedAt.toNumber() + point.offsetSecs,
+      yesShareBps: point.yesShareBps,
+      kind: point.kind,
+    }
+  })
+  return { betId: raw.betId, points }
+}
+
 // Minimal main state shape
 export type MainStateMinimal = {
   initialized: boolean
@@ -266,6 +285,15 @@ export const useWorkspaceStore = defineStore(
         programID,
       )
 
+      // TitleIndex PDA: ['title-index', creator, sha256(title)]
+      const titleHash = new Uint8Array(
+        await crypto.subtle.digest('SHA-256', new TextEncoder().encode(title)),
+      )
+      const [titleIndexPda] = PublicKey.findProgramAddressSync(
+        [new TextEncoder().encode('title-index'), wallet.value.publicKey.toBytes(), titleHash],
+        programID,
+      )
+
       const builder = program.value.methods
         .createPool({
           title,
@@ -279,6 +307,7 @@ export const useWorkspaceStore = defineStore(
           mainState: mainStatePda,
           poolState: poolStatePda,
           historyState: historyStatePda,
+          titleIndex: titleIndexPda,
           systemProgram: SystemProgram.programId,
         } as any) // eslint-disable-line @typescript-eslint/no-explicit-any
       const tx = await builder.rpc()
@@ -430,7 +459,7 @@ export const useWorkspaceStore = defineStore(
           if (!accDef) throw new Error('poolHistoryState account not available in IDL')
           return accDef.fetch(historyStatePda)
         })
-        const data = raw as PoolHistoryMinimal
+        const data = unrollHistory(raw as RawHistory)
         historyCache.value.set(betId, { data, fetchedAt: Date.now() })
         return data
       } catch (e) {
@@ -11,4 +11,126 @@ pub const CREATOR_FEE_PERCENT: u64 = 100; // 1% (in basis points: 100/10000 = 0.
 // Updated via Issue #34: Platform fee set to 2% (200 basis points)
 pub const PLATFORM_FEE_PERCENT: u64 = 200; // 2%
 
+// Upper bound for the sum of all fee rates (basis points)
+pub const MAX_TOTAL_FEE_BPS: u64 = 10_000;
+
 pub const VAULT_SEED: &str = "sol-vault";
+// Per-pool vault PDA: [POOL_VAULT_SEED, bet_id]
+pub const POOL_VAULT_SEED: &str = "pool-vault";
+
+// Commit-reveal deposits: how long a user has to reveal a sealed deposit before it can only be refunded
+pub const COMMIT_REVEAL_WINDOW_SECS: i64 = 300; // 5 minutes
+// Share of a sealed deposit, in bps, kept from a user who let its reveal window lapse
+pub const UNREVEALED_FORFEIT_BPS: u64 = 200;
+
+// Annuity-style pools: upper bound on the number of payout installments
+pub const MAX_INSTALLMENTS: u8 = 12;
+
+// Pool text limits, in UTF-8 bytes (see validation.rs)
+pub const MAX_TITLE_LEN: usize = 100;
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+
+// Pool display metadata limits, in UTF-8 bytes
+pub const MAX_UNIT_SYMBOL_LEN: usize = 10;
+pub const MAX_DISPLAY_DECIMALS: u8 = 18;
+pub const MAX_SIDE_LABEL_LEN: usize = 30;
+
+// Pools per set_winner_many call; each one creates an OutcomeOracle account, so keep the
+// batch well inside the compute and account limits of a single transaction
+pub const MAX_BATCH_RESOLUTIONS: usize = 8;
+
+// Deposit odds snapshots hash reserves rounded down to this many lamports, so a quote stays
+// valid while the pool only moves by dust
+pub const STATE_HASH_PRECISION: u64 = 1_000_000;
+
+// Deposit insurance: a losing insured deposit gets this share of its principal back from the
+// pool's premium sub-pool. The premium is the coverage times the implied probability of
+// losing, with a floor so near-certain bets still pay something
+pub const INSURANCE_COVERAGE_BPS: u64 = 5_000; // 50% of principal
+pub const MIN_INSURANCE_PREMIUM_BPS: u64 = 100; // 1% of the deposit
+
+// Minimum time between two publish_odds_feed updates of the same feed
+pub const ODDS_FEED_MIN_INTERVAL_SECS: i64 = 30;
+
+// PoolSummary risk flags: a single deposit at least this share of the pool counts as a
+// whale deposit, and an entry holding this share dominates the pool
+pub const WHALE_DEPOSIT_BPS: u64 = 2_500;
+pub const DOMINANT_HOLDER_BPS: u64 = 5_000;
+
+// Referee incentives: a resolution of a fixed-end pool counts as on time within this long
+// after the end, and every REFEREE_BONUS_STREAK consecutive on-time resolutions earn one
+// bonus from the owner-funded vault
+pub const REFEREE_ON_TIME_SECS: i64 = 24 * 60 * 60;
+pub const REFEREE_BONUS_STREAK: u32 = 10;
+pub const REFEREE_BONUS_VAULT_SEED: &str = "referee-bonus-vault";
+
+// Optimistic-oracle resolution: bond posted by an assertion (and matched by a dispute), and
+// how long an assertion stays open to disputes
+pub const ASSERTION_BOND: u64 = 100_000_000; // 0.1 SOL
+pub const ASSERTION_LIVENESS_SECS: i64 = 2 * 60 * 60;
+
+// SPL outcome tokens: one mint per pool side, with the pool PDA as mint authority
+pub const OUTCOME_MINT_SEED: &str = "outcome-mint";
+
+// Range markets: most value buckets a pool can define
+pub const MAX_RANGE_BUCKETS: usize = 8;
+
+// Fixed-point scale of PoolState's projected_payout_per_token_yes/no
+pub const PAYOUT_PER_TOKEN_SCALE: u64 = 1_000_000_000_000;
+
+// Platform fee treasury swept to the owner's cold wallet by sweep_treasury_if_above
+pub const TREASURY_SEED: &str = "treasury";
+
+// Initial claim window bounds (seconds after resolution), see ClaimWindowBounds
+pub const MIN_CLAIM_WINDOW_SECS: i64 = 24 * 60 * 60;
+pub const DEFAULT_CLAIM_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+pub const MAX_CLAIM_WINDOW_SECS: i64 = 365 * 24 * 60 * 60;
+
+// Longest confirmation window a pool may give its provisional outcome
+pub const MAX_PROVISIONAL_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+
+// Bond a participant posts to dispute a provisional outcome; returned if the owner overturns it
+pub const PROVISIONAL_DISPUTE_BOND: u64 = 100_000_000; // 0.1 SOL
+
+// Multi-outcome markets: outcome count bounds and the longest outcome name
+pub const MIN_OUTCOMES: usize = 2;
+pub const MAX_OUTCOMES: usize = 10;
+pub const MAX_OUTCOME_NAME_LEN: usize = 32;
+
+// An unresolved pool may be voided by anyone this long after its end time (the owner may
+// void it at any time)
+pub const VOID_GRACE_SECS: i64 = 14 * 24 * 60 * 60;
+
+// PoolSummary's rolling volume window: VOLUME_BUCKETS slots of VOLUME_BUCKET_SECS (24h)
+pub const VOLUME_BUCKETS: usize = 24;
+pub const VOLUME_BUCKET_SECS: i64 = 60 * 60;
+
+// Price-feed resolution: resolve_with_oracle only accepts a Pyth price published within this
+// long after the pool's end, so a caller cannot wait for a favourable tick. Past it, the
+// referee resolves the pool as usual.
+pub const ORACLE_RESOLUTION_WINDOW_SECS: i64 = 60;
+
+// Revenue share: the largest cut of platform fees stakers may get and the shortest epoch
+pub const MAX_REVENUE_SHARE_BPS: u64 = 5_000;
+pub const MIN_REVENUE_EPOCH_SECS: i64 = 24 * 60 * 60;
+
+// Creator analytics: reserve thresholds the owner can set for MilestoneReserveEvent, and the
+// unique-bettor counts that emit ParticipantMilestoneEvent
+pub const MAX_RESERVE_MILESTONES: usize = 4;
+pub const PARTICIPANT_MILESTONES: [u32; 2] = [10, 100];
+
+// Referee committees: at most this many members, sized so a pool keeps them inline
+pub const MAX_COMMITTEE_REFEREES: usize = 5;
+
+// Social recovery: how long the user has to cancel a recovery their recovery key started
+// before it may claim the entry's winnings
+pub const RECOVERY_CHALLENGE_SECS: i64 = 7 * 24 * 60 * 60;
+
+// LMSR pricing: the smallest liquidity parameter `b` a pool may use, in lamports, so one
+// deposit can't swing the price from one extreme to the other
+pub const MIN_LMSR_LIQUIDITY: u64 = 10_000_000;
+
+// Pool creation rate limit: CreatorRateLimit counts a creator's pools in CREATION_BUCKETS
+// slots of CREATION_BUCKET_SECS, a rolling 24h window
+pub const CREATION_BUCKETS: usize = 24;
+pub const CREATION_BUCKET_SECS: i64 = 60 * 60;
@@ -32,10 +32,10 @@ pub enum BettingError {
     #[msg("Wrong bet")]
     WrongBet,
 
-    #[msg("Title is too long (max 100 characters)")]
+    #[msg("Title is too long (max 100 bytes of UTF-8)")]
     TitleTooLong,
 
-    #[msg("Description is too long (max 500 characters)")]
+    #[msg("Description is too long (max 500 bytes of UTF-8)")]
     DescriptionTooLong,
 
     #[msg("Title cannot be empty")]
@@ -46,4 +46,523 @@ pub enum BettingError {
 
     #[msg("Math overflow")]
     MathOverflow,
+
+    #[msg("Commit-reveal is not enabled for this pool")]
+    CommitRevealDisabled,
+
+    #[msg("This pool only accepts commit-reveal deposits")]
+    CommitRevealRequired,
+
+    #[msg("Revealed side and salt do not match the commitment")]
+    InvalidReveal,
+
+    #[msg("Reveal window has closed")]
+    RevealWindowClosed,
+
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+
+    #[msg("Invalid installment configuration")]
+    InvalidInstallmentConfig,
+
+    #[msg("Next installment is not due yet")]
+    InstallmentNotDue,
+
+    #[msg("Too many default referees")]
+    TooManyReferees,
+
+    #[msg("Provide exactly one of referee or referee_index")]
+    InvalidRefereeSelection,
+
+    #[msg("Referee index is out of range")]
+    InvalidRefereeIndex,
+
+    #[msg("Default referee list account is required")]
+    DefaultRefereesMissing,
+
+    #[msg("Unit symbol is too long (max 10 bytes of UTF-8)")]
+    UnitSymbolTooLong,
+
+    #[msg("Display decimals are out of range (max 18)")]
+    InvalidDecimals,
+
+    #[msg("Side label is too long (max 30 bytes of UTF-8)")]
+    SideLabelTooLong,
+
+    #[msg("Fee configuration is invalid")]
+    InvalidFee,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Deposit would exceed the pool's liability cap")]
+    LiabilityCapExceeded,
+
+    #[msg("History is disabled for this pool; omit the history account")]
+    HistoryDisabled,
+
+    #[msg("History account is required for this pool")]
+    HistoryAccountRequired,
+
+    #[msg("Client nonce must be non-zero")]
+    InvalidClientNonce,
+
+    #[msg("Deposit with this client nonce was already processed")]
+    DuplicateDeposit,
+
+    #[msg("Too many translations (max 3)")]
+    TooManyTranslations,
+
+    #[msg("Language tag must be 1-8 bytes")]
+    InvalidLanguageTag,
+
+    #[msg("Linked pool has already been opened")]
+    LinkAlreadyOpened,
+
+    #[msg("Parent pool did not resolve to the linked outcome")]
+    LinkOutcomeMismatch,
+
+    #[msg("Invalid referee/refund deadlines")]
+    InvalidResolutionDeadlines,
+
+    #[msg("Only the referee can resolve before the referee deadline")]
+    RefereeWindowActive,
+
+    #[msg("Resolution window has closed; the pool can only be refunded")]
+    ResolutionWindowClosed,
+
+    #[msg("Refund deadline has not been reached")]
+    RefundDeadlineNotReached,
+
+    #[msg("Pool is in refund mode")]
+    PoolInRefundMode,
+
+    #[msg("Pool is not in refund mode")]
+    NotInRefundMode,
+
+    #[msg("Invalid batch: check the resolution count and remaining accounts")]
+    InvalidBatch,
+
+    #[msg("Pool odds changed since the quoted snapshot")]
+    StateHashMismatch,
+
+    #[msg("Pool already keeps its funds in its own vault")]
+    VaultMigrated,
+
+    #[msg("Pool cannot be migrated in its current state")]
+    VaultMigrationBlocked,
+
+    #[msg("Claim permit signature is missing or does not match")]
+    InvalidPermit,
+
+    #[msg("Claim permit has expired")]
+    PermitExpired,
+
+    #[msg("Creator donation must be at most 10000 bps")]
+    InvalidCreatorDonation,
+
+    #[msg("Entry has no insurance to claim")]
+    NotInsured,
+
+    #[msg("Entry has reached the pool's deposit limit")]
+    TooManyDeposits,
+
+    #[msg("Odds feed was updated too recently")]
+    OddsFeedRateLimited,
+
+    #[msg("Entry is on the losing side")]
+    NotWinnerSide,
+
+    #[msg("Market has not been resolved yet")]
+    MarketNotResolved,
+
+    #[msg("Market end time has not been reached")]
+    EndTimeNotReached,
+
+    #[msg("Entry was already claimed (claim time is in the log)")]
+    AlreadyClaimedAt,
+
+    #[msg("Referee has no unclaimed bonus")]
+    NoRefereeBonus,
+
+    #[msg("Referee bonus vault cannot cover this bonus")]
+    InsufficientRefereeBonusFunds,
+
+    #[msg("Creator already has an active pool with this title")]
+    DuplicateMarket,
+
+    #[msg("Title index account is missing or does not match the pool")]
+    TitleIndexMismatch,
+
+    #[msg("Fee curve must fall from small to large pools")]
+    InvalidFeeCurve,
+
+    #[msg("Pool does not accept optimistic assertions")]
+    OptimisticResolutionDisabled,
+
+    #[msg("Assertion is still open to disputes")]
+    AssertionLivenessActive,
+
+    #[msg("Assertion can no longer be disputed")]
+    AssertionExpired,
+
+    #[msg("Assertion is already disputed")]
+    AssertionAlreadyDisputed,
+
+    #[msg("Disputed assertion waits for the referee or council to resolve the pool")]
+    AssertionAwaitingResolution,
+
+    #[msg("Disputer account is missing or does not match the dispute")]
+    InvalidDisputer,
+
+    #[msg("Pool does not issue outcome tokens")]
+    OutcomeTokensDisabled,
+
+    #[msg("Insured or partly paid entries cannot be tokenized")]
+    EntryNotTokenizable,
+
+    #[msg("Outcome token pools pay out in a single installment")]
+    OutcomeTokensRequireSinglePayout,
+
+    #[msg("Range buckets must be sorted, non-overlapping and cover both outcomes")]
+    InvalidRangeBuckets,
+
+    #[msg("Range markets resolve by value through set_winner_by_value")]
+    RangeValueRequired,
+
+    #[msg("Pool is not a range market")]
+    NotRangeMarket,
+
+    #[msg("Value falls outside every bucket of the range market")]
+    ValueOutsideRange,
+
+    #[msg("Referee has not accepted the role for this pool yet")]
+    RefereeNotAccepted,
+
+    #[msg("Signer is not the pool's pending referee")]
+    NotPendingReferee,
+
+    #[msg("Treasury sweeping is not configured")]
+    TreasurySweepDisabled,
+
+    #[msg("Treasury balance does not exceed the sweep threshold")]
+    TreasuryBelowThreshold,
+
+    #[msg("Pool settlement figures no longer match its settlement report")]
+    SettlementReportMismatch,
+
+    #[msg("Notification endpoint URI is too long")]
+    NotificationUriTooLong,
+
+    #[msg("Pool subscriber list is full")]
+    SubscriberListFull,
+
+    #[msg("User is already subscribed to this pool")]
+    AlreadySubscribed,
+
+    #[msg("User is not subscribed to this pool")]
+    NotSubscribed,
+
+    #[msg("Open-ended pools never lock")]
+    PoolNeverLocks,
+
+    #[msg("Subscribers were already notified that the pool locked")]
+    LockAlreadyNotified,
+
+    #[msg("Bet balance cannot cover this amount")]
+    InsufficientBetBalance,
+
+    #[msg("Claim window is outside the allowed bounds")]
+    InvalidClaimWindow,
+
+    #[msg("Pool resolves in two phases through set_provisional_winner")]
+    ProvisionalResolutionRequired,
+
+    #[msg("Pool does not use two-phase resolution")]
+    NotProvisionalPool,
+
+    #[msg("Pool is locked behind a pending provisional outcome")]
+    ProvisionalOutcomePending,
+
+    #[msg("Pool has no provisional outcome")]
+    NoProvisionalOutcome,
+
+    #[msg("Provisional outcome is still within its confirmation window")]
+    ConfirmationWindowActive,
+
+    #[msg("Provisional outcome is past its confirmation window")]
+    ConfirmationWindowClosed,
+
+    #[msg("Provisional outcome was disputed; the owner resolves the pool")]
+    ProvisionalOutcomeDisputed,
+
+    #[msg("Invalid provisional confirmation window")]
+    InvalidProvisionalWindow,
+
+    #[msg("Optimistic and two-phase resolution cannot be combined")]
+    ConflictingResolutionModes,
+
+    #[msg("Only participants of the pool may do this")]
+    NotPoolParticipant,
+
+    #[msg("Outcome markets need between 2 and 10 outcomes")]
+    InvalidOutcomeCount,
+
+    #[msg("Outcome name is empty or too long")]
+    InvalidOutcomeName,
+
+    #[msg("Outcome index is out of range")]
+    InvalidOutcomeIndex,
+
+    #[msg("Start feed does not match the pool's")]
+    StartFeedMismatch,
+
+    #[msg("Event has not started yet")]
+    EventNotStarted,
+
+    #[msg("Pool is no longer trading")]
+    PoolAlreadyLocked,
+
+    #[msg("Pool was locked at event start; its end time is fixed")]
+    PoolLockedOnStart,
+
+    #[msg("Event start cannot be negative")]
+    InvalidEventStart,
+
+    #[msg("Pool is denominated in an SPL token; use the *_spl instructions")]
+    SplPoolRequiresTokenPath,
+
+    #[msg("Pool is denominated in SOL")]
+    NotSplPool,
+
+    #[msg("Option is not supported for SPL-denominated pools")]
+    UnsupportedForSplPool,
+
+    #[msg("Only the owner may void this pool yet")]
+    VoidGraceNotElapsed,
+
+    #[msg("Pool is not platform guaranteed")]
+    PoolNotGuaranteed,
+
+    #[msg("Guarantee stays escrowed until the claim window closes")]
+    GuaranteeLocked,
+
+    #[msg("Price condition needs a feed, a known comparison and a fixed end time")]
+    InvalidPriceCondition,
+
+    #[msg("Pool does not resolve from a price feed")]
+    OracleResolutionDisabled,
+
+    #[msg("Price feed does not match the pool's")]
+    PriceFeedMismatch,
+
+    #[msg("Account is not a trading Pyth price feed")]
+    InvalidPriceFeed,
+
+    #[msg("Price feed exponent does not match the pool's condition")]
+    PriceExponentMismatch,
+
+    #[msg("Price was not published within the resolution window after the pool's end")]
+    PriceOutsideResolutionWindow,
+
+    #[msg("Owner must rule on the disputed provisional outcome")]
+    DisputeVerdictRequired,
+
+    #[msg("Unrevealed commitments are still within their reveal window")]
+    CommitmentsPending,
+
+    #[msg("Entry still has a payout to claim")]
+    EntryNotSettled,
+
+    #[msg("Pool still has open entries, unclaimed fees, history or a guarantee")]
+    PoolNotSettled,
+
+    #[msg("Revenue share needs a cut of at most MAX_REVENUE_SHARE_BPS and a long enough epoch")]
+    InvalidRevenueShare,
+
+    #[msg("Revenue share needs platform fees routed through the treasury")]
+    RevenueShareRequiresTreasury,
+
+    #[msg("Stake mint does not match the revenue share's")]
+    StakeMintMismatch,
+
+    #[msg("Claim every closed revenue epoch before changing stake")]
+    RevenueClaimsPending,
+
+    #[msg("No closed revenue epoch left to claim")]
+    NoRevenueToClaim,
+
+    #[msg("Revenue epoch has not ended yet")]
+    RevenueEpochNotOver,
+
+    #[msg("Stake amount must be positive and at most the staked balance")]
+    InvalidStakeAmount,
+
+    #[msg("Pool already uses the current layout")]
+    PoolAlreadyMigrated,
+
+    #[msg("Account is not a pool in the legacy layout")]
+    InvalidLegacyPool,
+
+    #[msg("Pool refereed by its creator would exceed the self-referee reserve cap")]
+    SelfRefereeCapExceeded,
+
+    #[msg("Deposit would mint fewer tokens than the minimum accepted")]
+    SlippageExceeded,
+
+    #[msg("Pool holds no seed liquidity the creator can reclaim")]
+    NoSeedToReclaim,
+
+    #[msg("Invalid meta-market condition")]
+    InvalidMetaCondition,
+
+    #[msg("Pool is not a meta-market")]
+    NotMetaMarket,
+
+    #[msg("Meta-market target is already resolved or refunded")]
+    MetaTargetSettled,
+
+    #[msg("Meta-market target has not been resolved or refunded yet")]
+    MetaTargetStillTrading,
+
+    #[msg("Scalar bounds must have min below max")]
+    InvalidScalarBounds,
+
+    #[msg("Not available for scalar markets")]
+    UnsupportedForScalarPool,
+
+    #[msg("Pool was already resolved")]
+    AlreadyResolved,
+
+    #[msg("The pool's claim deadline has passed")]
+    ClaimWindowClosed,
+
+    #[msg("The pool's claim deadline has not passed yet")]
+    ClaimWindowOpen,
+
+    #[msg("Settlement destinations do not add up to the pool's reserves")]
+    SettlementNotConserved,
+
+    #[msg("Invalid referrer for this entry")]
+    InvalidReferrer,
+
+    #[msg("No referral fees to claim")]
+    NoReferralFee,
+
+    #[msg("Opening time must be in the future and before the pool's end")]
+    InvalidOpenTime,
+
+    #[msg("The pool does not take deposits before its opening time")]
+    PoolNotOpen,
+
+    #[msg("Queued deposits must execute before new deposits")]
+    WaitlistPending,
+
+    #[msg("Deposits can only be queued before the pool opens")]
+    WaitlistClosed,
+
+    #[msg("Queued deposits execute in queue order")]
+    WaitlistOutOfOrder,
+
+    #[msg("Invalid referee committee or threshold")]
+    InvalidRefereeCommittee,
+
+    #[msg("Pools with a referee committee resolve through propose_winner")]
+    CommitteeResolutionRequired,
+
+    #[msg("The pool has no referee committee")]
+    NotCommitteePool,
+
+    #[msg("Signer is not on the pool's referee committee")]
+    NotCommitteeMember,
+
+    #[msg("Committee member already voted")]
+    AlreadyVoted,
+
+    #[msg("Resolution accounts must be passed exactly with the vote that reaches the threshold")]
+    ResolutionAccountsMismatch,
+
+    #[msg("Signer is not the entry's recovery key")]
+    NotRecoveryKey,
+
+    #[msg("No recovery is pending for this entry")]
+    NoRecoveryPending,
+
+    #[msg("A recovery is already pending for this entry")]
+    RecoveryPending,
+
+    #[msg("The recovery's challenge window has not passed yet")]
+    RecoveryChallengeActive,
+
+    #[msg("Invalid recovery key")]
+    InvalidRecoveryKey,
+
+    #[msg("History account already uses the ring buffer layout")]
+    HistoryAlreadyMigrated,
+
+    #[msg("Account is not a legacy history account")]
+    InvalidLegacyHistory,
+
+    #[msg("Event data does not decode as the event its discriminator names")]
+    InvalidReplayEvent,
+
+    #[msg("Replayed event belongs to another pool")]
+    ReplayWrongPool,
+
+    #[msg("Replayed event cannot follow the events before it")]
+    ReplayOutOfOrder,
+
+    #[msg("Replayed state does not match the pool")]
+    ReplayMismatch,
+
+    #[msg("Creator has reached the daily pool creation limit")]
+    CreationRateLimited,
+
+    #[msg("The pool's final odds are already recorded")]
+    FinalOddsRecorded,
+
+    #[msg("Pool is already in the referee's queue")]
+    AlreadyQueued,
+
+    #[msg("Referee queue is full")]
+    RefereeQueueFull,
+
+    #[msg("Pool is not in the referee's queue")]
+    NotQueued,
+
+    #[msg("Pool is still pending the queued referee's resolution")]
+    ResolutionPending,
+
+    #[msg("Token-gated pool requires the user's token account for the gate mint")]
+    GateTokenRequired,
+
+    #[msg("Gate token balance is below the pool's minimum")]
+    GateBalanceTooLow,
+
+    #[msg("Gate minimum amount requires a gate mint and must be positive")]
+    InvalidGate,
+
+    #[msg("Deposit would take the pool's reserves over its cap")]
+    PoolCapExceeded,
+
+    #[msg("Deposit would take the user's deposits in this pool over their cap")]
+    UserCapExceeded,
+
+    #[msg("Exposure caps were set by the owner and can't be changed by the creator")]
+    ExposureCapsSetByOwner,
+
+    #[msg("LMSR pools need a liquidity parameter of at least the minimum; other pools take none")]
+    InvalidLmsrLiquidity,
+
+    #[msg("Entries can only be transferred to another wallet holding the same side and referrer")]
+    InvalidEntryTransfer,
+
+    #[msg("Optimistic resolution, start feeds and price conditions need the oracle feature")]
+    OracleFeatureDisabled,
+
+    #[msg("Pool summary account is required for this pool")]
+    PoolSummaryRequired,
+
+    #[msg("Settlement report account is required for this pool")]
+    SettlementReportRequired,
 }
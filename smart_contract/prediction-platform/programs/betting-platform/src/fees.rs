@@ -0,0 +1,200 @@
+//! Fee math shared by the resolution and claim paths.
+
+use crate::{
+    constants::{INSURANCE_COVERAGE_BPS, MIN_INSURANCE_PREMIUM_BPS, PAYOUT_PER_TOKEN_SCALE},
+    implied_prices, EntryState, MainState, PoolState, PRICE_SCALE,
+};
+use anchor_lang::prelude::Pubkey;
+
+// Basis-point share of `amount` (10_000 bps = 100%)
+pub fn bps_of(amount: u128, bps: u64) -> u128 {
+    amount.saturating_mul(bps as u128) / 10_000u128
+}
+
+// Platform fee rate in effect for a pool holding `total_reserve`: the configured fee curve,
+// or the flat rate while no curve is set. Builds without the `platform-fees` feature (friend
+// mode) never charge a platform fee, whatever MainState holds.
+pub fn platform_fee_bps(main_state: &MainState, total_reserve: u64) -> u64 {
+    #[cfg(feature = "platform-fees")]
+    {
+        let curve = &main_state.platform_fee_curve;
+        if curve.is_enabled() {
+            curve.bps_for(total_reserve)
+        } else {
+            main_state.platform_fee_percent
+        }
+    }
+    #[cfg(not(feature = "platform-fees"))]
+    {
+        let _ = (main_state, total_reserve);
+        0
+    }
+}
+
+// Fixes the pool's fees at resolution and returns the platform fee to transfer. The platform
+// fee is charged on total reserves to keep fee impact symmetric across both sides. Winners
+// are always repaid their principal, so fees can only ever come out of the losing reserve:
+// both fees are clamped to it, creator fee first, or the shortfall would be paid out of
+// other pools' funds in the shared vault.
+pub fn settle_resolution_fees(pool_state: &mut PoolState, winner_is_yes: bool, bps: u64) -> u64 {
+    let fees = resolution_fees(pool_state, winner_is_yes, bps);
+    pool_state.creator_fee_accrued = fees.creator_fee;
+    pool_state.creator_fee_donated = fees.creator_fee_donated;
+    pool_state.platform_fee_amount = fees.platform_fee;
+    fees.platform_fee
+}
+
+// Fees a resolution with `winner_is_yes` would fix right now (see settle_resolution_fees)
+pub struct ResolutionFees {
+    pub creator_fee: u64, // still owed to the creator, after the donation
+    pub creator_fee_donated: u64,
+    pub platform_fee: u64,
+}
+
+pub fn resolution_fees(pool_state: &PoolState, winner_is_yes: bool, bps: u64) -> ResolutionFees {
+    let losing_reserve = if winner_is_yes {
+        pool_state.no_reserve
+    } else {
+        pool_state.yes_reserve
+    };
+    let creator_fee = pool_state.creator_fee_accrued.min(losing_reserve);
+
+    // The creator's seed liquidity is not charged: it backs the market rather than betting on it
+    let total_reserve = (pool_state.yes_reserve as u128)
+        .saturating_add(pool_state.no_reserve as u128)
+        .saturating_sub(pool_state.seed_yes_liquidity as u128)
+        .saturating_sub(pool_state.seed_no_liquidity as u128);
+    let platform_fee = bps_of(total_reserve, bps)
+        .min(losing_reserve.saturating_sub(creator_fee) as u128)
+        as u64;
+
+    // The donated part of the creator fee simply stops being reserved for the creator, which
+    // leaves it in the winners' profit pot; the platform fee above is unaffected
+    let donation = bps_of(creator_fee as u128, pool_state.creator_donation_bps) as u64;
+
+    ResolutionFees {
+        creator_fee: creator_fee - donation,
+        creator_fee_donated: donation,
+        platform_fee,
+    }
+}
+
+// Recomputes the pool's payout preview. A winning entry's projected payout is its principal
+// plus token_balance * projected_payout_per_token / PAYOUT_PER_TOKEN_SCALE, matching
+// settle_claim.
+pub fn refresh_payout_projection(pool_state: &mut PoolState, platform_bps: u64) {
+    pool_state.projected_payout_per_token_yes =
+        projected_payout_per_token(pool_state, true, platform_bps);
+    pool_state.projected_payout_per_token_no =
+        projected_payout_per_token(pool_state, false, platform_bps);
+}
+
+// Profit each winning token would earn if the side won now, with today's fees, scaled by
+// PAYOUT_PER_TOKEN_SCALE. Winners split the losing reserve after fees (winner_profit_pot).
+pub fn projected_payout_per_token(pool_state: &PoolState, winner_is_yes: bool, bps: u64) -> u64 {
+    let (losing_reserve, winning_supply) = if winner_is_yes {
+        (pool_state.no_reserve, pool_state.yes_supply)
+    } else {
+        (pool_state.yes_reserve, pool_state.no_supply)
+    };
+    if winning_supply == 0 {
+        return 0;
+    }
+    let fees = resolution_fees(pool_state, winner_is_yes, bps);
+    let profit = losing_reserve
+        .saturating_sub(fees.creator_fee)
+        .saturating_sub(fees.platform_fee) as u128;
+    (profit.saturating_mul(PAYOUT_PER_TOKEN_SCALE as u128) / winning_supply as u128)
+        .min(u64::MAX as u128) as u64
+}
+
+// Insurance premium for insuring a deposit of `amount`, priced against the same (yes, no)
+// reserves as the deposit itself
+pub fn insurance_premium(amount: u64, is_yes: bool, pricing_reserves: (u64, u64)) -> u64 {
+    let (yes_price, no_price) = implied_prices(pricing_reserves.0, pricing_reserves.1);
+    let losing_price = if is_yes { no_price } else { yes_price };
+    let premium_bps = ((INSURANCE_COVERAGE_BPS as u128) * losing_price / PRICE_SCALE) as u64;
+    bps_of(amount as u128, premium_bps.max(MIN_INSURANCE_PREMIUM_BPS)) as u64
+}
+
+// Coverage owed to every insured deposit on the losing side
+fn insurance_liability(pool_state: &PoolState, winner_is_yes: bool) -> u128 {
+    let insured_losing = if winner_is_yes {
+        pool_state.insured_no
+    } else {
+        pool_state.insured_yes
+    };
+    bps_of(insured_losing as u128, INSURANCE_COVERAGE_BPS)
+}
+
+// Insurance owed to a losing entry: its full coverage when the premiums cover every losing
+// insured deposit, otherwise a pro-rata share of the premium sub-pool, so the sub-pool can
+// never be overdrawn and claim order never matters
+pub fn insurance_payout(pool_state: &PoolState, winner_is_yes: bool, insured_amount: u64) -> u64 {
+    let coverage = bps_of(insured_amount as u128, INSURANCE_COVERAGE_BPS);
+    let liability = insurance_liability(pool_state, winner_is_yes);
+    let premiums = pool_state.insurance_premiums as u128;
+    if liability <= premiums {
+        coverage as u64
+    } else {
+        (coverage * premiums / liability) as u64
+    }
+}
+
+// Premiums left once every losing insured deposit is covered; paid to the platform, which
+// underwrites the insurance, at resolution
+pub fn insurance_surplus(pool_state: &PoolState, winner_is_yes: bool) -> u64 {
+    (pool_state.insurance_premiums as u128)
+        .saturating_sub(insurance_liability(pool_state, winner_is_yes)) as u64
+}
+
+// Part of the platform fee held back at resolution for the referrers of winning entries:
+// referral_fee_percent of the fee, pro rata to the winning side's referred deposits within
+// the fee base (seed liquidity excluded, as in resolution_fees)
+pub fn referral_fee_escrow(pool_state: &PoolState, winner_is_yes: bool, referral_bps: u64) -> u64 {
+    let referred = if winner_is_yes {
+        pool_state.referred_yes
+    } else {
+        pool_state.referred_no
+    } as u128;
+    let fee_base = (pool_state.yes_reserve as u128)
+        .saturating_add(pool_state.no_reserve as u128)
+        .saturating_sub(pool_state.seed_yes_liquidity as u128)
+        .saturating_sub(pool_state.seed_no_liquidity as u128);
+    if fee_base == 0 {
+        return 0;
+    }
+    let referral_fees = bps_of(pool_state.platform_fee_amount as u128, referral_bps);
+    (referral_fees.saturating_mul(referred) / fee_base).min(referral_fees) as u64
+}
+
+// The referee's cut of a just-resolved pool's platform fee: referee_fee_bps of it, out of what
+// the referrers' escrow left
+pub fn referee_fee(pool_state: &PoolState) -> u64 {
+    let platform_fee = pool_state
+        .platform_fee_amount
+        .saturating_sub(pool_state.referral_fee_escrow);
+    (bps_of(pool_state.platform_fee_amount as u128, pool_state.referee_fee_bps) as u64)
+        .min(platform_fee)
+}
+
+// An entry's referrer's slice of the escrow, pro rata to the entry's deposits among the
+// winning side's referred ones; nothing once credited or for entries nobody referred
+pub fn referral_slice(pool_state: &PoolState, entry_state: &EntryState) -> u64 {
+    let winner_is_yes = pool_state.winner.is_yes();
+    let referred = if winner_is_yes {
+        pool_state.referred_yes
+    } else {
+        pool_state.referred_no
+    };
+    if entry_state.referrer == Pubkey::default()
+        || entry_state.referral_credited
+        || entry_state.is_yes != winner_is_yes
+        || referred == 0
+    {
+        return 0;
+    }
+    ((pool_state.referral_fee_escrow as u128)
+        .saturating_mul(entry_state.deposited_sol_amount as u128)
+        / referred as u128) as u64
+}
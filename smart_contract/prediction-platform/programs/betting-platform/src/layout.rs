@@ -0,0 +1,47 @@
+//! Room to grow. MainState, PoolState and EntryState end in a zeroed `reserved` block: a new
+//! field goes right before it and the block's length shrinks by the field's size, so account
+//! lengths stay put and accounts created before the upgrade read the new field as zero.
+//! Their lengths are derived from the fields (InitSpace) and tests::layout pins them, so a
+//! field that is not carved out of the reserve fails there instead of on chain. Once a reserve runs out, grow_account resizes accounts in place.
+
+use anchor_lang::prelude::*;
+
+// Zeroed bytes set aside for fields added by later upgrades
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reserved<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Space for Reserved<N> {
+    const INIT_SPACE: usize = N;
+}
+
+impl<const N: usize> Default for Reserved<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+// Grows `account` to `new_len` bytes, zero-filled, with `payer` topping its rent up
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<u64> {
+    let rent_top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(rent_top_up)
+}
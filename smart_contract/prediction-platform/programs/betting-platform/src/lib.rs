@@ -11,6 +11,22 @@ pub mod pool;
 
 pub mod constants;
 pub mod error;
+pub mod fees;
+pub mod layout;
+pub mod permit;
+pub mod pricing;
+#[cfg(feature = "oracle")]
+pub mod pyth;
+pub mod replay;
+pub mod resolution;
+pub mod schema;
+pub mod share_id;
+pub mod time;
+pub mod validation;
+pub mod vault;
+
+#[cfg(test)]
+mod tests;
 
 use main_state::*;
 use pool::*;
@@ -32,6 +48,66 @@ pub mod betting_program {
         main_state::update_main_state(ctx, input)
     }
 
+    pub fn set_admins(ctx: Context<ASetAdmins>, input: SetAdminsInput) -> Result<()> {
+        main_state::set_admins(ctx, input)
+    }
+
+    pub fn set_fees(ctx: Context<ASetFees>, input: SetFeesInput) -> Result<()> {
+        main_state::set_fees(ctx, input)
+    }
+
+    pub fn set_paused(ctx: Context<ASetPaused>, input: SetPausedInput) -> Result<()> {
+        main_state::set_paused(ctx, input)
+    }
+
+    #[cfg(feature = "referee-registry")]
+    pub fn set_default_referees(
+        ctx: Context<ASetDefaultReferees>,
+        input: SetDefaultRefereesInput,
+    ) -> Result<()> {
+        main_state::set_default_referees(ctx, input)
+    }
+
+    #[cfg(feature = "test-utils")]
+    pub fn set_mock_clock(ctx: Context<ASetMockClock>, input: SetMockClockInput) -> Result<()> {
+        main_state::set_mock_clock(ctx, input)
+    }
+
+    #[cfg(feature = "referee-registry")]
+    pub fn fund_referee_bonus(
+        ctx: Context<AFundRefereeBonus>,
+        input: FundRefereeBonusInput,
+    ) -> Result<()> {
+        main_state::fund_referee_bonus(ctx, input)
+    }
+
+    #[cfg(feature = "referee-registry")]
+    pub fn claim_referee_bonus(ctx: Context<AClaimRefereeBonus>) -> Result<()> {
+        main_state::claim_referee_bonus(ctx)
+    }
+
+    pub fn set_treasury_sweep(
+        ctx: Context<ASetTreasurySweep>,
+        input: SetTreasurySweepInput,
+    ) -> Result<()> {
+        main_state::set_treasury_sweep(ctx, input)
+    }
+
+    pub fn sweep_treasury_if_above(ctx: Context<ASweepTreasuryIfAbove>) -> Result<()> {
+        main_state::sweep_treasury_if_above(ctx)
+    }
+
+    pub fn set_notification_prefs(
+        ctx: Context<ASetNotificationPrefs>,
+        input: SetNotificationPrefsInput,
+    ) -> Result<()> {
+        main_state::set_notification_prefs(ctx, input)
+    }
+
+    pub fn close_notification_prefs(ctx: Context<ACloseNotificationPrefs>) -> Result<()> {
+        main_state::close_notification_prefs(ctx)
+    }
+
     pub fn create_pool(ctx: Context<ACreatePool>, input: CreatePoolInput) -> Result<()> {
         pool::create_pool(ctx, input)
     }
@@ -40,6 +116,13 @@ pub mod betting_program {
         pool::update_pool(ctx, input)
     }
 
+    pub fn set_pool_metadata(
+        ctx: Context<ASetPoolMetadata>,
+        input: SetPoolMetadataInput,
+    ) -> Result<()> {
+        pool::set_pool_metadata(ctx, input)
+    }
+
     pub fn create_entry(ctx: Context<ACreateEntry>, input: CreateEntryInput) -> Result<()> {
         pool::create_entry(ctx, input)
     }
@@ -52,11 +135,504 @@ pub mod betting_program {
         pool::set_winner(ctx, input)
     }
 
+    pub fn set_winner_by_value(
+        ctx: Context<ASetWinnerByValue>,
+        input: SetWinnerByValueInput,
+    ) -> Result<()> {
+        pool::set_winner_by_value(ctx, input)
+    }
+
+    pub fn set_winner_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ASetWinnerMany<'info>>,
+        input: SetWinnerManyInput,
+    ) -> Result<()> {
+        pool::set_winner_many(ctx, input)
+    }
+
     pub fn claim(ctx: Context<AClaim>, input: ClaimInput) -> Result<()> {
         pool::claim(ctx, input)
     }
 
+    pub fn emergency_claim(
+        ctx: Context<AEmergencyClaim>,
+        input: EmergencyClaimInput,
+    ) -> Result<()> {
+        pool::emergency_claim(ctx, input)
+    }
+
+    pub fn claim_with_permit(
+        ctx: Context<AClaimWithPermit>,
+        input: ClaimWithPermitInput,
+    ) -> Result<()> {
+        pool::claim_with_permit(ctx, input)
+    }
+
     pub fn claim_creator_fee(ctx: Context<AClaimCreatorFee>, input: ClaimCreatorFeeInput) -> Result<()> {
         pool::claim_creator_fee(ctx, input)
     }
+
+    pub fn claim_referee_fee(ctx: Context<AClaimRefereeFee>, input: ClaimRefereeFeeInput) -> Result<()> {
+        pool::claim_referee_fee(ctx, input)
+    }
+
+    pub fn commit_deposit(ctx: Context<ACommitDeposit>, input: CommitDepositInput) -> Result<()> {
+        pool::commit_deposit(ctx, input)
+    }
+
+    pub fn reveal_deposit(ctx: Context<ARevealDeposit>, input: RevealDepositInput) -> Result<()> {
+        pool::reveal_deposit(ctx, input)
+    }
+
+    pub fn refund_commitment(
+        ctx: Context<ARefundCommitment>,
+        input: RefundCommitmentInput,
+    ) -> Result<()> {
+        pool::refund_commitment(ctx, input)
+    }
+
+    pub fn refresh_share_uuid(
+        ctx: Context<ARefreshShareUuid>,
+        input: RefreshShareUuidInput,
+    ) -> Result<()> {
+        pool::refresh_share_uuid(ctx, input)
+    }
+
+    pub fn set_liability_cap(
+        ctx: Context<ASetLiabilityCap>,
+        input: SetLiabilityCapInput,
+    ) -> Result<()> {
+        pool::set_liability_cap(ctx, input)
+    }
+
+    pub fn link_pools(ctx: Context<ALinkPools>, input: LinkPoolsInput) -> Result<()> {
+        pool::link_pools(ctx, input)
+    }
+
+    pub fn open_linked_pool(
+        ctx: Context<AOpenLinkedPool>,
+        input: OpenLinkedPoolInput,
+    ) -> Result<()> {
+        pool::open_linked_pool(ctx, input)
+    }
+
+    pub fn trigger_refund_mode(
+        ctx: Context<ATriggerRefundMode>,
+        input: TriggerRefundModeInput,
+    ) -> Result<()> {
+        pool::trigger_refund_mode(ctx, input)
+    }
+
+    pub fn claim_refund(ctx: Context<AClaimRefund>, input: ClaimRefundInput) -> Result<()> {
+        pool::claim_refund(ctx, input)
+    }
+
+    pub fn migrate_vault(ctx: Context<AMigrateVault>, input: MigrateVaultInput) -> Result<()> {
+        pool::migrate_vault(ctx, input)
+    }
+
+    pub fn claim_insurance(ctx: Context<AClaimInsurance>, input: ClaimInsuranceInput) -> Result<()> {
+        pool::claim_insurance(ctx, input)
+    }
+
+    pub fn check_claim(ctx: Context<ACheckClaim>, input: CheckClaimInput) -> Result<ClaimCheck> {
+        pool::check_claim(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn publish_odds_feed(
+        ctx: Context<APublishOddsFeed>,
+        input: PublishOddsFeedInput,
+    ) -> Result<()> {
+        pool::publish_odds_feed(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn assert_outcome(ctx: Context<AAssertOutcome>, input: AssertOutcomeInput) -> Result<()> {
+        pool::assert_outcome(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn dispute_assertion(
+        ctx: Context<ADisputeAssertion>,
+        input: DisputeAssertionInput,
+    ) -> Result<()> {
+        pool::dispute_assertion(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn settle_assertion(
+        ctx: Context<ASettleAssertion>,
+        input: SettleAssertionInput,
+    ) -> Result<()> {
+        pool::settle_assertion(ctx, input)
+    }
+
+    pub fn close_history(ctx: Context<ACloseHistory>, input: CloseHistoryInput) -> Result<()> {
+        pool::close_history(ctx, input)
+    }
+
+    pub fn tokenize_entry(ctx: Context<ATokenizeEntry>, input: TokenizeEntryInput) -> Result<()> {
+        pool::tokenize_entry(ctx, input)
+    }
+
+    pub fn claim_with_tokens(
+        ctx: Context<AClaimWithTokens>,
+        input: ClaimWithTokensInput,
+    ) -> Result<()> {
+        pool::claim_with_tokens(ctx, input)
+    }
+
+    pub fn accept_referee_role(
+        ctx: Context<AAcceptRefereeRole>,
+        input: AcceptRefereeRoleInput,
+    ) -> Result<()> {
+        pool::accept_referee_role(ctx, input)
+    }
+
+    pub fn subscribe_pool(ctx: Context<ASubscribePool>, input: SubscribePoolInput) -> Result<()> {
+        pool::subscribe_pool(ctx, input)
+    }
+
+    pub fn unsubscribe_pool(
+        ctx: Context<AUnsubscribePool>,
+        input: SubscribePoolInput,
+    ) -> Result<()> {
+        pool::unsubscribe_pool(ctx, input)
+    }
+
+    pub fn notify_pool_locked(
+        ctx: Context<ANotifyPoolLocked>,
+        input: NotifyPoolLockedInput,
+    ) -> Result<()> {
+        pool::notify_pool_locked(ctx, input)
+    }
+
+    pub fn fund_bet_balance(ctx: Context<AFundBetBalance>, input: BetBalanceInput) -> Result<()> {
+        pool::fund_bet_balance(ctx, input)
+    }
+
+    pub fn withdraw_bet_balance(
+        ctx: Context<AWithdrawBetBalance>,
+        input: BetBalanceInput,
+    ) -> Result<()> {
+        pool::withdraw_bet_balance(ctx, input)
+    }
+
+    pub fn deposit_from_balance(
+        ctx: Context<ADepositFromBalance>,
+        input: DepositInput,
+    ) -> Result<()> {
+        pool::deposit_from_balance(ctx, input)
+    }
+
+    pub fn set_provisional_winner(
+        ctx: Context<ASetProvisionalWinner>,
+        input: SetProvisionalWinnerInput,
+    ) -> Result<()> {
+        pool::set_provisional_winner(ctx, input)
+    }
+
+    pub fn dispute_provisional_winner(
+        ctx: Context<ADisputeProvisionalWinner>,
+        input: DisputeProvisionalWinnerInput,
+    ) -> Result<()> {
+        pool::dispute_provisional_winner(ctx, input)
+    }
+
+    pub fn finalize_resolution(
+        ctx: Context<AFinalizeResolution>,
+        input: FinalizeResolutionInput,
+    ) -> Result<()> {
+        pool::finalize_resolution(ctx, input)
+    }
+
+    pub fn get_claimable_total<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AGetClaimableTotal<'info>>,
+    ) -> Result<u64> {
+        pool::get_claimable_total(ctx)
+    }
+
+    pub fn create_outcome_market(
+        ctx: Context<ACreateOutcomeMarket>,
+        input: CreateOutcomeMarketInput,
+    ) -> Result<()> {
+        pool::create_outcome_market(ctx, input)
+    }
+
+    pub fn deposit_outcome(
+        ctx: Context<ADepositOutcome>,
+        input: DepositOutcomeInput,
+    ) -> Result<()> {
+        pool::deposit_outcome(ctx, input)
+    }
+
+    pub fn set_outcome_winner(
+        ctx: Context<ASetOutcomeWinner>,
+        input: SetOutcomeWinnerInput,
+    ) -> Result<()> {
+        pool::set_outcome_winner(ctx, input)
+    }
+
+    pub fn claim_outcome(ctx: Context<AClaimOutcome>, input: ClaimOutcomeInput) -> Result<()> {
+        pool::claim_outcome(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn publish_event_start(
+        ctx: Context<APublishEventStart>,
+        input: PublishEventStartInput,
+    ) -> Result<()> {
+        pool::publish_event_start(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn crank_lock_on_start(
+        ctx: Context<ACrankLockOnStart>,
+        input: CrankLockOnStartInput,
+    ) -> Result<()> {
+        pool::crank_lock_on_start(ctx, input)
+    }
+
+    pub fn create_pool_spl(ctx: Context<ACreatePoolSpl>, input: CreatePoolInput) -> Result<()> {
+        pool::create_pool_spl(ctx, input)
+    }
+
+    pub fn deposit_spl(ctx: Context<ADepositSpl>, input: DepositInput) -> Result<()> {
+        pool::deposit_spl(ctx, input)
+    }
+
+    pub fn claim_spl(ctx: Context<AClaimSpl>, input: ClaimSplInput) -> Result<()> {
+        pool::claim_spl(ctx, input)
+    }
+
+    pub fn claim_creator_fee_spl(
+        ctx: Context<AClaimFeeSpl>,
+        input: ClaimFeeSplInput,
+    ) -> Result<()> {
+        pool::claim_creator_fee_spl(ctx, input)
+    }
+
+    pub fn claim_platform_fee_spl(
+        ctx: Context<AClaimFeeSpl>,
+        input: ClaimFeeSplInput,
+    ) -> Result<()> {
+        pool::claim_platform_fee_spl(ctx, input)
+    }
+
+    pub fn void_pool(ctx: Context<AVoidPool>, input: VoidPoolInput) -> Result<()> {
+        pool::void_pool(ctx, input)
+    }
+
+    pub fn guarantee_pool(ctx: Context<AGuaranteePool>, input: GuaranteePoolInput) -> Result<()> {
+        pool::guarantee_pool(ctx, input)
+    }
+
+    pub fn release_guarantee(
+        ctx: Context<AReleaseGuarantee>,
+        input: ReleaseGuaranteeInput,
+    ) -> Result<()> {
+        pool::release_guarantee(ctx, input)
+    }
+
+    #[cfg(feature = "oracle")]
+    pub fn resolve_with_oracle(
+        ctx: Context<AResolveWithOracle>,
+        input: ResolveWithOracleInput,
+    ) -> Result<()> {
+        pool::resolve_with_oracle(ctx, input)
+    }
+
+    pub fn refund_unrevealed(
+        ctx: Context<ARefundUnrevealed>,
+        input: RefundUnrevealedInput,
+    ) -> Result<()> {
+        pool::refund_unrevealed(ctx, input)
+    }
+
+    pub fn close_entry(ctx: Context<ACloseEntry>, input: CloseEntryInput) -> Result<()> {
+        pool::close_entry(ctx, input)
+    }
+
+    pub fn close_pool(ctx: Context<AClosePool>, input: ClosePoolInput) -> Result<()> {
+        pool::close_pool(ctx, input)
+    }
+
+    pub fn set_revenue_share(
+        ctx: Context<ASetRevenueShare>,
+        input: SetRevenueShareInput,
+    ) -> Result<()> {
+        main_state::set_revenue_share(ctx, input)
+    }
+
+    pub fn stake_platform_token(
+        ctx: Context<AStakePlatformToken>,
+        input: StakePlatformTokenInput,
+    ) -> Result<()> {
+        main_state::stake_platform_token(ctx, input)
+    }
+
+    pub fn unstake_platform_token(
+        ctx: Context<AUnstakePlatformToken>,
+        input: UnstakePlatformTokenInput,
+    ) -> Result<()> {
+        main_state::unstake_platform_token(ctx, input)
+    }
+
+    pub fn checkpoint_epoch(ctx: Context<ACheckpointEpoch>) -> Result<()> {
+        main_state::checkpoint_epoch(ctx)
+    }
+
+    pub fn claim_revenue_share(ctx: Context<AClaimRevenueShare>) -> Result<()> {
+        main_state::claim_revenue_share(ctx)
+    }
+
+    pub fn migrate_pool_winner(
+        ctx: Context<AMigratePoolWinner>,
+        input: MigratePoolWinnerInput,
+    ) -> Result<()> {
+        pool::migrate_pool_winner(ctx, input)
+    }
+
+    pub fn get_vault_health(
+        ctx: Context<AGetVaultHealth>,
+        input: GetVaultHealthInput,
+    ) -> Result<VaultHealth> {
+        pool::get_vault_health(ctx, input)
+    }
+
+    pub fn reclaim_seed_liquidity(
+        ctx: Context<AReclaimSeedLiquidity>,
+        input: ReclaimSeedLiquidityInput,
+    ) -> Result<()> {
+        pool::reclaim_seed_liquidity(ctx, input)
+    }
+
+    pub fn create_meta_pool(
+        ctx: Context<ACreateMetaPool>,
+        input: CreateMetaPoolInput,
+    ) -> Result<()> {
+        pool::create_meta_pool(ctx, input)
+    }
+
+    pub fn resolve_meta_market(
+        ctx: Context<AResolveMetaMarket>,
+        input: ResolveMetaMarketInput,
+    ) -> Result<()> {
+        pool::resolve_meta_market(ctx, input)
+    }
+
+    pub fn get_schema_version(ctx: Context<AGetSchemaVersion>) -> Result<SchemaVersion> {
+        main_state::get_schema_version(ctx)
+    }
+
+    pub fn sweep_unclaimed(
+        ctx: Context<ASweepUnclaimed>,
+        input: SweepUnclaimedInput,
+    ) -> Result<()> {
+        pool::sweep_unclaimed(ctx, input)
+    }
+
+    pub fn register_referrer(ctx: Context<ARegisterReferrer>) -> Result<()> {
+        main_state::register_referrer(ctx)
+    }
+
+    pub fn claim_referral_fee(ctx: Context<AClaimReferralFee>) -> Result<()> {
+        main_state::claim_referral_fee(ctx)
+    }
+
+    pub fn queue_deposit(ctx: Context<AQueueDeposit>, input: QueueDepositInput) -> Result<()> {
+        pool::queue_deposit(ctx, input)
+    }
+
+    pub fn execute_pending_deposit(
+        ctx: Context<AExecutePendingDeposit>,
+        input: ExecutePendingDepositInput,
+    ) -> Result<()> {
+        pool::execute_pending_deposit(ctx, input)
+    }
+
+    pub fn propose_winner(ctx: Context<AProposeWinner>, input: ProposeWinnerInput) -> Result<()> {
+        pool::propose_winner(ctx, input)
+    }
+
+    pub fn set_recovery_key(
+        ctx: Context<ASetRecoveryKey>,
+        input: SetRecoveryKeyInput,
+    ) -> Result<()> {
+        pool::set_recovery_key(ctx, input)
+    }
+
+    pub fn initiate_recovery(
+        ctx: Context<AInitiateRecovery>,
+        input: InitiateRecoveryInput,
+    ) -> Result<()> {
+        pool::initiate_recovery(ctx, input)
+    }
+
+    pub fn cancel_recovery(
+        ctx: Context<ACancelRecovery>,
+        input: CancelRecoveryInput,
+    ) -> Result<()> {
+        pool::cancel_recovery(ctx, input)
+    }
+
+    pub fn recover_claim(ctx: Context<ARecoverClaim>, input: RecoverClaimInput) -> Result<()> {
+        pool::recover_claim(ctx, input)
+    }
+
+    pub fn migrate_history(
+        ctx: Context<AMigrateHistory>,
+        input: MigrateHistoryInput,
+    ) -> Result<()> {
+        pool::migrate_history(ctx, input)
+    }
+
+    pub fn verify_replay(
+        ctx: Context<AVerifyReplay>,
+        input: VerifyReplayInput,
+    ) -> Result<[u8; 32]> {
+        pool::verify_replay(ctx, input)
+    }
+
+    pub fn quote_deposit(
+        ctx: Context<AQuoteDeposit>,
+        input: QuoteDepositInput,
+    ) -> Result<DepositQuote> {
+        pool::quote_deposit(ctx, input)
+    }
+
+    pub fn record_final_odds(
+        ctx: Context<ARecordFinalOdds>,
+        input: RecordFinalOddsInput,
+    ) -> Result<()> {
+        pool::record_final_odds(ctx, input)
+    }
+
+    pub fn enqueue_pending_resolution(
+        ctx: Context<AEnqueuePendingResolution>,
+        input: RefereeQueueInput,
+    ) -> Result<()> {
+        pool::enqueue_pending_resolution(ctx, input)
+    }
+
+    pub fn prune_referee_queue(
+        ctx: Context<APruneRefereeQueue>,
+        input: RefereeQueueInput,
+    ) -> Result<()> {
+        pool::prune_referee_queue(ctx, input)
+    }
+
+    pub fn transfer_entry(
+        ctx: Context<ATransferEntry>,
+        input: TransferEntryInput,
+    ) -> Result<()> {
+        pool::transfer_entry(ctx, input)
+    }
+
+    pub fn settle_creator_bond(
+        ctx: Context<ASettleCreatorBond>,
+        input: SettleCreatorBondInput,
+    ) -> Result<()> {
+        pool::settle_creator_bond(ctx, input)
+    }
 }
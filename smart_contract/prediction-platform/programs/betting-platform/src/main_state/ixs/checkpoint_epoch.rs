@@ -0,0 +1,107 @@
+use crate::{
+    constants::TREASURY_SEED, error::BettingError, time, MainState, RevenueEpoch,
+    RevenueEpochClosedEvent, RevenueShare,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+
+// Permissionless: closes the open revenue epoch once it has run its length. The fees it
+// accrued move from the treasury to the revenue share pool and are recorded against the
+// stake that earned through the epoch; stake added during it starts earning now. With
+// nothing staked, the revenue stays pending for the next epoch.
+pub fn checkpoint_epoch(ctx: Context<ACheckpointEpoch>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let now = time::now(main_state)?;
+    require!(
+        now >= revenue_share.epoch_ends_at(),
+        BettingError::RevenueEpochNotOver
+    );
+
+    let revenue = if revenue_share.total_staked > 0 {
+        main_state.revenue_share_pending
+    } else {
+        0
+    };
+    if revenue > 0 {
+        let treasury = &ctx.accounts.treasury;
+        invoke_signed(
+            &system_instruction::transfer(treasury.key, &revenue_share.key(), revenue),
+            &[
+                treasury.to_account_info(),
+                revenue_share.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[TREASURY_SEED.as_bytes(), &[ctx.bumps.treasury]]],
+        )?;
+        main_state.revenue_share_pending = 0;
+    }
+
+    let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+    revenue_epoch.epoch = revenue_share.epoch;
+    revenue_epoch.revenue = revenue;
+    revenue_epoch.total_staked = revenue_share.total_staked;
+    revenue_epoch.claimed = 0;
+    revenue_epoch.closed_at = now;
+
+    revenue_share.total_distributed = revenue_share
+        .total_distributed
+        .checked_add(revenue)
+        .ok_or(BettingError::MathOverflow)?;
+    revenue_share.total_staked = revenue_share
+        .total_staked
+        .checked_add(revenue_share.activating_stake)
+        .ok_or(BettingError::MathOverflow)?;
+    revenue_share.activating_stake = 0;
+    revenue_share.epoch += 1;
+    revenue_share.epoch_started_at = now;
+
+    emit!(RevenueEpochClosedEvent {
+        epoch: revenue_epoch.epoch,
+        revenue,
+        total_staked: revenue_epoch.total_staked,
+        caller: ctx.accounts.caller.key(),
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ACheckpointEpoch<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [RevenueShare::PREFIX_SEED],
+        bump
+    )]
+    pub revenue_share: Box<Account<'info, RevenueShare>>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + RevenueEpoch::MAX_SIZE,
+        seeds = [RevenueEpoch::PREFIX_SEED, &revenue_share.epoch.to_le_bytes()],
+        bump
+    )]
+    pub revenue_epoch: Box<Account<'info, RevenueEpoch>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA treasury only signs to transfer lamports
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
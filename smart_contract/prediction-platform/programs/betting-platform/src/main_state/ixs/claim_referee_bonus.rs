@@ -0,0 +1,93 @@
+use crate::{
+    constants::REFEREE_BONUS_VAULT_SEED, error::BettingError, MainState, RefereeBonusClaimedEvent,
+    RefereeProfile,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+
+// Pays the referee the current bonus rate for every streak completed since the last claim
+pub fn claim_referee_bonus(ctx: Context<AClaimRefereeBonus>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let referee_profile = &mut ctx.accounts.referee_profile;
+
+    let streaks = referee_profile.unclaimed_bonus_streaks();
+    let amount = (streaks as u64)
+        .checked_mul(main_state.referee_bonus_per_streak)
+        .ok_or(BettingError::MathOverflow)?;
+    require!(amount > 0, BettingError::NoRefereeBonus);
+
+    // The vault keeps its rent-exempt minimum
+    let bonus_vault = &ctx.accounts.bonus_vault;
+    let available = bonus_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(
+        amount <= available,
+        BettingError::InsufficientRefereeBonusFunds
+    );
+
+    invoke_signed(
+        &system_instruction::transfer(bonus_vault.key, ctx.accounts.referee.key, amount),
+        &[
+            bonus_vault.to_account_info(),
+            ctx.accounts.referee.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[
+            REFEREE_BONUS_VAULT_SEED.as_bytes(),
+            &[ctx.bumps.bonus_vault],
+        ]],
+    )?;
+
+    referee_profile.bonus_streaks_paid = referee_profile.bonus_streaks_earned;
+    referee_profile.bonus_paid = referee_profile
+        .bonus_paid
+        .checked_add(amount)
+        .ok_or(BettingError::MathOverflow)?;
+    main_state.referee_bonus_paid = main_state
+        .referee_bonus_paid
+        .checked_add(amount)
+        .ok_or(BettingError::MathOverflow)?;
+
+    emit!(RefereeBonusClaimedEvent {
+        referee: referee_profile.referee,
+        streaks,
+        amount,
+        current_streak: referee_profile.current_streak,
+        on_time_resolutions: referee_profile.on_time_resolutions,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClaimRefereeBonus<'info> {
+    #[account(mut)]
+    pub referee: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [RefereeProfile::PREFIX_SEED, referee.key().as_ref()],
+        bump,
+        has_one = referee,
+    )]
+    pub referee_profile: Box<Account<'info, RefereeProfile>>,
+
+    #[account(
+        mut,
+        seeds = [REFEREE_BONUS_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub bonus_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,37 @@
+use crate::{error::BettingError, ReferralFeeClaimedEvent, ReferralState};
+use anchor_lang::prelude::*;
+
+// Pays the referrer everything their ReferralState was credited since their last claim; the
+// account keeps its rent
+pub fn claim_referral_fee(ctx: Context<AClaimReferralFee>) -> Result<()> {
+    let referral_state = &mut ctx.accounts.referral_state;
+    let amount = referral_state.unclaimed();
+    require!(amount > 0, BettingError::NoReferralFee);
+
+    referral_state.claimed = referral_state.accrued;
+    referral_state.sub_lamports(amount)?;
+    ctx.accounts.referrer.add_lamports(amount)?;
+
+    emit!(ReferralFeeClaimedEvent {
+        referrer: referral_state.referrer,
+        amount,
+        total_claimed: referral_state.claimed,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClaimReferralFee<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, referrer.key().as_ref()],
+        bump,
+        has_one = referrer,
+    )]
+    pub referral_state: Account<'info, ReferralState>,
+}
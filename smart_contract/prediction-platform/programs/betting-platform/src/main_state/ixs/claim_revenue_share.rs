@@ -0,0 +1,61 @@
+use crate::{error::BettingError, RevenueEpoch, RevenueShare, RevenueShareClaimedEvent, StakeState};
+use anchor_lang::prelude::*;
+
+// Pays the user's pro-rata share of their next unclaimed closed epoch. Epochs are claimed in
+// order, one per call, so the stake each is split across is known exactly.
+pub fn claim_revenue_share(ctx: Context<AClaimRevenueShare>) -> Result<()> {
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let stake_state = &mut ctx.accounts.stake_state;
+    let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+    require!(
+        !stake_state.is_caught_up(revenue_share),
+        BettingError::NoRevenueToClaim
+    );
+
+    stake_state.activate(revenue_epoch.epoch);
+    let amount = revenue_epoch.share_of(stake_state.amount);
+    if amount > 0 {
+        revenue_share.sub_lamports(amount)?;
+        ctx.accounts.user.add_lamports(amount)?;
+    }
+    revenue_epoch.claimed = revenue_epoch.claimed.saturating_add(amount);
+    revenue_share.total_claimed = revenue_share.total_claimed.saturating_add(amount);
+    stake_state.total_claimed = stake_state.total_claimed.saturating_add(amount);
+    stake_state.next_claim_epoch += 1;
+
+    emit!(RevenueShareClaimedEvent {
+        user: stake_state.user,
+        epoch: revenue_epoch.epoch,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClaimRevenueShare<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RevenueShare::PREFIX_SEED],
+        bump
+    )]
+    pub revenue_share: Box<Account<'info, RevenueShare>>,
+
+    #[account(
+        mut,
+        seeds = [StakeState::PREFIX_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub stake_state: Box<Account<'info, StakeState>>,
+
+    #[account(
+        mut,
+        seeds = [RevenueEpoch::PREFIX_SEED, &stake_state.next_claim_epoch.to_le_bytes()],
+        bump
+    )]
+    pub revenue_epoch: Box<Account<'info, RevenueEpoch>>,
+}
@@ -0,0 +1,74 @@
+use crate::{constants::REFEREE_BONUS_VAULT_SEED, error::BettingError, MainState};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct FundRefereeBonusInput {
+    pub amount: u64,
+    pub bonus_per_streak: Option<u64>,
+}
+
+// Tops up the referee bonus vault and optionally changes the payout per completed streak.
+// The first deposit also covers the vault's rent exemption.
+pub fn fund_referee_bonus(
+    ctx: Context<AFundRefereeBonus>,
+    input: FundRefereeBonusInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(main_state.initialized.eq(&true), BettingError::Uninitialized);
+
+    if let Some(bonus_per_streak) = input.bonus_per_streak {
+        main_state.referee_bonus_per_streak = bonus_per_streak;
+    }
+
+    let bonus_vault = &ctx.accounts.bonus_vault;
+    let rent_top_up = if bonus_vault.lamports() == 0 {
+        Rent::get()?.minimum_balance(0)
+    } else {
+        0
+    };
+    let transfer = input
+        .amount
+        .checked_add(rent_top_up)
+        .ok_or(BettingError::MathOverflow)?;
+    if transfer > 0 {
+        invoke(
+            &system_instruction::transfer(ctx.accounts.owner.key, bonus_vault.key, transfer),
+            &[
+                ctx.accounts.owner.to_account_info(),
+                bonus_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    main_state.referee_bonus_funded = main_state
+        .referee_bonus_funded
+        .checked_add(input.amount)
+        .ok_or(BettingError::MathOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AFundRefereeBonus<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [REFEREE_BONUS_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only holds lamports
+    pub bonus_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
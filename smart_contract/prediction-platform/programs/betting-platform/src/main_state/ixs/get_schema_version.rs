@@ -0,0 +1,31 @@
+use crate::schema::{self, SCHEMA_VERSION};
+use crate::MainState;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchemaVersion {
+    pub version: u32,
+    pub main_state_len: u32,
+    pub pool_state_len: u32,
+    pub entry_state_len: u32,
+}
+
+// Layout version of the deployed program for indexers (call it with `.view()`): a version
+// other than the one an indexer was built against means its offsets may be stale
+pub fn get_schema_version(_ctx: Context<AGetSchemaVersion>) -> Result<SchemaVersion> {
+    Ok(SchemaVersion {
+        version: SCHEMA_VERSION,
+        main_state_len: schema::main_state::LEN as u32,
+        pool_state_len: schema::pool_state::LEN as u32,
+        entry_state_len: schema::entry_state::LEN as u32,
+    })
+}
+
+#[derive(Accounts)]
+pub struct AGetSchemaVersion<'info> {
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
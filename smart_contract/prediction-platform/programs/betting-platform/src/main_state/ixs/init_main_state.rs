@@ -1,10 +1,14 @@
 use crate::{
-    constants::{CREATOR_FEE_PERCENT, INITIAL_PRICE, SCALE_FACTOR, VAULT_SEED, PLATFORM_FEE_PERCENT},
+    constants::{
+        CREATOR_FEE_PERCENT, DEFAULT_CLAIM_WINDOW_SECS, INITIAL_PRICE, MAX_CLAIM_WINDOW_SECS,
+        MAX_RESERVE_MILESTONES, MIN_CLAIM_WINDOW_SECS, PLATFORM_FEE_PERCENT, SCALE_FACTOR,
+        VAULT_SEED,
+    },
     error::BettingError,
-    MainState,
+    ClaimWindowBounds, FeeCurve, MainState,
 };
-use anchor_lang::{prelude::*, solana_program};
 use anchor_lang::solana_program::sysvar::rent::Rent;
+use anchor_lang::{prelude::*, solana_program};
 
 pub fn init_main_state(ctx: Context<AInitMainState>) -> Result<()> {
     let state = &mut ctx.accounts.main_state;
@@ -20,6 +24,36 @@ pub fn init_main_state(ctx: Context<AInitMainState>) -> Result<()> {
     state.current_bet_id = 0;
     state.creator_fee_percent = CREATOR_FEE_PERCENT;
     state.platform_fee_percent = PLATFORM_FEE_PERCENT;
+    state.fee_admin = Pubkey::default();
+    state.ops_admin = Pubkey::default();
+    state.paused = false;
+    state.resolved_pools = 0;
+    state.total_market_duration_secs = 0;
+    state.avg_market_duration_secs = 0;
+    state.timed_resolutions = 0;
+    state.total_resolution_latency_secs = 0;
+    state.avg_resolution_latency_secs = 0;
+    state.clock_offset_secs = 0;
+    state.referee_bonus_per_streak = 0;
+    state.referee_bonus_funded = 0;
+    state.referee_bonus_paid = 0;
+    state.platform_fee_curve = FeeCurve::default();
+    state.treasury = Pubkey::default();
+    state.treasury_cold_wallet = Pubkey::default();
+    state.treasury_sweep_threshold = 0;
+    state.treasury_swept = 0;
+    state.claim_window = ClaimWindowBounds {
+        min_secs: MIN_CLAIM_WINDOW_SECS,
+        default_secs: DEFAULT_CLAIM_WINDOW_SECS,
+        max_secs: MAX_CLAIM_WINDOW_SECS,
+    };
+    state.revenue_share_bps = 0;
+    state.revenue_share_pending = 0;
+    state.self_referee_reserve_cap = 0;
+    state.reserve_milestones = [0; MAX_RESERVE_MILESTONES];
+    state.max_pools_per_day = 0;
+    state.referee_fee_percent = 0;
+    state.creator_bond_lamports = 0;
 
     let ix = solana_program::system_instruction::transfer(
         ctx.accounts.owner.to_account_info().key,
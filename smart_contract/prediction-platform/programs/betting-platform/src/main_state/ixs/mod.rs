@@ -1,5 +1,67 @@
-pub mod init_main_state;
-pub use init_main_state::*;
-
-pub mod update_main_state;
-pub use update_main_state::*;
+pub mod init_main_state;
+pub use init_main_state::*;
+
+pub mod update_main_state;
+pub use update_main_state::*;
+
+pub mod set_admins;
+pub use set_admins::*;
+
+pub mod set_fees;
+pub use set_fees::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+#[cfg(feature = "referee-registry")]
+pub mod set_default_referees;
+#[cfg(feature = "referee-registry")]
+pub use set_default_referees::*;
+
+// Always compiled: IDL generation resolves instruction types even for cfg'd-out instructions.
+// The handler itself only exists with `test-utils`.
+pub mod set_mock_clock;
+pub use set_mock_clock::*;
+
+#[cfg(feature = "referee-registry")]
+pub mod fund_referee_bonus;
+#[cfg(feature = "referee-registry")]
+pub use fund_referee_bonus::*;
+
+#[cfg(feature = "referee-registry")]
+pub mod claim_referee_bonus;
+#[cfg(feature = "referee-registry")]
+pub use claim_referee_bonus::*;
+
+pub mod set_treasury_sweep;
+pub use set_treasury_sweep::*;
+
+pub mod sweep_treasury_if_above;
+pub use sweep_treasury_if_above::*;
+
+pub mod set_notification_prefs;
+pub use set_notification_prefs::*;
+
+pub mod set_revenue_share;
+pub use set_revenue_share::*;
+
+pub mod stake_platform_token;
+pub use stake_platform_token::*;
+
+pub mod unstake_platform_token;
+pub use unstake_platform_token::*;
+
+pub mod checkpoint_epoch;
+pub use checkpoint_epoch::*;
+
+pub mod claim_revenue_share;
+pub use claim_revenue_share::*;
+
+pub mod get_schema_version;
+pub use get_schema_version::*;
+
+pub mod register_referrer;
+pub use register_referrer::*;
+
+pub mod claim_referral_fee;
+pub use claim_referral_fee::*;
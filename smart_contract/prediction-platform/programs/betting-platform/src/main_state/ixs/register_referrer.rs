@@ -0,0 +1,26 @@
+use crate::ReferralState;
+use anchor_lang::prelude::*;
+
+// Opens the caller's ReferralState so claims of the entries they refer can credit it. Deposits
+// may name a referrer before they register; those slices are credited once they have.
+pub fn register_referrer(ctx: Context<ARegisterReferrer>) -> Result<()> {
+    ctx.accounts.referral_state.referrer = ctx.accounts.referrer.key();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ARegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferralState::MAX_SIZE,
+        seeds = [ReferralState::PREFIX_SEED, referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_state: Account<'info, ReferralState>,
+
+    pub system_program: Program<'info, System>,
+}
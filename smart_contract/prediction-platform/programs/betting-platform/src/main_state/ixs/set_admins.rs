@@ -0,0 +1,32 @@
+use crate::{error::BettingError, MainState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct SetAdminsInput {
+    pub fee_admin: Pubkey, // Pubkey::default() revokes the role
+    pub ops_admin: Pubkey,
+}
+
+pub fn set_admins(ctx: Context<ASetAdmins>, input: SetAdminsInput) -> Result<()> {
+    let state = &mut ctx.accounts.main_state;
+    require!(state.initialized.eq(&true), BettingError::Uninitialized);
+
+    state.fee_admin = input.fee_admin;
+    state.ops_admin = input.ops_admin;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetAdmins<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        has_one = owner,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
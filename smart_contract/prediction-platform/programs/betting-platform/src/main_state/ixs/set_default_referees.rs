@@ -0,0 +1,49 @@
+use crate::{error::BettingError, DefaultReferees, MainState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone)]
+pub struct SetDefaultRefereesInput {
+    pub referees: Vec<Pubkey>,
+}
+
+pub fn set_default_referees(
+    ctx: Context<ASetDefaultReferees>,
+    input: SetDefaultRefereesInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(main_state.initialized.eq(&true), BettingError::Uninitialized);
+    require!(
+        input.referees.len() <= DefaultReferees::MAX_REFEREES,
+        BettingError::TooManyReferees
+    );
+
+    // The whole list is replaced so existing indices may shift; creators resolve the
+    // index at creation time and pools store the referee pubkey itself.
+    ctx.accounts.default_referees.referees = input.referees;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetDefaultReferees<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        has_one = owner,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + DefaultReferees::MAX_SIZE,
+        seeds = [DefaultReferees::PREFIX_SEED],
+        bump
+    )]
+    pub default_referees: Account<'info, DefaultReferees>,
+
+    pub system_program: Program<'info, System>,
+}
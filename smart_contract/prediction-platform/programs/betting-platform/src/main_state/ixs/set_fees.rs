@@ -0,0 +1,71 @@
+use crate::{constants::MAX_TOTAL_FEE_BPS, error::BettingError, FeeCurve, MainState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct SetFeesInput {
+    pub creator_fee_percent: u64,
+    pub platform_fee_percent: u64,
+    pub platform_fee_curve: Option<FeeCurve>, // None keeps the current curve
+    pub referral_fee_percent: Option<u64>,    // bps of the platform fee; None keeps the current
+    pub referee_fee_percent: Option<u64>,     // bps of the platform fee; None keeps the current
+}
+
+// Fee admin (or owner) may change fee rates, nothing else
+pub fn set_fees(ctx: Context<ASetFees>, input: SetFeesInput) -> Result<()> {
+    let state = &mut ctx.accounts.main_state;
+    require!(state.initialized.eq(&true), BettingError::Uninitialized);
+    require!(
+        state.is_fee_admin(ctx.accounts.admin.key),
+        BettingError::Unauthorized
+    );
+    require!(
+        input
+            .creator_fee_percent
+            .saturating_add(input.platform_fee_percent)
+            <= MAX_TOTAL_FEE_BPS,
+        BettingError::InvalidFee
+    );
+
+    let curve = input.platform_fee_curve.unwrap_or(state.platform_fee_curve);
+    require!(curve.is_valid(), BettingError::InvalidFeeCurve);
+    // The curve's highest rate (tiny pools) must fit the same budget as the flat rate
+    require!(
+        input
+            .creator_fee_percent
+            .saturating_add(curve.small_pool_bps)
+            <= MAX_TOTAL_FEE_BPS,
+        BettingError::InvalidFee
+    );
+
+    let referral_fee_percent = input
+        .referral_fee_percent
+        .unwrap_or(state.referral_fee_percent);
+    let referee_fee_percent = input
+        .referee_fee_percent
+        .unwrap_or(state.referee_fee_percent);
+    // Both cuts come out of the same platform fee
+    require!(
+        referral_fee_percent.saturating_add(referee_fee_percent) <= 10_000,
+        BettingError::InvalidFee
+    );
+
+    state.creator_fee_percent = input.creator_fee_percent;
+    state.platform_fee_percent = input.platform_fee_percent;
+    state.platform_fee_curve = curve;
+    state.referral_fee_percent = referral_fee_percent;
+    state.referee_fee_percent = referee_fee_percent;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
@@ -0,0 +1,27 @@
+use crate::{error::BettingError, MainState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct SetMockClockInput {
+    pub offset_secs: i64,
+}
+
+// Test-only time travel: every time check sees the cluster clock plus `offset_secs`
+#[cfg(feature = "test-utils")]
+pub fn set_mock_clock(ctx: Context<ASetMockClock>, input: SetMockClockInput) -> Result<()> {
+    ctx.accounts.main_state.clock_offset_secs = input.offset_secs;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetMockClock<'info> {
+    #[account(address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
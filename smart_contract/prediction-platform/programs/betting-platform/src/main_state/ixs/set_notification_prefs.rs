@@ -0,0 +1,68 @@
+use crate::{error::BettingError, NotificationPrefs};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct SetNotificationPrefsInput {
+    pub endpoint_hash: [u8; 32],
+    pub endpoint_uri: String,
+    pub notify_on_lock: bool,
+    pub notify_on_resolution: bool,
+}
+
+// Creates or replaces the signer's notification pointer
+pub fn set_notification_prefs(
+    ctx: Context<ASetNotificationPrefs>,
+    input: SetNotificationPrefsInput,
+) -> Result<()> {
+    require!(
+        input.endpoint_uri.len() <= NotificationPrefs::MAX_URI_LEN,
+        BettingError::NotificationUriTooLong
+    );
+
+    let prefs = &mut ctx.accounts.notification_prefs;
+    prefs.user = ctx.accounts.user.key();
+    prefs.endpoint_hash = input.endpoint_hash;
+    prefs.endpoint_uri = input.endpoint_uri;
+    prefs.notify_on_lock = input.notify_on_lock;
+    prefs.notify_on_resolution = input.notify_on_resolution;
+    prefs.updated_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+// Opting out closes the account and refunds its rent
+pub fn close_notification_prefs(_ctx: Context<ACloseNotificationPrefs>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetNotificationPrefs<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + NotificationPrefs::MAX_SIZE,
+        seeds = [NotificationPrefs::PREFIX_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub notification_prefs: Box<Account<'info, NotificationPrefs>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ACloseNotificationPrefs<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [NotificationPrefs::PREFIX_SEED, user.key().as_ref()],
+        bump,
+        has_one = user,
+    )]
+    pub notification_prefs: Box<Account<'info, NotificationPrefs>>,
+}
@@ -0,0 +1,34 @@
+use crate::{error::BettingError, MainState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct SetPausedInput {
+    pub paused: bool,
+}
+
+// Ops admin (or owner) may halt pool creation, entries and deposits during an incident.
+// Resolution and claims stay available so funds are never locked by a pause.
+pub fn set_paused(ctx: Context<ASetPaused>, input: SetPausedInput) -> Result<()> {
+    let state = &mut ctx.accounts.main_state;
+    require!(state.initialized.eq(&true), BettingError::Uninitialized);
+    require!(
+        state.is_ops_admin(ctx.accounts.admin.key),
+        BettingError::Unauthorized
+    );
+
+    state.paused = input.paused;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
@@ -0,0 +1,95 @@
+use crate::{
+    constants::{MAX_REVENUE_SHARE_BPS, MIN_REVENUE_EPOCH_SECS},
+    error::BettingError,
+    time, MainState, RevenueShare, RevenueShareConfiguredEvent,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct SetRevenueShareInput {
+    pub share_bps: u64, // 0 stops accruing; stakers keep what closed epochs already hold
+    pub epoch_duration_secs: i64,
+}
+
+// Sets what share of platform fees goes to platform-token stakers and how long each revenue
+// epoch runs. The first call creates the staking pool for `stake_mint` and opens epoch 0; a
+// new epoch length also applies to the open epoch.
+pub fn set_revenue_share(
+    ctx: Context<ASetRevenueShare>,
+    input: SetRevenueShareInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(main_state.initialized.eq(&true), BettingError::Uninitialized);
+    require!(
+        input.share_bps <= MAX_REVENUE_SHARE_BPS
+            && input.epoch_duration_secs >= MIN_REVENUE_EPOCH_SECS,
+        BettingError::InvalidRevenueShare
+    );
+    // Fees paid straight to the owner's wallet can't be set aside for stakers
+    require!(
+        input.share_bps == 0 || main_state.treasury_sweep_enabled(),
+        BettingError::RevenueShareRequiresTreasury
+    );
+
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let stake_mint = ctx.accounts.stake_mint.key();
+    if revenue_share.stake_mint == Pubkey::default() {
+        revenue_share.stake_mint = stake_mint;
+        revenue_share.epoch_started_at = time::now(main_state)?;
+    }
+    require_keys_eq!(
+        revenue_share.stake_mint,
+        stake_mint,
+        BettingError::StakeMintMismatch
+    );
+    revenue_share.epoch_duration_secs = input.epoch_duration_secs;
+    main_state.revenue_share_bps = input.share_bps;
+
+    emit!(RevenueShareConfiguredEvent {
+        stake_mint,
+        share_bps: input.share_bps,
+        epoch_duration_secs: input.epoch_duration_secs,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetRevenueShare<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + RevenueShare::MAX_SIZE,
+        seeds = [RevenueShare::PREFIX_SEED],
+        bump
+    )]
+    pub revenue_share: Box<Account<'info, RevenueShare>>,
+
+    pub stake_mint: Box<Account<'info, Mint>>,
+
+    // Holds every staker's platform tokens
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = stake_mint,
+        associated_token::authority = revenue_share,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,76 @@
+use crate::{
+    constants::TREASURY_SEED, error::BettingError, MainState, TreasurySweepConfiguredEvent,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct SetTreasurySweepInput {
+    pub cold_wallet: Pubkey, // Pubkey::default() sends platform fees straight to the owner again
+    pub threshold: u64,
+}
+
+// Routes platform fees into the treasury PDA and sets where and above what balance they are
+// swept. The first call also covers the treasury's rent exemption so it can receive fees.
+pub fn set_treasury_sweep(
+    ctx: Context<ASetTreasurySweep>,
+    input: SetTreasurySweepInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(main_state.initialized.eq(&true), BettingError::Uninitialized);
+    // Stakers' revenue share is set aside in the treasury, so it can't be bypassed while on
+    require!(
+        input.cold_wallet != Pubkey::default() || main_state.revenue_share_bps == 0,
+        BettingError::RevenueShareRequiresTreasury
+    );
+
+    let treasury = &ctx.accounts.treasury;
+    let rent_minimum = Rent::get()?.minimum_balance(0);
+    let rent_top_up = rent_minimum.saturating_sub(treasury.lamports());
+    if rent_top_up > 0 {
+        invoke(
+            &system_instruction::transfer(ctx.accounts.owner.key, treasury.key, rent_top_up),
+            &[
+                ctx.accounts.owner.to_account_info(),
+                treasury.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    main_state.treasury = treasury.key();
+    main_state.treasury_cold_wallet = input.cold_wallet;
+    main_state.treasury_sweep_threshold = input.threshold;
+
+    emit!(TreasurySweepConfiguredEvent {
+        treasury: treasury.key(),
+        cold_wallet: input.cold_wallet,
+        threshold: input.threshold,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetTreasurySweep<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA treasury only holds lamports
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
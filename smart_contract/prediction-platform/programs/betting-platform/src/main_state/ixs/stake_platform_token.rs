@@ -0,0 +1,95 @@
+use crate::{error::BettingError, RevenueShare, StakeChangedEvent, StakeState};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct StakePlatformTokenInput {
+    pub amount: u64,
+}
+
+// Moves platform tokens into the revenue share vault. They start earning at the next epoch.
+pub fn stake_platform_token(
+    ctx: Context<AStakePlatformToken>,
+    input: StakePlatformTokenInput,
+) -> Result<()> {
+    require!(input.amount > 0, BettingError::InvalidStakeAmount);
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let stake_state = &mut ctx.accounts.stake_state;
+    if stake_state.user == Pubkey::default() {
+        stake_state.user = ctx.accounts.user.key();
+        stake_state.next_claim_epoch = revenue_share.epoch;
+    }
+    require!(
+        stake_state.is_caught_up(revenue_share),
+        BettingError::RevenueClaimsPending
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
+
+    stake_state.add(input.amount, revenue_share.epoch);
+    revenue_share.activating_stake = revenue_share
+        .activating_stake
+        .checked_add(input.amount)
+        .ok_or(BettingError::MathOverflow)?;
+
+    emit!(StakeChangedEvent {
+        user: stake_state.user,
+        staked: true,
+        amount: input.amount,
+        earning: stake_state.amount,
+        activating: stake_state.activating,
+        epoch: revenue_share.epoch,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AStakePlatformToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RevenueShare::PREFIX_SEED],
+        bump
+    )]
+    pub revenue_share: Box<Account<'info, RevenueShare>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + StakeState::MAX_SIZE,
+        seeds = [StakeState::PREFIX_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub stake_state: Box<Account<'info, StakeState>>,
+
+    #[account(
+        mut,
+        token::mint = revenue_share.stake_mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = revenue_share.stake_mint,
+        associated_token::authority = revenue_share,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,76 @@
+use crate::{constants::TREASURY_SEED, error::BettingError, MainState, TreasurySweptEvent};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+
+// Permissionless keeper entry point: moves everything above the owner's threshold from the
+// treasury to the cold wallet. Fails with TreasuryBelowThreshold otherwise, so keepers can
+// simulate it to decide whether to send.
+pub fn sweep_treasury_if_above(ctx: Context<ASweepTreasuryIfAbove>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(
+        main_state.treasury_sweep_enabled(),
+        BettingError::TreasurySweepDisabled
+    );
+
+    // The treasury keeps its rent-exempt minimum and the stakers' pending revenue share
+    let treasury = &ctx.accounts.treasury;
+    let balance = treasury
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0))
+        .saturating_sub(main_state.revenue_share_pending);
+    let threshold = main_state.treasury_sweep_threshold;
+    require!(balance > threshold, BettingError::TreasuryBelowThreshold);
+    let amount = balance - threshold;
+
+    invoke_signed(
+        &system_instruction::transfer(treasury.key, ctx.accounts.cold_wallet.key, amount),
+        &[
+            treasury.to_account_info(),
+            ctx.accounts.cold_wallet.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[TREASURY_SEED.as_bytes(), &[ctx.bumps.treasury]]],
+    )?;
+
+    main_state.treasury_swept = main_state
+        .treasury_swept
+        .checked_add(amount)
+        .ok_or(BettingError::MathOverflow)?;
+
+    emit!(TreasurySweptEvent {
+        cold_wallet: ctx.accounts.cold_wallet.key(),
+        amount,
+        threshold,
+        remaining: threshold,
+        total_swept: main_state.treasury_swept,
+        swept_by: ctx.accounts.caller.key(),
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASweepTreasuryIfAbove<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA treasury only signs to transfer lamports
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.treasury_cold_wallet @ BettingError::Unauthorized)]
+    pub cold_wallet: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,91 @@
+use crate::{error::BettingError, RevenueShare, StakeChangedEvent, StakeState};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct UnstakePlatformTokenInput {
+    pub amount: u64,
+}
+
+// Returns staked platform tokens, stake that has not started earning first. Earning stake
+// withdrawn now forfeits its share of the open epoch.
+pub fn unstake_platform_token(
+    ctx: Context<AUnstakePlatformToken>,
+    input: UnstakePlatformTokenInput,
+) -> Result<()> {
+    require!(input.amount > 0, BettingError::InvalidStakeAmount);
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let stake_state = &mut ctx.accounts.stake_state;
+    require!(
+        stake_state.is_caught_up(revenue_share),
+        BettingError::RevenueClaimsPending
+    );
+
+    let (from_activating, from_earning) = stake_state
+        .remove(input.amount, revenue_share.epoch)
+        .ok_or(BettingError::InvalidStakeAmount)?;
+    revenue_share.activating_stake = revenue_share
+        .activating_stake
+        .saturating_sub(from_activating);
+    revenue_share.total_staked = revenue_share.total_staked.saturating_sub(from_earning);
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: revenue_share.to_account_info(),
+            },
+            &[&[RevenueShare::PREFIX_SEED, &[ctx.bumps.revenue_share]]],
+        ),
+        input.amount,
+    )?;
+
+    emit!(StakeChangedEvent {
+        user: stake_state.user,
+        staked: false,
+        amount: input.amount,
+        earning: stake_state.amount,
+        activating: stake_state.activating,
+        epoch: revenue_share.epoch,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AUnstakePlatformToken<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RevenueShare::PREFIX_SEED],
+        bump
+    )]
+    pub revenue_share: Box<Account<'info, RevenueShare>>,
+
+    #[account(
+        mut,
+        seeds = [StakeState::PREFIX_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub stake_state: Box<Account<'info, StakeState>>,
+
+    #[account(
+        mut,
+        token::mint = revenue_share.stake_mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = revenue_share.stake_mint,
+        associated_token::authority = revenue_share,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -1,4 +1,8 @@
-use crate::{error::BettingError, MainState};
+use crate::{
+    constants::MAX_RESERVE_MILESTONES,
+    error::BettingError,
+    ClaimWindowBounds, MainState,
+};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
@@ -8,6 +12,11 @@ pub struct UpdateMainStateInput {
     scale_factor: u64,
     creator_fee_percent: u64,
     platform_fee_percent: u64,
+    claim_window: Option<ClaimWindowBounds>, // None keeps the current bounds
+    self_referee_reserve_cap: Option<u64>,   // None keeps the current cap, 0 removes it
+    reserve_milestones: Option<[u64; MAX_RESERVE_MILESTONES]>, // None keeps the current ones
+    max_pools_per_day: Option<u64>, // None keeps the current limit, 0 removes it
+    creator_bond_lamports: Option<u64>, // None keeps the current bond, 0 removes it
 }
 
 pub fn update_main_state(
@@ -22,6 +31,22 @@ pub fn update_main_state(
     state.scale_factor = input.scale_factor;
     state.creator_fee_percent = input.creator_fee_percent;
     state.platform_fee_percent = input.platform_fee_percent;
+    if let Some(claim_window) = input.claim_window {
+        require!(claim_window.is_valid(), BettingError::InvalidClaimWindow);
+        state.claim_window = claim_window;
+    }
+    if let Some(self_referee_reserve_cap) = input.self_referee_reserve_cap {
+        state.self_referee_reserve_cap = self_referee_reserve_cap;
+    }
+    if let Some(reserve_milestones) = input.reserve_milestones {
+        state.reserve_milestones = reserve_milestones;
+    }
+    if let Some(max_pools_per_day) = input.max_pools_per_day {
+        state.max_pools_per_day = max_pools_per_day;
+    }
+    if let Some(creator_bond_lamports) = input.creator_bond_lamports {
+        state.creator_bond_lamports = creator_bond_lamports;
+    }
 
     Ok(())
 }
@@ -1,17 +1,428 @@
-use anchor_lang::prelude::*;
-
-#[account]
-pub struct MainState {
-    pub initialized: bool,
-    pub owner: Pubkey,
-    pub scale_factor: u64,
-    pub initial_price: u64,
-    pub current_bet_id: u64,
-    pub creator_fee_percent: u64, // Creator fee percentage in basis points (e.g., 100 = 1%)
-    pub platform_fee_percent: u64, // Platform fee percentage in basis points, paid to owner
-}
-
-impl MainState {
-    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
-    pub const PREFIX_SEED: &'static [u8] = b"main";
-}
+use crate::constants::MAX_RESERVE_MILESTONES;
+#[cfg(feature = "referee-registry")]
+use crate::constants::REFEREE_BONUS_STREAK;
+use crate::fees::bps_of;
+use crate::layout::Reserved;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct MainState {
+    pub initialized: bool,
+    pub owner: Pubkey,
+    pub scale_factor: u64,
+    pub initial_price: u64,
+    pub current_bet_id: u64,
+    pub creator_fee_percent: u64, // Creator fee percentage in basis points (e.g., 100 = 1%)
+    pub platform_fee_percent: u64, // Platform fee percentage in basis points, paid to owner
+    // Former clock_tolerance_secs. Accounts from before its removal still hold a value here,
+    // so unlike `reserved` these bytes are not known to be zero.
+    pub retired: Reserved<8>,
+
+    // Role-scoped admin keys (Pubkey::default() = role unassigned). The owner holds every role.
+    pub fee_admin: Pubkey, // may change fee bps only
+    pub ops_admin: Pubkey, // may pause/unpause only
+    pub paused: bool,      // blocks pool creation, entries and deposits
+
+    // Resolution statistics, updated at every resolution (averages rounded down, seconds).
+    // Market duration covers all pools; latency (resolved_at - end_timestamp) only fixed-end ones.
+    pub resolved_pools: u64,
+    pub total_market_duration_secs: u64,
+    pub avg_market_duration_secs: u64,
+    pub timed_resolutions: u64,
+    pub total_resolution_latency_secs: u64,
+    pub avg_resolution_latency_secs: u64,
+
+    // Seconds added to the cluster clock by time checks; only honoured by `test-utils` builds
+    pub clock_offset_secs: i64,
+
+    // Referee bonus pool, funded by the owner into the REFEREE_BONUS_VAULT_SEED vault
+    pub referee_bonus_per_streak: u64, // lamports paid per completed REFEREE_BONUS_STREAK
+    pub referee_bonus_funded: u64,
+    pub referee_bonus_paid: u64,
+
+    // Liquidity-sensitive platform fee; replaces platform_fee_percent while enabled
+    pub platform_fee_curve: FeeCurve,
+
+    // Cold storage sweeps: while a cold wallet is set, platform fees accrue in the TREASURY_SEED
+    // PDA and anyone may sweep the balance above the threshold to the cold wallet
+    pub treasury: Pubkey, // TREASURY_SEED PDA, recorded by set_treasury_sweep
+    pub treasury_cold_wallet: Pubkey, // Pubkey::default() = sweeping disabled
+    pub treasury_sweep_threshold: u64,
+    pub treasury_swept: u64,
+
+    // Bounds and default for how long winners have to claim after resolution
+    pub claim_window: ClaimWindowBounds,
+
+    // Stakers' cut of platform fees (see RevenueShare). Fees keep landing in the treasury;
+    // revenue_share_pending is the part owed to the open epoch, which sweeps leave behind
+    pub revenue_share_bps: u64,
+    pub revenue_share_pending: u64,
+
+    // Anti-collusion guard: reserve a pool refereed by its own creator may hold (0 = no cap)
+    pub self_referee_reserve_cap: u64,
+
+    // Total reserves at which pools emit MilestoneReserveEvent (0 = unused slot)
+    pub reserve_milestones: [u64; MAX_RESERVE_MILESTONES],
+
+    // Referrers' cut of the platform fee on the deposits they brought in, in bps of the fee
+    // (see referral_fee_escrow)
+    pub referral_fee_percent: u64,
+
+    // Pools a single wallet may create in any rolling 24h window (0 = unlimited), counted in
+    // each creator's CreatorRateLimit
+    pub max_pools_per_day: u64,
+
+    // Referees' cut of the platform fee on the pools they resolve themselves, in bps of the fee;
+    // new pools take it as their referee_fee_bps (see referee_fee)
+    pub referee_fee_percent: u64,
+
+    // Lamports every pool and outcome market creation escrows from its payer (0 = no bond); see
+    // settle_creator_bond and set_outcome_winner
+    pub creator_bond_lamports: u64,
+
+    // Zeroed headroom; new fields are declared above and taken out of it (see layout.rs)
+    pub reserved: Reserved<{ MainState::RESERVED }>,
+}
+
+impl MainState {
+    pub const RESERVED: usize = 190;
+    pub const MAX_SIZE: usize = Self::INIT_SPACE;
+    pub const PREFIX_SEED: &'static [u8] = b"main";
+
+    pub fn is_fee_admin(&self, key: &Pubkey) -> bool {
+        self.owner.eq(key) || (self.fee_admin.ne(&Pubkey::default()) && self.fee_admin.eq(key))
+    }
+
+    pub fn is_ops_admin(&self, key: &Pubkey) -> bool {
+        self.owner.eq(key) || (self.ops_admin.ne(&Pubkey::default()) && self.ops_admin.eq(key))
+    }
+
+    pub fn treasury_sweep_enabled(&self) -> bool {
+        self.treasury_cold_wallet.ne(&Pubkey::default())
+    }
+
+    // Account that receives platform fees and slashed bonds at resolution
+    pub fn platform_fee_recipient(&self) -> Pubkey {
+        if self.treasury_sweep_enabled() {
+            self.treasury
+        } else {
+            self.owner
+        }
+    }
+
+    // Sets aside the stakers' share of a platform fee paid into the treasury
+    pub fn accrue_revenue_share(&mut self, platform_fee: u64) {
+        if !self.treasury_sweep_enabled() {
+            return;
+        }
+        let share = bps_of(platform_fee as u128, self.revenue_share_bps) as u64;
+        self.revenue_share_pending = self.revenue_share_pending.saturating_add(share);
+    }
+
+    pub fn record_resolution(&mut self, market_duration_secs: u64, latency_secs: Option<u64>) {
+        self.resolved_pools = self.resolved_pools.saturating_add(1);
+        self.total_market_duration_secs = self
+            .total_market_duration_secs
+            .saturating_add(market_duration_secs);
+        self.avg_market_duration_secs = self.total_market_duration_secs / self.resolved_pools;
+
+        if let Some(latency_secs) = latency_secs {
+            self.timed_resolutions = self.timed_resolutions.saturating_add(1);
+            self.total_resolution_latency_secs = self
+                .total_resolution_latency_secs
+                .saturating_add(latency_secs);
+            self.avg_resolution_latency_secs =
+                self.total_resolution_latency_secs / self.timed_resolutions;
+        }
+    }
+}
+
+// Platform fee as a function of the pool's total reserve at resolution: `small_pool_bps` up to
+// `small_pool_reserve`, `large_pool_bps` from `large_pool_reserve` on, linear in between.
+// Disabled (flat platform_fee_percent) while `large_pool_reserve` is 0.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct FeeCurve {
+    pub small_pool_reserve: u64,
+    pub large_pool_reserve: u64,
+    pub small_pool_bps: u64,
+    pub large_pool_bps: u64,
+}
+
+impl FeeCurve {
+    pub fn is_enabled(&self) -> bool {
+        self.large_pool_reserve > 0
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !self.is_enabled()
+            || (self.small_pool_reserve < self.large_pool_reserve
+                && self.small_pool_bps >= self.large_pool_bps)
+    }
+
+    pub fn bps_for(&self, total_reserve: u64) -> u64 {
+        if total_reserve <= self.small_pool_reserve {
+            return self.small_pool_bps;
+        }
+        if total_reserve >= self.large_pool_reserve {
+            return self.large_pool_bps;
+        }
+        let progress = (total_reserve - self.small_pool_reserve) as u128;
+        let span = (self.large_pool_reserve - self.small_pool_reserve) as u128;
+        let drop = (self.small_pool_bps - self.large_pool_bps) as u128;
+        self.small_pool_bps - (drop * progress / span) as u64
+    }
+}
+
+// Seconds after resolution a pool's winners have to claim. Creators may pick any window in
+// [min_secs, max_secs] with `claim_deadline_override`; pools that don't get `default_secs`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct ClaimWindowBounds {
+    pub min_secs: i64,
+    pub default_secs: i64,
+    pub max_secs: i64,
+}
+
+impl ClaimWindowBounds {
+    pub fn is_valid(&self) -> bool {
+        0 < self.min_secs && self.min_secs <= self.default_secs && self.default_secs <= self.max_secs
+    }
+
+    pub fn contains(&self, window_secs: i64) -> bool {
+        (self.min_secs..=self.max_secs).contains(&window_secs)
+    }
+}
+
+// Owner-curated list of vetted referees that creators can pick by index in create_pool
+#[cfg(feature = "referee-registry")]
+#[account]
+pub struct DefaultReferees {
+    pub referees: Vec<Pubkey>,
+}
+
+#[cfg(feature = "referee-registry")]
+impl DefaultReferees {
+    pub const MAX_REFEREES: usize = 16;
+    pub const MAX_SIZE: usize = 4 + 32 * Self::MAX_REFEREES;
+    pub const PREFIX_SEED: &'static [u8] = b"default-referees";
+}
+
+// A referrer's account (register_referrer): claims of the entries they referred credit their
+// slice of the platform fee here, in lamports held by the account itself, until
+// claim_referral_fee pays it out
+#[account]
+#[derive(Default)]
+pub struct ReferralState {
+    pub referrer: Pubkey,
+    pub accrued: u64, // lamports credited since registration
+    pub claimed: u64, // lamports paid out to the referrer
+}
+
+impl ReferralState {
+    pub const MAX_SIZE: usize = 32 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"referral";
+
+    pub fn unclaimed(&self) -> u64 {
+        self.accrued.saturating_sub(self.claimed)
+    }
+}
+
+// Per-referee track record, updated at every resolution of a pool the referee was assigned to.
+// A resolution is on time when the referee resolves it themselves before its referee deadline
+// (and, for fixed-end pools, within REFEREE_ON_TIME_SECS of the end); anything else is a miss
+// and resets the streak.
+#[cfg(feature = "referee-registry")]
+#[account]
+#[derive(Default)]
+pub struct RefereeProfile {
+    pub referee: Pubkey,
+    pub resolutions: u64,
+    pub on_time_resolutions: u64,
+    pub missed_resolutions: u64,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub bonus_streaks_earned: u32, // completed REFEREE_BONUS_STREAK runs
+    pub bonus_streaks_paid: u32,
+    pub bonus_paid: u64,
+    pub last_resolved_at: i64,
+}
+
+#[cfg(feature = "referee-registry")]
+impl RefereeProfile {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"referee-profile";
+
+    pub fn record_resolution(&mut self, on_time: bool, now: i64) {
+        self.resolutions = self.resolutions.saturating_add(1);
+        self.last_resolved_at = now;
+        if !on_time {
+            self.missed_resolutions = self.missed_resolutions.saturating_add(1);
+            self.current_streak = 0;
+            return;
+        }
+
+        self.on_time_resolutions = self.on_time_resolutions.saturating_add(1);
+        self.current_streak = self.current_streak.saturating_add(1);
+        self.best_streak = self.best_streak.max(self.current_streak);
+        if self.current_streak.is_multiple_of(REFEREE_BONUS_STREAK) {
+            self.bonus_streaks_earned = self.bonus_streaks_earned.saturating_add(1);
+        }
+    }
+
+    pub fn unclaimed_bonus_streaks(&self) -> u32 {
+        self.bonus_streaks_earned.saturating_sub(self.bonus_streaks_paid)
+    }
+}
+
+// Pools past their end time still waiting on this referee, so a dashboard reads one account
+// instead of scanning every pool. enqueue_pending_resolution queues a pool once it ended,
+// set_winner takes it off, and prune_referee_queue drops pools another path decided or whose
+// referee changed.
+#[account]
+#[derive(Default)]
+pub struct RefereeQueue {
+    pub referee: Pubkey,
+    pub bet_ids: Vec<u64>, // max MAX_PENDING, oldest first
+}
+
+impl RefereeQueue {
+    pub const MAX_PENDING: usize = 64;
+    pub const MAX_SIZE: usize = 32 + 4 + 8 * Self::MAX_PENDING;
+    pub const PREFIX_SEED: &'static [u8] = b"referee-queue";
+
+    pub fn is_queued(&self, bet_id: u64) -> bool {
+        self.bet_ids.contains(&bet_id)
+    }
+
+    // Keeps the queue in the order pools were queued; returns whether `bet_id` was queued
+    pub fn remove(&mut self, bet_id: u64) -> bool {
+        let Some(position) = self.bet_ids.iter().position(|queued| *queued == bet_id) else {
+            return false;
+        };
+        self.bet_ids.remove(position);
+        true
+    }
+}
+
+// Opt-in, wallet-portable pointer to a user's off-chain notification settings: a hash of the
+// endpoint configuration plus a URI to fetch it from. Nothing on-chain reads the endpoint;
+// notifiers match it against PoolSubscribers lists when PoolNotificationEvents fire.
+#[account]
+#[derive(Default)]
+pub struct NotificationPrefs {
+    pub user: Pubkey,
+    pub endpoint_hash: [u8; 32],
+    pub endpoint_uri: String, // max MAX_URI_LEN bytes
+    pub notify_on_lock: bool,
+    pub notify_on_resolution: bool,
+    pub updated_at: i64,
+}
+
+impl NotificationPrefs {
+    pub const MAX_URI_LEN: usize = 128;
+    pub const MAX_SIZE: usize = 32 + 32 + (4 + Self::MAX_URI_LEN) + 1 + 1 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"notification-prefs";
+}
+
+// Platform-token staking pool that earns MainState::revenue_share_bps of platform fees.
+// Fees accrue in the treasury during an epoch; checkpoint_epoch moves them here and records a
+// RevenueEpoch that stakers claim from pro rata. Stake added during an epoch starts earning
+// at the next one, and stake withdrawn during an epoch forfeits it.
+#[account]
+#[derive(Default)]
+pub struct RevenueShare {
+    pub stake_mint: Pubkey, // set by the first set_revenue_share, fixed afterwards
+    pub epoch_duration_secs: i64,
+    pub epoch: u64, // the open epoch, closed by the next checkpoint_epoch
+    pub epoch_started_at: i64,
+    pub total_staked: u64,     // stake earning the open epoch
+    pub activating_stake: u64, // stake joining total_staked at the next checkpoint
+    pub total_distributed: u64,
+    pub total_claimed: u64,
+}
+
+impl RevenueShare {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"revenue-share";
+
+    pub fn epoch_ends_at(&self) -> i64 {
+        self.epoch_started_at.saturating_add(self.epoch_duration_secs)
+    }
+}
+
+// A closed epoch's revenue and the stake it is split across
+#[account]
+#[derive(Default)]
+pub struct RevenueEpoch {
+    pub epoch: u64,
+    pub revenue: u64,
+    pub total_staked: u64,
+    pub claimed: u64,
+    pub closed_at: i64,
+}
+
+impl RevenueEpoch {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"revenue-epoch";
+
+    // Revenue owed to `stake` out of this epoch, rounded down
+    pub fn share_of(&self, stake: u64) -> u64 {
+        if self.total_staked == 0 {
+            return 0;
+        }
+        ((self.revenue as u128) * (stake as u128) / (self.total_staked as u128)) as u64
+    }
+}
+
+// A user's platform tokens staked in the RevenueShare vault. Epochs are claimed one at a time
+// in order; stake can only change once every closed epoch is claimed, so `amount` is always
+// the stake the next unclaimed epoch was split across.
+#[account]
+#[derive(Default)]
+pub struct StakeState {
+    pub user: Pubkey,
+    pub amount: u64,           // earning since before the open epoch
+    pub activating: u64,       // added during `activating_epoch`, earning from the one after
+    pub activating_epoch: u64,
+    pub next_claim_epoch: u64,
+    pub total_claimed: u64,
+}
+
+impl StakeState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"stake";
+
+    pub fn is_caught_up(&self, revenue_share: &RevenueShare) -> bool {
+        self.next_claim_epoch >= revenue_share.epoch
+    }
+
+    // Folds stake added before `epoch` into the earning amount
+    pub fn activate(&mut self, epoch: u64) {
+        if self.activating > 0 && self.activating_epoch < epoch {
+            self.amount = self.amount.saturating_add(self.activating);
+            self.activating = 0;
+        }
+    }
+
+    // Adds stake during the open `epoch`. With nothing earning yet, the open epoch owes this
+    // stake nothing and is skipped by claims.
+    pub fn add(&mut self, amount: u64, epoch: u64) {
+        self.activate(epoch);
+        if self.amount == 0 {
+            self.next_claim_epoch = self.next_claim_epoch.max(epoch.saturating_add(1));
+        }
+        self.activating = self.activating.saturating_add(amount);
+        self.activating_epoch = epoch;
+    }
+
+    // Withdraws `amount` during the open `epoch`, activating stake first. Returns how much
+    // came out of (activating, earning) stake.
+    pub fn remove(&mut self, amount: u64, epoch: u64) -> Option<(u64, u64)> {
+        self.activate(epoch);
+        let from_activating = amount.min(self.activating);
+        let from_earning = amount - from_activating;
+        self.amount = self.amount.checked_sub(from_earning)?;
+        self.activating -= from_activating;
+        Some((from_activating, from_earning))
+    }
+}
@@ -0,0 +1,81 @@
+//! Signed claim permits for gasless claims.
+//!
+//! `message = PERMIT_DOMAIN || program_id || bet_id_le || recipient || expires_at_le`
+//!
+//! The winner signs the message off-chain; a relayer submits it through the native Ed25519
+//! program in the instruction right before `claim_with_permit`, which checks that instruction
+//! through the instructions sysvar.
+
+use crate::error::BettingError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions::get_instruction_relative};
+
+#[constant]
+pub const PERMIT_DOMAIN: &[u8] = b"delphi-claim-permit";
+
+// Ed25519 instruction layout: [num_signatures u8, padding u8, offsets...] followed by the data
+const OFFSETS_START: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+// Offsets referring to data inside the Ed25519 instruction itself
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub fn permit_message(
+    program_id: &Pubkey,
+    bet_id: u64,
+    recipient: &Pubkey,
+    expires_at: i64,
+) -> Vec<u8> {
+    [
+        PERMIT_DOMAIN,
+        program_id.as_ref(),
+        &bet_id.to_le_bytes(),
+        recipient.as_ref(),
+        &expires_at.to_le_bytes(),
+    ]
+    .concat()
+}
+
+// Checks that the previous instruction verified exactly one `signer` signature over `message`
+pub fn verify_permit(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let ix = get_instruction_relative(-1, instructions_sysvar)
+        .map_err(|_| error!(BettingError::InvalidPermit))?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, BettingError::InvalidPermit);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= OFFSETS_START + OFFSETS_LEN && data[0] == 1,
+        BettingError::InvalidPermit
+    );
+    let offsets = &data[OFFSETS_START..OFFSETS_START + OFFSETS_LEN];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    let signature_ix = read_u16(2);
+    let pubkey_offset = read_u16(4) as usize;
+    let pubkey_ix = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_len = read_u16(10) as usize;
+    let message_ix = read_u16(12);
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        BettingError::InvalidPermit
+    );
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(BettingError::InvalidPermit)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(BettingError::InvalidPermit)?;
+    require!(
+        signed_pubkey == signer.as_ref() && signed_message == message,
+        BettingError::InvalidPermit
+    );
+
+    Ok(())
+}
@@ -1,31 +1,698 @@
-use anchor_lang::prelude::*;
-
-#[event]
-pub struct CreateEvent {
-    pub creator: Pubkey,
-    pub bet_id: u64,
-    pub title: String,
-    pub description: String,
-    pub end_timestamp: i64,
-    pub referee: Pubkey,
-    pub share_uuid: String,
-    pub timestamp: i64,
-}
-
-#[event]
-pub struct DepositEvent {
-    pub user: Pubkey,
-    pub bet_id: u64,
-    pub sol_amount: u64,
-    pub token_amount: u64,
-    pub is_yes: bool,
-    pub timestamp: i64,
-}
-
-#[event]
-pub struct CompleteEvent {
-    pub referee: Pubkey,
-    pub bet_id: u64,
-    pub winner: String,
-    pub timestamp: i64,
-}
+use crate::{RangeBucket, WinnerSide};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CreateEvent {
+    pub creator: Pubkey,
+    pub bet_id: u64,
+    pub title: String,
+    pub description: String,
+    pub end_timestamp: i64,
+    pub referee: Pubkey,
+    pub share_uuid: String,
+    pub unit_symbol: String,
+    pub decimals: u8,
+    pub side_a_label: String,
+    pub side_b_label: String,
+    pub creator_donation_bps: u64,
+    pub range_buckets: Vec<RangeBucket>, // empty for YES/NO markets
+    pub claim_window_secs: i64,          // winners may claim until resolution + this
+    pub rent_payer: Pubkey,
+    pub pool_rent_lamports: u64,
+    pub history_rent_lamports: u64,
+    pub initial_yes_liquidity: u64, // creator seed liquidity, 0 when unseeded
+    pub initial_no_liquidity: u64,
+    pub opens_at: i64, // 0 when the pool opens at creation
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub is_yes: bool,
+    pub insurance_premium: u64, // 0 for uninsured deposits
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompleteEvent {
+    pub referee: Pubkey,
+    pub bet_id: u64,
+    pub winner: WinnerSide,
+    pub creator_fee_donated: u64, // part of the creator fee added to the winners' profit
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitDepositEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub sol_amount: u64,
+    pub reveal_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentRefundEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub sol_amount: u64,
+    pub forfeit: u64, // kept from a lapsed commitment and paid to the platform
+    pub timestamp: i64,
+}
+
+// Emitted when the oldest point of PoolHistoryState's ring is overwritten, so archives can
+// keep the full series
+#[event]
+pub struct HistoryPrunedEvent {
+    pub bet_id: u64,
+    pub point_timestamp: i64, // the overwritten point
+    pub yes_share_bps: u16,
+    pub kind: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LinkedPoolOpenedEvent {
+    pub parent_bet_id: u64,
+    pub child_bet_id: u64,
+    pub on_yes: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundModeEvent {
+    pub bet_id: u64,
+    pub triggered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted by migrate_vault; `amount` is the liability moved out of the shared sol-vault
+#[event]
+pub struct VaultMigratedEvent {
+    pub bet_id: u64,
+    pub pool_vault: Pubkey,
+    pub amount: u64,
+    pub rent_top_up: u64,
+    pub legacy_vault_balance: u64, // shared vault balance after the move
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimedEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// The council resolved a pool whose referee let `referee_deadline` pass
+#[event]
+pub struct RefereeNoShowEvent {
+    pub bet_id: u64,
+    pub referee: Pubkey,
+    pub resolved_by: Pubkey,
+    pub referee_deadline: i64,
+    pub timestamp: i64,
+}
+
+// A referee was paid for completed streaks of on-time resolutions
+#[event]
+pub struct RefereeBonusClaimedEvent {
+    pub referee: Pubkey,
+    pub streaks: u32,
+    pub amount: u64,
+    pub current_streak: u32,
+    pub on_time_resolutions: u64,
+    pub timestamp: i64,
+}
+
+// Optimistic-oracle lifecycle
+#[event]
+pub struct AssertionMadeEvent {
+    pub bet_id: u64,
+    pub asserter: Pubkey,
+    pub is_yes: bool,
+    pub bond: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssertionDisputedEvent {
+    pub bet_id: u64,
+    pub asserter: Pubkey,
+    pub disputer: Pubkey,
+    pub timestamp: i64,
+}
+
+// `resolved_pool` is true when the unchallenged assertion itself resolved the pool;
+// `bond_recipient` received the dispute bond(s) (the asserter when undisputed)
+#[event]
+pub struct AssertionSettledEvent {
+    pub bet_id: u64,
+    pub asserter: Pubkey,
+    pub is_yes: bool,
+    pub resolved_pool: bool,
+    pub bond_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+// Exact lamports returned when one of a pool's accounts closes: to the pool's rent payer, or
+// to the user for their entry
+#[event]
+pub struct RentReclaimedEvent {
+    pub bet_id: u64,
+    pub account: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EntryTokenizedEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub token_amount: u64,
+    pub principal: u64,
+    pub timestamp: i64,
+}
+
+// A position moved between wallets by transfer_entry
+#[event]
+pub struct EntryTransferredEvent {
+    pub bet_id: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub is_yes: bool,
+    pub token_amount: u64,
+    pub deposited_amount: u64,
+    pub timestamp: i64,
+}
+
+// Burn-to-claim: `payout` is principal plus profit share (principal only in refund mode)
+#[event]
+pub struct TokenClaimEvent {
+    pub holder: Pubkey,
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub token_amount: u64,
+    pub payout: u64,
+    pub timestamp: i64,
+}
+
+// Value submitted for a range market and the bucket outcome it mapped to
+#[event]
+pub struct RangeResolvedEvent {
+    pub bet_id: u64,
+    pub value: i64,
+    pub is_yes: bool,
+    pub timestamp: i64,
+}
+
+// Scalar markets: the submitted value and the long (YES) side's share of the pot it gives
+#[event]
+pub struct ScalarResolvedEvent {
+    pub bet_id: u64,
+    pub value: i64,
+    pub long_bps: u64,
+    pub timestamp: i64,
+}
+
+// Referee handoff: update_pool assigns a new referee, who gains resolution power only once
+// they sign accept_referee_role
+#[event]
+pub struct RefereeAssignedEvent {
+    pub bet_id: u64,
+    pub previous_referee: Pubkey,
+    pub referee: Pubkey,
+    pub timestamp: i64,
+}
+
+// Every field an update_pool call changed, old and new; unchanged fields are None on both
+#[event]
+pub struct UpdatePoolEvent {
+    pub bet_id: u64,
+    pub updater: Pubkey,
+    pub previous_title: Option<String>,
+    pub title: Option<String>,
+    pub previous_description: Option<String>,
+    pub description: Option<String>,
+    pub previous_end_timestamp: Option<i64>,
+    pub end_timestamp: Option<i64>,
+    pub previous_referee: Option<Pubkey>,
+    pub referee: Option<Pubkey>,
+    pub previous_max_pool_reserve: Option<u64>,
+    pub max_pool_reserve: Option<u64>,
+    pub previous_max_user_deposit: Option<u64>,
+    pub max_user_deposit: Option<u64>,
+    pub update_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefereeAcceptedEvent {
+    pub bet_id: u64,
+    pub referee: Pubkey,
+    pub timestamp: i64,
+}
+
+// Platform fees above the threshold moved from the treasury to the cold wallet
+#[event]
+pub struct TreasurySweptEvent {
+    pub cold_wallet: Pubkey,
+    pub amount: u64,
+    pub threshold: u64,
+    pub remaining: u64,
+    pub total_swept: u64,
+    pub swept_by: Pubkey,
+    pub timestamp: i64,
+}
+
+// Owner changed the treasury's cold wallet or sweep threshold
+#[event]
+pub struct TreasurySweepConfiguredEvent {
+    pub treasury: Pubkey,
+    pub cold_wallet: Pubkey,
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+// Off-chain notifiers deliver this to the users listed in `subscribers` (a PoolSubscribers
+// PDA, which may not exist when nobody subscribed)
+#[event]
+pub struct PoolNotificationEvent {
+    pub bet_id: u64,
+    pub kind: u8, // PoolSubscribers::NOTIFY_*
+    pub subscribers: Pubkey,
+    pub timestamp: i64,
+}
+
+// Lamports moved into (positive) or out of (negative) a user's BetBalance, excluding bets
+#[event]
+pub struct BetBalanceEvent {
+    pub user: Pubkey,
+    pub change: i64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+// Two-phase resolution: an outcome was recorded and trading stopped; it becomes final at
+// `confirms_at` unless a participant disputes it first
+#[event]
+pub struct ProvisionalWinnerEvent {
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub resolver: Pubkey,
+    pub confirms_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProvisionalDisputedEvent {
+    pub bet_id: u64,
+    pub disputer: Pubkey,
+    pub is_yes: bool, // the disputed provisional outcome
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+// The owner ruled on a disputed provisional outcome; the bond went back to the disputer if
+// they overturned it, to the platform otherwise
+#[event]
+pub struct ProvisionalDisputeSettledEvent {
+    pub bet_id: u64,
+    pub disputer: Pubkey,
+    pub overturned: bool,
+    pub bond_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeMarketCreatedEvent {
+    pub creator: Pubkey,
+    pub bet_id: u64,
+    pub title: String,
+    pub outcomes: Vec<String>,
+    pub end_timestamp: i64,
+    pub referee: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeDepositEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub outcome: u8,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeResolvedEvent {
+    pub referee: Pubkey,
+    pub bet_id: u64,
+    pub winner: u8,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+    pub timestamp: i64,
+}
+
+// An oracle-bound pool stopped trading because its StartFeed reported the event start
+#[event]
+pub struct PoolLockedOnStartEvent {
+    pub bet_id: u64,
+    pub start_feed: Pubkey,
+    pub started_at: i64,
+    pub previous_end_timestamp: i64,
+    pub cranked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+// A fixed-end pool's closing line was locked (see PoolState::record_final_odds); prices are
+// scaled by PRICE_SCALE
+#[event]
+pub struct FinalOddsEvent {
+    pub bet_id: u64,
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+    pub yes_price: u64,
+    pub no_price: u64,
+    pub end_timestamp: i64,
+    pub timestamp: i64,
+}
+
+// A pool denominated in `token_mint`; its CreateEvent amounts are in the mint's base units
+#[event]
+pub struct SplPoolCreatedEvent {
+    pub bet_id: u64,
+    pub token_mint: Pubkey,
+    pub token_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+// void_pool switched an unresolved pool into refund mode; `by_owner` is false when the grace
+// period after its end had passed and anyone could void it. Also emitted by set_winner when
+// the resolver declares the question invalid.
+#[event]
+pub struct PoolVoidedEvent {
+    pub bet_id: u64,
+    pub voided_by: Pubkey,
+    pub by_owner: bool,
+    pub timestamp: i64,
+}
+
+// Lamports moved into (positive) or out of (negative) a pool's guarantee escrow: owner top-ups
+// and releases, or a claim covering a vault shortfall (`user` is the claimant, else the owner)
+#[event]
+pub struct GuaranteeEvent {
+    pub bet_id: u64,
+    pub user: Pubkey,
+    pub change: i64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+// resolve_with_oracle settled a pool from its price feed (alongside its CompleteEvent, whose
+// referee is the feed); `price` * 10^`expo` is the aggregate price published at `publish_time`
+#[event]
+pub struct OracleResolutionEvent {
+    pub bet_id: u64,
+    pub feed: Pubkey,
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+// Owner set the stakers' cut of platform fees and the revenue epoch length
+#[event]
+pub struct RevenueShareConfiguredEvent {
+    pub stake_mint: Pubkey,
+    pub share_bps: u64,
+    pub epoch_duration_secs: i64,
+    pub timestamp: i64,
+}
+
+// A user staked or unstaked platform tokens; `activating` starts earning next epoch
+#[event]
+pub struct StakeChangedEvent {
+    pub user: Pubkey,
+    pub staked: bool,
+    pub amount: u64,
+    pub earning: u64,
+    pub activating: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+// checkpoint_epoch closed `epoch`, moving its revenue out of the treasury for stakers to claim
+#[event]
+pub struct RevenueEpochClosedEvent {
+    pub epoch: u64,
+    pub revenue: u64,
+    pub total_staked: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueShareClaimedEvent {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// migrate_pool_winner moved a pool from the String winner layout to WinnerSide
+#[event]
+pub struct PoolWinnerMigratedEvent {
+    pub bet_id: u64,
+    pub winner: WinnerSide,
+    pub rent_top_up: u64, // lamports the owner paid for the reserved block
+    pub timestamp: i64,
+}
+
+// A winning entry was paid: everything at once, or one installment of an annuity pool
+#[event]
+pub struct ClaimEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub principal: u64,
+    pub profit: u64,
+    pub total: u64, // principal + profit, as it left the vault
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeeClaimedEvent {
+    pub creator: Pubkey,
+    pub bet_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefereeFeeClaimedEvent {
+    pub referee: Pubkey,
+    pub bet_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// Creator analytics funnel (see emit_funnel_events); created_timestamp lets dashboards
+// measure each step from the pool's creation
+#[event]
+pub struct FirstDepositEvent {
+    pub bet_id: u64,
+    pub user: Pubkey,
+    pub created_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneReserveEvent {
+    pub bet_id: u64,
+    pub milestone: u64, // the MainState.reserve_milestones entry crossed
+    pub total_reserve: u64,
+    pub created_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParticipantMilestoneEvent {
+    pub bet_id: u64,
+    pub user: Pubkey, // the bettor who reached the milestone
+    pub participants: u32,
+    pub created_timestamp: i64,
+    pub timestamp: i64,
+}
+
+// Seed liquidity handed back to the creator (see PoolState::reclaimable_seed)
+#[event]
+pub struct SeedLiquidityReclaimedEvent {
+    pub creator: Pubkey,
+    pub bet_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// A meta-market bound to `target_bet_id` (see MetaCondition)
+#[event]
+pub struct MetaPoolCreatedEvent {
+    pub bet_id: u64,
+    pub target_bet_id: u64,
+    pub metric: u8,
+    pub comparison: u8,
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+// Permissionless resolve_meta_market: the target's final `value` for the pool's metric
+#[event]
+pub struct MetaResolutionEvent {
+    pub bet_id: u64,
+    pub target_bet_id: u64,
+    pub metric: u8,
+    pub value: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+// Winnings left unclaimed past the claim deadline, moved to the treasury by sweep_unclaimed
+#[event]
+pub struct UnclaimedSweptEvent {
+    pub bet_id: u64,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub claim_deadline: i64,
+    pub timestamp: i64,
+}
+
+// A creator's bond paid back to them, or to the treasury when `slashed` for an abandoned pool
+#[event]
+pub struct CreatorBondSettledEvent {
+    pub bet_id: u64,
+    pub creator: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub slashed: bool,
+    pub timestamp: i64,
+}
+
+// Every destination of a resolved pool's funds, emitted at resolution once they were checked
+// to add up: winners_pot + creator_fee + platform_fee + seed_returned + unallocated equals
+// total_reserve, and the insurance premiums split into payouts and the platform's surplus
+#[event]
+pub struct SettlementAuditEvent {
+    pub bet_id: u64,
+    pub winner: WinnerSide,
+    pub total_reserve: u64,
+    pub winners_pot: u64,   // principal and profit of every winning token (both sides if scalar)
+    pub creator_fee: u64,   // after the donation, which stays in winners_pot
+    pub platform_fee: u64,
+    pub referral_fees: u64, // part of platform_fee held back for referrers
+    pub referee_fee: u64,   // part of platform_fee held back for the referee
+    pub seed_returned: u64, // seed liquidity the creator may reclaim
+    pub unallocated: u64,   // profit no winning token can claim, left for sweep_unclaimed
+    pub insurance_payouts: u64,
+    pub insurance_surplus: u64,
+    pub timestamp: i64,
+}
+
+// A claim credited the entry's referrer their slice of the platform fee
+#[event]
+pub struct ReferralCreditedEvent {
+    pub referrer: Pubkey,
+    pub bet_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeeClaimedEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+// A deposit joined a scheduled pool's waitlist before it opened
+#[event]
+pub struct DepositQueuedEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub position: u64,
+    pub is_yes: bool,
+    pub sol_amount: u64,
+    pub opens_at: i64,
+    pub timestamp: i64,
+}
+
+// The head of the waitlist left the queue: placed into the pool (a DepositEvent follows) or,
+// when the pool could no longer take it, handed back to the user
+#[event]
+pub struct PendingDepositExecutedEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub position: u64,
+    pub sol_amount: u64,
+    pub placed: bool,
+    pub token_amount: u64, // 0 when refunded
+    pub timestamp: i64,
+}
+
+// A referee committee member voted; `resolved` once the vote brought its side to the threshold
+#[event]
+pub struct WinnerProposedEvent {
+    pub bet_id: u64,
+    pub member: Pubkey,
+    pub is_yes: bool,
+    pub yes_votes: u8,
+    pub no_votes: u8,
+    pub threshold: u8,
+    pub resolved: bool,
+    pub timestamp: i64,
+}
+
+// A user registered, replaced or removed (Pubkey::default()) the recovery key of their entry
+#[event]
+pub struct RecoveryKeySetEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub recovery_key: Pubkey,
+    pub timestamp: i64,
+}
+
+// The recovery key started taking over an entry; the user may cancel until `unlocks_at`
+#[event]
+pub struct RecoveryInitiatedEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub recovery_key: Pubkey,
+    pub unlocks_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelledEvent {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub recovery_key: Pubkey,
+    pub timestamp: i64,
+}
+
+// A history account created before the ring buffer was rewritten into it
+#[event]
+pub struct HistoryMigratedEvent {
+    pub bet_id: u64,
+    pub points: u16, // carried over, oldest first
+    pub timestamp: i64,
+}
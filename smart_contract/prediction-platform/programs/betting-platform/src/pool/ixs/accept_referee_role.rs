@@ -0,0 +1,48 @@
+use crate::{error::BettingError, PoolState, RefereeAcceptedEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct AcceptRefereeRoleInput {
+    bet_id: u64,
+}
+
+// The referee assigned through update_pool signs to take over resolution of the pool
+pub fn accept_referee_role(
+    ctx: Context<AAcceptRefereeRole>,
+    input: AcceptRefereeRoleInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let referee = &ctx.accounts.referee;
+
+    require!(
+        pool_state.referee.eq(&referee.key()),
+        BettingError::NotPendingReferee
+    );
+    require!(!pool_state.complete, BettingError::BetComplete);
+
+    pool_state.referee_accepted = true;
+
+    emit!(RefereeAcceptedEvent {
+        bet_id: input.bet_id,
+        referee: referee.key(),
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: AcceptRefereeRoleInput)]
+pub struct AAcceptRefereeRole<'info> {
+    pub referee: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
@@ -0,0 +1,101 @@
+use crate::{
+    constants::{ASSERTION_BOND, ASSERTION_LIVENESS_SECS},
+    error::BettingError,
+    resolution, time, Assertion, AssertionMadeEvent, MainState, PoolState,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct AssertOutcomeInput {
+    bet_id: u64,
+    is_yes: bool,
+}
+
+// Anyone may propose the outcome of an optimistic pool by bonding ASSERTION_BOND. The
+// assertion resolves the pool through settle_assertion once ASSERTION_LIVENESS_SECS pass
+// without a dispute.
+pub fn assert_outcome(ctx: Context<AAssertOutcome>, input: AssertOutcomeInput) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let now = time::now(&ctx.accounts.main_state)?;
+
+    require!(
+        pool_state.optimistic_resolution,
+        BettingError::OptimisticResolutionDisabled
+    );
+    // A range or scalar market's outcome comes from a value only the referee submits
+    require!(
+        !pool_state.resolves_by_value(),
+        BettingError::RangeValueRequired
+    );
+    resolution::ensure_unresolved(pool_state)?;
+    require!(
+        pool_state.has_ended(now),
+        BettingError::BetNotEnded
+    );
+
+    // The bond is escrowed in the assertion account itself
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.asserter.to_account_info(),
+                to: ctx.accounts.assertion.to_account_info(),
+            },
+        ),
+        ASSERTION_BOND,
+    )?;
+
+    let assertion = &mut ctx.accounts.assertion;
+    assertion.bet_id = input.bet_id;
+    assertion.asserter = ctx.accounts.asserter.key();
+    assertion.is_yes = input.is_yes;
+    assertion.bond = ASSERTION_BOND;
+    assertion.asserted_at = now;
+    assertion.expires_at = now.saturating_add(ASSERTION_LIVENESS_SECS);
+
+    emit!(AssertionMadeEvent {
+        bet_id: input.bet_id,
+        asserter: assertion.asserter,
+        is_yes: input.is_yes,
+        bond: ASSERTION_BOND,
+        expires_at: assertion.expires_at,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: AssertOutcomeInput)]
+pub struct AAssertOutcome<'info> {
+    #[account(mut)]
+    pub asserter: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // One open assertion per pool
+    #[account(
+        init,
+        payer = asserter,
+        space = 8 + Assertion::MAX_SIZE,
+        seeds = [Assertion::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub assertion: Box<Account<'info, Assertion>>,
+
+    pub system_program: Program<'info, System>,
+}
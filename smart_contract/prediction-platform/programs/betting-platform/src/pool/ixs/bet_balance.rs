@@ -0,0 +1,106 @@
+use crate::{error::BettingError, BetBalance, BetBalanceEvent, MainState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct BetBalanceInput {
+    pub amount: u64,
+}
+
+// Escrows lamports into the signer's BetBalance, creating it on first use
+pub fn fund_bet_balance(ctx: Context<AFundBetBalance>, input: BetBalanceInput) -> Result<()> {
+    require!(!ctx.accounts.main_state.paused, BettingError::ProtocolPaused);
+    require!(input.amount > 0, BettingError::InvalidBet);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.bet_balance.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
+
+    let bet_balance = &mut ctx.accounts.bet_balance;
+    bet_balance.user = ctx.accounts.user.key();
+    bet_balance.balance = bet_balance
+        .balance
+        .checked_add(input.amount)
+        .ok_or(BettingError::MathOverflow)?;
+    bet_balance.total_funded = bet_balance
+        .total_funded
+        .checked_add(input.amount)
+        .ok_or(BettingError::MathOverflow)?;
+
+    emit!(BetBalanceEvent {
+        user: bet_balance.user,
+        change: input.amount as i64,
+        balance: bet_balance.balance,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+// Returns unspent lamports to the user; always available, even while paused
+pub fn withdraw_bet_balance(
+    ctx: Context<AWithdrawBetBalance>,
+    input: BetBalanceInput,
+) -> Result<()> {
+    let bet_balance = &mut ctx.accounts.bet_balance;
+    require!(
+        input.amount <= bet_balance.balance,
+        BettingError::InsufficientBetBalance
+    );
+
+    bet_balance.balance -= input.amount;
+    bet_balance.sub_lamports(input.amount)?;
+    ctx.accounts.user.add_lamports(input.amount)?;
+
+    emit!(BetBalanceEvent {
+        user: bet_balance.user,
+        change: -(input.amount as i64),
+        balance: bet_balance.balance,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AFundBetBalance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + BetBalance::MAX_SIZE,
+        seeds = [BetBalance::PREFIX_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub bet_balance: Box<Account<'info, BetBalance>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AWithdrawBetBalance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BetBalance::PREFIX_SEED, user.key().as_ref()],
+        bump,
+        has_one = user,
+    )]
+    pub bet_balance: Box<Account<'info, BetBalance>>,
+}
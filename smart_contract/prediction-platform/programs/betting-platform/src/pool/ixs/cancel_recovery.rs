@@ -0,0 +1,61 @@
+use crate::time;
+use crate::{error::BettingError, EntryState, MainState, PoolState, RecoveryCancelledEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CancelRecoveryInput {
+    pub bet_id: u64,
+}
+
+// The user still holds their key: the recovery their recovery key started is called off. It
+// works at any time, even after the challenge window, so a user who notices late can stop
+// further installments going to the recovery key.
+pub fn cancel_recovery(ctx: Context<ACancelRecovery>, input: CancelRecoveryInput) -> Result<()> {
+    let entry_state = &mut ctx.accounts.entry_state;
+    require!(
+        entry_state.recovery_initiated_at != 0,
+        BettingError::NoRecoveryPending
+    );
+    entry_state.recovery_initiated_at = 0;
+
+    emit!(RecoveryCancelledEvent {
+        user: entry_state.user,
+        bet_id: input.bet_id,
+        recovery_key: entry_state.recovery_key,
+        timestamp: time::now(&ctx.accounts.main_state)?
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CancelRecoveryInput)]
+pub struct ACancelRecovery<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+}
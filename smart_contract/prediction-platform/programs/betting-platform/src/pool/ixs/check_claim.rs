@@ -0,0 +1,74 @@
+use crate::{require_claim_window, settle_claim, EntryState, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CheckClaimInput {
+    bet_id: u64,
+}
+
+// Why a claim would (not) go through, without the transaction failing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimCheck {
+    pub claimable: bool,
+    pub amount: u64,      // what `claim` would pay right now
+    pub reason_code: u32, // BettingError code `claim` would fail with (0 when claimable)
+    pub claimed_at: i64,
+}
+
+// Read-only dry run of `claim` for frontends (call it with `.view()`): runs the same checks
+// and settlement math on a copy of the entry and reports the outcome as return data
+pub fn check_claim(ctx: Context<ACheckClaim>, _input: CheckClaimInput) -> Result<ClaimCheck> {
+    let pool_state = &ctx.accounts.pool_state;
+    let mut entry_state: EntryState = (*ctx.accounts.entry_state).clone();
+    let claimed_at = entry_state.claimed_at;
+
+    let outcome = require_claim_window(pool_state, &ctx.accounts.main_state)
+        .and_then(|now| settle_claim(pool_state, &mut entry_state, now));
+    match outcome {
+        Ok(amount) => Ok(ClaimCheck {
+            claimable: true,
+            amount,
+            reason_code: 0,
+            claimed_at,
+        }),
+        Err(Error::AnchorError(error)) => Ok(ClaimCheck {
+            claimable: false,
+            amount: 0,
+            reason_code: error.error_code_number,
+            claimed_at,
+        }),
+        Err(error) => Err(error),
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: CheckClaimInput)]
+pub struct ACheckClaim<'info> {
+    /// CHECK: only used to derive the entry address; no signature is needed for a dry run
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+}
@@ -1,7 +1,12 @@
-use crate::constants::VAULT_SEED;
-use crate::{error::BettingError, EntryState, MainState, PoolState};
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::fees::{bps_of, referral_slice};
+use crate::vault::PoolVault;
+use crate::time;
+use crate::{
+    draw_guarantee, error::BettingError, ClaimEvent, EntryState, MainState, PoolGuarantee,
+    PoolState, ReferralCreditedEvent, ReferralState, SettlementReport,
+};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::system_instruction;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct ClaimInput {
@@ -14,23 +19,177 @@ pub fn claim(ctx: Context<AClaim>, _input: ClaimInput) -> Result<()> {
     let main_state = &ctx.accounts.main_state;
     let _system_program = &ctx.accounts.system_program;
     let user = &ctx.accounts.user;
+    pool_state.require_sol_denominated()?;
 
+    let now = require_claim_window(pool_state, main_state)?;
+    pool_state.check_settlement_report_passed(ctx.accounts.settlement_report.is_some())?;
+    if let Some(report) = &ctx.accounts.settlement_report {
+        check_settlement_report(pool_state, report)?;
+    }
+    let installments_before = entry_state.installments_claimed;
+    let claimable_amount = settle_claim(pool_state, entry_state, now)?;
+    emit_claim_event(pool_state, entry_state, installments_before, claimable_amount)?;
+
+    let vault = PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    );
+    // Guaranteed pools: whatever the vault can't cover comes out of the guarantee escrow
+    let drawn = draw_guarantee(
+        pool_state,
+        ctx.accounts.guarantee.as_deref_mut(),
+        &user.to_account_info(),
+        claimable_amount.saturating_sub(vault.available()?),
+    )?;
+
+    vault.pay(
+        pool_state,
+        &user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        claimable_amount - drawn,
+    )?;
+
+    if let Some(referral_state) = ctx.accounts.referral_state.as_deref_mut() {
+        credit_referral(
+            pool_state,
+            entry_state,
+            &vault,
+            referral_state,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    Ok(())
+}
+
+// Moves the entry's referrer their slice of the platform fee held back at resolution (see
+// referral_slice) into their ReferralState, once per entry. Claims without the account still
+// pay the winner; the slice then waits for a later installment or goes with sweep_unclaimed.
+pub(crate) fn credit_referral<'info>(
+    pool_state: &mut PoolState,
+    entry_state: &mut EntryState,
+    vault: &PoolVault<'_, 'info>,
+    referral_state: &mut Account<'info, ReferralState>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let amount = referral_slice(pool_state, entry_state);
+    if amount == 0 {
+        return Ok(());
+    }
+    entry_state.referral_credited = true;
+    referral_state.accrued = referral_state
+        .accrued
+        .checked_add(amount)
+        .ok_or(BettingError::MathOverflow)?;
+    vault.pay(pool_state, &referral_state.to_account_info(), system_program, amount)?;
+
+    emit!(ReferralCreditedEvent {
+        referrer: referral_state.referrer,
+        bet_id: pool_state.bet_id,
+        user: entry_state.user,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+// For fixed-time markets, ensure the end time passed; for open-ended markets, completion suffices.
+// Claims never depend on how recent the transaction is, only on state, so offline-signed
+// claims stay valid.
+// Returns the current time for settle_claim.
+pub(crate) fn require_claim_window(pool_state: &PoolState, main_state: &MainState) -> Result<i64> {
+    let now = time::now(main_state)?;
+    require!(
+        pool_state.has_ended(now),
+        BettingError::EndTimeNotReached
+    );
+    Ok(now)
+}
+
+// Claims against a pool with a SettlementReport must split the exact profit pot the report
+// recorded at resolution, so any later drift in PoolState's settlement figures is caught
+pub(crate) fn check_settlement_report(
+    pool_state: &PoolState,
+    report: &SettlementReport,
+) -> Result<()> {
+    let winner = pool_state.winner.is_yes();
     require!(
-        !entry_state.is_claimed,
-        BettingError::AlreadyClaimed
+        report.winner_is_yes == winner
+            && winner_profit_pot(pool_state, winner) == report.winner_profit_pot as u128,
+        BettingError::SettlementReportMismatch
     );
-    // For fixed-time markets, ensure the end time passed; for open-ended markets, completion suffices.
-    if pool_state.end_timestamp >= 0 {
-        require!(
-            pool_state.end_timestamp < Clock::get()?.unix_timestamp,
-            BettingError::BetNotEnded
-        );
+    Ok(())
+}
+
+// ---------------------------------------------------------------------
+// Settles a winning entry and returns the lamports to pay out now.
+// Architectural rule: payouts depend ONLY on PoolState, EntryState and the vault. Auxiliary
+// accounts (history, summaries, metadata, oracles, even MainState) must never be able to
+// block a winner from being paid; emergency_claim relies on this.
+// ---------------------------------------------------------------------
+pub(crate) fn settle_claim(
+    pool_state: &PoolState,
+    entry_state: &mut EntryState,
+    now: i64,
+) -> Result<u64> {
+    if entry_state.is_claimed {
+        msg!("Entry was claimed at {}", entry_state.claimed_at);
+        return err!(BettingError::AlreadyClaimedAt);
     }
-    require!(pool_state.complete, BettingError::BetNotComplete);
-    let winner: bool = pool_state.winner.eq(&"yes");
-    require!(entry_state.is_yes == winner, BettingError::WrongBet);
+    require!(pool_state.complete, BettingError::MarketNotResolved);
+    require!(
+        !pool_state.claims_closed(now),
+        BettingError::ClaimWindowClosed
+    );
+    let claim_total_u128 = if pool_state.scalar_bounds.is_set() {
+        scalar_claim_total(pool_state, entry_state)?
+    } else {
+        binary_claim_total(pool_state, entry_state)?
+    };
+
+    // Annuity-style pools release the claim in equal installments after resolution.
+    // Amounts are computed cumulatively so the final installment absorbs rounding dust.
+    let installment_count = pool_state.installment_count.max(1);
+    let installments_due: u8 = if installment_count == 1 {
+        1
+    } else {
+        let elapsed = now
+            .saturating_sub(pool_state.resolved_timestamp)
+            .max(0);
+        let periods = elapsed / pool_state.installment_period.max(1);
+        (1 + periods).min(installment_count as i64) as u8
+    };
+    require!(
+        installments_due > entry_state.installments_claimed,
+        BettingError::InstallmentNotDue
+    );
+
+    let paid_before = claim_total_u128
+        .saturating_mul(entry_state.installments_claimed as u128)
+        / installment_count as u128;
+    let paid_after = claim_total_u128
+        .saturating_mul(installments_due as u128)
+        / installment_count as u128;
 
-    entry_state.is_claimed = true;
+    entry_state.installments_claimed = installments_due;
+    entry_state.is_claimed = installments_due == installment_count;
+    entry_state.claimed_at = now;
+
+    let claimable_amount: u64 = paid_after
+        .saturating_sub(paid_before)
+        .try_into()
+        .map_err(|_| error!(BettingError::MathOverflow))?;
+
+    Ok(claimable_amount)
+}
+
+// Full payout of a winning entry in a YES/NO (or range) market
+fn binary_claim_total(pool_state: &PoolState, entry_state: &EntryState) -> Result<u128> {
+    let winner: bool = pool_state.winner.is_yes();
+    require!(entry_state.is_yes == winner, BettingError::NotWinnerSide);
 
     // ------------------------------------------------------------------
     // PRINCIPAL + LOSING RESERVE PROFIT MODEL
@@ -43,76 +202,180 @@ pub fn claim(ctx: Context<AClaim>, _input: ClaimInput) -> Result<()> {
     // ------------------------------------------------------------------
 
     // Identify reserves & supplies by outcome
-    let (yes_reserve, no_reserve, winning_supply, user_tokens) = if winner {
-        (
-            pool_state.yes_reserve as u128,
-            pool_state.no_reserve as u128,
-            pool_state.yes_supply as u128,
-            entry_state.token_balance as u128,
-        )
+    let (winning_supply, user_tokens) = if winner {
+        (pool_state.yes_supply as u128, entry_state.token_balance as u128)
     } else {
-        (
-            pool_state.yes_reserve as u128,
-            pool_state.no_reserve as u128,
-            pool_state.no_supply as u128,
-            entry_state.token_balance as u128,
-        )
+        (pool_state.no_supply as u128, entry_state.token_balance as u128)
     };
 
     require!(winning_supply > 0, BettingError::MathOverflow);
     require!(user_tokens > 0, BettingError::WrongBet);
 
-    let total_reserve = yes_reserve.saturating_add(no_reserve);
+    let available_profit = winner_profit_pot(pool_state, winner);
 
-    // Fees now applied on total reserve (both sides contribute)
-    let creator_fee = total_reserve
-        .saturating_mul(main_state.creator_fee_percent as u128)
-        / 10000u128;
-    let platform_fee = total_reserve
-        .saturating_mul(main_state.platform_fee_percent as u128)
-        / 10000u128;
+    let profit_share_u128 = if available_profit > 0 {
+        user_tokens
+            .saturating_mul(available_profit)
+            / winning_supply
+    } else { 0 };
+
+    let principal_u128: u128 = entry_state.deposited_sol_amount as u128;
+    let claim_total_u128 = principal_u128.saturating_add(profit_share_u128);
+
+    Ok(claim_total_u128)
+}
+
+// Full payout of an entry in a scalar market: its side's pot (see scalar_side_pot), split by
+// token weight. Either side may claim, and a side's principal is not guaranteed.
+fn scalar_claim_total(pool_state: &PoolState, entry_state: &EntryState) -> Result<u128> {
+    let side_supply = if entry_state.is_yes {
+        pool_state.yes_supply
+    } else {
+        pool_state.no_supply
+    } as u128;
+    let user_tokens = entry_state.token_balance as u128;
+    require!(side_supply > 0 && user_tokens > 0, BettingError::WrongBet);
+
+    Ok(scalar_side_pot(pool_state, entry_state.is_yes).saturating_mul(user_tokens) / side_supply)
+}
+
+// Emits ClaimEvent for the `total` settle_claim just released, given the entry's installment
+// count before the claim. Principal is spread over installments the same cumulative way as
+// the payout, so the entry's principals add up to its deposit once fully claimed.
+pub(crate) fn emit_claim_event(
+    pool_state: &PoolState,
+    entry_state: &EntryState,
+    installments_before: u8,
+    total: u64,
+) -> Result<()> {
+    let principal = claimed_principal(pool_state, entry_state, installments_before).min(total);
+    emit!(ClaimEvent {
+        user: entry_state.user,
+        bet_id: pool_state.bet_id,
+        principal,
+        profit: total - principal,
+        total,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+// Principal part of the installments settled since `installments_before`
+pub(crate) fn claimed_principal(
+    pool_state: &PoolState,
+    entry_state: &EntryState,
+    installments_before: u8,
+) -> u64 {
+    let installment_count = pool_state.installment_count.max(1) as u128;
+    let principal = entry_state.deposited_sol_amount as u128;
+    let paid_by = |installments: u8| principal * installments as u128 / installment_count;
+    (paid_by(entry_state.installments_claimed) - paid_by(installments_before)) as u64
+}
+
+// Profit split among the winning side's tokens: the losing reserve after fees. Fees are
+// applied on total reserve (both sides contribute). The creator fee was accrued deposit by
+// deposit and the platform fee was fixed at resolution, so the exact figures paid out are
+// reserved here.
+pub(crate) fn winner_profit_pot(pool_state: &PoolState, winner: bool) -> u128 {
+    let total_reserve = (pool_state.yes_reserve as u128).saturating_add(pool_state.no_reserve as u128);
+    let creator_fee = pool_state.creator_fee_accrued as u128;
+    let platform_fee = pool_state.platform_fee_amount as u128;
 
     // Principal of winning side is the sum of deposits represented by its token supply.
     // We reconstruct an approximate principal_winning_side by summing reserves on that side.
-    let winning_reserve = if winner { yes_reserve } else { no_reserve };
+    let winning_reserve = if winner {
+        pool_state.yes_reserve
+    } else {
+        pool_state.no_reserve
+    } as u128;
 
     // Available profit after removing winning principal and fees
-    let available_profit = total_reserve
+    total_reserve
         .saturating_sub(winning_reserve)
         .saturating_sub(creator_fee)
-        .saturating_sub(platform_fee);
+        .saturating_sub(platform_fee)
+}
 
-    let profit_share_u128 = if available_profit > 0 {
-        user_tokens
-            .saturating_mul(available_profit)
-            / winning_supply
-    } else { 0 };
+// A scalar market's pot is everything both sides put in, minus the fees. Long (YES) takes
+// scalar_long_bps of it and short (NO) the rest, except that a side nobody backed leaves its
+// share to the other rather than stranding it in the vault.
+pub(crate) fn scalar_side_pot(pool_state: &PoolState, is_yes: bool) -> u128 {
+    let pot = (pool_state.yes_reserve as u128)
+        .saturating_add(pool_state.no_reserve as u128)
+        .saturating_sub(pool_state.creator_fee_accrued as u128)
+        .saturating_sub(pool_state.platform_fee_amount as u128);
+    let long_bps = match (pool_state.yes_supply > 0, pool_state.no_supply > 0) {
+        (true, false) => 10_000,
+        (false, true) => 0,
+        _ => pool_state.scalar_long_bps,
+    };
+    let long_pot = bps_of(pot, long_bps);
+    if is_yes {
+        long_pot
+    } else {
+        pot - long_pot
+    }
+}
 
-    let principal_u128: u128 = entry_state.deposited_sol_amount as u128;
-    let claim_total_u128 = principal_u128.saturating_add(profit_share_u128);
+// Burn-to-claim counterpart of settle_claim for `amount` SPL outcome tokens of one side. A
+// token carries the average principal of its side's tokenized entries plus the same profit
+// share per token as an entry claim, so tokenized and untokenized positions settle against
+// the same totals. In refund mode either side gets its principal back. Same architectural
+// rule as settle_claim: only PoolState and the vault are involved.
+pub(crate) fn settle_token_claim(
+    pool_state: &mut PoolState,
+    is_yes: bool,
+    amount: u64,
+) -> Result<u64> {
+    require!(
+        pool_state.complete || pool_state.refund_mode,
+        BettingError::MarketNotResolved
+    );
+    let winner = pool_state.winner.is_yes();
+    if !pool_state.refund_mode {
+        require!(is_yes == winner, BettingError::NotWinnerSide);
+    }
+
+    let (tokenized_supply, tokenized_principal) = if is_yes {
+        (pool_state.tokenized_yes_supply, pool_state.tokenized_yes_principal)
+    } else {
+        (pool_state.tokenized_no_supply, pool_state.tokenized_no_principal)
+    };
+    require!(
+        amount > 0 && amount <= tokenized_supply,
+        BettingError::InvalidBet
+    );
 
-    let claimable_amount: u64 = claim_total_u128
+    // The last tokens out take whatever principal rounding left behind
+    let principal: u64 = ((amount as u128).saturating_mul(tokenized_principal as u128)
+        / tokenized_supply as u128)
         .try_into()
         .map_err(|_| error!(BettingError::MathOverflow))?;
 
-    let transfer_instruction = system_instruction::transfer(
-        &ctx.accounts.sol_vault.to_account_info().key(),
-        &user.to_account_info().key(),
-        claimable_amount,
-    );
+    let profit_share: u64 = if pool_state.refund_mode {
+        0
+    } else {
+        let winning_supply = if winner {
+            pool_state.yes_supply
+        } else {
+            pool_state.no_supply
+        } as u128;
+        ((amount as u128).saturating_mul(winner_profit_pot(pool_state, winner)) / winning_supply)
+            .try_into()
+            .map_err(|_| error!(BettingError::MathOverflow))?
+    };
 
-    // Invoke the transfer instruction with the PDA's seeds
-    anchor_lang::solana_program::program::invoke_signed(
-        &transfer_instruction,
-        &[
-            ctx.accounts.sol_vault.to_account_info(),
-            user.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[&[VAULT_SEED.as_bytes(), &[ctx.bumps.sol_vault]]],
-    )?;
+    if is_yes {
+        pool_state.tokenized_yes_supply -= amount;
+        pool_state.tokenized_yes_principal -= principal;
+    } else {
+        pool_state.tokenized_no_supply -= amount;
+        pool_state.tokenized_no_principal -= principal;
+    }
 
-    Ok(())
+    principal
+        .checked_add(profit_share)
+        .ok_or(error!(BettingError::MathOverflow))
 }
 
 #[derive(Accounts)]
@@ -156,5 +419,37 @@ pub struct AClaim<'info> {
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub sol_vault: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    // Required once the pool has one (see has_settlement_report); omitted for pools resolved
+    // before settlement reports existed
+    #[account(
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Option<Box<Account<'info, SettlementReport>>>,
+
+    // Only passed for guaranteed pools, see draw_guarantee. Matched by its `pool` field rather
+    // than seeds so clients don't derive it for every claim.
+    #[account(
+        mut,
+        constraint = guarantee.pool == pool_state.key() @ BettingError::PoolNotGuaranteed
+    )]
+    pub guarantee: Option<Box<Account<'info, PoolGuarantee>>>,
+
+    // Only passed for entries with a referrer, see credit_referral
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, entry_state.referrer.as_ref()],
+        bump
+    )]
+    pub referral_state: Option<Box<Account<'info, ReferralState>>>,
+
     pub system_program: Program<'info, System>,
 }
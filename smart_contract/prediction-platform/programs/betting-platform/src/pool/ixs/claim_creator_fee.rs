@@ -1,7 +1,8 @@
-use crate::constants::VAULT_SEED;
-use crate::{error::BettingError, MainState, PoolState};
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::time;
+use crate::{error::BettingError, CreatorFeeClaimedEvent, MainState, PoolState};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::system_instruction;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct ClaimCreatorFeeInput {
@@ -12,6 +13,7 @@ pub fn claim_creator_fee(ctx: Context<AClaimCreatorFee>, _input: ClaimCreatorFee
     let pool_state = &mut ctx.accounts.pool_state;
     let main_state = &ctx.accounts.main_state;
     let creator = &ctx.accounts.creator;
+    pool_state.require_sol_denominated()?;
 
     require!(
         pool_state.creator == creator.key(),
@@ -22,45 +24,42 @@ pub fn claim_creator_fee(ctx: Context<AClaimCreatorFee>, _input: ClaimCreatorFee
         BettingError::AlreadyClaimed
     );
     // For fixed-time markets, ensure end time passed; open-ended markets use completion only.
-    if pool_state.end_timestamp >= 0 {
-        require!(
-            pool_state.end_timestamp < Clock::get()?.unix_timestamp,
-            BettingError::BetNotEnded
-        );
-    }
+    require!(
+        pool_state.has_ended(time::now(main_state)?),
+        BettingError::BetNotEnded
+    );
     require!(pool_state.complete, BettingError::BetNotComplete);
 
     // Mark as claimed first to prevent reentrancy
     pool_state.creator_fee_claimed = true;
 
-    // Fee taken proportionally from total reserve
-    let total_reserve = (pool_state.yes_reserve as u128)
-        .saturating_add(pool_state.no_reserve as u128);
-    let creator_fee = total_reserve
-        .saturating_mul(main_state.creator_fee_percent as u128)
-        / 10000u128;
-    let creator_fee: u64 = creator_fee.min(u64::MAX as u128) as u64;
+    // Fee accrued on each deposit at the pool's creator fee rate
+    let creator_fee: u64 = pool_state.creator_fee_accrued;
 
     // Only transfer if there's actually a fee to claim
     if creator_fee > 0 {
-        let transfer_instruction = system_instruction::transfer(
-            &ctx.accounts.sol_vault.to_account_info().key(),
-            &creator.to_account_info().key(),
+        PoolVault::select(
+            pool_state,
+            &ctx.accounts.sol_vault,
+            ctx.bumps.sol_vault,
+            &ctx.accounts.pool_vault,
+            ctx.bumps.pool_vault,
+        )
+        .pay(
+            pool_state,
+            &creator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
             creator_fee,
-        );
-
-        // Invoke the transfer instruction with the PDA's seeds
-        anchor_lang::solana_program::program::invoke_signed(
-            &transfer_instruction,
-            &[
-                ctx.accounts.sol_vault.to_account_info(),
-                creator.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&[VAULT_SEED.as_bytes(), &[ctx.bumps.sol_vault]]],
         )?;
     }
 
+    emit!(CreatorFeeClaimedEvent {
+        creator: creator.key(),
+        bet_id: pool_state.bet_id,
+        amount: creator_fee,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
     Ok(())
 }
 
@@ -95,5 +94,13 @@ pub struct AClaimCreatorFee<'info> {
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub sol_vault: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
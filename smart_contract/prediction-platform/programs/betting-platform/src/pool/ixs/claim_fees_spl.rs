@@ -0,0 +1,103 @@
+use crate::{
+    error::BettingError, pay_from_token_vault, time, CreatorFeeClaimedEvent, MainState, PoolState,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimFeeSplInput {
+    bet_id: u64,
+}
+
+// claim_creator_fee for SPL-denominated pools
+pub fn claim_creator_fee_spl(ctx: Context<AClaimFeeSpl>, input: ClaimFeeSplInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        pool_state.creator.eq(&ctx.accounts.claimer.key()),
+        BettingError::Unauthorized
+    );
+    require!(!pool_state.creator_fee_claimed, BettingError::AlreadyClaimed);
+    require!(
+        pool_state.has_ended(time::now(main_state)?),
+        BettingError::BetNotEnded
+    );
+    require!(pool_state.complete, BettingError::BetNotComplete);
+    pool_state.creator_fee_claimed = true;
+
+    pay_from_token_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.destination,
+        pool_state,
+        input.bet_id,
+        ctx.bumps.pool_state,
+        pool_state.creator_fee_accrued,
+    )?;
+
+    emit!(CreatorFeeClaimedEvent {
+        creator: pool_state.creator,
+        bet_id: input.bet_id,
+        amount: pool_state.creator_fee_accrued,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+// SPL pools keep the platform fee fixed at resolution in their token vault (SOL pools pay it
+// out during resolution); the owner withdraws it here
+pub fn claim_platform_fee_spl(ctx: Context<AClaimFeeSpl>, input: ClaimFeeSplInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(
+        ctx.accounts.main_state.owner.eq(&ctx.accounts.claimer.key()),
+        BettingError::Unauthorized
+    );
+    require!(pool_state.complete, BettingError::BetNotComplete);
+    require!(!pool_state.platform_fee_claimed, BettingError::AlreadyClaimed);
+    pool_state.platform_fee_claimed = true;
+
+    pay_from_token_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.destination,
+        pool_state,
+        input.bet_id,
+        ctx.bumps.pool_state,
+        pool_state.platform_fee_amount,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimFeeSplInput)]
+pub struct AClaimFeeSpl<'info> {
+    pub claimer: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_state.token_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = pool_state.token_mint)]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
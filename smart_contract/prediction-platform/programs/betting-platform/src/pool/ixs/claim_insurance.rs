@@ -0,0 +1,104 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::fees::insurance_payout;
+use crate::{error::BettingError, EntryState, InsuranceClaimedEvent, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimInsuranceInput {
+    bet_id: u64,
+}
+
+// Pays a losing insured entry its coverage out of the pool's premium sub-pool
+pub fn claim_insurance(ctx: Context<AClaimInsurance>, _input: ClaimInsuranceInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+    let user = &ctx.accounts.user;
+    pool_state.require_sol_denominated()?;
+
+    require!(pool_state.complete, BettingError::BetNotComplete);
+    require!(entry_state.insured_amount > 0, BettingError::NotInsured);
+    require!(!entry_state.insurance_claimed, BettingError::AlreadyClaimed);
+    require!(
+        !pool_state.claims_closed(Clock::get()?.unix_timestamp),
+        BettingError::ClaimWindowClosed
+    );
+
+    let winner_is_yes = pool_state.winner.is_yes();
+    // Winners are paid by `claim`; insurance only covers the losing side
+    require!(entry_state.is_yes != winner_is_yes, BettingError::WrongBet);
+
+    entry_state.insurance_claimed = true;
+    let amount = insurance_payout(pool_state, winner_is_yes, entry_state.insured_amount);
+
+    if amount > 0 {
+        PoolVault::select(
+            pool_state,
+            &ctx.accounts.sol_vault,
+            ctx.bumps.sol_vault,
+            &ctx.accounts.pool_vault,
+            ctx.bumps.pool_vault,
+        )
+        .pay(
+            pool_state,
+            &user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            amount,
+        )?;
+    }
+
+    emit!(InsuranceClaimedEvent {
+        user: user.key(),
+        bet_id: pool_state.bet_id,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimInsuranceInput)]
+pub struct AClaimInsurance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
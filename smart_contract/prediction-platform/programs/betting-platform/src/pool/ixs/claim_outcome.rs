@@ -0,0 +1,76 @@
+use crate::{
+    constants::VAULT_SEED, error::BettingError, pay_from_vault, OutcomeEntry, OutcomeMarket,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimOutcomeInput {
+    pub bet_id: u64,
+}
+
+// Pays a winning OutcomeEntry its principal plus its token-weighted share of the profit pot,
+// the settle_claim model with outcomes indexed by u8
+pub fn claim_outcome(ctx: Context<AClaimOutcome>, _input: ClaimOutcomeInput) -> Result<()> {
+    let market = &ctx.accounts.outcome_market;
+    let entry = &mut ctx.accounts.outcome_entry;
+    if entry.is_claimed {
+        msg!("Entry was claimed at {}", entry.claimed_at);
+        return err!(BettingError::AlreadyClaimedAt);
+    }
+    require!(market.complete, BettingError::MarketNotResolved);
+    require!(entry.outcome == market.winner, BettingError::NotWinnerSide);
+    require!(entry.token_balance > 0, BettingError::WrongBet);
+
+    let winning_supply = market.supplies[market.winner as usize] as u128;
+    let profit_share = (entry.token_balance as u128).saturating_mul(market.winner_profit_pot())
+        / winning_supply;
+    let payout: u64 = (entry.deposited_sol_amount as u128)
+        .saturating_add(profit_share)
+        .try_into()
+        .map_err(|_| error!(BettingError::MathOverflow))?;
+
+    entry.is_claimed = true;
+    entry.claimed_at = Clock::get()?.unix_timestamp;
+
+    pay_from_vault(
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.bumps.sol_vault,
+        payout,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimOutcomeInput)]
+pub struct AClaimOutcome<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [OutcomeMarket::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub outcome_market: Box<Account<'info, OutcomeMarket>>,
+
+    #[account(
+        mut,
+        seeds = [
+            OutcomeEntry::PREFIX_SEED,
+            &outcome_market.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub outcome_entry: Account<'info, OutcomeEntry>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
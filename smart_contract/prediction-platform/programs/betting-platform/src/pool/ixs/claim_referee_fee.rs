@@ -0,0 +1,99 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{error::BettingError, time, MainState, PoolState, RefereeFeeClaimedEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimRefereeFeeInput {
+    bet_id: u64,
+}
+
+// Pays the referee who resolved the pool their cut of its platform fee, set aside by
+// finalize_outcome (see fees::referee_fee) together with the key that earned it
+pub fn claim_referee_fee(ctx: Context<AClaimRefereeFee>, _input: ClaimRefereeFeeInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let referee = &ctx.accounts.referee;
+    pool_state.require_sol_denominated()?;
+
+    require!(pool_state.complete, BettingError::BetNotComplete);
+    require!(
+        !pool_state.referee_fee_claimed,
+        BettingError::AlreadyClaimed
+    );
+
+    let referee_fee = pool_state.referee_fee_amount;
+    require!(
+        referee_fee == 0 || pool_state.referee_fee_recipient.eq(referee.key),
+        BettingError::Unauthorized
+    );
+
+    // Mark as claimed first to prevent reentrancy
+    pool_state.referee_fee_claimed = true;
+
+    if referee_fee > 0 {
+        PoolVault::select(
+            pool_state,
+            &ctx.accounts.sol_vault,
+            ctx.bumps.sol_vault,
+            &ctx.accounts.pool_vault,
+            ctx.bumps.pool_vault,
+        )
+        .pay(
+            pool_state,
+            &referee.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            referee_fee,
+        )?;
+    }
+
+    emit!(RefereeFeeClaimedEvent {
+        referee: referee.key(),
+        bet_id: pool_state.bet_id,
+        amount: referee_fee,
+        timestamp: time::now(&ctx.accounts.main_state)?
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimRefereeFeeInput)]
+pub struct AClaimRefereeFee<'info> {
+    #[account(mut)]
+    pub referee: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = referee
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,87 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{error::BettingError, EntryState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimRefundInput {
+    bet_id: u64,
+}
+
+// Returns an entry's full deposit, and any insurance premium, from a pool in refund mode
+// (no fees are taken)
+pub fn claim_refund(ctx: Context<AClaimRefund>, _input: ClaimRefundInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+    let user = &ctx.accounts.user;
+    pool_state.require_sol_denominated()?;
+
+    require!(pool_state.refund_mode, BettingError::NotInRefundMode);
+    require!(!entry_state.is_claimed, BettingError::AlreadyClaimed);
+    require!(entry_state.deposited_sol_amount > 0, BettingError::InvalidBet);
+
+    entry_state.is_claimed = true;
+    entry_state.claimed_at = Clock::get()?.unix_timestamp;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        entry_state.deposited_sol_amount + entry_state.insurance_premium_paid,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimRefundInput)]
+pub struct AClaimRefund<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,115 @@
+use crate::{
+    check_settlement_report, emit_claim_event, require_claim_window, settle_claim, EntryState,
+    MainState, PoolState, SettlementReport,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimSplInput {
+    bet_id: u64,
+}
+
+// claim for SPL-denominated pools: same settlement as claim, paid from the pool's token vault
+pub fn claim_spl(ctx: Context<AClaimSpl>, input: ClaimSplInput) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+
+    let now = require_claim_window(pool_state, &ctx.accounts.main_state)?;
+    pool_state.check_settlement_report_passed(ctx.accounts.settlement_report.is_some())?;
+    if let Some(report) = &ctx.accounts.settlement_report {
+        check_settlement_report(pool_state, report)?;
+    }
+    let installments_before = entry_state.installments_claimed;
+    let claimable_amount = settle_claim(pool_state, entry_state, now)?;
+    emit_claim_event(pool_state, entry_state, installments_before, claimable_amount)?;
+
+    pay_from_token_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.user_token_account,
+        pool_state,
+        input.bet_id,
+        ctx.bumps.pool_state,
+        claimable_amount,
+    )
+}
+
+// Transfers `amount` out of an SPL pool's token vault, signed by the pool PDA
+pub(crate) fn pay_from_token_vault<'info>(
+    token_program: &Program<'info, Token>,
+    token_vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    pool_state: &Account<'info, PoolState>,
+    bet_id: u64,
+    pool_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: token_vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+            &[&[PoolState::PREFIX_SEED, &bet_id.to_le_bytes(), &[pool_bump]]],
+        ),
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimSplInput)]
+pub struct AClaimSpl<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_state.token_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = pool_state.token_mint)]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    // Required once the pool has one (see has_settlement_report); omitted for pools resolved
+    // before settlement reports existed
+    #[account(
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Option<Box<Account<'info, SettlementReport>>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,120 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::permit::{permit_message, verify_permit};
+use crate::time;
+use crate::{
+    emit_claim_event, error::BettingError, require_claim_window, settle_claim, EntryState,
+    MainState, PoolState,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimWithPermitInput {
+    pub bet_id: u64,
+    pub expires_at: i64,
+}
+
+// Gasless claim: a relayer pays for the transaction and the payout goes to the recipient the
+// winner named in their signed permit (see crate::permit for the message format)
+pub fn claim_with_permit(
+    ctx: Context<AClaimWithPermit>,
+    input: ClaimWithPermitInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+    let main_state = &ctx.accounts.main_state;
+    let recipient = &ctx.accounts.recipient;
+    pool_state.require_sol_denominated()?;
+
+    let now = time::now(main_state)?;
+    require!(now <= input.expires_at, BettingError::PermitExpired);
+
+    let message = permit_message(ctx.program_id, input.bet_id, recipient.key, input.expires_at);
+    verify_permit(&ctx.accounts.instructions, ctx.accounts.user.key, &message)?;
+
+    // Same end-time rule as claim
+    require_claim_window(pool_state, main_state)?;
+
+    let installments_before = entry_state.installments_claimed;
+    let claimable_amount = settle_claim(pool_state, entry_state, now)?;
+    emit_claim_event(pool_state, entry_state, installments_before, claimable_amount)?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &recipient.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        claimable_amount,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimWithPermitInput)]
+pub struct AClaimWithPermit<'info> {
+    // Pays the transaction fee; needs no relation to the winner
+    pub relayer: Signer<'info>,
+
+    /// CHECK: the winner; authorization comes from the Ed25519 permit, not a transaction signature
+    pub user: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    /// CHECK: checked against the instructions sysvar id
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,119 @@
+use crate::constants::{OUTCOME_MINT_SEED, POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{error::BettingError, settle_token_claim, PoolState, TokenClaimEvent};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClaimWithTokensInput {
+    bet_id: u64,
+    is_yes: bool,
+    amount: u64,
+}
+
+// Burn-to-claim: any holder of outcome tokens redeems them for the side's payout (or its
+// principal in refund mode). Untokenized positions keep claiming through their EntryState.
+pub fn claim_with_tokens(ctx: Context<AClaimWithTokens>, input: ClaimWithTokensInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let holder = &ctx.accounts.holder;
+    pool_state.require_sol_denominated()?;
+    require!(
+        !pool_state.claims_closed(Clock::get()?.unix_timestamp),
+        BettingError::ClaimWindowClosed
+    );
+
+    let payout = settle_token_claim(pool_state, input.is_yes, input.amount)?;
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.outcome_mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: holder.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
+
+    if payout > 0 {
+        PoolVault::select(
+            pool_state,
+            &ctx.accounts.sol_vault,
+            ctx.bumps.sol_vault,
+            &ctx.accounts.pool_vault,
+            ctx.bumps.pool_vault,
+        )
+        .pay(
+            pool_state,
+            &holder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            payout,
+        )?;
+    }
+
+    emit!(TokenClaimEvent {
+        holder: holder.key(),
+        bet_id: input.bet_id,
+        is_yes: input.is_yes,
+        token_amount: input.amount,
+        payout,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClaimWithTokensInput)]
+pub struct AClaimWithTokens<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            OUTCOME_MINT_SEED.as_bytes(),
+            &input.bet_id.to_le_bytes(),
+            &[input.is_yes as u8],
+        ],
+        bump
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = outcome_mint,
+        token::authority = holder,
+    )]
+    pub holder_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+use crate::{error::BettingError, EntryState, PoolState, RentReclaimedEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CloseEntryInput {
+    bet_id: u64,
+}
+
+// Permissionless: once nothing is left to pay out on an entry (see EntryState::is_settled),
+// it is closed and its rent goes back to the user who opened it
+pub fn close_entry(ctx: Context<ACloseEntry>, input: CloseEntryInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(
+        ctx.accounts.entry_state.is_settled(pool_state),
+        BettingError::EntryNotSettled
+    );
+    pool_state.open_entries = pool_state.open_entries.saturating_sub(1);
+
+    let entry_info = ctx.accounts.entry_state.to_account_info();
+    emit!(RentReclaimedEvent {
+        bet_id: input.bet_id,
+        account: entry_info.key(),
+        recipient: ctx.accounts.user.key(),
+        lamports: entry_info.lamports(),
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CloseEntryInput)]
+pub struct ACloseEntry<'info> {
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+}
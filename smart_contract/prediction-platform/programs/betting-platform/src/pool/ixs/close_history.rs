@@ -0,0 +1,61 @@
+use crate::{error::BettingError, PoolHistoryState, PoolState, RentReclaimedEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CloseHistoryInput {
+    bet_id: u64,
+}
+
+// Permissionless: once a pool can take no more deposits (resolved or in refund mode), its
+// PoolHistoryState is closed and the exact balance goes back to whoever funded it at creation
+pub fn close_history(ctx: Context<ACloseHistory>, input: CloseHistoryInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(
+        pool_state.complete || pool_state.refund_mode,
+        BettingError::BetNotComplete
+    );
+
+    let history_info = ctx.accounts.history_state.to_account_info();
+    let lamports = history_info.lamports();
+    pool_state.rent_reclaimed_lamports = pool_state
+        .rent_reclaimed_lamports
+        .checked_add(lamports)
+        .ok_or(BettingError::MathOverflow)?;
+    pool_state.history_closed = true;
+
+    emit!(RentReclaimedEvent {
+        bet_id: input.bet_id,
+        account: history_info.key(),
+        recipient: ctx.accounts.rent_payer.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CloseHistoryInput)]
+pub struct ACloseHistory<'info> {
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = rent_payer,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: AccountLoader<'info, PoolHistoryState>,
+
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+}
@@ -0,0 +1,97 @@
+use crate::{
+    constants::{POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    vault::PoolVault,
+    PoolState, RentReclaimedEvent,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClosePoolInput {
+    bet_id: u64,
+}
+
+// Permissionless: once a pool is fully settled (see PoolState::is_fully_settled), the pool
+// account and whatever its own vault still holds (its rent and rounding dust) go back to whoever
+// funded the pool at creation. The OutcomeOracle and SettlementReport stay as its record.
+pub fn close_pool(ctx: Context<AClosePool>, input: ClosePoolInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    // SPL pools would strand their token vault, which only the token program can close
+    pool_state.require_sol_denominated()?;
+    require!(pool_state.is_fully_settled(), BettingError::PoolNotSettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    let rent_payer = ctx.accounts.rent_payer.to_account_info();
+    // Pools still in the legacy shared vault have nothing of their own to return
+    if pool_state.own_vault {
+        let vault = PoolVault::select(
+            pool_state,
+            &ctx.accounts.sol_vault,
+            ctx.bumps.sol_vault,
+            &ctx.accounts.pool_vault,
+            ctx.bumps.pool_vault,
+        );
+        let lamports = vault.account.lamports();
+        vault.pay(
+            pool_state,
+            &rent_payer,
+            &ctx.accounts.system_program.to_account_info(),
+            lamports,
+        )?;
+        emit!(RentReclaimedEvent {
+            bet_id: input.bet_id,
+            account: vault.account.key(),
+            recipient: rent_payer.key(),
+            lamports,
+            timestamp: now
+        });
+    }
+
+    let pool_info = pool_state.to_account_info();
+    emit!(RentReclaimedEvent {
+        bet_id: input.bet_id,
+        account: pool_info.key(),
+        recipient: rent_payer.key(),
+        lamports: pool_info.lamports(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ClosePoolInput)]
+pub struct AClosePool<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = rent_payer,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
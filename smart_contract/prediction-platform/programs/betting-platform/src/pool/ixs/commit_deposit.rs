@@ -0,0 +1,139 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::time;
+use crate::{
+    error::BettingError, vault::pool_vault_account, CommitDepositEvent, CommitmentState,
+    Depositor, MainState, PoolState,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct CommitDepositInput {
+    pub bet_id: u64,
+    pub commitment_hash: [u8; 32],
+    pub amount: u64,
+}
+
+pub fn commit_deposit(ctx: Context<ACommitDeposit>, input: CommitDepositInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let commitment_state = &mut ctx.accounts.commitment_state;
+    pool_state.require_sol_denominated()?;
+
+    require!(!ctx.accounts.main_state.paused, BettingError::ProtocolPaused);
+    pool_state.check_gate(
+        Depositor::new(ctx.accounts.user.key(), &ctx.accounts.gate_token_account).gate_balance,
+    )?;
+    require!(pool_state.commit_reveal, BettingError::CommitRevealDisabled);
+    require!(!pool_state.complete, BettingError::BetComplete);
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+    require!(
+        !pool_state.has_provisional_outcome(),
+        BettingError::ProvisionalOutcomePending
+    );
+
+    let now = time::now(&ctx.accounts.main_state)?;
+    // Same trading window as a regular deposit
+    require!(!pool_state.trading_closed(now), BettingError::BetEnded);
+    pool_state.require_open_for_deposits(now)?;
+    require!(input.amount > 0, BettingError::InvalidBet);
+
+    // Lock the funds now; the side stays hidden until reveal
+    let vault = pool_vault_account(pool_state, &ctx.accounts.sol_vault, &ctx.accounts.pool_vault);
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: vault.clone(),
+            },
+        ),
+        input.amount,
+    )?;
+    pool_state.vault_liabilities += input.amount;
+
+    commitment_state.user = ctx.accounts.user.key();
+    commitment_state.bet_id = input.bet_id;
+    commitment_state.commitment_hash = input.commitment_hash;
+    commitment_state.amount = input.amount;
+    commitment_state.yes_reserve = pool_state.yes_reserve;
+    commitment_state.no_reserve = pool_state.no_reserve;
+    commitment_state.committed_timestamp = now;
+    commitment_state.reveal_deadline = pool_state.reveal_deadline(now);
+
+    pool_state.pending_commitments += 1;
+    pool_state.latest_reveal_deadline = pool_state
+        .latest_reveal_deadline
+        .max(commitment_state.reveal_deadline);
+
+    emit!(CommitDepositEvent {
+        user: commitment_state.user,
+        bet_id: input.bet_id,
+        sol_amount: input.amount,
+        reveal_deadline: commitment_state.reveal_deadline,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CommitDepositInput)]
+pub struct ACommitDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // One outstanding commitment per user and pool; it is closed on reveal or refund.
+    #[account(
+        init,
+        payer = user,
+        space = 8 + CommitmentState::MAX_SIZE,
+        seeds = [
+            CommitmentState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub commitment_state: Account<'info, CommitmentState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
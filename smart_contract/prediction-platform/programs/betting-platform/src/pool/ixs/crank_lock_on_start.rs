@@ -0,0 +1,82 @@
+use crate::{
+    annotate_history, error::BettingError, load_history, lock_final_odds, time, MainState,
+    PoolHistoryState, PoolLockedOnStartEvent, PoolState, ProbabilityPoint, StartFeed,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CrankLockOnStartInput {
+    bet_id: u64,
+}
+
+// Permissionless crank for oracle-bound pools: once the pool's StartFeed reports that the
+// event began, ends trading by moving the end time to now (deposits stop and the pool
+// becomes resolvable, as if it had been created with that end time)
+pub fn crank_lock_on_start(
+    ctx: Context<ACrankLockOnStart>,
+    input: CrankLockOnStartInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let start_feed = &ctx.accounts.start_feed;
+    require!(!pool_state.complete, BettingError::BetComplete);
+
+    let now = time::now(main_state)?;
+    require!(!pool_state.trading_closed(now), BettingError::PoolAlreadyLocked);
+    require!(start_feed.has_started(now), BettingError::EventNotStarted);
+
+    let previous_end_timestamp = pool_state.end_timestamp;
+    pool_state.end_timestamp = now;
+    // Trading now ends at end_timestamp, whatever trading deadline the pool had
+    pool_state.trading_end_timestamp = 0;
+    pool_state.locked_on_start = true;
+    lock_final_odds(pool_state, now);
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_LOCK,
+    )?;
+
+    emit!(PoolLockedOnStartEvent {
+        bet_id: input.bet_id,
+        start_feed: start_feed.key(),
+        started_at: start_feed.started_at,
+        previous_end_timestamp,
+        cranked_by: ctx.accounts.caller.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CrankLockOnStartInput)]
+pub struct ACrankLockOnStart<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(address = pool_state.start_feed @ BettingError::StartFeedMismatch)]
+    pub start_feed: Account<'info, StartFeed>,
+
+    // Omitted for pools created with history disabled; receives the lock marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+}
@@ -1,73 +1,108 @@
-use anchor_lang::prelude::*;
-
-use crate::{error::BettingError, EntryState, MainState, PoolState};
-
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
-pub struct CreateEntryInput {
-    pub bet_id: u64,
-}
-
-pub fn create_entry(ctx: Context<ACreateEntry>, input: CreateEntryInput) -> Result<()> {
-    let entry_state = &mut ctx.accounts.entry_state;
-    let pool_state = &mut ctx.accounts.pool_state;
-
-    // Disallow creating entries after the market is completed
-    require!(!pool_state.complete, BettingError::BetComplete);
-
-    // For fixed-time markets, prevent creating entries after end. Open-ended markets (negative
-    // end_timestamp) allow entries until the market is resolved.
-    if pool_state.end_timestamp >= 0 {
-        require!(
-            pool_state.end_timestamp > Clock::get()?.unix_timestamp,
-            BettingError::BetEnded
-        );
-    }
-
-    entry_state.user = ctx.accounts.user.key();
-    entry_state.bet_id = input.bet_id;
-    entry_state.deposited_sol_amount = 0;
-    entry_state.token_balance = 0;
-    entry_state.is_yes = true;
-    entry_state.is_claimed = false;
-
-    Ok(())
-}
-
-#[derive(Accounts)]
-#[instruction(input: CreateEntryInput)]
-pub struct ACreateEntry<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [MainState::PREFIX_SEED],
-        bump,
-    )]
-    pub main_state: Box<Account<'info, MainState>>,
-
-    #[account(
-        mut,
-        seeds =[
-            PoolState::PREFIX_SEED,
-            &input.bet_id.to_le_bytes(),
-        ],
-        bump
-    )]
-    pub pool_state: Box<Account<'info, PoolState>>,
-
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + EntryState::MAX_SIZE,
-        seeds = [
-            EntryState::PREFIX_SEED,
-            &pool_state.key().to_bytes(),
-            &user.key().to_bytes()
-        ],
-        bump
-    )]
-    pub entry_state: Account<'info, EntryState>,
-
-    pub system_program: Program<'info, System>,
-}
+use crate::time;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{error::BettingError, EntryState, MainState, PoolState};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct CreateEntryInput {
+    pub bet_id: u64,
+}
+
+pub fn create_entry(ctx: Context<ACreateEntry>, input: CreateEntryInput) -> Result<()> {
+    let entry_state = &mut ctx.accounts.entry_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+
+    require!(!ctx.accounts.main_state.paused, BettingError::ProtocolPaused);
+
+    pool_state.check_gate(
+        ctx.accounts
+            .gate_token_account
+            .as_ref()
+            .map(|account| account.amount),
+    )?;
+
+    // Disallow creating entries after the market is completed
+    require!(!pool_state.complete, BettingError::BetComplete);
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+
+    // For fixed-time markets, prevent creating entries after trading ends. Open-ended markets
+    // (negative end_timestamp) allow entries until the market is resolved.
+    require!(
+        !pool_state.trading_closed(time::now(&ctx.accounts.main_state)?),
+        BettingError::BetEnded
+    );
+
+    // Counted once, when the account is created; close_entry counts it out
+    if entry_state.user.eq(&Pubkey::default()) {
+        pool_state.open_entries = pool_state
+            .open_entries
+            .checked_add(1)
+            .ok_or(BettingError::MathOverflow)?;
+    }
+    entry_state.user = ctx.accounts.user.key();
+    entry_state.bet_id = input.bet_id;
+    entry_state.deposited_sol_amount = 0;
+    entry_state.token_balance = 0;
+    entry_state.is_yes = true;
+    entry_state.is_claimed = false;
+    entry_state.installments_claimed = 0;
+    entry_state.recent_nonces = [0; EntryState::NONCE_HISTORY];
+    entry_state.nonce_cursor = 0;
+    entry_state.creator_fee_exempt =
+        pool_state.waive_creator_self_fee && entry_state.user == pool_state.creator;
+    entry_state.creator_fee_paid = 0;
+    entry_state.insured_amount = 0;
+    entry_state.insurance_premium_paid = 0;
+    entry_state.insurance_claimed = false;
+    entry_state.deposit_count = 0;
+    entry_state.claimed_at = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CreateEntryInput)]
+pub struct ACreateEntry<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + EntryState::MAX_SIZE,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
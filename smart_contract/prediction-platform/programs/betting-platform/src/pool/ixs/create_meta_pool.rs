@@ -0,0 +1,186 @@
+use crate::{
+    constants::POOL_VAULT_SEED, error::BettingError, escrow_creator_bond, fund_seed_liquidity,
+    initialize_pool, record_pool_creation, vault::fund_pool_vault_rent, CreatePoolInput,
+    CreatorRateLimit, MainState, MetaCondition, MetaPoolCreatedEvent, PoolHistoryState,
+    PoolState, TitleIndex,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct CreateMetaPoolInput {
+    pub pool: CreatePoolInput,
+    pub condition: MetaCondition,
+}
+
+// create_pool for a side-bet on another pool's state (e.g. "will pool 42 gather 100 SOL?").
+// Nobody referees it: resolve_meta_market settles it from the target once the target stops
+// trading, and the owner keeps the usual fallback. Its own trading must close no later than a
+// fixed-end target's, so nobody bets on it after the target's figures are final.
+pub fn create_meta_pool(ctx: Context<ACreateMetaPool>, input: CreateMetaPoolInput) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        BettingError::Uninitialized
+    );
+    require!(!main_state.paused, BettingError::ProtocolPaused);
+
+    let condition = input.condition;
+    condition.validate()?;
+    let target = &ctx.accounts.target_pool_state;
+    require!(
+        !target.complete && !target.refund_mode,
+        BettingError::MetaTargetSettled
+    );
+    let pool = input.pool;
+    require!(
+        pool.referee.is_none() && pool.referee_index.is_none(),
+        BettingError::InvalidRefereeSelection
+    );
+    require!(
+        pool.end_timestamp >= 0
+            && (target.end_timestamp < 0 || pool.end_timestamp <= target.end_timestamp),
+        BettingError::InvalidMetaCondition
+    );
+    require!(
+        pool.range_buckets.is_none()
+            && pool.scalar_bounds.is_none()
+            && pool.price_condition.is_none()
+            && pool.provisional_window_secs.unwrap_or(0) == 0
+            && !pool.optimistic_resolution.unwrap_or(false),
+        BettingError::ConflictingResolutionModes
+    );
+
+    let title_index = &mut ctx.accounts.title_index;
+    require!(
+        title_index.pool.eq(&Pubkey::default()),
+        BettingError::DuplicateMarket
+    );
+    title_index.creator = ctx.accounts.creator.key();
+    title_index.pool = ctx.accounts.pool_state.key();
+    title_index.bet_id = main_state.current_bet_id;
+
+    record_pool_creation(
+        main_state,
+        &mut ctx.accounts.creator_rate_limit,
+        ctx.accounts.creator.key(),
+    )?;
+
+    let title_hash = TitleIndex::title_hash(&pool.title);
+    ctx.accounts.pool_state.rent_payer = ctx.accounts.creator.key();
+    initialize_pool(
+        ctx.program_id,
+        main_state,
+        &mut ctx.accounts.pool_state,
+        ctx.accounts.history_state.as_ref(),
+        ctx.accounts.creator.key(),
+        Pubkey::default(),
+        pool,
+    )?;
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.title_indexed = true;
+    pool_state.title_hash = title_hash;
+    pool_state.meta_condition = condition;
+
+    fund_pool_vault_rent(
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    pool_state.own_vault = true;
+    fund_seed_liquidity(
+        pool_state,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    escrow_creator_bond(
+        pool_state,
+        &ctx.accounts.main_state,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(MetaPoolCreatedEvent {
+        bet_id: pool_state.bet_id,
+        target_bet_id: condition.target_bet_id,
+        metric: condition.metric,
+        comparison: condition.comparison,
+        threshold: condition.threshold,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CreateMetaPoolInput)]
+pub struct ACreateMetaPool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolState::MAX_SIZE,
+        seeds = [PoolState::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    // Omitted when `disable_history` is set
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + TitleIndex::MAX_SIZE,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            creator.key().as_ref(),
+            &TitleIndex::title_hash(&input.pool.title),
+        ],
+        bump
+    )]
+    pub title_index: Box<Account<'info, TitleIndex>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorRateLimit::MAX_SIZE,
+        seeds = [CreatorRateLimit::PREFIX_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_rate_limit: Box<Account<'info, CreatorRateLimit>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault (see vault::PoolVault), funded with its rent-exempt minimum
+    pub pool_vault: AccountInfo<'info>,
+
+    // The pool the meta-market bets on
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.condition.target_bet_id.to_le_bytes()],
+        bump
+    )]
+    pub target_pool_state: Box<Account<'info, PoolState>>,
+
+    pub system_program: Program<'info, System>,
+}
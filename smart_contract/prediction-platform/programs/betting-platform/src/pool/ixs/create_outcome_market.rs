@@ -0,0 +1,119 @@
+use crate::{
+    constants::{MAX_OUTCOMES, MAX_OUTCOME_NAME_LEN, MIN_OUTCOMES, VAULT_SEED},
+    error::BettingError,
+    validation::validate_title,
+    MainState, OutcomeMarket, OutcomeMarketCreatedEvent,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct CreateOutcomeMarketInput {
+    pub title: String,
+    pub end_timestamp: i64,
+    pub referee: Pubkey,
+    pub outcomes: Vec<String>,
+}
+
+pub fn create_outcome_market(
+    ctx: Context<ACreateOutcomeMarket>,
+    input: CreateOutcomeMarketInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let market = &mut ctx.accounts.outcome_market;
+    require!(
+        main_state.initialized.eq(&true),
+        BettingError::Uninitialized
+    );
+    require!(!main_state.paused, BettingError::ProtocolPaused);
+
+    validate_title(&input.title)?;
+    require!(
+        (MIN_OUTCOMES..=MAX_OUTCOMES).contains(&input.outcomes.len()),
+        BettingError::InvalidOutcomeCount
+    );
+    require!(
+        input
+            .outcomes
+            .iter()
+            .all(|name| !name.is_empty() && name.len() <= MAX_OUTCOME_NAME_LEN),
+        BettingError::InvalidOutcomeName
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let outcome_count = input.outcomes.len();
+    market.bet_id = main_state.current_bet_id;
+    market.creator = ctx.accounts.creator.key();
+    market.referee = input.referee;
+    market.title = input.title.clone();
+    market.end_timestamp = input.end_timestamp;
+    market.created_timestamp = now;
+    market.outcomes = input.outcomes.clone();
+    market.reserves = vec![0; outcome_count];
+    market.supplies = vec![0; outcome_count];
+    market.creator_fee_bps = main_state.creator_fee_percent;
+    market.creator_fee_accrued = 0;
+    market.platform_fee_amount = 0;
+    market.complete = false;
+    market.winner = 0;
+    market.resolved_timestamp = 0;
+    market.creator_bond = main_state.creator_bond_lamports;
+    if market.creator_bond > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                },
+            ),
+            market.creator_bond,
+        )?;
+    }
+
+    // Outcome markets and pools share one id sequence
+    main_state.current_bet_id += 1;
+
+    emit!(OutcomeMarketCreatedEvent {
+        creator: market.creator,
+        bet_id: market.bet_id,
+        title: input.title,
+        outcomes: input.outcomes,
+        end_timestamp: market.end_timestamp,
+        referee: market.referee,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ACreateOutcomeMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + OutcomeMarket::MAX_SIZE,
+        seeds = [OutcomeMarket::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_market: Box<Account<'info, OutcomeMarket>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault; holds the creator bond until set_outcome_winner
+    pub sol_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
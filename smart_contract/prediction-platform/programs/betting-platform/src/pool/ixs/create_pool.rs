@@ -1,4 +1,19 @@
-use crate::{error::BettingError, CreateEvent, MainState, PoolState, PoolHistoryState, ProbabilityPoint};
+use crate::{
+    constants::{
+        MAX_COMMITTEE_REFEREES, MAX_DISPLAY_DECIMALS, MAX_INSTALLMENTS,
+        MAX_PROVISIONAL_WINDOW_SECS, MIN_LMSR_LIQUIDITY, POOL_VAULT_SEED,
+    },
+    error::BettingError,
+    share_id::derive_share_uuid,
+    validation::{validate_side_label, validate_title_and_description, validate_unit_symbol},
+    time,
+    vault::fund_pool_vault_rent,
+    CreateEvent, CreatorRateLimit, FinalOdds, MainState, MetaCondition,
+    PoolHistoryState, PoolState, PoolSummary, PriceCondition, PricingModel, ProbabilityPoint,
+    RangeBucket, ScalarBounds, TitleIndex, WinnerSide,
+};
+#[cfg(feature = "referee-registry")]
+use crate::DefaultReferees;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
@@ -6,7 +21,86 @@ pub struct CreatePoolInput {
     pub title: String,
     pub description: String,
     pub end_timestamp: i64,
-    pub referee: Pubkey,
+    // Either a raw referee pubkey or an index into the owner-curated DefaultReferees list
+    pub referee: Option<Pubkey>,
+    pub referee_index: Option<u8>,
+    // Optional: require commit-reveal deposits to protect against front-running (default: off)
+    pub commit_reveal: Option<bool>,
+    // Optional: pay winners in N equal installments, one per `installment_period_secs`
+    pub installments: Option<u8>,
+    pub installment_period_secs: Option<i64>,
+    // Optional display metadata for quantity markets
+    pub unit_symbol: Option<String>,
+    pub decimals: Option<u8>,
+    // Optional labels for the YES/NO sides; outcomes stay boolean internally
+    pub side_a_label: Option<String>,
+    pub side_b_label: Option<String>,
+    // Optional: skip on-chain PoolHistoryState (saves rent and compute) when events are indexed
+    pub disable_history: Option<bool>,
+    // Optional: exempt the creator's own entry from the creator fee (default: off)
+    pub waive_creator_self_fee: Option<bool>,
+    // Optional escalation deadlines: owner/ops council may resolve after `referee_deadline`,
+    // anyone may trigger refund mode after `refund_deadline` (requires a referee deadline)
+    pub referee_deadline: Option<i64>,
+    pub refund_deadline: Option<i64>,
+    // Optional: stop deposits at `trading_end_timestamp`, before end_timestamp (from then on the
+    // pool may be resolved), and let anyone trigger refund mode once `resolution_deadline`
+    // passes unresolved (SOL pools only); see PoolState::trading_end
+    pub trading_end_timestamp: Option<i64>,
+    pub resolution_deadline: Option<i64>,
+    // Optional: donate this share (bps) of the creator fee to the winners at resolution
+    pub creator_donation_bps: Option<u64>,
+    // Optional: cap the number of deposits per entry (default: unlimited)
+    pub max_deposits_per_entry: Option<u32>,
+    // Optional: let anyone resolve the pool through a bonded, disputable assertion (default: off)
+    pub optimistic_resolution: Option<bool>,
+    // Optional: let entries be converted into transferable SPL outcome tokens (default: off)
+    pub outcome_tokens: Option<bool>,
+    // Optional: make this a range market resolved by a numeric value (see RangeBucket)
+    pub range_buckets: Option<Vec<RangeBucket>>,
+    // Optional: seconds after resolution winners have to claim, within MainState's bounds
+    // (default: MainState's default window)
+    pub claim_deadline_override: Option<i64>,
+    // Optional: resolve in two phases, confirming the provisional outcome after this many
+    // seconds unless disputed (default: single-call resolution)
+    pub provisional_window_secs: Option<i64>,
+    // Optional: StartFeed whose event start locks trading through crank_lock_on_start
+    pub start_feed: Option<Pubkey>,
+    // Optional: let anyone resolve the pool from a Pyth price feed after its end time
+    // (resolve_with_oracle); the referee can still resolve it
+    pub price_condition: Option<PriceCondition>,
+    // Optional: lamports the creator seeds each side with, moved from the creator's wallet into
+    // the pool's vault to steady early odds (default: none; create_pool only)
+    pub initial_yes_liquidity: Option<u64>,
+    pub initial_no_liquidity: Option<u64>,
+    // Optional: make this a scalar market resolved by a numeric value in [min, max], paying
+    // both sides by where it lands (see ScalarBounds)
+    pub scalar_bounds: Option<ScalarBounds>,
+    // Optional: block time trading opens; until then deposits queue through queue_deposit
+    // (default: open at creation)
+    pub opens_at: Option<i64>,
+    // Optional: a committee of 2 to MAX_COMMITTEE_REFEREES referees, the pool's referee among
+    // them, that resolves by `referee_threshold` matching votes (default: simple majority)
+    pub referee_committee: Option<Vec<Pubkey>>,
+    pub referee_threshold: Option<u8>,
+    // Optional: only holders of at least `gate_min_amount` (default: 1) of this mint may
+    // create entries and deposit, e.g. a community token or an NFT collection's mint
+    pub gate_mint: Option<Pubkey>,
+    pub gate_min_amount: Option<u64>,
+    // Optional: entries set_winner needs to pay out; with fewer, or none on one side, the pool
+    // refunds every deposit instead (default: no minimum; SOL pools only)
+    pub min_participants: Option<u32>,
+    // Optional: caps on the pool's reserves and on each entry's deposits, enforced by deposit
+    // (default: uncapped); the creator and the owner can change them through update_pool
+    pub max_pool_reserve: Option<u64>,
+    pub max_user_deposit: Option<u64>,
+    // Optional: a lower referee cut of the platform fee than MainState's, e.g. none for a
+    // creator refereeing their own pool (default: MainState's referee_fee_percent)
+    pub referee_fee_bps: Option<u64>,
+    // Optional: price deposits with the LMSR and this liquidity parameter `b`, in lamports,
+    // instead of the reserve ratio (default: Ratio, which takes no liquidity parameter)
+    pub pricing_model: Option<PricingModel>,
+    pub lmsr_liquidity: Option<u64>,
 }
 
 pub fn create_pool(ctx: Context<ACreatePool>, input: CreatePoolInput) -> Result<()> {
@@ -15,48 +109,374 @@ pub fn create_pool(ctx: Context<ACreatePool>, input: CreatePoolInput) -> Result<
         main_state.initialized.eq(&true),
         BettingError::Uninitialized
     );
+    require!(!main_state.paused, BettingError::ProtocolPaused);
 
+    let referee = match (input.referee, input.referee_index) {
+        (Some(referee), None) => referee,
+        #[cfg(feature = "referee-registry")]
+        (None, Some(index)) => {
+            let default_referees = ctx
+                .accounts
+                .default_referees
+                .as_ref()
+                .ok_or(BettingError::DefaultRefereesMissing)?;
+            *default_referees
+                .referees
+                .get(index as usize)
+                .ok_or(BettingError::InvalidRefereeIndex)?
+        }
+        _ => return err!(BettingError::InvalidRefereeSelection),
+    };
+
+    // init_if_needed so an existing index surfaces as DuplicateMarket rather than a generic
+    // "account already in use" error
+    let title_index = &mut ctx.accounts.title_index;
+    require!(
+        title_index.pool.eq(&Pubkey::default()),
+        BettingError::DuplicateMarket
+    );
+    title_index.creator = ctx.accounts.creator.key();
+    title_index.pool = ctx.accounts.pool_state.key();
+    title_index.bet_id = main_state.current_bet_id;
+
+    record_pool_creation(
+        main_state,
+        &mut ctx.accounts.creator_rate_limit,
+        ctx.accounts.creator.key(),
+    )?;
+
+    let title_hash = TitleIndex::title_hash(&input.title);
+    ctx.accounts.pool_state.rent_payer = ctx.accounts.creator.key();
+    initialize_pool(
+        ctx.program_id,
+        main_state,
+        &mut ctx.accounts.pool_state,
+        ctx.accounts.history_state.as_ref(),
+        ctx.accounts.creator.key(),
+        referee,
+        input,
+    )?;
+    ctx.accounts.pool_state.title_indexed = true;
+    ctx.accounts.pool_state.title_hash = title_hash;
+    ctx.accounts.pool_summary.pool = ctx.accounts.pool_state.key();
+    ctx.accounts.pool_summary.bet_id = ctx.accounts.pool_state.bet_id;
+    ctx.accounts.pool_state.has_summary = true;
+
+    fund_pool_vault_rent(
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    ctx.accounts.pool_state.own_vault = true;
+    fund_seed_liquidity(
+        &mut ctx.accounts.pool_state,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    escrow_creator_bond(
+        &mut ctx.accounts.pool_state,
+        &ctx.accounts.main_state,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+// Escrows MainState's creator bond from whoever pays for the new pool's accounts in the vault
+// the pool pays out of; settle_creator_bond returns it to them or slashes it. Every create
+// path calls this, so no pool escapes the bond by picking another instruction.
+pub(crate) fn escrow_creator_bond<'info>(
+    pool_state: &mut PoolState,
+    main_state: &MainState,
+    payer: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let creator_bond = main_state.creator_bond_lamports;
+    if creator_bond > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: vault.clone(),
+                },
+            ),
+            creator_bond,
+        )?;
+        pool_state.creator_bond = creator_bond;
+        pool_state.vault_liabilities = pool_state
+            .vault_liabilities
+            .checked_add(creator_bond)
+            .ok_or(BettingError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+// Moves the seed liquidity initialize_pool booked into the pool's freshly funded own vault
+pub(crate) fn fund_seed_liquidity<'info>(
+    pool_state: &mut PoolState,
+    creator: &AccountInfo<'info>,
+    pool_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let seed = pool_state
+        .seed_yes_liquidity
+        .checked_add(pool_state.seed_no_liquidity)
+        .ok_or(BettingError::MathOverflow)?;
+    if seed > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: creator.clone(),
+                    to: pool_vault.clone(),
+                },
+            ),
+            seed,
+        )?;
+        pool_state.vault_liabilities += seed;
+    }
+    Ok(())
+}
+
+// Checks a committee for create_pool: distinct members, the referee one of them, and a
+// threshold no larger than the committee. Returns the seats, their count and the threshold.
+pub(crate) fn referee_committee(
+    members: &[Pubkey],
+    referee: &Pubkey,
+    threshold: Option<u8>,
+) -> Result<([Pubkey; MAX_COMMITTEE_REFEREES], u8, u8)> {
+    require!(
+        (2..=MAX_COMMITTEE_REFEREES).contains(&members.len()) && members.contains(referee),
+        BettingError::InvalidRefereeCommittee
+    );
+    let mut seats = [Pubkey::default(); MAX_COMMITTEE_REFEREES];
+    for (seat, member) in members.iter().enumerate() {
+        require!(
+            !members[..seat].contains(member) && member.ne(&Pubkey::default()),
+            BettingError::InvalidRefereeCommittee
+        );
+        seats[seat] = *member;
+    }
+    let size = members.len() as u8;
+    let threshold = threshold.unwrap_or(size / 2 + 1);
+    require!(
+        (1..=size).contains(&threshold),
+        BettingError::InvalidRefereeCommittee
+    );
+    Ok((seats, size, threshold))
+}
+
+// Counts a new pool against its creator's daily limit (MainState.max_pools_per_day); called by
+// the creator-facing create instructions, not by the open_linked_pool crank
+pub(crate) fn record_pool_creation(
+    main_state: &MainState,
+    rate_limit: &mut CreatorRateLimit,
+    creator: Pubkey,
+) -> Result<()> {
+    rate_limit.creator = creator;
+    rate_limit.record_creation(main_state.max_pools_per_day, time::now(main_state)?)
+}
+
+// Shared by create_pool and open_linked_pool: validates the input, fills in a freshly
+// initialized PoolState (and its history), assigns the next bet id and emits CreateEvent.
+// Callers set `pool_state.rent_payer` beforehand.
+pub(crate) fn initialize_pool(
+    program_id: &Pubkey,
+    main_state: &mut MainState,
+    pool_state: &mut Account<PoolState>,
+    history_state: Option<&AccountLoader<PoolHistoryState>>,
+    creator: Pubkey,
+    referee: Pubkey,
+    input: CreatePoolInput,
+) -> Result<()> {
     // Validate title and description lengths
+    validate_title_and_description(&input.title, &input.description)?;
+
+    let unit_symbol = input.unit_symbol.clone().unwrap_or_default();
+    let decimals = input.decimals.unwrap_or(0);
+    validate_unit_symbol(&unit_symbol)?;
+    require!(decimals <= MAX_DISPLAY_DECIMALS, BettingError::InvalidDecimals);
+
+    let side_a_label = input.side_a_label.clone().unwrap_or_default();
+    let side_b_label = input.side_b_label.clone().unwrap_or_default();
+    validate_side_label(&side_a_label)?;
+    validate_side_label(&side_b_label)?;
+
+    let installment_count = input.installments.unwrap_or(1);
+    let installment_period = input.installment_period_secs.unwrap_or(0);
     require!(
-        input.title.len() <= 100,
-        BettingError::TitleTooLong
+        (1..=MAX_INSTALLMENTS).contains(&installment_count),
+        BettingError::InvalidInstallmentConfig
     );
+    if installment_count > 1 {
+        require!(installment_period > 0, BettingError::InvalidInstallmentConfig);
+    }
+    let outcome_tokens = input.outcome_tokens.unwrap_or(false);
+    require!(
+        !outcome_tokens || installment_count == 1,
+        BettingError::OutcomeTokensRequireSinglePayout
+    );
+
+    let referee_deadline = input.referee_deadline.unwrap_or(0);
+    let refund_deadline = input.refund_deadline.unwrap_or(0);
+    if referee_deadline != 0 {
+        require!(
+            referee_deadline > 0 && referee_deadline >= input.end_timestamp,
+            BettingError::InvalidResolutionDeadlines
+        );
+    }
+    if refund_deadline != 0 {
+        require!(
+            referee_deadline > 0 && refund_deadline > referee_deadline,
+            BettingError::InvalidResolutionDeadlines
+        );
+    }
+
+    let provisional_window_secs = input.provisional_window_secs.unwrap_or(0);
     require!(
-        input.description.len() <= 500,
-        BettingError::DescriptionTooLong
+        (0..=MAX_PROVISIONAL_WINDOW_SECS).contains(&provisional_window_secs),
+        BettingError::InvalidProvisionalWindow
     );
+    let optimistic_resolution = input.optimistic_resolution.unwrap_or(false);
     require!(
-        !input.title.is_empty(),
-        BettingError::TitleEmpty
+        !optimistic_resolution || provisional_window_secs == 0,
+        BettingError::ConflictingResolutionModes
     );
+
+    // Assertions, start feeds and price feeds all need the oracle instructions
+    #[cfg(not(feature = "oracle"))]
     require!(
-        !input.description.is_empty(),
-        BettingError::DescriptionEmpty
+        !optimistic_resolution && input.start_feed.is_none() && input.price_condition.is_none(),
+        BettingError::OracleFeatureDisabled
     );
 
-    let pool_state = &mut ctx.accounts.pool_state;
-    let creator = ctx.accounts.creator.to_account_info();
+    let range_buckets = input.range_buckets.clone().unwrap_or_default();
+    if !range_buckets.is_empty() {
+        RangeBucket::validate_all(&range_buckets)?;
+    }
 
-    // Generate a unique share UUID using bet_id, timestamp, and slot
-    let clock = Clock::get()?;
-    let share_uuid = format!(
-        "{:x}-{:x}-{:x}", 
-        main_state.current_bet_id,
-        clock.unix_timestamp,
-        clock.slot
+    // The feed decides a single YES/NO outcome in one call, at a fixed time
+    let price_condition = input.price_condition.unwrap_or_default();
+    if input.price_condition.is_some() {
+        price_condition.validate()?;
+        require!(input.end_timestamp >= 0, BettingError::InvalidPriceCondition);
+        require!(
+            range_buckets.is_empty() && provisional_window_secs == 0 && !optimistic_resolution,
+            BettingError::ConflictingResolutionModes
+        );
+    }
+
+    // A scalar pot is shared by both sides, which the binary-only features can't express
+    let scalar_bounds = input.scalar_bounds.unwrap_or_default();
+    if input.scalar_bounds.is_some() {
+        require!(scalar_bounds.is_set(), BettingError::InvalidScalarBounds);
+        require!(
+            range_buckets.is_empty()
+                && input.price_condition.is_none()
+                && provisional_window_secs == 0
+                && !optimistic_resolution,
+            BettingError::ConflictingResolutionModes
+        );
+        require!(
+            !outcome_tokens
+                && input.initial_yes_liquidity.unwrap_or(0) == 0
+                && input.initial_no_liquidity.unwrap_or(0) == 0,
+            BettingError::UnsupportedForScalarPool
+        );
+    }
+
+    let (referee_committee, committee_size, committee_threshold) = match &input.referee_committee
+    {
+        Some(members) => {
+            // Votes pick one of two outcomes, so the committee can't back a numeric value or
+            // wait out a confirmation window
+            require!(
+                range_buckets.is_empty()
+                    && input.scalar_bounds.is_none()
+                    && provisional_window_secs == 0,
+                BettingError::ConflictingResolutionModes
+            );
+            referee_committee(members, &referee, input.referee_threshold)?
+        }
+        None => ([Pubkey::default(); MAX_COMMITTEE_REFEREES], 0, 0),
+    };
+
+    let claim_window_secs = input
+        .claim_deadline_override
+        .unwrap_or(main_state.claim_window.default_secs);
+    require!(
+        main_state.claim_window.contains(claim_window_secs),
+        BettingError::InvalidClaimWindow
+    );
+
+    let gate_min_amount = match input.gate_mint {
+        Some(_) => input.gate_min_amount.unwrap_or(1),
+        None => 0,
+    };
+    require!(
+        gate_min_amount > 0 || (input.gate_mint.is_none() && input.gate_min_amount.is_none()),
+        BettingError::InvalidGate
     );
 
-    pool_state.creator = creator.key();
+    let referee_fee_bps = input
+        .referee_fee_bps
+        .unwrap_or(main_state.referee_fee_percent);
+    require!(
+        referee_fee_bps <= main_state.referee_fee_percent,
+        BettingError::InvalidFee
+    );
+
+    let pricing_model = input.pricing_model.unwrap_or_default();
+    let lmsr_liquidity = input.lmsr_liquidity.unwrap_or(0);
+    require!(
+        match pricing_model {
+            PricingModel::Ratio => lmsr_liquidity == 0,
+            PricingModel::Lmsr => lmsr_liquidity >= MIN_LMSR_LIQUIDITY,
+        },
+        BettingError::InvalidLmsrLiquidity
+    );
+
+    let creator_donation_bps = input.creator_donation_bps.unwrap_or(0);
+    require!(creator_donation_bps <= 10_000, BettingError::InvalidCreatorDonation);
+
+    // Deterministic share id so clients can derive the link from bet_id alone
+    let clock = Clock::get()?;
+
+    let opens_at = input.opens_at.unwrap_or(0);
+    if input.opens_at.is_some() {
+        require!(
+            opens_at > clock.unix_timestamp
+                && (input.end_timestamp < 0 || opens_at < input.end_timestamp),
+            BettingError::InvalidOpenTime
+        );
+    }
+    let share_uuid = derive_share_uuid(program_id, main_state.current_bet_id);
+
+    pool_state.creator = creator;
     pool_state.bet_id = main_state.current_bet_id;
     pool_state.initial_price = main_state.initial_price;
     pool_state.scale_factor = main_state.scale_factor;
 
+    // Seed liquidity enters the reserves without minting tokens
+    let seed_yes = input.initial_yes_liquidity.unwrap_or(0);
+    let seed_no = input.initial_no_liquidity.unwrap_or(0);
     pool_state.total_supply = 0;
-    pool_state.total_reserve = 0;
+    pool_state.total_reserve = seed_yes
+        .checked_add(seed_no)
+        .ok_or(BettingError::MathOverflow)?;
     pool_state.yes_supply = 0;
-    pool_state.yes_reserve = 0;
+    pool_state.yes_reserve = seed_yes;
     pool_state.no_supply = 0;
-    pool_state.no_reserve = 0;
+    pool_state.no_reserve = seed_no;
+    pool_state.seed_yes_liquidity = seed_yes;
+    pool_state.seed_no_liquidity = seed_no;
+    pool_state.seed_reclaimed = false;
 
     pool_state.title = input.title.clone();
     pool_state.description = input.description.clone();
@@ -65,23 +485,132 @@ pub fn create_pool(ctx: Context<ACreatePool>, input: CreatePoolInput) -> Result<
     pool_state.end_timestamp = input.end_timestamp;
     // Record creation time from current block time
     pool_state.created_timestamp = clock.unix_timestamp;
-    pool_state.referee = input.referee;
+    pool_state.referee = referee;
+    pool_state.referee_accepted = true;
+    pool_state.yes_entries = 0;
+    pool_state.no_entries = 0;
 
+    pool_state.winner = WinnerSide::Unresolved;
     pool_state.complete = false;
     pool_state.creator_fee_claimed = false;
     pool_state.platform_fee_claimed = false;
+    pool_state.platform_fee_amount = 0;
 
-    // Initialize history with an initial point at creation time (all reserves 0)
-    let history = &mut ctx.accounts.history_state;
-    history.pool = pool_state.key();
-    history.bet_id = pool_state.bet_id;
-    history.points = Vec::new();
-    history.points.push(ProbabilityPoint {
-        timestamp: clock.unix_timestamp,
-        yes_reserve: 0,
-        no_reserve: 0,
-    });
+    pool_state.commit_reveal = input.commit_reveal.unwrap_or(false);
+    pool_state.pending_commitments = 0;
+    pool_state.latest_reveal_deadline = 0;
+    pool_state.open_entries = 0;
+    pool_state.history_closed = false;
+    pool_state.history_pruned = false;
+
+    pool_state.installment_count = installment_count;
+    pool_state.installment_period = installment_period;
+    pool_state.resolved_timestamp = 0;
+
+    pool_state.unit_symbol = unit_symbol;
+    pool_state.decimals = decimals;
+    pool_state.side_a_label = side_a_label;
+    pool_state.side_b_label = side_b_label;
+
+    pool_state.creator_fee_bps = main_state.creator_fee_percent;
+    pool_state.creator_fee_accrued = 0;
+    pool_state.liability_cap = 0;
+    pool_state.waive_creator_self_fee = input.waive_creator_self_fee.unwrap_or(false);
+    pool_state.creator_donation_bps = creator_donation_bps;
+    pool_state.creator_fee_donated = 0;
+    pool_state.insurance_premiums = 0;
+    pool_state.insured_yes = 0;
+    pool_state.insured_no = 0;
+    pool_state.max_deposits_per_entry = input.max_deposits_per_entry.unwrap_or(0);
+    pool_state.referee_no_show = false;
+    pool_state.optimistic_resolution = optimistic_resolution;
+    pool_state.outcome_tokens = outcome_tokens;
+    pool_state.tokenized_yes_supply = 0;
+    pool_state.tokenized_yes_principal = 0;
+    pool_state.tokenized_no_supply = 0;
+    pool_state.tokenized_no_principal = 0;
+    pool_state.range_buckets = range_buckets;
+    pool_state.range_value = 0;
+    pool_state.claim_window_secs = claim_window_secs;
+    pool_state.provisional_window_secs = provisional_window_secs;
+    pool_state.provisional_at = 0;
+    pool_state.provisional_is_yes = false;
+    pool_state.provisional_by = Pubkey::default();
+    pool_state.provisional_disputed = false;
+    pool_state.start_feed = input.start_feed.unwrap_or_default();
+    pool_state.locked_on_start = false;
+    pool_state.token_mint = Pubkey::default();
+    pool_state.voided_at = 0;
+    pool_state.guaranteed = false;
+    pool_state.price_condition = price_condition;
+    pool_state.meta_condition = MetaCondition::default();
+    pool_state.scalar_bounds = scalar_bounds;
+    pool_state.scalar_long_bps = 0;
+    pool_state.projected_payout_per_token_yes = 0;
+    pool_state.projected_payout_per_token_no = 0;
+    pool_state.title_indexed = false;
+    pool_state.title_hash = [0; 32];
+    pool_state.referee_committee = referee_committee;
+    pool_state.committee_size = committee_size;
+    pool_state.committee_threshold = committee_threshold;
+    pool_state.final_odds = FinalOdds::default();
+    pool_state.gate_mint = input.gate_mint.unwrap_or_default();
+    pool_state.gate_min_amount = gate_min_amount;
+    pool_state.min_participants = input.min_participants.unwrap_or(0);
+    pool_state.max_pool_reserve = input.max_pool_reserve.unwrap_or(0);
+    pool_state.max_user_deposit = input.max_user_deposit.unwrap_or(0);
+    pool_state.exposure_caps_by_owner = false;
+    pool_state.referee_fee_bps = referee_fee_bps;
+    pool_state.referee_fee_amount = 0;
+    pool_state.referee_fee_claimed = false;
+    pool_state.pricing_model = pricing_model;
+    pool_state.lmsr_liquidity = lmsr_liquidity;
+    pool_state.last_updated_timestamp = 0;
+    pool_state.update_count = 0;
+    pool_state.trading_end_timestamp = input.trading_end_timestamp.unwrap_or(0);
+    pool_state.resolution_deadline = input.resolution_deadline.unwrap_or(0);
+    pool_state.check_split_deadlines()?;
+    pool_state.creator_bond = 0;
+    pool_state.creator_bond_settled = false;
+    pool_state.opens_at = opens_at;
+    pool_state.waitlist_queued = 0;
+    pool_state.waitlist_executed = 0;
+
+    pool_state.referee_deadline = referee_deadline;
+    pool_state.refund_deadline = refund_deadline;
+    pool_state.refund_mode = false;
+    // Callers that fund the pool's vault switch it on (SPL pools hold a token vault instead)
+    pool_state.own_vault = false;
+    pool_state.resolution_latency_secs = 0;
 
+    // Initialize history with an initial point at creation time (the seeded reserves), unless the
+    // deployment indexes DepositEvents off-chain and opted out of on-chain history
+    pool_state.history_disabled = input.disable_history.unwrap_or(false);
+    pool_state.history_rent_lamports = 0;
+    match history_state {
+        Some(history) => {
+            require!(!pool_state.history_disabled, BettingError::HistoryDisabled);
+            pool_state.history_rent_lamports = history.to_account_info().lamports();
+            let mut history = history.load_init()?;
+            history.reset(pool_state.key(), pool_state.bet_id, clock.unix_timestamp);
+            history.push(
+                clock.unix_timestamp,
+                pool_state.yes_reserve,
+                pool_state.no_reserve,
+                ProbabilityPoint::KIND_SNAPSHOT,
+            );
+        }
+        None => require!(
+            pool_state.history_disabled,
+            BettingError::HistoryAccountRequired
+        ),
+    }
+
+    // Both accounts were just funded by the rent payer, so their balances are exactly the rent
+    pool_state.pool_rent_lamports = pool_state.to_account_info().lamports();
+    pool_state.rent_reclaimed_lamports = 0;
+
+    pool_state.check_self_referee_cap(main_state)?;
     main_state.current_bet_id += 1;
 
     emit!(CreateEvent {
@@ -91,7 +620,20 @@ pub fn create_pool(ctx: Context<ACreatePool>, input: CreatePoolInput) -> Result<
         description: input.description,
         end_timestamp: pool_state.end_timestamp,
         referee: pool_state.referee,
-        share_uuid: share_uuid,
+        share_uuid,
+        unit_symbol: pool_state.unit_symbol.clone(),
+        decimals: pool_state.decimals,
+        side_a_label: pool_state.side_a_label.clone(),
+        side_b_label: pool_state.side_b_label.clone(),
+        creator_donation_bps: pool_state.creator_donation_bps,
+        range_buckets: pool_state.range_buckets.clone(),
+        claim_window_secs: pool_state.claim_window_secs,
+        rent_payer: pool_state.rent_payer,
+        pool_rent_lamports: pool_state.pool_rent_lamports,
+        history_rent_lamports: pool_state.history_rent_lamports,
+        initial_yes_liquidity: pool_state.seed_yes_liquidity,
+        initial_no_liquidity: pool_state.seed_no_liquidity,
+        opens_at: pool_state.opens_at,
         timestamp: Clock::get()?.unix_timestamp
     });
 
@@ -130,7 +672,52 @@ pub struct ACreatePool<'info> {
         seeds = [PoolHistoryState::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
         bump
     )]
-    pub history_state: Box<Account<'info, PoolHistoryState>>,
+    // Omitted when `disable_history` is set
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolSummary::MAX_SIZE,
+        seeds = [PoolSummary::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_summary: Box<Account<'info, PoolSummary>>,
+
+    // Only needed when picking a referee by index. The account type already guarantees it is
+    // the program's single DefaultReferees PDA, so no seeds constraint is required here.
+    #[cfg(feature = "referee-registry")]
+    pub default_referees: Option<Box<Account<'info, DefaultReferees>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + TitleIndex::MAX_SIZE,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            creator.key().as_ref(),
+            &TitleIndex::title_hash(&input.title),
+        ],
+        bump
+    )]
+    pub title_index: Box<Account<'info, TitleIndex>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorRateLimit::MAX_SIZE,
+        seeds = [CreatorRateLimit::PREFIX_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_rate_limit: Box<Account<'info, CreatorRateLimit>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault (see vault::PoolVault), funded with its rent-exempt minimum
+    pub pool_vault: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,164 @@
+use crate::{
+    constants::VAULT_SEED, error::BettingError, escrow_creator_bond, initialize_pool,
+    record_pool_creation, CreatePoolInput, CreatorRateLimit, MainState, PoolHistoryState,
+    PoolState, SplPoolCreatedEvent, TitleIndex,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+// create_pool for a pool denominated in an SPL mint (e.g. USDC). Funds sit in the pool PDA's
+// associated token account and move through deposit_spl/claim_spl. Options that move SOL out
+// of the shared vault (commit-reveal refunds, outcome tokens, refund mode, a participant
+// minimum) aren't available, nor is SOL seed liquidity, and the referee must be given as a
+// pubkey. The creator bond is still SOL, escrowed in the shared vault.
+pub fn create_pool_spl(ctx: Context<ACreatePoolSpl>, input: CreatePoolInput) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        BettingError::Uninitialized
+    );
+    require!(!main_state.paused, BettingError::ProtocolPaused);
+    require!(
+        !input.commit_reveal.unwrap_or(false)
+            && !input.outcome_tokens.unwrap_or(false)
+            && input.refund_deadline.unwrap_or(0) == 0
+            && input.resolution_deadline.unwrap_or(0) == 0
+            && input.initial_yes_liquidity.unwrap_or(0) == 0
+            && input.initial_no_liquidity.unwrap_or(0) == 0
+            && input.min_participants.unwrap_or(0) == 0,
+        BettingError::UnsupportedForSplPool
+    );
+    let referee = match (input.referee, input.referee_index) {
+        (Some(referee), None) => referee,
+        _ => return err!(BettingError::InvalidRefereeSelection),
+    };
+
+    let title_index = &mut ctx.accounts.title_index;
+    require!(
+        title_index.pool.eq(&Pubkey::default()),
+        BettingError::DuplicateMarket
+    );
+    title_index.creator = ctx.accounts.creator.key();
+    title_index.pool = ctx.accounts.pool_state.key();
+    title_index.bet_id = main_state.current_bet_id;
+
+    record_pool_creation(
+        main_state,
+        &mut ctx.accounts.creator_rate_limit,
+        ctx.accounts.creator.key(),
+    )?;
+
+    let title_hash = TitleIndex::title_hash(&input.title);
+    ctx.accounts.pool_state.rent_payer = ctx.accounts.creator.key();
+    initialize_pool(
+        ctx.program_id,
+        main_state,
+        &mut ctx.accounts.pool_state,
+        ctx.accounts.history_state.as_ref(),
+        ctx.accounts.creator.key(),
+        referee,
+        input,
+    )?;
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.title_indexed = true;
+    pool_state.title_hash = title_hash;
+    pool_state.token_mint = ctx.accounts.token_mint.key();
+    escrow_creator_bond(
+        pool_state,
+        &ctx.accounts.main_state,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(SplPoolCreatedEvent {
+        bet_id: pool_state.bet_id,
+        token_mint: pool_state.token_mint,
+        token_vault: ctx.accounts.token_vault.key(),
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CreatePoolInput)]
+pub struct ACreatePoolSpl<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolState::MAX_SIZE,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &main_state.current_bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    // Omitted when `disable_history` is set
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + TitleIndex::MAX_SIZE,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            creator.key().as_ref(),
+            &TitleIndex::title_hash(&input.title),
+        ],
+        bump
+    )]
+    pub title_index: Box<Account<'info, TitleIndex>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorRateLimit::MAX_SIZE,
+        seeds = [CreatorRateLimit::PREFIX_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_rate_limit: Box<Account<'info, CreatorRateLimit>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // The pool's token vault
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault; holds the creator bond, since the pool's own vault holds tokens
+    pub sol_vault: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,117 +1,471 @@
-use crate::constants::VAULT_SEED;
-use crate::{error::BettingError, DepositEvent, EntryState, PoolState, PoolHistoryState, ProbabilityPoint};
+use crate::constants::{PARTICIPANT_MILESTONES, POOL_VAULT_SEED, VAULT_SEED, WHALE_DEPOSIT_BPS};
+use crate::fees::{bps_of, insurance_premium, platform_fee_bps, refresh_payout_projection};
+use crate::pricing::lmsr_token_amount;
+use crate::time;
+use crate::{
+    error::BettingError, vault::pool_vault_account, DepositEvent, EntryState, FirstDepositEvent,
+    HistoryPrunedEvent, MainState, MilestoneReserveEvent, ParticipantMilestoneEvent,
+    PoolHistoryState, PoolState, PoolSummary, PricingModel, ProbabilityPoint,
+};
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use std::cell::RefMut;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
 pub struct DepositInput {
-    bet_id: u64,
-    is_yes: bool,
-    amount: u64,
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub amount: u64,
+    // Optional idempotency key; a retry with a recently used nonce is rejected
+    pub client_nonce: Option<u64>,
+    // Optional odds snapshot (PoolState::state_hash) the user was quoted; rejects the deposit
+    // if the pool has moved beyond STATE_HASH_PRECISION since
+    pub expected_state_hash: Option<[u8; 32]>,
+    // Optional: insure this deposit, paying a premium on top of `amount` (default: off)
+    pub insure: Option<bool>,
+    // Slippage guard: fewest tokens the user accepts for `amount` (0 = any)
+    pub min_token_amount: u64,
+    // Optional: who referred the user. Taken on the entry's first deposit only; later deposits
+    // must name the same referrer or none.
+    pub referrer: Option<Pubkey>,
 }
 
 pub fn deposit(ctx: Context<ADeposit>, input: DepositInput) -> Result<()> {
-    let pool_state = &mut ctx.accounts.pool_state;
-    let entry_state = &mut ctx.accounts.entry_state;
-    let history_state = &mut ctx.accounts.history_state;
-    let system_program = &ctx.accounts.system_program;
+    ctx.accounts.pool_state.require_sol_denominated()?;
+    let premium = place_deposit(
+        &ctx.accounts.main_state,
+        &mut ctx.accounts.pool_state,
+        &mut ctx.accounts.entry_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        ctx.accounts.pool_summary.as_deref_mut().map(|summary| &mut **summary),
+        Depositor::new(ctx.accounts.user.key(), &ctx.accounts.gate_token_account),
+        &input,
+    )?;
+
+    // Transfer SOL (and the insurance premium) from the user to the pool's vault
+    let vault = pool_vault_account(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.pool_vault,
+    );
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: vault.clone(),
+            },
+        ),
+        input.amount + premium,
+    )?;
+    ctx.accounts.pool_state.vault_liabilities += input.amount + premium;
+
+    Ok(())
+}
+
+// The wallet behind a deposit, and what it holds of a gated pool's mint per the token account
+// it passed (see PoolState::check_gate)
+pub(crate) struct Depositor {
+    pub key: Pubkey,
+    pub gate_balance: Option<u64>,
+}
+
+impl Depositor {
+    pub fn new(key: Pubkey, gate_token_account: &Option<Box<Account<TokenAccount>>>) -> Self {
+        Self {
+            key,
+            gate_balance: gate_token_account.as_ref().map(|account| account.amount),
+        }
+    }
+}
+
+// Checks and books a deposit, shared by deposit, deposit_from_balance and deposit_spl. The
+// caller moves `input.amount` plus the returned insurance premium into the vault.
+pub(crate) fn place_deposit(
+    main_state: &MainState,
+    pool_state: &mut Account<PoolState>,
+    entry_state: &mut EntryState,
+    history_state: Option<&mut PoolHistoryState>,
+    pool_summary: Option<&mut PoolSummary>,
+    depositor: Depositor,
+    input: &DepositInput,
+) -> Result<u64> {
+    require!(!main_state.paused, BettingError::ProtocolPaused);
+    pool_state.check_gate(depositor.gate_balance)?;
+    pool_state.check_summary(pool_summary.is_some())?;
+    let user = depositor.key;
 
     // Disallow deposits once the market is completed
     require!(!pool_state.complete, BettingError::BetComplete);
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+    require!(
+        !pool_state.has_provisional_outcome(),
+        BettingError::ProvisionalOutcomePending
+    );
 
-    // For markets with a fixed end time (>= 0), disallow deposits after end.
+    // For markets with a fixed end time (>= 0), disallow deposits after trading ends.
     // Open-ended markets (negative end_timestamp) remain open for deposits until resolved.
-    if pool_state.end_timestamp >= 0 {
-        require!(
-            pool_state.end_timestamp > Clock::get()?.unix_timestamp,
-            BettingError::BetEnded
-        );
-    }
+    let now = time::now(main_state)?;
+    require!(!pool_state.trading_closed(now), BettingError::BetEnded);
+    pool_state.require_open_for_deposits(now)?;
 
     // Minimum buy amount removed: allow any positive deposit amount.
     // Frontend should still nudge users to avoid dust values that may be uneconomical.
     require!(input.amount > 0, BettingError::InvalidBet);
 
-    require!(
-        entry_state.token_balance == 0 || entry_state.is_yes.eq(&input.is_yes),
-        BettingError::InvalidBet
-    );
+    // Commit-reveal pools only accept sealed deposits
+    require!(!pool_state.commit_reveal, BettingError::CommitRevealRequired);
+
+    if let Some(expected_state_hash) = input.expected_state_hash {
+        require!(
+            pool_state.state_hash() == expected_state_hash,
+            BettingError::StateHashMismatch
+        );
+    }
+
+    if let Some(client_nonce) = input.client_nonce {
+        entry_state.record_client_nonce(client_nonce)?;
+    }
 
-    let user = &ctx.accounts.user.to_account_info();
+    if let Some(referrer) = input.referrer {
+        require!(referrer.ne(&user), BettingError::InvalidReferrer);
+        if entry_state.deposit_count == 0 {
+            entry_state.referrer = referrer;
+        }
+        require!(
+            entry_state.referrer.eq(&referrer),
+            BettingError::InvalidReferrer
+        );
+    }
 
-    // Compute token amount and (optionally) prices using extracted helper.
-    let (token_amount, _yes_price, _no_price) = calculate_token_amount_and_prices(
+    let pricing_reserves = (pool_state.yes_reserve, pool_state.no_reserve);
+    let premium = if input.insure.unwrap_or(false) {
+        // Insurance covers a losing principal, which a shared scalar pot has no notion of
+        require!(
+            !pool_state.scalar_bounds.is_set(),
+            BettingError::UnsupportedForScalarPool
+        );
+        insurance_premium(input.amount, input.is_yes, pricing_reserves)
+    } else {
+        0
+    };
+
+    let pool_key = pool_state.key();
+    let entry_before = entry_state.deposited_sol_amount;
+    let funnel_before = FunnelSnapshot::of(pool_state);
+    let token_amount = apply_deposit(
+        pool_state,
+        entry_state,
+        history_state,
+        pool_key,
         input.amount,
         input.is_yes,
-        pool_state.yes_reserve,
-        pool_state.no_reserve,
+        pricing_reserves,
     )?;
+    require!(
+        token_amount >= input.min_token_amount,
+        BettingError::SlippageExceeded
+    );
+    pool_state.check_self_referee_cap(main_state)?;
+
+    update_pool_summary(pool_summary, pool_state, user, entry_before, input.amount, now);
+    emit_funnel_events(main_state, pool_state, user, funnel_before, now);
+
+    let total_reserve = pool_state.yes_reserve.saturating_add(pool_state.no_reserve);
+    refresh_payout_projection(pool_state, platform_fee_bps(main_state, total_reserve));
+
+    if premium > 0 {
+        pool_state.insurance_premiums += premium;
+        if input.is_yes {
+            pool_state.insured_yes += input.amount;
+        } else {
+            pool_state.insured_no += input.amount;
+        }
+        entry_state.insured_amount += input.amount;
+        entry_state.insurance_premium_paid += premium;
+    }
+
+    emit!(DepositEvent {
+        user,
+        bet_id: pool_state.bet_id,
+        sol_amount: input.amount,
+        token_amount,
+        is_yes: input.is_yes,
+        insurance_premium: premium,
+        timestamp: now
+    });
+
+    Ok(premium)
+}
+
+// ---------------------------------------------------------------------
+// Position Bookkeeping
+// ---------------------------------------------------------------------
+// Applies an already-funded deposit to the pool, the user's entry and the probability
+// history (if the pool keeps one). `pricing_reserves` are the (yes, no) reserves the bet
+// is priced against: the live reserves for regular deposits, or the reserves recorded at
+// commit time for commit-reveal deposits. Returns the minted token amount.
+// ---------------------------------------------------------------------
+pub(crate) fn apply_deposit(
+    pool_state: &mut PoolState,
+    entry_state: &mut EntryState,
+    history_state: Option<&mut PoolHistoryState>,
+    pool_key: Pubkey,
+    amount: u64,
+    is_yes: bool,
+    pricing_reserves: (u64, u64),
+) -> Result<u64> {
+    require!(
+        entry_state.token_balance == 0 || entry_state.is_yes.eq(&is_yes),
+        BettingError::InvalidBet
+    );
+    // History accounts must be passed exactly when the pool keeps on-chain history
+    match (pool_state.history_disabled, history_state.is_some()) {
+        (true, true) => return err!(BettingError::HistoryDisabled),
+        (false, false) => return err!(BettingError::HistoryAccountRequired),
+        _ => {}
+    }
+    // A sealed deposit rejected here stays refundable through refund_commitment
+    if pool_state.max_deposits_per_entry > 0 {
+        require!(
+            entry_state.deposit_count < pool_state.max_deposits_per_entry,
+            BettingError::TooManyDeposits
+        );
+    }
+    pool_state.check_exposure_caps(entry_state.deposited_sol_amount, amount)?;
+
+    // Compute token amount and (optionally) prices using extracted helper. LMSR pools price
+    // against the tokens outstanding now, also for a commit-reveal deposit.
+    let token_amount = match pool_state.pricing_model {
+        PricingModel::Ratio => {
+            let (token_amount, _yes_price, _no_price) = calculate_token_amount_and_prices(
+                amount,
+                is_yes,
+                pricing_reserves.0,
+                pricing_reserves.1,
+            )?;
+            token_amount
+        }
+        PricingModel::Lmsr => lmsr_token_amount(
+            amount,
+            is_yes,
+            pool_state.yes_supply,
+            pool_state.no_supply,
+            pool_state.lmsr_liquidity,
+        )?,
+    };
 
     pool_state.total_supply += token_amount;
-    pool_state.total_reserve += input.amount;
-    if input.is_yes.eq(&true) {
+    pool_state.total_reserve += amount;
+    if is_yes.eq(&true) {
         pool_state.yes_supply += token_amount;
-        pool_state.yes_reserve += input.amount;
+        pool_state.yes_reserve += amount;
     } else {
         pool_state.no_supply += token_amount;
-        pool_state.no_reserve += input.amount;
+        pool_state.no_reserve += amount;
     }
 
-    entry_state.deposited_sol_amount += input.amount;
+    // Accrue the creator's cut of this deposit, unless this is the creator's own exempt entry
+    let creator_fee: u64 = if entry_state.creator_fee_exempt {
+        0
+    } else {
+        bps_of(amount as u128, pool_state.creator_fee_bps)
+            .try_into()
+            .map_err(|_| error!(BettingError::MathOverflow))?
+    };
+    pool_state.creator_fee_accrued += creator_fee;
+    entry_state.creator_fee_paid += creator_fee;
+
+    // Liability guard: reject deposits that would let the pool owe more than it may
+    if pool_state.liability_cap > 0 {
+        require!(
+            pool_state.worst_case_payout() <= pool_state.liability_cap,
+            BettingError::LiabilityCapExceeded
+        );
+    }
+
+    if entry_state.deposit_count == 0 {
+        if is_yes {
+            pool_state.yes_entries += 1;
+        } else {
+            pool_state.no_entries += 1;
+        }
+    }
+    if entry_state.referrer.ne(&Pubkey::default()) {
+        if is_yes {
+            pool_state.referred_yes += amount;
+        } else {
+            pool_state.referred_no += amount;
+        }
+    }
+    entry_state.deposited_sol_amount += amount;
     entry_state.token_balance += token_amount;
-    entry_state.is_yes = input.is_yes;
+    entry_state.is_yes = is_yes;
+    entry_state.deposit_count += 1;
 
-    // Transfer SOL from the user to the pool PDA
-    anchor_lang::system_program::transfer(
-        CpiContext::new(
-            system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: user.clone(),
-                to: ctx.accounts.sol_vault.to_account_info(),
-            },
-        ),
-        input.amount,
-    )?;
+    if let Some(history_state) = history_state {
+        // The opening deposit holds the whole pool, so it doesn't count as large
+        let total_reserve = pool_state.yes_reserve.saturating_add(pool_state.no_reserve);
+        let large = total_reserve > amount
+            && (amount as u128) * 10_000 >= (total_reserve as u128) * WHALE_DEPOSIT_BPS as u128;
+        let kind = if large {
+            ProbabilityPoint::KIND_LARGE_DEPOSIT
+        } else {
+            ProbabilityPoint::KIND_SNAPSHOT
+        };
+        record_history_point(pool_state, history_state, pool_key, kind)?;
+    }
 
-        emit!(DepositEvent {
-            user: *ctx.accounts.user.key,
-            bet_id: pool_state.bet_id,
-            sol_amount: input.amount,
-            token_amount,
-            is_yes: input.is_yes,
-            timestamp: Clock::get()?.unix_timestamp
+    Ok(token_amount)
+}
+
+// Refreshes the pool's risk indicators and rolling volume after a deposit, if it has a
+// summary (see PoolState::check_summary); shared with reveal_deposit
+pub(crate) fn update_pool_summary(
+    pool_summary: Option<&mut PoolSummary>,
+    pool_state: &Account<PoolState>,
+    user: Pubkey,
+    entry_before: u64,
+    amount: u64,
+    now: i64,
+) {
+    let Some(pool_summary) = pool_summary else {
+        return;
+    };
+    pool_summary.pool = pool_state.key();
+    pool_summary.bet_id = pool_state.bet_id;
+    pool_summary.record_deposit(user, entry_before, amount);
+    pool_summary.record_volume(amount, now);
+}
+
+// Reserve and unique-bettor counts of a pool before a deposit, for emit_funnel_events
+#[derive(Clone, Copy)]
+pub(crate) struct FunnelSnapshot {
+    total_reserve: u64,
+    participants: u32,
+}
+
+impl FunnelSnapshot {
+    pub(crate) fn of(pool_state: &PoolState) -> Self {
+        Self {
+            total_reserve: pool_state.yes_reserve.saturating_add(pool_state.no_reserve),
+            participants: pool_state.yes_entries.saturating_add(pool_state.no_entries),
+        }
+    }
+}
+
+// Creator analytics funnel after a deposit landed (shared with reveal_deposit): the pool's
+// first bet, every MainState.reserve_milestones threshold the deposit crossed and the
+// PARTICIPANT_MILESTONES-th unique bettor
+pub(crate) fn emit_funnel_events(
+    main_state: &MainState,
+    pool_state: &PoolState,
+    user: Pubkey,
+    before: FunnelSnapshot,
+    now: i64,
+) {
+    let after = FunnelSnapshot::of(pool_state);
+    let bet_id = pool_state.bet_id;
+    let created_timestamp = pool_state.created_timestamp;
+
+    if before.participants == 0 && after.participants > 0 {
+        emit!(FirstDepositEvent {
+            bet_id,
+            user,
+            created_timestamp,
+            timestamp: now
         });
+    }
+    for &milestone in main_state.reserve_milestones.iter() {
+        if milestone > 0 && before.total_reserve < milestone && after.total_reserve >= milestone {
+            emit!(MilestoneReserveEvent {
+                bet_id,
+                milestone,
+                total_reserve: after.total_reserve,
+                created_timestamp,
+                timestamp: now
+            });
+        }
+    }
+    for milestone in PARTICIPANT_MILESTONES {
+        if before.participants < milestone && after.participants >= milestone {
+            emit!(ParticipantMilestoneEvent {
+                bet_id,
+                user,
+                participants: after.participants,
+                created_timestamp,
+                timestamp: now
+            });
+        }
+    }
+}
 
+// Appends a probability snapshot of the current reserves, flagged with `kind`, over the oldest
+// point once the ring is full. Lifecycle events (lock, resolution, cancellation) append one
+// through annotate_history when the caller passes the pool's history account.
+pub(crate) fn record_history_point(
+    pool_state: &mut PoolState,
+    history_state: &mut PoolHistoryState,
+    pool_key: Pubkey,
+    kind: u8,
+) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
     // Ensure history_state is initialized (in case of legacy pools)
     if history_state.bet_id == 0 {
-        history_state.bet_id = pool_state.bet_id;
-        history_state.pool = pool_state.key();
-        if history_state.points.is_empty() {
-            history_state.points.push(ProbabilityPoint {
-                timestamp: now,
-                yes_reserve: 0,
-                no_reserve: 0,
-            });
-        }
+        history_state.reset(pool_key, pool_state.bet_id, now);
+        history_state.push(now, 0, 0, ProbabilityPoint::KIND_SNAPSHOT);
     }
 
-    // Append probability snapshot after deposit
-    let point = ProbabilityPoint {
-        timestamp: now,
-        yes_reserve: pool_state.yes_reserve,
-        no_reserve: pool_state.no_reserve,
-    };
-    history_state.points.push(point);
-    // Cap number of points to avoid unbounded growth
-    if history_state.points.len() > PoolHistoryState::MAX_POINTS {
-        // Remove oldest
-        let overflow = history_state.points.len() - PoolHistoryState::MAX_POINTS;
-        history_state.points.drain(0..overflow);
+    let overwritten =
+        history_state.push(now, pool_state.yes_reserve, pool_state.no_reserve, kind);
+    if let Some(point) = overwritten {
+        emit!(HistoryPrunedEvent {
+            bet_id: pool_state.bet_id,
+            point_timestamp: history_state.timestamp_of(&point),
+            yes_share_bps: point.yes_share_bps,
+            kind: point.kind,
+            timestamp: now
+        });
+        pool_state.history_pruned = true;
     }
 
     Ok(())
 }
 
+// Borrows the pool's history for writing, if passed. An account that init_if_needed created in
+// this instruction gets its discriminator only on exit, so it is loaded for initialization.
+// One still in the pre-ring layout is left alone until migrate_history converts it: lifecycle
+// markers go unrecorded rather than block a resolution (deposits fail on its size anyway).
+pub(crate) fn load_history<'a>(
+    history_state: &'a Option<AccountLoader<PoolHistoryState>>,
+) -> Result<Option<RefMut<'a, PoolHistoryState>>> {
+    let Some(history_state) = history_state else {
+        return Ok(None);
+    };
+    if history_state.as_ref().data_len() != 8 + PoolHistoryState::MAX_SIZE {
+        return Ok(None);
+    }
+    let created = history_state.as_ref().try_borrow_data()?[..8] == [0; 8];
+    if created {
+        history_state.load_init().map(Some)
+    } else {
+        history_state.load_mut().map(Some)
+    }
+}
+
+// Marks a lifecycle event on the pool's chart; a no-op for pools without on-chain history
+pub(crate) fn annotate_history(
+    pool_state: &mut PoolState,
+    history_state: Option<&mut PoolHistoryState>,
+    pool_key: Pubkey,
+    kind: u8,
+) -> Result<()> {
+    match history_state {
+        Some(history_state) => record_history_point(pool_state, history_state, pool_key, kind),
+        None => Ok(()),
+    }
+}
+
 // ---------------------------------------------------------------------
 // Pricing Helper
 // ---------------------------------------------------------------------
@@ -119,27 +473,43 @@ pub fn deposit(ctx: Context<ADeposit>, input: DepositInput) -> Result<()> {
 // instruction logic focused. Returning prices as well can enable future event
 // emission or analytics without recalculating.
 // ---------------------------------------------------------------------
+// Virtual reserve (1 SOL) to stabilize early odds & avoid div-by-zero
+const VIRTUAL_AMOUNT: u64 = 1_000_000_000; // lamports
+pub(crate) const PRICE_SCALE: u128 = 1_000_000_000u128; // probability precision (1e9)
+
+// Implied (yes, no) prices scaled by PRICE_SCALE; they always sum to ~PRICE_SCALE.
+pub(crate) fn implied_prices(yes_reserve: u64, no_reserve: u64) -> (u128, u128) {
+    let virtual_yes: u128 = (yes_reserve as u128) + (VIRTUAL_AMOUNT as u128);
+    let virtual_no: u128 = (no_reserve as u128) + (VIRTUAL_AMOUNT as u128);
+    let denom: u128 = virtual_yes + virtual_no; // guaranteed > 0
+
+    let yes_price: u128 = virtual_yes * PRICE_SCALE / denom; // scaled price
+    let no_price: u128 = virtual_no * PRICE_SCALE / denom;
+
+    (yes_price, no_price)
+}
+
+// N-way counterpart of implied_prices: price of outcome `index` scaled by PRICE_SCALE, with
+// the same virtual reserve on every outcome. For two outcomes it equals implied_prices.
+pub(crate) fn implied_outcome_price(reserves: &[u64], index: usize) -> u128 {
+    let virtual_reserve = |reserve: u64| (reserve as u128) + (VIRTUAL_AMOUNT as u128);
+    let denom: u128 = reserves.iter().map(|reserve| virtual_reserve(*reserve)).sum();
+
+    virtual_reserve(reserves[index]) * PRICE_SCALE / denom
+}
+
 fn calculate_token_amount_and_prices(
     deposit_amount: u64,
     is_yes: bool,
     yes_reserve: u64,
     no_reserve: u64,
 ) -> Result<(u64, u128, u128)> {
-    // Virtual reserve (1 SOL) to stabilize early odds & avoid div-by-zero
-    const VIRTUAL_AMOUNT: u64 = 1_000_000_000; // lamports
-    const SCALE: u128 = 1_000_000_000u128; // probability precision (1e9)
-
-    let virtual_yes: u128 = (yes_reserve as u128) + (VIRTUAL_AMOUNT as u128);
-    let virtual_no: u128 = (no_reserve as u128) + (VIRTUAL_AMOUNT as u128);
-    let denom: u128 = virtual_yes + virtual_no; // guaranteed > 0
-
-    let yes_price: u128 = virtual_yes * SCALE / denom; // scaled price
-    let no_price: u128 = virtual_no * SCALE / denom;
+    let (yes_price, no_price) = implied_prices(yes_reserve, no_reserve);
 
     let selected_price = if is_yes { yes_price } else { no_price };
 
     // token_amount = deposit * SCALE / selected_price
-    let token_amount: u64 = ((deposit_amount as u128) * SCALE / selected_price)
+    let token_amount: u64 = ((deposit_amount as u128) * PRICE_SCALE / selected_price)
         .try_into()
         .map_err(|_| error!(BettingError::MathOverflow))?;
 
@@ -152,6 +522,12 @@ pub struct ADeposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
     #[account(
         mut,
         seeds =[
@@ -173,6 +549,7 @@ pub struct ADeposit<'info> {
     )]
     pub entry_state: Account<'info, EntryState>,
 
+    // Omitted for pools created with history disabled
     #[account(
         init_if_needed,
         payer = user,
@@ -180,7 +557,15 @@ pub struct ADeposit<'info> {
         seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
         bump
     )]
-    pub history_state: Box<Account<'info, PoolHistoryState>>,
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolSummary::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    // Required once the pool has a summary (see has_summary); omitted for the rest
+    pub pool_summary: Option<Box<Account<'info, PoolSummary>>>,
 
     #[account(
         mut,
@@ -190,5 +575,20 @@ pub struct ADeposit<'info> {
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub sol_vault: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,131 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::{
+    error::BettingError, load_history, place_deposit, vault::pool_vault_account, BetBalance,
+    DepositInput, Depositor, EntryState, MainState, PoolHistoryState, PoolState, PoolSummary,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+// deposit funded from the user's BetBalance: lamports move between program-owned accounts
+// directly, so no system transfer is needed. Same checks and bookkeeping as deposit.
+pub fn deposit_from_balance(ctx: Context<ADepositFromBalance>, input: DepositInput) -> Result<()> {
+    ctx.accounts.pool_state.require_sol_denominated()?;
+    let premium = place_deposit(
+        &ctx.accounts.main_state,
+        &mut ctx.accounts.pool_state,
+        &mut ctx.accounts.entry_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        ctx.accounts.pool_summary.as_deref_mut().map(|summary| &mut **summary),
+        Depositor::new(ctx.accounts.user.key(), &ctx.accounts.gate_token_account),
+        &input,
+    )?;
+
+    let cost = input
+        .amount
+        .checked_add(premium)
+        .ok_or(BettingError::MathOverflow)?;
+    let bet_balance = &mut ctx.accounts.bet_balance;
+    require!(cost <= bet_balance.balance, BettingError::InsufficientBetBalance);
+
+    bet_balance.balance -= cost;
+    bet_balance.total_bet = bet_balance
+        .total_bet
+        .checked_add(cost)
+        .ok_or(BettingError::MathOverflow)?;
+    bet_balance.sub_lamports(cost)?;
+    pool_vault_account(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.pool_vault,
+    )
+    .add_lamports(cost)?;
+    ctx.accounts.pool_state.vault_liabilities += cost;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: DepositInput)]
+pub struct ADepositFromBalance<'info> {
+    // Only pays rent when the pool's history or summary account is created
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [BetBalance::PREFIX_SEED, user.key().as_ref()],
+        bump,
+        has_one = user,
+    )]
+    pub bet_balance: Box<Account<'info, BetBalance>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    // Omitted for pools created with history disabled
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolSummary::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    // Required once the pool has a summary (see has_summary); omitted for the rest
+    pub pool_summary: Option<Box<Account<'info, PoolSummary>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only receives lamports here
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
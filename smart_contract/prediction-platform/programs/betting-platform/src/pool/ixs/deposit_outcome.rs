@@ -0,0 +1,120 @@
+use crate::constants::VAULT_SEED;
+use crate::fees::bps_of;
+use crate::time;
+use crate::{
+    error::BettingError, implied_outcome_price, MainState, OutcomeDepositEvent, OutcomeEntry,
+    OutcomeMarket, PRICE_SCALE,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct DepositOutcomeInput {
+    pub bet_id: u64,
+    pub outcome: u8,
+    pub amount: u64,
+}
+
+// Buys tokens of one outcome of an OutcomeMarket, priced against all outcomes' reserves the
+// way deposit prices a yes/no pool
+pub fn deposit_outcome(ctx: Context<ADepositOutcome>, input: DepositOutcomeInput) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let market = &mut ctx.accounts.outcome_market;
+    let entry = &mut ctx.accounts.outcome_entry;
+    require!(!main_state.paused, BettingError::ProtocolPaused);
+    require!(!market.complete, BettingError::BetComplete);
+    if market.end_timestamp >= 0 {
+        require!(
+            market.end_timestamp > time::now(main_state)?,
+            BettingError::BetEnded
+        );
+    }
+    require!(input.amount > 0, BettingError::InvalidBet);
+    let index = market.check_outcome(input.outcome)?;
+    require!(
+        entry.token_balance == 0 || entry.outcome == input.outcome,
+        BettingError::InvalidBet
+    );
+
+    let price = implied_outcome_price(&market.reserves, index);
+    let token_amount: u64 = ((input.amount as u128) * PRICE_SCALE / price)
+        .try_into()
+        .map_err(|_| error!(BettingError::MathOverflow))?;
+    let creator_fee: u64 = bps_of(input.amount as u128, market.creator_fee_bps)
+        .try_into()
+        .map_err(|_| error!(BettingError::MathOverflow))?;
+
+    market.reserves[index] += input.amount;
+    market.supplies[index] += token_amount;
+    market.creator_fee_accrued += creator_fee;
+
+    entry.user = ctx.accounts.user.key();
+    entry.bet_id = input.bet_id;
+    entry.outcome = input.outcome;
+    entry.deposited_sol_amount += input.amount;
+    entry.token_balance += token_amount;
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.sol_vault.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
+
+    emit!(OutcomeDepositEvent {
+        user: entry.user,
+        bet_id: input.bet_id,
+        outcome: input.outcome,
+        sol_amount: input.amount,
+        token_amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: DepositOutcomeInput)]
+pub struct ADepositOutcome<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [OutcomeMarket::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub outcome_market: Box<Account<'info, OutcomeMarket>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + OutcomeEntry::MAX_SIZE,
+        seeds = [
+            OutcomeEntry::PREFIX_SEED,
+            &outcome_market.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub outcome_entry: Account<'info, OutcomeEntry>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
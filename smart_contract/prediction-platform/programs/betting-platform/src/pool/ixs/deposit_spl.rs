@@ -0,0 +1,114 @@
+use crate::{
+    error::BettingError, load_history, place_deposit, DepositInput, Depositor, EntryState,
+    MainState, PoolHistoryState, PoolState, PoolSummary,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+// deposit for SPL-denominated pools: `amount` is in the mint's base units and is moved from
+// the user's token account into the pool's token vault. Insurance premiums and referral fees
+// are paid in SOL, so insured and referred deposits aren't available here.
+pub fn deposit_spl(ctx: Context<ADepositSpl>, input: DepositInput) -> Result<()> {
+    require!(ctx.accounts.pool_state.is_spl(), BettingError::NotSplPool);
+    require!(
+        !input.insure.unwrap_or(false) && input.referrer.is_none(),
+        BettingError::UnsupportedForSplPool
+    );
+    place_deposit(
+        &ctx.accounts.main_state,
+        &mut ctx.accounts.pool_state,
+        &mut ctx.accounts.entry_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        ctx.accounts.pool_summary.as_deref_mut().map(|summary| &mut **summary),
+        Depositor::new(ctx.accounts.user.key(), &ctx.accounts.gate_token_account),
+        &input,
+    )?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        input.amount,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(input: DepositInput)]
+pub struct ADepositSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    // Omitted for pools created with history disabled
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolSummary::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    // Required once the pool has a summary (see has_summary); omitted for the rest
+    pub pool_summary: Option<Box<Account<'info, PoolSummary>>>,
+
+    #[account(
+        mut,
+        token::mint = pool_state.token_mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_state.token_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,72 @@
+use crate::{error::BettingError, time, Assertion, AssertionDisputedEvent, MainState};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct DisputeAssertionInput {
+    bet_id: u64,
+}
+
+// Challenges an open assertion by matching its bond. The pool then has to be resolved by its
+// referee (or the council once the referee deadline passes) through set_winner, and
+// settle_assertion pays both bonds to whichever side the resolution proves right.
+pub fn dispute_assertion(
+    ctx: Context<ADisputeAssertion>,
+    input: DisputeAssertionInput,
+) -> Result<()> {
+    let assertion = &mut ctx.accounts.assertion;
+    let disputer = &ctx.accounts.disputer;
+    let now = time::now(&ctx.accounts.main_state)?;
+
+    require!(!assertion.is_disputed(), BettingError::AssertionAlreadyDisputed);
+    require!(now < assertion.expires_at, BettingError::AssertionExpired);
+    require!(
+        disputer.key().ne(&assertion.asserter),
+        BettingError::InvalidDisputer
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: disputer.to_account_info(),
+                to: assertion.to_account_info(),
+            },
+        ),
+        assertion.bond,
+    )?;
+
+    assertion.disputer = disputer.key();
+    assertion.disputed_at = now;
+
+    emit!(AssertionDisputedEvent {
+        bet_id: input.bet_id,
+        asserter: assertion.asserter,
+        disputer: disputer.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: DisputeAssertionInput)]
+pub struct ADisputeAssertion<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [Assertion::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub assertion: Box<Account<'info, Assertion>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,116 @@
+use crate::{
+    constants::PROVISIONAL_DISPUTE_BOND, error::BettingError, time, EntryState, MainState,
+    PoolState, ProvisionalDisputedEvent, ResolutionDispute,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct DisputeProvisionalWinnerInput {
+    bet_id: u64,
+}
+
+// Any participant may dispute a provisional outcome within its confirmation window by posting
+// PROVISIONAL_DISPUTE_BOND. The pool stays locked until the owner confirms or overturns the
+// outcome through finalize_resolution, which settles the bond.
+pub fn dispute_provisional_winner(
+    ctx: Context<ADisputeProvisionalWinner>,
+    input: DisputeProvisionalWinnerInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &ctx.accounts.entry_state;
+    require!(
+        pool_state.has_provisional_outcome(),
+        BettingError::NoProvisionalOutcome
+    );
+    require!(
+        !pool_state.provisional_disputed,
+        BettingError::ProvisionalOutcomeDisputed
+    );
+    require!(
+        entry_state.token_balance > 0,
+        BettingError::NotPoolParticipant
+    );
+
+    let now = time::now(&ctx.accounts.main_state)?;
+    require!(
+        now < pool_state
+            .provisional_at
+            .saturating_add(pool_state.provisional_window_secs),
+        BettingError::ConfirmationWindowClosed
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.disputer.to_account_info(),
+                to: ctx.accounts.resolution_dispute.to_account_info(),
+            },
+        ),
+        PROVISIONAL_DISPUTE_BOND,
+    )?;
+
+    let dispute = &mut ctx.accounts.resolution_dispute;
+    dispute.bet_id = input.bet_id;
+    dispute.disputer = ctx.accounts.disputer.key();
+    dispute.is_yes = pool_state.provisional_is_yes;
+    dispute.bond = PROVISIONAL_DISPUTE_BOND;
+    dispute.disputed_at = now;
+    pool_state.provisional_disputed = true;
+
+    emit!(ProvisionalDisputedEvent {
+        bet_id: input.bet_id,
+        disputer: dispute.disputer,
+        is_yes: dispute.is_yes,
+        bond: dispute.bond,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: DisputeProvisionalWinnerInput)]
+pub struct ADisputeProvisionalWinner<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &disputer.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    // Escrows the bond; one dispute per pool
+    #[account(
+        init,
+        payer = disputer,
+        space = 8 + ResolutionDispute::MAX_SIZE,
+        seeds = [ResolutionDispute::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub resolution_dispute: Box<Account<'info, ResolutionDispute>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,87 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{emit_claim_event, settle_claim, EntryState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct EmergencyClaimInput {
+    bet_id: u64,
+}
+
+// Fallback for `claim` that touches nothing but PoolState, EntryState and the vault, so a
+// corrupted or missing auxiliary account can never lock winners out of their payout.
+// Pays out exactly what `claim` would.
+pub fn emergency_claim(ctx: Context<AEmergencyClaim>, _input: EmergencyClaimInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+    let user = &ctx.accounts.user;
+    pool_state.require_sol_denominated()?;
+
+    // A complete pool has already passed set_winner's end-time check, so this needs no
+    // MainState (nor its mock clock offset)
+    let installments_before = entry_state.installments_claimed;
+    let claimable_amount = settle_claim(pool_state, entry_state, Clock::get()?.unix_timestamp)?;
+    emit_claim_event(pool_state, entry_state, installments_before, claimable_amount)?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        claimable_amount,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: EmergencyClaimInput)]
+pub struct AEmergencyClaim<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
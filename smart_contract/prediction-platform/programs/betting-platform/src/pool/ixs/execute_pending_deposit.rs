@@ -0,0 +1,243 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::fees::{platform_fee_bps, refresh_payout_projection};
+use crate::vault::pool_vault_account;
+use crate::{
+    apply_deposit, emit_funnel_events, error::BettingError, load_history, time,
+    update_pool_summary, DepositEvent, Depositor, EntryState, FunnelSnapshot, MainState,
+    PendingDeposit, PendingDepositExecutedEvent, PoolHistoryState, PoolState, PoolSummary,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct ExecutePendingDepositInput {
+    pub bet_id: u64,
+    pub position: u64,
+}
+
+// Permissionless crank over a scheduled pool's waitlist, one PendingDeposit per call and strictly
+// in queue order: from the opening time on, the head of the queue is placed at the then-current
+// reserves, as a regular deposit would be. A head the pool can no longer take (see
+// waitlist_accepts) goes back to its user instead, which a resolved or voided pool does even
+// before it opens. Either way the PendingDeposit closes to the user.
+pub fn execute_pending_deposit(
+    ctx: Context<AExecutePendingDeposit>,
+    input: ExecutePendingDepositInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let pending_deposit = &ctx.accounts.pending_deposit;
+    require!(
+        pending_deposit.position == pool_state.waitlist_executed,
+        BettingError::WaitlistOutOfOrder
+    );
+    pool_state.check_summary(ctx.accounts.pool_summary.is_some())?;
+
+    let now = time::now(main_state)?;
+    let abandoned = pool_state.complete || pool_state.refund_mode;
+    if !abandoned {
+        require!(!main_state.paused, BettingError::ProtocolPaused);
+        require!(pool_state.is_open(now), BettingError::PoolNotOpen);
+    }
+    pool_state.waitlist_executed += 1;
+
+    let entry_state = &mut ctx.accounts.entry_state;
+    let amount = pending_deposit.amount;
+    let mut token_amount = 0;
+    let gate_balance =
+        Depositor::new(pending_deposit.user, &ctx.accounts.gate_token_account).gate_balance;
+    let placed = !abandoned
+        && waitlist_accepts(
+            main_state,
+            pool_state,
+            entry_state,
+            pending_deposit,
+            gate_balance,
+            now,
+        );
+    if placed {
+        let pool_key = pool_state.key();
+        let entry_before = entry_state.deposited_sol_amount;
+        let funnel_before = FunnelSnapshot::of(pool_state);
+        let pricing_reserves = (pool_state.yes_reserve, pool_state.no_reserve);
+        token_amount = apply_deposit(
+            pool_state,
+            entry_state,
+            load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+            pool_key,
+            amount,
+            pending_deposit.is_yes,
+            pricing_reserves,
+        )?;
+
+        update_pool_summary(
+            ctx.accounts.pool_summary.as_deref_mut().map(|summary| &mut **summary),
+            pool_state,
+            pending_deposit.user,
+            entry_before,
+            amount,
+            now,
+        );
+        emit_funnel_events(main_state, pool_state, pending_deposit.user, funnel_before, now);
+        let total_reserve = pool_state.yes_reserve.saturating_add(pool_state.no_reserve);
+        refresh_payout_projection(pool_state, platform_fee_bps(main_state, total_reserve));
+
+        // The escrow moves into the vault; `close = user` hands the user only the rent back
+        let vault =
+            pool_vault_account(pool_state, &ctx.accounts.sol_vault, &ctx.accounts.pool_vault);
+        pending_deposit.sub_lamports(amount)?;
+        vault.add_lamports(amount)?;
+        pool_state.vault_liabilities += amount;
+
+        emit!(DepositEvent {
+            user: pending_deposit.user,
+            bet_id: pool_state.bet_id,
+            sol_amount: amount,
+            token_amount,
+            is_yes: pending_deposit.is_yes,
+            insurance_premium: 0, // queued deposits cannot be insured
+            timestamp: now
+        });
+    }
+
+    emit!(PendingDepositExecutedEvent {
+        user: pending_deposit.user,
+        bet_id: input.bet_id,
+        position: pending_deposit.position,
+        sol_amount: amount,
+        placed,
+        token_amount,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+// Whether the pool can still take a queued deposit at its turn. Trading may have ended, the
+// entry may have switched sides or hit a cap, or the user may no longer show the gate token
+// (`gate_balance`) since it queued; those deposits are refunded rather than left at the head,
+// where they would stall everyone behind them. The deposit is
+// tried on copies of the pool and entry, so the checks stay the ones apply_deposit makes.
+pub(crate) fn waitlist_accepts(
+    main_state: &MainState,
+    pool_state: &PoolState,
+    entry_state: &EntryState,
+    pending_deposit: &PendingDeposit,
+    gate_balance: Option<u64>,
+    now: i64,
+) -> bool {
+    if pool_state.has_provisional_outcome()
+        || pool_state.trading_closed(now)
+        || pool_state.check_gate(gate_balance).is_err()
+    {
+        return false;
+    }
+    let mut pool = pool_state.clone();
+    pool.history_disabled = true;
+    let mut entry = entry_state.clone();
+    let reserves = (pool.yes_reserve, pool.no_reserve);
+    apply_deposit(
+        &mut pool,
+        &mut entry,
+        None,
+        Pubkey::default(),
+        pending_deposit.amount,
+        pending_deposit.is_yes,
+        reserves,
+    )
+    .is_ok()
+        && pool.check_self_referee_cap(main_state).is_ok()
+}
+
+#[derive(Accounts)]
+#[instruction(input: ExecutePendingDepositInput)]
+pub struct AExecutePendingDeposit<'info> {
+    // Anyone; pays for the pool's history and summary accounts if they don't exist yet
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            PendingDeposit::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &input.position.to_le_bytes()
+        ],
+        bump
+    )]
+    pub pending_deposit: Account<'info, PendingDeposit>,
+
+    #[account(mut, address = pending_deposit.user @ BettingError::Unauthorized)]
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &pending_deposit.user.to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Box<Account<'info, EntryState>>,
+
+    // Omitted for pools created with history disabled
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolSummary::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    // Required once the pool has a summary (see has_summary); omitted for the rest
+    pub pool_summary: Option<Box<Account<'info, PoolSummary>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    // Token-gated pools only: the queued user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
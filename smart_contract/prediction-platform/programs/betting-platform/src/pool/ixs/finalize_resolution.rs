@@ -0,0 +1,243 @@
+use crate::{
+    annotate_history,
+    constants::{POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    finalize_outcome, load_history,
+    resolution::ResolutionSource,
+    time,
+    vault::PoolVault,
+    write_settlement_report, CompleteEvent, MainState, PoolHistoryState, PoolState,
+    ProbabilityPoint, ProvisionalDisputeSettledEvent, ResolutionDispute, SettlementReport,
+    TitleIndex,
+};
+#[cfg(feature = "oracle")]
+use crate::{publish_outcome, OutcomeOracle};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct FinalizeResolutionInput {
+    bet_id: u64,
+    // The owner's ruling on a disputed provisional outcome; ignored otherwise
+    is_yes: Option<bool>,
+}
+
+// Second phase of two-phase resolution. Permissionless: once the confirmation window has
+// passed without a dispute, anyone can make the provisional outcome final and open claims.
+// A disputed outcome is the owner's to confirm or overturn, at any time; their ruling settles
+// the dispute bond.
+pub fn finalize_resolution(
+    ctx: Context<AFinalizeResolution>,
+    input: FinalizeResolutionInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(
+        pool_state.has_provisional_outcome(),
+        BettingError::NoProvisionalOutcome
+    );
+    let disputed = pool_state.provisional_disputed;
+    let caller = ctx.accounts.caller.key();
+    require!(
+        !disputed || caller.eq(&main_state.owner),
+        BettingError::ProvisionalOutcomeDisputed
+    );
+    require!(
+        ctx.accounts.title_index.is_some() == pool_state.title_indexed,
+        BettingError::TitleIndexMismatch
+    );
+
+    let now = time::now(main_state)?;
+    let (is_yes, resolver, source) = if disputed {
+        let is_yes = input.is_yes.ok_or(BettingError::DisputeVerdictRequired)?;
+        (is_yes, caller, ResolutionSource::DisputeRuling)
+    } else {
+        require!(
+            now >= pool_state
+                .provisional_at
+                .saturating_add(pool_state.provisional_window_secs),
+            BettingError::ConfirmationWindowActive
+        );
+        (
+            pool_state.provisional_is_yes,
+            pool_state.provisional_by,
+            ResolutionSource::Provisional,
+        )
+    };
+    let platform_payout = finalize_outcome(pool_state, main_state, source, is_yes, now)?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        platform_payout,
+    )?;
+
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_RESOLUTION,
+    )?;
+
+    #[cfg(feature = "oracle")]
+    publish_outcome(&mut ctx.accounts.outcome_oracle, pool_state, pool_key);
+    write_settlement_report(
+        &mut ctx.accounts.settlement_report,
+        pool_state,
+        pool_key,
+        resolver,
+    );
+
+    if disputed {
+        let dispute = ctx
+            .accounts
+            .resolution_dispute
+            .as_ref()
+            .ok_or(BettingError::InvalidDisputer)?;
+        let disputer = ctx
+            .accounts
+            .disputer
+            .as_ref()
+            .filter(|disputer| disputer.key().eq(&dispute.disputer))
+            .ok_or(BettingError::InvalidDisputer)?;
+        let overturned = is_yes != dispute.is_yes;
+        let bond_recipient = if overturned {
+            disputer.to_account_info()
+        } else {
+            let platform_owner = ctx.accounts.platform_owner.to_account_info();
+            dispute.sub_lamports(dispute.bond)?;
+            platform_owner.add_lamports(dispute.bond)?;
+            platform_owner
+        };
+        // The rent, and the bond if it was not forfeited, go back to the disputer
+        dispute.close(disputer.to_account_info())?;
+
+        emit!(ProvisionalDisputeSettledEvent {
+            bet_id: input.bet_id,
+            disputer: disputer.key(),
+            overturned,
+            bond_recipient: bond_recipient.key(),
+            timestamp: now
+        });
+    }
+
+    emit!(CompleteEvent {
+        referee: resolver,
+        bet_id: input.bet_id,
+        winner: pool_state.winner,
+        creator_fee_donated: pool_state.creator_fee_donated,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: FinalizeResolutionInput)]
+pub struct AFinalizeResolution<'info> {
+    // Pays the oracle and report rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    // Omitted for pools without a title index (linked pools)
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    #[cfg(feature = "oracle")]
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + OutcomeOracle::MAX_SIZE,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_oracle: Box<Account<'info, OutcomeOracle>>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + SettlementReport::MAX_SIZE,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Box<Account<'info, SettlementReport>>,
+
+    // Omitted for pools created with history disabled; receives the resolution marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    // Required once the provisional outcome is disputed
+    #[account(
+        mut,
+        seeds = [ResolutionDispute::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub resolution_dispute: Option<Box<Account<'info, ResolutionDispute>>>,
+
+    #[account(mut)]
+    pub disputer: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
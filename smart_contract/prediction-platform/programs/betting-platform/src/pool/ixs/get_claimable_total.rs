@@ -0,0 +1,56 @@
+use crate::{
+    error::BettingError, require_claim_window, settle_claim, EntryState, MainState, PoolState,
+};
+use anchor_lang::prelude::*;
+
+// Read-only sum of what `claim` would pay the user right now across several pools, for a
+// single "Claim all" figure (call it with `.view()`). remaining_accounts holds one
+// (PoolState, EntryState) pair per pool; entries that could not be claimed right now
+// (unresolved, losing, already claimed, ...) count as zero, like check_claim's dry run.
+pub fn get_claimable_total<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AGetClaimableTotal<'info>>,
+) -> Result<u64> {
+    let user = ctx.accounts.user.key();
+    let main_state = &ctx.accounts.main_state;
+    let remaining = ctx.remaining_accounts;
+    require!(remaining.len().is_multiple_of(2), BettingError::InvalidBatch);
+
+    let mut total: u64 = 0;
+    for pair in remaining.chunks(2) {
+        let pool_state = Account::<PoolState>::try_from(&pair[0])?;
+        let (entry_key, _) = Pubkey::find_program_address(
+            &[
+                EntryState::PREFIX_SEED,
+                &pair[0].key().to_bytes(),
+                &user.to_bytes(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(pair[1].key(), entry_key, BettingError::InvalidBatch);
+        let mut entry_state: EntryState = (*Account::<EntryState>::try_from(&pair[1])?).clone();
+
+        let outcome = require_claim_window(&pool_state, main_state)
+            .and_then(|now| settle_claim(&pool_state, &mut entry_state, now));
+        match outcome {
+            Ok(amount) => {
+                total = total.checked_add(amount).ok_or(BettingError::MathOverflow)?;
+            }
+            Err(Error::AnchorError(_)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(total)
+}
+
+#[derive(Accounts)]
+pub struct AGetClaimableTotal<'info> {
+    /// CHECK: only used to derive the entry addresses; no signature is needed for a dry run
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Account<'info, MainState>,
+}
@@ -0,0 +1,77 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::pool_vault_account;
+use crate::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct GetVaultHealthInput {
+    bet_id: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VaultHealth {
+    pub vault_lamports: u64,
+    pub liabilities: u64, // the pool's vault_liabilities ledger
+    pub rent_exempt_minimum: u64,
+    // Lamports above the rent-exempt minimum per lamport owed, in bps (10_000 = exactly
+    // covered); u64::MAX while nothing is owed
+    pub coverage_bps: u64,
+}
+
+impl VaultHealth {
+    pub fn new(vault_lamports: u64, liabilities: u64, rent_exempt_minimum: u64) -> Self {
+        let available = vault_lamports.saturating_sub(rent_exempt_minimum) as u128;
+        let coverage_bps = if liabilities == 0 {
+            u64::MAX
+        } else {
+            (available * 10_000 / liabilities as u128).min(u64::MAX as u128) as u64
+        };
+        Self {
+            vault_lamports,
+            liabilities,
+            rent_exempt_minimum,
+            coverage_bps,
+        }
+    }
+}
+
+// Read-only health of the vault holding a SOL pool's funds, for monitoring bots (call it with
+// `.view()`). Pools still in the legacy shared vault report its whole balance against their
+// own liabilities, so their coverage is only meaningful summed over every legacy pool.
+pub fn get_vault_health(
+    ctx: Context<AGetVaultHealth>,
+    _input: GetVaultHealthInput,
+) -> Result<VaultHealth> {
+    let pool_state = &ctx.accounts.pool_state;
+    pool_state.require_sol_denominated()?;
+    let vault = pool_vault_account(pool_state, &ctx.accounts.sol_vault, &ctx.accounts.pool_vault);
+    Ok(VaultHealth::new(
+        vault.lamports(),
+        pool_state.vault_liabilities,
+        Rent::get()?.minimum_balance(0),
+    ))
+}
+
+#[derive(Accounts)]
+#[instruction(input: GetVaultHealthInput)]
+pub struct AGetVaultHealth<'info> {
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: the legacy shared vault; only its balance is read
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: the pool's own vault; only its balance is read
+    pub pool_vault: AccountInfo<'info>,
+}
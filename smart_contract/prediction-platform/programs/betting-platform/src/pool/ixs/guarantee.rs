@@ -0,0 +1,185 @@
+use crate::time;
+use crate::{error::BettingError, GuaranteeEvent, MainState, PoolGuarantee, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct GuaranteePoolInput {
+    pub bet_id: u64,
+    pub amount: u64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct ReleaseGuaranteeInput {
+    pub bet_id: u64,
+}
+
+// Escrows owner lamports behind a promoted pool and flags it as platform guaranteed; later
+// calls top the escrow up
+pub fn guarantee_pool(ctx: Context<AGuaranteePool>, input: GuaranteePoolInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    // Claims against SPL pools pay from the token vault, which the escrow can't back
+    pool_state.require_sol_denominated()?;
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+    require!(input.amount > 0, BettingError::InvalidBet);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.guarantee.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
+
+    let guarantee = &mut ctx.accounts.guarantee;
+    guarantee.pool = pool_state.key();
+    guarantee.bet_id = input.bet_id;
+    guarantee.balance = guarantee
+        .balance
+        .checked_add(input.amount)
+        .ok_or(BettingError::MathOverflow)?;
+    guarantee.total_funded = guarantee
+        .total_funded
+        .checked_add(input.amount)
+        .ok_or(BettingError::MathOverflow)?;
+    pool_state.guaranteed = true;
+
+    emit!(GuaranteeEvent {
+        bet_id: input.bet_id,
+        user: ctx.accounts.owner.key(),
+        change: input.amount as i64,
+        balance: guarantee.balance,
+        timestamp: time::now(&ctx.accounts.main_state)?
+    });
+
+    Ok(())
+}
+
+// Returns the undrawn guarantee to the owner once no winner can claim anymore: after the
+// pool's claim window, or right away for a pool in refund mode (refunds never draw on it)
+pub fn release_guarantee(
+    ctx: Context<AReleaseGuarantee>,
+    input: ReleaseGuaranteeInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let now = time::now(&ctx.accounts.main_state)?;
+    require!(
+        pool_state.refund_mode || pool_state.claims_closed(now),
+        BettingError::GuaranteeLocked
+    );
+
+    let guarantee = &mut ctx.accounts.guarantee;
+    let amount = guarantee.balance;
+    guarantee.balance = 0;
+    guarantee.sub_lamports(amount)?;
+    ctx.accounts.owner.add_lamports(amount)?;
+    pool_state.guaranteed = false;
+
+    emit!(GuaranteeEvent {
+        bet_id: input.bet_id,
+        user: ctx.accounts.owner.key(),
+        change: -(amount as i64),
+        balance: 0,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+// Covers the part of a claim the vault can't pay from the pool's guarantee, if any; returns
+// the lamports taken from the escrow
+pub(crate) fn draw_guarantee(
+    pool_state: &PoolState,
+    guarantee: Option<&mut Account<PoolGuarantee>>,
+    user: &AccountInfo,
+    shortfall: u64,
+) -> Result<u64> {
+    let Some(guarantee) = guarantee else {
+        return Ok(0);
+    };
+    if shortfall == 0 || !pool_state.guaranteed {
+        return Ok(0);
+    }
+
+    let drawn = shortfall.min(guarantee.balance);
+    guarantee.balance -= drawn;
+    guarantee.total_drawn = guarantee.total_drawn.saturating_add(drawn);
+    guarantee.sub_lamports(drawn)?;
+    user.add_lamports(drawn)?;
+
+    emit!(GuaranteeEvent {
+        bet_id: pool_state.bet_id,
+        user: user.key(),
+        change: -(drawn as i64),
+        balance: guarantee.balance,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(drawn)
+}
+
+#[derive(Accounts)]
+#[instruction(input: GuaranteePoolInput)]
+pub struct AGuaranteePool<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PoolGuarantee::MAX_SIZE,
+        seeds = [PoolGuarantee::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub guarantee: Box<Account<'info, PoolGuarantee>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(input: ReleaseGuaranteeInput)]
+pub struct AReleaseGuarantee<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolGuarantee::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub guarantee: Box<Account<'info, PoolGuarantee>>,
+}
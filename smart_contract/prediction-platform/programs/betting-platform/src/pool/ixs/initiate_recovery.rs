@@ -0,0 +1,76 @@
+use crate::time;
+use crate::{error::BettingError, EntryState, MainState, PoolState, RecoveryInitiatedEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct InitiateRecoveryInput {
+    pub bet_id: u64,
+}
+
+// Signed by the entry's recovery key when the user lost their own: starts the challenge window
+// (RECOVERY_CHALLENGE_SECS) after which recover_claim pays the entry's winnings to the
+// recovery key, unless the user cancels it first
+pub fn initiate_recovery(
+    ctx: Context<AInitiateRecovery>,
+    input: InitiateRecoveryInput,
+) -> Result<()> {
+    let entry_state = &mut ctx.accounts.entry_state;
+    let recovery_key = ctx.accounts.recovery_key.key();
+    require!(
+        entry_state.recovery_key != Pubkey::default() && entry_state.recovery_key == recovery_key,
+        BettingError::NotRecoveryKey
+    );
+    require!(
+        entry_state.recovery_initiated_at == 0,
+        BettingError::RecoveryPending
+    );
+    require!(!entry_state.is_claimed, BettingError::AlreadyClaimed);
+
+    let now = time::now(&ctx.accounts.main_state)?;
+    entry_state.recovery_initiated_at = now;
+
+    emit!(RecoveryInitiatedEvent {
+        user: entry_state.user,
+        bet_id: input.bet_id,
+        recovery_key,
+        unlocks_at: entry_state.recovery_unlocks_at(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: InitiateRecoveryInput)]
+pub struct AInitiateRecovery<'info> {
+    pub recovery_key: Signer<'info>,
+
+    /// CHECK: the entry's owner, who need not sign; only locates the entry
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+}
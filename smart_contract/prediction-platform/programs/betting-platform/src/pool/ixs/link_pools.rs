@@ -0,0 +1,63 @@
+use crate::{
+    error::BettingError, validation::validate_title_and_description, PoolLink, PoolState,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct LinkPoolsInput {
+    pub parent_bet_id: u64,
+    pub on_yes: bool,
+    pub title: String,
+    pub description: String,
+    pub duration_secs: i64, // negative = open-ended child
+}
+
+// Parent creator registers the successor market for one outcome of an unresolved pool
+pub fn link_pools(ctx: Context<ALinkPools>, input: LinkPoolsInput) -> Result<()> {
+    let parent = &ctx.accounts.parent_pool_state;
+    require!(!parent.complete, BettingError::BetComplete);
+    validate_title_and_description(&input.title, &input.description)?;
+
+    let pool_link = &mut ctx.accounts.pool_link;
+    pool_link.parent_bet_id = parent.bet_id;
+    pool_link.on_yes = input.on_yes;
+    pool_link.creator = parent.creator;
+    pool_link.title = input.title;
+    pool_link.description = input.description;
+    pool_link.duration_secs = input.duration_secs;
+    pool_link.opened = false;
+    pool_link.child_bet_id = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: LinkPoolsInput)]
+pub struct ALinkPools<'info> {
+    #[account(mut, address = parent_pool_state.creator @ BettingError::Unauthorized)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.parent_bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub parent_pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolLink::MAX_SIZE,
+        seeds = [
+            PoolLink::PREFIX_SEED,
+            &input.parent_bet_id.to_le_bytes(),
+            &[input.on_yes as u8],
+        ],
+        bump
+    )]
+    pub pool_link: Box<Account<'info, PoolLink>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,81 @@
+use crate::{error::BettingError, HistoryMigratedEvent, PoolHistoryState};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use bytemuck::Zeroable;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct MigrateHistoryInput {
+    pub bet_id: u64,
+}
+
+// Borsh layout of history accounts created before the ring buffer
+#[derive(AnchorDeserialize)]
+struct LegacyPoolHistory {
+    pool: Pubkey,
+    bet_id: u64,
+    points: Vec<LegacyProbabilityPoint>,
+}
+
+#[derive(AnchorDeserialize)]
+struct LegacyProbabilityPoint {
+    timestamp: i64,
+    yes_reserve: u64,
+    no_reserve: u64,
+    kind: u8,
+}
+
+// Permissionless: rewrites a history account created before the ring buffer into the
+// zero-copy layout, keeping its points, and shrinks it to the new size. Pools with such a
+// history take no deposits until it is migrated, as init_if_needed rejects its size.
+pub fn migrate_history(ctx: Context<AMigrateHistory>, input: MigrateHistoryInput) -> Result<()> {
+    let info = ctx.accounts.history_state.to_account_info();
+    require!(
+        info.data_len() == 8 + PoolHistoryState::LEGACY_SIZE,
+        BettingError::HistoryAlreadyMigrated
+    );
+    let points = convert_legacy_history(&mut info.try_borrow_mut_data()?)?;
+    // The rent the smaller account no longer needs stays in it until close_history
+    info.realloc(8 + PoolHistoryState::MAX_SIZE, false)?;
+
+    emit!(HistoryMigratedEvent {
+        bet_id: input.bet_id,
+        points,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+// Rewrites the legacy history in `data` (discriminator included) into the ring layout at its
+// front, zeroing the bytes past it. Returns how many points carried over.
+pub fn convert_legacy_history(data: &mut [u8]) -> Result<u16> {
+    require!(
+        data.len() == 8 + PoolHistoryState::LEGACY_SIZE
+            && data.starts_with(PoolHistoryState::DISCRIMINATOR),
+        BettingError::InvalidLegacyHistory
+    );
+    let legacy = LegacyPoolHistory::deserialize(&mut &data[8..])
+        .map_err(|_| error!(BettingError::InvalidLegacyHistory))?;
+
+    let mut history = PoolHistoryState::zeroed();
+    let started_at = legacy.points.first().map_or(0, |point| point.timestamp);
+    history.reset(legacy.pool, legacy.bet_id, started_at);
+    for point in &legacy.points {
+        history.push(point.timestamp, point.yes_reserve, point.no_reserve, point.kind);
+    }
+
+    data[8..].fill(0);
+    data[8..8 + PoolHistoryState::MAX_SIZE].copy_from_slice(bytemuck::bytes_of(&history));
+    Ok(history.len)
+}
+
+#[derive(Accounts)]
+#[instruction(input: MigrateHistoryInput)]
+pub struct AMigrateHistory<'info> {
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: AccountLoader<'info, PoolHistoryState>,
+}
@@ -0,0 +1,119 @@
+use crate::{
+    error::BettingError, layout::grow_account, MainState, PoolState, PoolWinnerMigratedEvent,
+    WinnerSide,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+// Offset of `title`, PoolState's first variable-length field, in the account data
+const TITLE_OFFSET: usize = 8 + 32 + 8 * 3 + 8 * 6 + 8 * 2 + 32;
+// `winner` used to be a String of at most 50 bytes
+const LEGACY_WINNER_SIZE: usize = 4 + 50;
+// Account length of pools created while `winner` was a String, before the reserved block
+pub const LEGACY_POOL_SIZE: usize =
+    8 + PoolState::MAX_SIZE - PoolState::RESERVED - 1 + LEGACY_WINNER_SIZE;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct MigratePoolWinnerInput {
+    pub bet_id: u64,
+}
+
+// Owner-only: rewrites a pool created while `winner` was a "yes"/"no" String into the
+// WinnerSide layout, then grows the account by the reserved block with the owner paying the
+// extra rent
+pub fn migrate_pool_winner(
+    ctx: Context<AMigratePoolWinner>,
+    input: MigratePoolWinnerInput,
+) -> Result<()> {
+    let info = ctx.accounts.pool_state.to_account_info();
+    require!(
+        info.data_len() == LEGACY_POOL_SIZE,
+        BettingError::PoolAlreadyMigrated
+    );
+    let legacy_winner = splice_legacy_winner(&mut info.try_borrow_mut_data()?)?;
+    let rent_top_up = grow_account(
+        &info,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + PoolState::MAX_SIZE,
+    )?;
+
+    let mut pool_state = PoolState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    pool_state.winner = if pool_state.refund_mode {
+        WinnerSide::Void
+    } else {
+        legacy_winner
+    };
+    pool_state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(PoolWinnerMigratedEvent {
+        bet_id: input.bet_id,
+        winner: pool_state.winner,
+        rent_top_up,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+// Replaces the legacy String winner in `data` by the one-byte WinnerSide it maps to, moving
+// every later field forward. Returns the side; the bytes left over at the end are zeroed.
+pub fn splice_legacy_winner(data: &mut [u8]) -> Result<WinnerSide> {
+    require!(
+        data.starts_with(PoolState::DISCRIMINATOR),
+        BettingError::InvalidLegacyPool
+    );
+    let u32_at = |data: &[u8], at: usize| -> Result<usize> {
+        let bytes = data
+            .get(at..at + 4)
+            .ok_or(BettingError::InvalidLegacyPool)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+    // title, description and share_uuid precede the winner
+    let mut winner_at = TITLE_OFFSET;
+    for _ in 0..3 {
+        winner_at += 4 + u32_at(data, winner_at)?;
+    }
+    let winner_len = u32_at(data, winner_at)?;
+    let rest_at = winner_at + 4 + winner_len;
+    let side = match data.get(winner_at + 4..rest_at) {
+        Some(b"") => WinnerSide::Unresolved,
+        Some(b"yes") => WinnerSide::Yes,
+        Some(b"no") => WinnerSide::No,
+        _ => return err!(BettingError::InvalidLegacyPool),
+    };
+
+    data[winner_at] = side as u8;
+    data.copy_within(rest_at.., winner_at + 1);
+    let freed = rest_at - (winner_at + 1);
+    let len = data.len();
+    data[len - freed..].fill(0);
+    Ok(side)
+}
+
+#[derive(Accounts)]
+#[instruction(input: MigratePoolWinnerInput)]
+pub struct AMigratePoolWinner<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    /// CHECK: a legacy pool does not deserialize as PoolState; the handler parses it
+    pub pool_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
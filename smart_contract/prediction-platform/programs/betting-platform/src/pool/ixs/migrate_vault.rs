@@ -0,0 +1,110 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::{
+    error::BettingError, vault::fund_pool_vault_rent, MainState, PoolState, VaultMigratedEvent,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_instruction;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct MigrateVaultInput {
+    pub bet_id: u64,
+}
+
+// Owner-only: moves a pool created before per-pool vaults from the legacy shared sol-vault
+// into its own pool-vault PDA, after which every instruction settles it from there (see
+// vault::PoolVault). Only open pools without pending commitments can migrate, so the
+// liability is exactly the pool's reserves plus insurance premiums: nothing has been paid out
+// and no sealed deposit is in flight. Pools that already have their own vault fail on the
+// `own_vault` flag.
+pub fn migrate_vault(ctx: Context<AMigrateVault>, _input: MigrateVaultInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.require_sol_denominated()?;
+
+    require!(!pool_state.own_vault, BettingError::VaultMigrated);
+    require!(
+        !pool_state.complete && !pool_state.refund_mode && pool_state.pending_commitments == 0,
+        BettingError::VaultMigrationBlocked
+    );
+
+    let amount = pool_state
+        .yes_reserve
+        .checked_add(pool_state.no_reserve)
+        .and_then(|reserves| reserves.checked_add(pool_state.insurance_premiums))
+        .ok_or(BettingError::MathOverflow)?;
+
+    // The owner funds the pool vault's rent-exempt minimum so it can hold any balance
+    let pool_vault = ctx.accounts.pool_vault.to_account_info();
+    let rent_top_up = fund_pool_vault_rent(
+        &ctx.accounts.owner.to_account_info(),
+        &pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    if amount > 0 {
+        let transfer_instruction =
+            system_instruction::transfer(ctx.accounts.sol_vault.key, pool_vault.key, amount);
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_instruction,
+            &[
+                ctx.accounts.sol_vault.to_account_info(),
+                pool_vault.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[VAULT_SEED.as_bytes(), &[ctx.bumps.sol_vault]]],
+        )?;
+    }
+
+    pool_state.own_vault = true;
+
+    emit!(VaultMigratedEvent {
+        bet_id: pool_state.bet_id,
+        pool_vault: pool_vault.key(),
+        amount,
+        rent_top_up,
+        legacy_vault_balance: ctx.accounts.sol_vault.lamports(),
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: MigrateVaultInput)]
+pub struct AMigrateVault<'info> {
+    #[account(mut, address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
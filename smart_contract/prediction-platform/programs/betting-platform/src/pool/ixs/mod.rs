@@ -19,3 +19,225 @@ pub use claim::*;
 pub mod claim_creator_fee;
 pub use claim_creator_fee::*;
 
+pub mod claim_referee_fee;
+pub use claim_referee_fee::*;
+
+pub mod commit_deposit;
+pub use commit_deposit::*;
+
+pub mod reveal_deposit;
+pub use reveal_deposit::*;
+
+pub mod refund_commitment;
+pub use refund_commitment::*;
+
+pub mod refresh_share_uuid;
+pub use refresh_share_uuid::*;
+
+pub mod set_liability_cap;
+pub use set_liability_cap::*;
+
+pub mod set_pool_metadata;
+pub use set_pool_metadata::*;
+
+pub mod link_pools;
+pub use link_pools::*;
+
+pub mod open_linked_pool;
+pub use open_linked_pool::*;
+
+pub mod emergency_claim;
+pub use emergency_claim::*;
+
+pub mod trigger_refund_mode;
+pub use trigger_refund_mode::*;
+
+pub mod claim_refund;
+pub use claim_refund::*;
+
+pub mod set_winner_many;
+pub use set_winner_many::*;
+
+pub mod migrate_vault;
+pub use migrate_vault::*;
+
+pub mod claim_with_permit;
+pub use claim_with_permit::*;
+
+pub mod claim_insurance;
+pub use claim_insurance::*;
+
+#[cfg(feature = "oracle")]
+pub mod publish_odds_feed;
+#[cfg(feature = "oracle")]
+pub use publish_odds_feed::*;
+
+pub mod check_claim;
+pub use check_claim::*;
+
+#[cfg(feature = "oracle")]
+pub mod assert_outcome;
+#[cfg(feature = "oracle")]
+pub use assert_outcome::*;
+
+#[cfg(feature = "oracle")]
+pub mod dispute_assertion;
+#[cfg(feature = "oracle")]
+pub use dispute_assertion::*;
+
+#[cfg(feature = "oracle")]
+pub mod settle_assertion;
+#[cfg(feature = "oracle")]
+pub use settle_assertion::*;
+
+pub mod close_history;
+pub use close_history::*;
+
+pub mod tokenize_entry;
+pub use tokenize_entry::*;
+
+pub mod claim_with_tokens;
+pub use claim_with_tokens::*;
+
+pub mod set_winner_by_value;
+pub use set_winner_by_value::*;
+
+pub mod accept_referee_role;
+pub use accept_referee_role::*;
+
+pub mod subscribe_pool;
+pub use subscribe_pool::*;
+
+pub mod notify_pool_locked;
+pub use notify_pool_locked::*;
+
+pub mod bet_balance;
+pub use bet_balance::*;
+
+pub mod deposit_from_balance;
+pub use deposit_from_balance::*;
+
+pub mod set_provisional_winner;
+pub use set_provisional_winner::*;
+
+pub mod dispute_provisional_winner;
+pub use dispute_provisional_winner::*;
+
+pub mod finalize_resolution;
+pub use finalize_resolution::*;
+
+pub mod get_claimable_total;
+pub use get_claimable_total::*;
+
+pub mod create_outcome_market;
+pub use create_outcome_market::*;
+
+pub mod deposit_outcome;
+pub use deposit_outcome::*;
+
+pub mod set_outcome_winner;
+pub use set_outcome_winner::*;
+
+pub mod claim_outcome;
+pub use claim_outcome::*;
+
+#[cfg(feature = "oracle")]
+pub mod publish_event_start;
+#[cfg(feature = "oracle")]
+pub use publish_event_start::*;
+
+#[cfg(feature = "oracle")]
+pub mod crank_lock_on_start;
+#[cfg(feature = "oracle")]
+pub use crank_lock_on_start::*;
+
+pub mod create_pool_spl;
+pub use create_pool_spl::*;
+
+pub mod deposit_spl;
+pub use deposit_spl::*;
+
+pub mod claim_spl;
+pub use claim_spl::*;
+
+pub mod claim_fees_spl;
+pub use claim_fees_spl::*;
+
+pub mod void_pool;
+pub use void_pool::*;
+
+pub mod guarantee;
+pub use guarantee::*;
+
+#[cfg(feature = "oracle")]
+pub mod resolve_with_oracle;
+#[cfg(feature = "oracle")]
+pub use resolve_with_oracle::*;
+
+pub mod refund_unrevealed;
+pub use refund_unrevealed::*;
+
+pub mod close_entry;
+pub use close_entry::*;
+
+pub mod close_pool;
+pub use close_pool::*;
+
+pub mod migrate_pool_winner;
+pub use migrate_pool_winner::*;
+pub mod get_vault_health;
+pub use get_vault_health::*;
+
+pub mod reclaim_seed_liquidity;
+pub use reclaim_seed_liquidity::*;
+
+pub mod create_meta_pool;
+pub use create_meta_pool::*;
+
+pub mod resolve_meta_market;
+pub use resolve_meta_market::*;
+
+pub mod sweep_unclaimed;
+pub use sweep_unclaimed::*;
+
+pub mod queue_deposit;
+pub use queue_deposit::*;
+
+pub mod execute_pending_deposit;
+pub use execute_pending_deposit::*;
+
+pub mod propose_winner;
+pub use propose_winner::*;
+
+pub mod set_recovery_key;
+pub use set_recovery_key::*;
+
+pub mod initiate_recovery;
+pub use initiate_recovery::*;
+
+pub mod cancel_recovery;
+pub use cancel_recovery::*;
+
+pub mod recover_claim;
+pub use recover_claim::*;
+
+pub mod migrate_history;
+pub use migrate_history::*;
+
+pub mod verify_replay;
+pub use verify_replay::*;
+
+pub mod quote_deposit;
+pub use quote_deposit::*;
+
+pub mod record_final_odds;
+pub use record_final_odds::*;
+
+pub mod referee_queue;
+pub use referee_queue::*;
+
+pub mod transfer_entry;
+pub use transfer_entry::*;
+
+pub mod settle_creator_bond;
+pub use settle_creator_bond::*;
@@ -0,0 +1,60 @@
+use crate::{
+    error::BettingError, time, MainState, PoolNotificationEvent, PoolState, PoolSubscribers,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct NotifyPoolLockedInput {
+    bet_id: u64,
+}
+
+// Permissionless crank: once a fixed-end pool stops taking deposits, announces it to the
+// pool's subscribers exactly once
+pub fn notify_pool_locked(
+    ctx: Context<ANotifyPoolLocked>,
+    input: NotifyPoolLockedInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let pool_state = &ctx.accounts.pool_state;
+    let subscribers = &mut ctx.accounts.pool_subscribers;
+    require!(pool_state.end_timestamp >= 0, BettingError::PoolNeverLocks);
+    let now = time::now(main_state)?;
+    require!(
+        pool_state.has_ended(now),
+        BettingError::BetNotEnded
+    );
+    require!(!subscribers.lock_notified, BettingError::LockAlreadyNotified);
+    subscribers.lock_notified = true;
+
+    emit!(PoolNotificationEvent {
+        bet_id: input.bet_id,
+        kind: PoolSubscribers::NOTIFY_LOCK,
+        subscribers: subscribers.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: NotifyPoolLockedInput)]
+pub struct ANotifyPoolLocked<'info> {
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolSubscribers::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_subscribers: Box<Account<'info, PoolSubscribers>>,
+}
@@ -0,0 +1,203 @@
+use crate::{
+    constants::POOL_VAULT_SEED, error::BettingError, escrow_creator_bond, initialize_pool,
+    vault::fund_pool_vault_rent, CreatePoolInput, LinkedPoolOpenedEvent, MainState,
+    PoolHistoryState, PoolLink, PoolState,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct OpenLinkedPoolInput {
+    pub parent_bet_id: u64,
+    pub on_yes: bool,
+}
+
+// Permissionless crank: once the parent resolves to the linked outcome, open the child pool
+// with the parent's referee and market settings. The caller pays rent for the new accounts
+// and posts the creator bond, which settle_creator_bond returns to them as the rent payer.
+pub fn open_linked_pool(ctx: Context<AOpenLinkedPool>, input: OpenLinkedPoolInput) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        BettingError::Uninitialized
+    );
+    require!(!main_state.paused, BettingError::ProtocolPaused);
+
+    let parent = &ctx.accounts.parent_pool_state;
+    require!(parent.complete, BettingError::BetNotComplete);
+    let winner_is_yes = parent.winner.is_yes();
+    require!(
+        winner_is_yes == input.on_yes,
+        BettingError::LinkOutcomeMismatch
+    );
+
+    let pool_link = &mut ctx.accounts.pool_link;
+    require!(!pool_link.opened, BettingError::LinkAlreadyOpened);
+
+    let end_timestamp = if pool_link.duration_secs < 0 {
+        -1
+    } else {
+        Clock::get()?
+            .unix_timestamp
+            .checked_add(pool_link.duration_secs)
+            .ok_or(BettingError::MathOverflow)?
+    };
+
+    let child_bet_id = main_state.current_bet_id;
+    // The parent's window may have fallen outside bounds the owner changed since
+    let claim_window_secs = parent
+        .claim_window_secs
+        .clamp(main_state.claim_window.min_secs, main_state.claim_window.max_secs);
+    ctx.accounts.pool_state.rent_payer = ctx.accounts.payer.key();
+    initialize_pool(
+        ctx.program_id,
+        main_state,
+        &mut ctx.accounts.pool_state,
+        ctx.accounts.history_state.as_ref(),
+        pool_link.creator,
+        parent.referee,
+        CreatePoolInput {
+            title: pool_link.title.clone(),
+            description: pool_link.description.clone(),
+            end_timestamp,
+            referee: Some(parent.referee),
+            referee_index: None,
+            commit_reveal: Some(parent.commit_reveal),
+            installments: Some(parent.installment_count),
+            installment_period_secs: Some(parent.installment_period),
+            unit_symbol: Some(parent.unit_symbol.clone()),
+            decimals: Some(parent.decimals),
+            side_a_label: Some(parent.side_a_label.clone()),
+            side_b_label: Some(parent.side_b_label.clone()),
+            disable_history: Some(parent.history_disabled),
+            waive_creator_self_fee: Some(parent.waive_creator_self_fee),
+            creator_donation_bps: Some(parent.creator_donation_bps),
+            max_deposits_per_entry: Some(parent.max_deposits_per_entry),
+            optimistic_resolution: Some(parent.optimistic_resolution),
+            outcome_tokens: Some(parent.outcome_tokens),
+            range_buckets: Some(parent.range_buckets.clone()),
+            scalar_bounds: parent
+                .scalar_bounds
+                .is_set()
+                .then_some(parent.scalar_bounds),
+            claim_deadline_override: Some(claim_window_secs),
+            provisional_window_secs: Some(parent.provisional_window_secs),
+            // Absolute deadlines and event feeds don't carry over to a pool that opens later
+            referee_deadline: None,
+            refund_deadline: None,
+            trading_end_timestamp: None,
+            resolution_deadline: None,
+            start_feed: None,
+            price_condition: None,
+            opens_at: None,
+            referee_committee: parent.has_committee().then(|| {
+                parent.referee_committee[..parent.committee_size as usize].to_vec()
+            }),
+            referee_threshold: parent.has_committee().then_some(parent.committee_threshold),
+            gate_mint: parent.is_gated().then_some(parent.gate_mint),
+            gate_min_amount: parent.is_gated().then_some(parent.gate_min_amount),
+            min_participants: Some(parent.min_participants),
+            max_pool_reserve: Some(parent.max_pool_reserve),
+            max_user_deposit: Some(parent.max_user_deposit),
+            referee_fee_bps: None,
+            pricing_model: Some(parent.pricing_model),
+            lmsr_liquidity: Some(parent.lmsr_liquidity),
+            // The caller is not the creator, so nobody seeds the child
+            initial_yes_liquidity: None,
+            initial_no_liquidity: None,
+        },
+    )?;
+
+    // A referee who never accepted the parent doesn't gain the child either
+    ctx.accounts.pool_state.referee_accepted = parent.referee_accepted;
+    pool_link.opened = true;
+    pool_link.child_bet_id = child_bet_id;
+
+    fund_pool_vault_rent(
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    ctx.accounts.pool_state.own_vault = true;
+    escrow_creator_bond(
+        &mut ctx.accounts.pool_state,
+        &ctx.accounts.main_state,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.pool_vault,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(LinkedPoolOpenedEvent {
+        parent_bet_id: parent.bet_id,
+        child_bet_id,
+        on_yes: input.on_yes,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: OpenLinkedPoolInput)]
+pub struct AOpenLinkedPool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.parent_bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub parent_pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolLink::PREFIX_SEED,
+            &input.parent_bet_id.to_le_bytes(),
+            &[input.on_yes as u8],
+        ],
+        bump
+    )]
+    pub pool_link: Box<Account<'info, PoolLink>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolState::MAX_SIZE,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &main_state.current_bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    // Omitted when the parent pool has history disabled
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &main_state.current_bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The child pool's own vault (see vault::PoolVault), funded with its rent minimum
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
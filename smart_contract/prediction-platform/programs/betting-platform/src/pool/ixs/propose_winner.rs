@@ -0,0 +1,222 @@
+use crate::{
+    annotate_history,
+    constants::{POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    finalize_outcome, load_history,
+    resolution::{self, ResolutionSource},
+    time,
+    vault::PoolVault,
+    write_settlement_report, CompleteEvent, MainState, PoolHistoryState, PoolState,
+    ProbabilityPoint, ResolutionState, SettlementReport, TitleIndex, WinnerProposedEvent,
+};
+#[cfg(feature = "oracle")]
+use crate::{publish_outcome, OutcomeOracle};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ProposeWinnerInput {
+    bet_id: u64,
+    is_yes: bool,
+}
+
+// A referee committee member's vote on the outcome. Each member votes once; the vote that
+// brings either side to the committee's threshold resolves the pool like set_winner, and must
+// come with the oracle, report and (for indexed pools) title index accounts. Earlier votes
+// pass none of them. Like a lone referee, the committee may vote until the refund deadline,
+// and the council may step in once the referee deadline passes.
+pub fn propose_winner(ctx: Context<AProposeWinner>, input: ProposeWinnerInput) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let member = ctx.accounts.member.key();
+    require!(pool_state.has_committee(), BettingError::NotCommitteePool);
+    resolution::ensure_unresolved(pool_state)?;
+    let seat = pool_state
+        .committee_seat(&member)
+        .ok_or(BettingError::NotCommitteeMember)?;
+
+    let now = time::now(main_state)?;
+    require!(
+        !pool_state.resolution_window_closed(now),
+        BettingError::ResolutionWindowClosed
+    );
+
+    let resolution_state = &mut ctx.accounts.resolution_state;
+    resolution_state.bet_id = input.bet_id;
+    let votes = resolution_state.record_vote(seat, input.is_yes, now)?;
+    let resolved = votes >= pool_state.committee_threshold;
+
+    let accounts_passed = ctx.accounts.settlement_report.is_some()
+        && ctx.accounts.title_index.is_some() == pool_state.title_indexed;
+    let no_accounts_passed =
+        ctx.accounts.settlement_report.is_none() && ctx.accounts.title_index.is_none();
+    require!(
+        if resolved { accounts_passed } else { no_accounts_passed },
+        BettingError::ResolutionAccountsMismatch
+    );
+    // The OutcomeOracle comes and goes with the SettlementReport
+    #[cfg(feature = "oracle")]
+    require!(
+        ctx.accounts.outcome_oracle.is_some() == ctx.accounts.settlement_report.is_some(),
+        BettingError::ResolutionAccountsMismatch
+    );
+
+    emit!(WinnerProposedEvent {
+        bet_id: input.bet_id,
+        member,
+        is_yes: input.is_yes,
+        yes_votes: resolution_state.yes_votes,
+        no_votes: resolution_state.no_votes,
+        threshold: pool_state.committee_threshold,
+        resolved,
+        timestamp: now
+    });
+    if !resolved {
+        return Ok(());
+    }
+
+    let platform_payout =
+        finalize_outcome(pool_state, main_state, ResolutionSource::Committee, input.is_yes, now)?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        platform_payout,
+    )?;
+
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_RESOLUTION,
+    )?;
+
+    #[cfg(feature = "oracle")]
+    if let Some(outcome_oracle) = ctx.accounts.outcome_oracle.as_deref_mut() {
+        publish_outcome(outcome_oracle, pool_state, pool_key);
+    }
+    if let Some(settlement_report) = ctx.accounts.settlement_report.as_deref_mut() {
+        write_settlement_report(settlement_report, pool_state, pool_key, member);
+    }
+
+    emit!(CompleteEvent {
+        referee: member,
+        bet_id: input.bet_id,
+        winner: pool_state.winner,
+        creator_fee_donated: pool_state.creator_fee_donated,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ProposeWinnerInput)]
+pub struct AProposeWinner<'info> {
+    // Pays the ResolutionState rent with the first vote, and the oracle and report rent with
+    // the deciding one
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + ResolutionState::MAX_SIZE,
+        seeds = [ResolutionState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub resolution_state: Box<Account<'info, ResolutionState>>,
+
+    // Deciding vote only, for pools with a title index
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    // Deciding vote only
+    #[cfg(feature = "oracle")]
+    #[account(
+        init,
+        payer = member,
+        space = 8 + OutcomeOracle::MAX_SIZE,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_oracle: Option<Box<Account<'info, OutcomeOracle>>>,
+
+    // Deciding vote only
+    #[account(
+        init,
+        payer = member,
+        space = 8 + SettlementReport::MAX_SIZE,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Option<Box<Account<'info, SettlementReport>>>,
+
+    // Omitted for pools created with history disabled; receives the resolution marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,47 @@
+use crate::{error::BettingError, StartFeed};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PublishEventStartInput {
+    pub feed_id: u64,
+    // Start time of the event; 0 clears a premature report
+    pub started_at: i64,
+}
+
+// Oracle operators report event starts here. Creating the feed ahead of time (started_at = 0)
+// gives creators an address to bind pools to.
+pub fn publish_event_start(
+    ctx: Context<APublishEventStart>,
+    input: PublishEventStartInput,
+) -> Result<()> {
+    require!(input.started_at >= 0, BettingError::InvalidEventStart);
+    let start_feed = &mut ctx.accounts.start_feed;
+    start_feed.authority = ctx.accounts.authority.key();
+    start_feed.feed_id = input.feed_id;
+    start_feed.started_at = input.started_at;
+    start_feed.updated_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: PublishEventStartInput)]
+pub struct APublishEventStart<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + StartFeed::MAX_SIZE,
+        seeds = [
+            StartFeed::PREFIX_SEED,
+            authority.key().as_ref(),
+            &input.feed_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub start_feed: Account<'info, StartFeed>,
+
+    pub system_program: Program<'info, System>,
+}
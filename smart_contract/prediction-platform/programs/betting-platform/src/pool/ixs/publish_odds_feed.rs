@@ -0,0 +1,73 @@
+use crate::constants::ODDS_FEED_MIN_INTERVAL_SECS;
+use crate::{error::BettingError, OddsFeed, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct PublishOddsFeedInput {
+    pub bet_id: u64,
+}
+
+// Permissionless crank writing a pool's current implied probabilities into its OddsFeed,
+// at most once per ODDS_FEED_MIN_INTERVAL_SECS. The payer funds the feed on first publish.
+pub fn publish_odds_feed(
+    ctx: Context<APublishOddsFeed>,
+    _input: PublishOddsFeedInput,
+) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let odds_feed = &mut ctx.accounts.odds_feed;
+    let clock = Clock::get()?;
+
+    // publish_time is 0 on a freshly created feed
+    require!(
+        clock.unix_timestamp >= odds_feed.publish_time + ODDS_FEED_MIN_INTERVAL_SECS,
+        BettingError::OddsFeedRateLimited
+    );
+
+    let (yes_price, no_price) = pool_state.current_prices();
+    odds_feed.version = OddsFeed::VERSION;
+    odds_feed.pool = pool_state.key();
+    odds_feed.bet_id = pool_state.bet_id;
+    odds_feed.status = if pool_state.refund_mode {
+        OddsFeed::STATUS_REFUNDING
+    } else if pool_state.complete {
+        OddsFeed::STATUS_RESOLVED
+    } else {
+        OddsFeed::STATUS_TRADING
+    };
+    odds_feed.expo = OddsFeed::EXPO;
+    odds_feed.yes_price = yes_price as i64;
+    odds_feed.no_price = no_price as i64;
+    odds_feed.yes_reserve = pool_state.yes_reserve;
+    odds_feed.no_reserve = pool_state.no_reserve;
+    odds_feed.publish_time = clock.unix_timestamp;
+    odds_feed.publish_slot = clock.slot;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: PublishOddsFeedInput)]
+pub struct APublishOddsFeed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + OddsFeed::MAX_SIZE,
+        seeds = [OddsFeed::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub odds_feed: Account<'info, OddsFeed>,
+
+    pub system_program: Program<'info, System>,
+}
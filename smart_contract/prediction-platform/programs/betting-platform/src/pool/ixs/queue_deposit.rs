@@ -0,0 +1,123 @@
+use crate::time;
+use crate::{
+    error::BettingError, DepositQueuedEvent, Depositor, EntryState, MainState, PendingDeposit,
+    PoolState,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct QueueDepositInput {
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub amount: u64,
+}
+
+// Joins a scheduled pool's waitlist before it opens: the lamports move into a PendingDeposit at
+// the back of the queue, which execute_pending_deposit places once the pool opens. The user
+// needs an entry in the pool already so the crank has somewhere to book the position.
+pub fn queue_deposit(ctx: Context<AQueueDeposit>, input: QueueDepositInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.require_sol_denominated()?;
+
+    require!(!ctx.accounts.main_state.paused, BettingError::ProtocolPaused);
+    require!(!pool_state.complete, BettingError::BetComplete);
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+    // Queued deposits open in the clear, which a sealed-bid pool can't allow
+    require!(!pool_state.commit_reveal, BettingError::CommitRevealRequired);
+    pool_state.check_gate(
+        Depositor::new(ctx.accounts.user.key(), &ctx.accounts.gate_token_account).gate_balance,
+    )?;
+
+    let now = time::now(&ctx.accounts.main_state)?;
+    require!(!pool_state.is_open(now), BettingError::WaitlistClosed);
+    require!(input.amount > 0, BettingError::InvalidBet);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.pending_deposit.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
+
+    let pending_deposit = &mut ctx.accounts.pending_deposit;
+    pending_deposit.user = ctx.accounts.user.key();
+    pending_deposit.bet_id = input.bet_id;
+    pending_deposit.position = pool_state.waitlist_queued;
+    pending_deposit.is_yes = input.is_yes;
+    pending_deposit.amount = input.amount;
+    pending_deposit.queued_at = now;
+    pool_state.waitlist_queued += 1;
+
+    emit!(DepositQueuedEvent {
+        user: pending_deposit.user,
+        bet_id: input.bet_id,
+        position: pending_deposit.position,
+        is_yes: input.is_yes,
+        sol_amount: input.amount,
+        opens_at: pool_state.opens_at,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: QueueDepositInput)]
+pub struct AQueueDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Box<Account<'info, EntryState>>,
+
+    // Keyed by queue position, so the crank can always derive the head of the queue
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PendingDeposit::MAX_SIZE,
+        seeds = [
+            PendingDeposit::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &pool_state.waitlist_queued.to_le_bytes()
+        ],
+        bump
+    )]
+    pub pending_deposit: Account<'info, PendingDeposit>,
+
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
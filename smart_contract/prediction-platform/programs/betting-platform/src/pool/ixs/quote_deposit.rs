@@ -0,0 +1,99 @@
+use crate::constants::PAYOUT_PER_TOKEN_SCALE;
+use crate::fees::{platform_fee_bps, refresh_payout_projection};
+use crate::{apply_deposit, error::BettingError, EntryState, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct QuoteDepositInput {
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub amount: u64,
+}
+
+// What a fresh deposit of `amount` on one side would get right now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DepositQuote {
+    pub token_amount: u64,
+    // Implied prices after the deposit, scaled by PRICE_SCALE
+    pub yes_price: u64,
+    pub no_price: u64,
+    // Principal plus profit if the chosen side won with no further deposits, after fees
+    pub estimated_payout: u64,
+}
+
+// Read-only quote for frontends (call it with `.view()`), so they use the on-chain pricing
+// instead of reimplementing it. Insurance premiums are not included.
+pub fn quote_deposit(
+    ctx: Context<AQuoteDeposit>,
+    input: QuoteDepositInput,
+) -> Result<DepositQuote> {
+    deposit_quote(
+        &ctx.accounts.main_state,
+        &ctx.accounts.pool_state,
+        input.is_yes,
+        input.amount,
+    )
+}
+
+// Books the deposit on a copy of the pool through apply_deposit, so the quote goes through
+// the same pricing, fee accrual and liability cap as a real deposit
+pub(crate) fn deposit_quote(
+    main_state: &MainState,
+    pool_state: &PoolState,
+    is_yes: bool,
+    amount: u64,
+) -> Result<DepositQuote> {
+    require!(amount > 0, BettingError::InvalidBet);
+    // A shared scalar pot pays by resolved value, not per winning token
+    require!(
+        !pool_state.scalar_bounds.is_set(),
+        BettingError::UnsupportedForScalarPool
+    );
+
+    let mut pool = pool_state.clone();
+    pool.history_disabled = true;
+    let mut entry = EntryState::default();
+    let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+    let token_amount = apply_deposit(
+        &mut pool,
+        &mut entry,
+        None,
+        Pubkey::default(),
+        amount,
+        is_yes,
+        pricing_reserves,
+    )?;
+
+    let total_reserve = pool.yes_reserve.saturating_add(pool.no_reserve);
+    refresh_payout_projection(&mut pool, platform_fee_bps(main_state, total_reserve));
+    let per_token = if is_yes {
+        pool.projected_payout_per_token_yes
+    } else {
+        pool.projected_payout_per_token_no
+    };
+    let profit = token_amount as u128 * per_token as u128 / PAYOUT_PER_TOKEN_SCALE as u128;
+    let (yes_price, no_price) = pool.current_prices();
+
+    Ok(DepositQuote {
+        token_amount,
+        yes_price: yes_price as u64,
+        no_price: no_price as u64,
+        estimated_payout: (amount as u128 + profit).min(u64::MAX as u128) as u64,
+    })
+}
+
+#[derive(Accounts)]
+#[instruction(input: QuoteDepositInput)]
+pub struct AQuoteDeposit<'info> {
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
@@ -0,0 +1,87 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{error::BettingError, PoolState, SeedLiquidityReclaimedEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ReclaimSeedLiquidityInput {
+    bet_id: u64,
+}
+
+// Returns the creator's unused seed liquidity once the pool is resolved or refunded (see
+// PoolState::reclaimable_seed). One-shot: whatever is reclaimable is paid out in full.
+pub fn reclaim_seed_liquidity(
+    ctx: Context<AReclaimSeedLiquidity>,
+    _input: ReclaimSeedLiquidityInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let creator = &ctx.accounts.creator;
+    pool_state.require_sol_denominated()?;
+
+    require!(
+        pool_state.complete || pool_state.refund_mode,
+        BettingError::MarketNotResolved
+    );
+    require!(!pool_state.seed_reclaimed, BettingError::AlreadyClaimed);
+    let amount = pool_state.reclaimable_seed();
+    require!(amount > 0, BettingError::NoSeedToReclaim);
+
+    // Mark as reclaimed first to prevent reentrancy
+    pool_state.seed_reclaimed = true;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &creator.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    emit!(SeedLiquidityReclaimedEvent {
+        creator: creator.key(),
+        bet_id: pool_state.bet_id,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ReclaimSeedLiquidityInput)]
+pub struct AReclaimSeedLiquidity<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+        has_one = creator
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: the legacy shared vault; seeded pools always have their own, so it never pays
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault, holding the seed since create_pool
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,61 @@
+use crate::{error::BettingError, time, FinalOddsEvent, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RecordFinalOddsInput {
+    bet_id: u64,
+}
+
+// Permissionless crank: locks a fixed-end pool's closing line once its end time passed, for
+// pools nobody has touched since. Resolution, event-start locks and late reveals take the
+// snapshot on their own.
+pub fn record_final_odds(
+    ctx: Context<ARecordFinalOdds>,
+    _input: RecordFinalOddsInput,
+) -> Result<()> {
+    let now = time::now(&ctx.accounts.main_state)?;
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(
+        !pool_state.final_odds.is_recorded(),
+        BettingError::FinalOddsRecorded
+    );
+    require!(
+        pool_state.trading_closed(now),
+        BettingError::BetNotEnded
+    );
+    lock_final_odds(pool_state, now);
+    Ok(())
+}
+
+// Takes the pool's final odds snapshot if it is due and emits FinalOddsEvent; called by every
+// post-end path that can see the pool before its reserves move again
+pub(crate) fn lock_final_odds(pool_state: &mut PoolState, now: i64) {
+    if let Some(final_odds) = pool_state.record_final_odds(now) {
+        emit!(FinalOddsEvent {
+            bet_id: pool_state.bet_id,
+            yes_reserve: final_odds.yes_reserve,
+            no_reserve: final_odds.no_reserve,
+            yes_price: final_odds.yes_price,
+            no_price: final_odds.no_price,
+            end_timestamp: pool_state.end_timestamp,
+            timestamp: now
+        });
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: RecordFinalOddsInput)]
+pub struct ARecordFinalOdds<'info> {
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
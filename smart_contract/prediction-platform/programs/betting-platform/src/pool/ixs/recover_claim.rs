@@ -0,0 +1,99 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{
+    emit_claim_event, require_claim_window, settle_claim, EntryState, MainState, PoolState,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RecoverClaimInput {
+    pub bet_id: u64,
+}
+
+// Claim of a user who lost their key, paid to the recovery key they registered once its
+// recovery outlasted the challenge window (see EntryState::check_recovery). Settles exactly as
+// claim does; installments still due later follow the same way unless the user cancels.
+pub fn recover_claim(ctx: Context<ARecoverClaim>, _input: RecoverClaimInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+    let recovery_key = &ctx.accounts.recovery_key;
+    pool_state.require_sol_denominated()?;
+
+    let now = require_claim_window(pool_state, &ctx.accounts.main_state)?;
+    entry_state.check_recovery(recovery_key.key, now)?;
+
+    let installments_before = entry_state.installments_claimed;
+    let claimable_amount = settle_claim(pool_state, entry_state, now)?;
+    emit_claim_event(pool_state, entry_state, installments_before, claimable_amount)?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &recovery_key.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        claimable_amount,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RecoverClaimInput)]
+pub struct ARecoverClaim<'info> {
+    #[account(mut)]
+    pub recovery_key: Signer<'info>,
+
+    /// CHECK: the entry's owner, who lost their key; only locates the entry
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
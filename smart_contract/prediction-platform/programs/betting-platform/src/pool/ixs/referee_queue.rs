@@ -0,0 +1,101 @@
+use crate::{error::BettingError, resolution, time, MainState, PoolState, RefereeQueue};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RefereeQueueInput {
+    bet_id: u64,
+}
+
+// Permissionless crank: adds a fixed-end pool that passed its end time unresolved to its
+// referee's queue. The caller pays for the queue the first time.
+pub fn enqueue_pending_resolution(
+    ctx: Context<AEnqueuePendingResolution>,
+    input: RefereeQueueInput,
+) -> Result<()> {
+    let now = time::now(&ctx.accounts.main_state)?;
+    let pool_state = &ctx.accounts.pool_state;
+    let referee_queue = &mut ctx.accounts.referee_queue;
+    require!(
+        pool_state.trading_closed(now),
+        BettingError::BetNotEnded
+    );
+    resolution::ensure_unresolved(pool_state)?;
+    require!(
+        !referee_queue.is_queued(input.bet_id),
+        BettingError::AlreadyQueued
+    );
+    require!(
+        referee_queue.bet_ids.len() < RefereeQueue::MAX_PENDING,
+        BettingError::RefereeQueueFull
+    );
+
+    referee_queue.referee = pool_state.referee;
+    referee_queue.bet_ids.push(input.bet_id);
+
+    Ok(())
+}
+
+// Permissionless crank: drops a pool the queued referee no longer owes a resolution, because
+// another path decided it or the referee was handed over
+pub fn prune_referee_queue(
+    ctx: Context<APruneRefereeQueue>,
+    input: RefereeQueueInput,
+) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let referee_queue = &mut ctx.accounts.referee_queue;
+    require!(
+        resolution::ensure_unresolved(pool_state).is_err()
+            || pool_state.referee != referee_queue.referee,
+        BettingError::ResolutionPending
+    );
+    require!(referee_queue.remove(input.bet_id), BettingError::NotQueued);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RefereeQueueInput)]
+pub struct AEnqueuePendingResolution<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RefereeQueue::MAX_SIZE,
+        seeds = [RefereeQueue::PREFIX_SEED, pool_state.referee.as_ref()],
+        bump
+    )]
+    pub referee_queue: Box<Account<'info, RefereeQueue>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(input: RefereeQueueInput)]
+pub struct APruneRefereeQueue<'info> {
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [RefereeQueue::PREFIX_SEED, referee_queue.referee.as_ref()],
+        bump
+    )]
+    pub referee_queue: Box<Account<'info, RefereeQueue>>,
+}
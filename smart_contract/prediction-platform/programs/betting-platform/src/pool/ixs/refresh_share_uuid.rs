@@ -0,0 +1,35 @@
+use crate::{share_id::derive_share_uuid, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct RefreshShareUuidInput {
+    pub bet_id: u64,
+}
+
+// Rewrites a legacy (timestamp/slot based) share_uuid with the deterministic one.
+// Permissionless and idempotent: the result only depends on the program id and bet_id.
+pub fn refresh_share_uuid(
+    ctx: Context<ARefreshShareUuid>,
+    _input: RefreshShareUuidInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.share_uuid = derive_share_uuid(ctx.program_id, pool_state.bet_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RefreshShareUuidInput)]
+pub struct ARefreshShareUuid<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
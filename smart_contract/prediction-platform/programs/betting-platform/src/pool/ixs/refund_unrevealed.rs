@@ -0,0 +1,117 @@
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::time;
+use crate::{error::BettingError, CommitmentRefundEvent, CommitmentState, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct RefundUnrevealedInput {
+    pub bet_id: u64,
+}
+
+// Permissionless crank behind refund_commitment: once a commitment can no longer be revealed,
+// anyone may return its lamports (and the commitment's rent) to the user who committed them,
+// less the forfeit of a lapsed commitment (see CommitmentState::forfeit).
+pub fn refund_unrevealed(
+    ctx: Context<ARefundUnrevealed>,
+    input: RefundUnrevealedInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let commitment_state = &ctx.accounts.commitment_state;
+
+    let now = time::now(&ctx.accounts.main_state)?;
+    require!(
+        commitment_state.is_refundable(pool_state, now),
+        BettingError::RevealWindowOpen
+    );
+
+    let forfeit = commitment_state.forfeit(pool_state);
+    let refund = commitment_state.amount - forfeit;
+    let vault = PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    );
+    let system_program = ctx.accounts.system_program.to_account_info();
+    vault.pay(pool_state, &ctx.accounts.user.to_account_info(), &system_program, refund)?;
+    vault.pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &system_program,
+        forfeit,
+    )?;
+
+    pool_state.pending_commitments = pool_state.pending_commitments.saturating_sub(1);
+
+    emit!(CommitmentRefundEvent {
+        user: commitment_state.user,
+        bet_id: input.bet_id,
+        sol_amount: refund,
+        forfeit,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RefundUnrevealedInput)]
+pub struct ARefundUnrevealed<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Receives the refund and the commitment's rent
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            CommitmentState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub commitment_state: Account<'info, CommitmentState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    // Receives the forfeit of a lapsed commitment
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,201 @@
+use crate::{
+    annotate_history,
+    constants::{POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    finalize_outcome, load_history,
+    resolution::ResolutionSource,
+    time,
+    vault::PoolVault,
+    write_settlement_report, CompleteEvent, MainState, MetaResolutionEvent, PoolHistoryState,
+    PoolState, ProbabilityPoint, SettlementReport, TitleIndex,
+};
+#[cfg(feature = "oracle")]
+use crate::{publish_outcome, OutcomeOracle};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ResolveMetaMarketInput {
+    bet_id: u64,
+}
+
+// Permissionless resolution of a meta-market (see create_meta_pool): once its own end passed
+// and its target was resolved or refunded, the winner follows from the target's final state
+pub fn resolve_meta_market(
+    ctx: Context<AResolveMetaMarket>,
+    input: ResolveMetaMarketInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let condition = pool_state.meta_condition;
+    require!(condition.is_set(), BettingError::NotMetaMarket);
+    require!(
+        ctx.accounts.title_index.is_some() == pool_state.title_indexed,
+        BettingError::TitleIndexMismatch
+    );
+
+    let target = &ctx.accounts.target_pool_state;
+    require!(
+        target.complete || target.refund_mode,
+        BettingError::MetaTargetStillTrading
+    );
+    let value = condition.value_of(target)?;
+    let is_yes = condition.is_met(value)?;
+    let now = time::now(main_state)?;
+    let platform_payout = finalize_outcome(
+        pool_state,
+        main_state,
+        ResolutionSource::MetaMarket,
+        is_yes,
+        now,
+    )?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        platform_payout,
+    )?;
+
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_RESOLUTION,
+    )?;
+
+    let target_key = target.key();
+    #[cfg(feature = "oracle")]
+    publish_outcome(&mut ctx.accounts.outcome_oracle, pool_state, pool_key);
+    write_settlement_report(
+        &mut ctx.accounts.settlement_report,
+        pool_state,
+        pool_key,
+        target_key,
+    );
+
+    emit!(CompleteEvent {
+        referee: target_key,
+        bet_id: input.bet_id,
+        winner: pool_state.winner,
+        creator_fee_donated: pool_state.creator_fee_donated,
+        timestamp: now
+    });
+    emit!(MetaResolutionEvent {
+        bet_id: input.bet_id,
+        target_bet_id: condition.target_bet_id,
+        metric: condition.metric,
+        value,
+        caller: ctx.accounts.caller.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ResolveMetaMarketInput)]
+pub struct AResolveMetaMarket<'info> {
+    // Pays the oracle and report rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &pool_state.meta_condition.target_bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub target_pool_state: Box<Account<'info, PoolState>>,
+
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    // Omitted for pools without a title index (linked pools)
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    #[cfg(feature = "oracle")]
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + OutcomeOracle::MAX_SIZE,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_oracle: Box<Account<'info, OutcomeOracle>>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + SettlementReport::MAX_SIZE,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Box<Account<'info, SettlementReport>>,
+
+    // Omitted for pools created with history disabled; receives the resolution marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    pub system_program: Program<'info, System>,
+}
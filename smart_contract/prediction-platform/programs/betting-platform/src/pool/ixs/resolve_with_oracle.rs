@@ -0,0 +1,198 @@
+use crate::{
+    annotate_history,
+    constants::{ORACLE_RESOLUTION_WINDOW_SECS, POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    finalize_outcome, load_history, publish_outcome, pyth,
+    resolution::ResolutionSource,
+    time,
+    vault::PoolVault,
+    write_settlement_report, CompleteEvent, MainState, OracleResolutionEvent, OutcomeOracle,
+    PoolHistoryState, PoolState, ProbabilityPoint, SettlementReport, TitleIndex,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ResolveWithOracleInput {
+    bet_id: u64,
+}
+
+// Permissionless resolution of a pool created with a PriceCondition: the winner follows from
+// the feed's aggregate price, without the referee. Only a price published within
+// ORACLE_RESOLUTION_WINDOW_SECS of the end time counts; after that the referee resolves.
+pub fn resolve_with_oracle(
+    ctx: Context<AResolveWithOracle>,
+    input: ResolveWithOracleInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let condition = pool_state.price_condition;
+    require!(condition.is_set(), BettingError::OracleResolutionDisabled);
+    require!(
+        ctx.accounts.title_index.is_some() == pool_state.title_indexed,
+        BettingError::TitleIndexMismatch
+    );
+
+    let price = pyth::read_price(&ctx.accounts.price_feed)?;
+    require!(price.expo == condition.expo, BettingError::PriceExponentMismatch);
+    require!(
+        price.publish_time >= pool_state.end_timestamp
+            && price.publish_time
+                <= pool_state
+                    .end_timestamp
+                    .saturating_add(ORACLE_RESOLUTION_WINDOW_SECS),
+        BettingError::PriceOutsideResolutionWindow
+    );
+
+    let is_yes = condition.is_met(price.price)?;
+    let now = time::now(main_state)?;
+    let platform_payout = finalize_outcome(
+        pool_state,
+        main_state,
+        ResolutionSource::Oracle,
+        is_yes,
+        now,
+    )?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        platform_payout,
+    )?;
+
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_RESOLUTION,
+    )?;
+
+    publish_outcome(&mut ctx.accounts.outcome_oracle, pool_state, pool_key);
+    write_settlement_report(
+        &mut ctx.accounts.settlement_report,
+        pool_state,
+        pool_key,
+        condition.feed,
+    );
+
+    emit!(CompleteEvent {
+        referee: condition.feed,
+        bet_id: input.bet_id,
+        winner: pool_state.winner,
+        creator_fee_donated: pool_state.creator_fee_donated,
+        timestamp: now
+    });
+    emit!(OracleResolutionEvent {
+        bet_id: input.bet_id,
+        feed: condition.feed,
+        price: price.price,
+        expo: price.expo,
+        publish_time: price.publish_time,
+        caller: ctx.accounts.caller.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: ResolveWithOracleInput)]
+pub struct AResolveWithOracle<'info> {
+    // Pays the oracle and report rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(address = pool_state.price_condition.feed @ BettingError::PriceFeedMismatch)]
+    /// CHECK: Pyth price account; its owner and layout are checked by pyth::read_price
+    pub price_feed: AccountInfo<'info>,
+
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    // Omitted for pools without a title index (linked pools)
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + OutcomeOracle::MAX_SIZE,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_oracle: Box<Account<'info, OutcomeOracle>>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + SettlementReport::MAX_SIZE,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Box<Account<'info, SettlementReport>>,
+
+    // Omitted for pools created with history disabled; receives the resolution marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    pub system_program: Program<'info, System>,
+}
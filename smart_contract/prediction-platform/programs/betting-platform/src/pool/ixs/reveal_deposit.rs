@@ -0,0 +1,184 @@
+use crate::fees::{platform_fee_bps, refresh_payout_projection};
+use crate::{
+    apply_deposit, emit_funnel_events, error::BettingError, load_history, time,
+    update_pool_summary, CommitmentState, DepositEvent, Depositor, EntryState, FunnelSnapshot,
+    MainState, PoolHistoryState, PoolState, PoolSummary,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token::TokenAccount;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct RevealDepositInput {
+    pub bet_id: u64,
+    pub is_yes: bool,
+    pub salt: [u8; 32],
+    // Slippage guard: fewest tokens the user accepts for the committed amount (0 = any)
+    pub min_token_amount: u64,
+}
+
+pub fn reveal_deposit(ctx: Context<ARevealDeposit>, input: RevealDepositInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.check_summary(ctx.accounts.pool_summary.is_some())?;
+    let entry_state = &mut ctx.accounts.entry_state;
+    let mut history_state = load_history(&ctx.accounts.history_state)?;
+    let commitment_state = &ctx.accounts.commitment_state;
+
+    require!(!ctx.accounts.main_state.paused, BettingError::ProtocolPaused);
+    // The holding is checked again, as the commitment alone doesn't prove it is still there
+    pool_state.check_gate(
+        Depositor::new(ctx.accounts.user.key(), &ctx.accounts.gate_token_account).gate_balance,
+    )?;
+    // A resolved pool can no longer take positions; the commitment is refundable instead.
+    require!(!pool_state.complete, BettingError::BetComplete);
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+    // Revealing after the outcome is known would be a free bet
+    require!(
+        !pool_state.has_provisional_outcome(),
+        BettingError::ProvisionalOutcomePending
+    );
+
+    let now = time::now(&ctx.accounts.main_state)?;
+    // Commitments from before reveal windows were capped at the trading end still close there
+    require!(!pool_state.trading_closed(now), BettingError::BetEnded);
+    require!(
+        now <= commitment_state.reveal_deadline,
+        BettingError::RevealWindowClosed
+    );
+
+    let expected_hash = hashv(&[&[input.is_yes as u8], &input.salt]);
+    require!(
+        expected_hash.to_bytes() == commitment_state.commitment_hash,
+        BettingError::InvalidReveal
+    );
+
+    // Price against the reserves recorded at commit time, not the current ones
+    let pricing_reserves = (commitment_state.yes_reserve, commitment_state.no_reserve);
+    let pool_key = pool_state.key();
+    let entry_before = entry_state.deposited_sol_amount;
+    let funnel_before = FunnelSnapshot::of(pool_state);
+    let token_amount = apply_deposit(
+        pool_state,
+        entry_state,
+        history_state.as_deref_mut(),
+        pool_key,
+        commitment_state.amount,
+        input.is_yes,
+        pricing_reserves,
+    )?;
+    require!(
+        token_amount >= input.min_token_amount,
+        BettingError::SlippageExceeded
+    );
+    pool_state.check_self_referee_cap(&ctx.accounts.main_state)?;
+
+    update_pool_summary(
+        ctx.accounts.pool_summary.as_deref_mut().map(|summary| &mut **summary),
+        pool_state,
+        ctx.accounts.user.key(),
+        entry_before,
+        commitment_state.amount,
+        now,
+    );
+    emit_funnel_events(
+        &ctx.accounts.main_state,
+        pool_state,
+        ctx.accounts.user.key(),
+        funnel_before,
+        now,
+    );
+
+    let total_reserve = pool_state.yes_reserve.saturating_add(pool_state.no_reserve);
+    refresh_payout_projection(
+        pool_state,
+        platform_fee_bps(&ctx.accounts.main_state, total_reserve),
+    );
+
+    pool_state.pending_commitments = pool_state.pending_commitments.saturating_sub(1);
+
+    emit!(DepositEvent {
+        user: commitment_state.user,
+        bet_id: pool_state.bet_id,
+        sol_amount: commitment_state.amount,
+        token_amount,
+        is_yes: input.is_yes,
+        insurance_premium: 0, // sealed deposits cannot be insured
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RevealDepositInput)]
+pub struct ARevealDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+
+    // Omitted for pools created with history disabled
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PoolHistoryState::MAX_SIZE,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolSummary::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    // Required once the pool has a summary (see has_summary); omitted for the rest
+    pub pool_summary: Option<Box<Account<'info, PoolSummary>>>,
+
+    // Locked lamports already sit in the vault; only the commitment rent goes back to the user
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            CommitmentState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub commitment_state: Account<'info, CommitmentState>,
+
+    // Token-gated pools only: the user's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = user,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
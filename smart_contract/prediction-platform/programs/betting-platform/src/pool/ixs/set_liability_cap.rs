@@ -0,0 +1,44 @@
+use crate::{error::BettingError, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct SetLiabilityCapInput {
+    pub bet_id: u64,
+    pub liability_cap: u64, // 0 removes the cap
+}
+
+// Owner-only: caps what the vault may owe a pool, e.g. to match an insurance limit
+pub fn set_liability_cap(
+    ctx: Context<ASetLiabilityCap>,
+    input: SetLiabilityCapInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(!pool_state.complete, BettingError::BetComplete);
+
+    pool_state.liability_cap = input.liability_cap;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SetLiabilityCapInput)]
+pub struct ASetLiabilityCap<'info> {
+    #[account(address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
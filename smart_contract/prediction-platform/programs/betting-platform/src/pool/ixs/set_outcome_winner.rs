@@ -0,0 +1,155 @@
+use crate::{
+    constants::{VAULT_SEED, VOID_GRACE_SECS},
+    error::BettingError,
+    fees::{bps_of, platform_fee_bps},
+    pay_from_vault, time, CreatorBondSettledEvent, MainState, OutcomeMarket,
+    OutcomeResolvedEvent,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct SetOutcomeWinnerInput {
+    pub bet_id: u64,
+    pub winner: u8,
+}
+
+// Resolves an OutcomeMarket by outcome index. The referee or the owner may resolve, as for a
+// pool without a referee deadline. Fees are settled like settle_resolution_fees: both come
+// out of the losing reserves only, creator fee first, and are paid out right away. So is the
+// creator bond: back to the creator unless the referee left it to the owner past the grace
+// after which anyone could void a pool, in which case it is forfeit to the platform.
+pub fn set_outcome_winner(
+    ctx: Context<ASetOutcomeWinner>,
+    input: SetOutcomeWinnerInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let market = &mut ctx.accounts.outcome_market;
+    let referee = ctx.accounts.referee.key();
+    require!(!market.complete, BettingError::BetComplete);
+    require!(
+        market.referee.eq(&referee) || main_state.owner.eq(&referee),
+        BettingError::Unauthorized
+    );
+    let now = time::now(main_state)?;
+    require!(
+        market.has_ended(now),
+        BettingError::BetNotEnded
+    );
+    let index = market.check_outcome(input.winner)?;
+
+    let total_reserve = market.total_reserve();
+    let losing_reserve = total_reserve - market.reserves[index];
+    let creator_fee = market.creator_fee_accrued.min(losing_reserve);
+    let platform_fee = bps_of(
+        total_reserve as u128,
+        platform_fee_bps(main_state, total_reserve),
+    )
+    .min(losing_reserve.saturating_sub(creator_fee) as u128) as u64;
+
+    market.complete = true;
+    market.winner = input.winner;
+    market.resolved_timestamp = now;
+    market.creator_fee_accrued = creator_fee;
+    market.platform_fee_amount = platform_fee;
+
+    let market_duration = now.saturating_sub(market.created_timestamp).max(0) as u64;
+    let latency = if market.end_timestamp >= 0 {
+        Some(now.saturating_sub(market.end_timestamp).max(0) as u64)
+    } else {
+        None
+    };
+    main_state.record_resolution(market_duration, latency);
+    main_state.accrue_revenue_share(platform_fee);
+
+    let system_program = ctx.accounts.system_program.to_account_info();
+    pay_from_vault(
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &system_program,
+        ctx.bumps.sol_vault,
+        platform_fee,
+    )?;
+    pay_from_vault(
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.creator.to_account_info(),
+        &system_program,
+        ctx.bumps.sol_vault,
+        creator_fee,
+    )?;
+
+    let creator_bond = market.creator_bond;
+    if creator_bond > 0 {
+        let slashed = market.referee.ne(&referee)
+            && market.end_timestamp >= 0
+            && now >= market.end_timestamp.saturating_add(VOID_GRACE_SECS);
+        let recipient = if slashed {
+            ctx.accounts.platform_owner.to_account_info()
+        } else {
+            ctx.accounts.creator.to_account_info()
+        };
+        pay_from_vault(
+            &ctx.accounts.sol_vault,
+            &recipient,
+            &system_program,
+            ctx.bumps.sol_vault,
+            creator_bond,
+        )?;
+        emit!(CreatorBondSettledEvent {
+            bet_id: input.bet_id,
+            creator: market.creator,
+            recipient: recipient.key(),
+            amount: creator_bond,
+            slashed,
+            timestamp: now
+        });
+    }
+
+    emit!(OutcomeResolvedEvent {
+        referee,
+        bet_id: input.bet_id,
+        winner: input.winner,
+        creator_fee,
+        platform_fee,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SetOutcomeWinnerInput)]
+pub struct ASetOutcomeWinner<'info> {
+    pub referee: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [OutcomeMarket::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+        has_one = creator,
+    )]
+    pub outcome_market: Box<Account<'info, OutcomeMarket>>,
+
+    // Receives the creator fee and, unless slashed, the creator bond
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
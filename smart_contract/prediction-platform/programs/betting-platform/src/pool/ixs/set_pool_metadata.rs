@@ -0,0 +1,79 @@
+use crate::{
+    error::BettingError, validation::validate_localized_text, LocalizedText, MainState,
+    PoolMetadata, PoolState,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct SetPoolMetadataInput {
+    pub bet_id: u64,
+    pub translations: Vec<LocalizedText>, // replaces the stored translations
+}
+
+// Creates or replaces a pool's translations. Same authorization rules as update_pool, so
+// clients usually send it in the same transaction as create_pool.
+pub fn set_pool_metadata(
+    ctx: Context<ASetPoolMetadata>,
+    input: SetPoolMetadataInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let pool_state = &ctx.accounts.pool_state;
+    let updater = &ctx.accounts.updater;
+
+    // Only pool creator or main state owner can update the pool
+    require!(
+        pool_state.creator.eq(&updater.key()) || main_state.owner.eq(&updater.key()),
+        BettingError::Unauthorized
+    );
+
+    // Cannot update a completed pool
+    require!(!pool_state.complete, BettingError::BetComplete);
+
+    require!(
+        input.translations.len() <= PoolMetadata::MAX_TRANSLATIONS,
+        BettingError::TooManyTranslations
+    );
+    for translation in input.translations.iter() {
+        validate_localized_text(translation)?;
+    }
+
+    let pool_metadata = &mut ctx.accounts.pool_metadata;
+    pool_metadata.pool = pool_state.key();
+    pool_metadata.bet_id = pool_state.bet_id;
+    pool_metadata.translations = input.translations;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SetPoolMetadataInput)]
+pub struct ASetPoolMetadata<'info> {
+    #[account(mut)]
+    pub updater: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init_if_needed,
+        payer = updater,
+        space = 8 + PoolMetadata::MAX_SIZE,
+        seeds = [PoolMetadata::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_metadata: Box<Account<'info, PoolMetadata>>,
+
+    pub system_program: Program<'info, System>,
+}
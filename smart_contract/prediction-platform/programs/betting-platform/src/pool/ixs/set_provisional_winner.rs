@@ -0,0 +1,113 @@
+use crate::{
+    error::BettingError, record_referee_resolution, resolution, time, MainState, PoolState,
+    ProvisionalWinnerEvent,
+};
+#[cfg(feature = "referee-registry")]
+use crate::RefereeProfile;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct SetProvisionalWinnerInput {
+    bet_id: u64,
+    is_yes: bool,
+    // Range markets submit the value instead; `is_yes` is then ignored
+    value: Option<i64>,
+}
+
+// First phase of two-phase resolution: same resolution guard as set_winner, but the outcome
+// only stops trading. finalize_resolution makes it final after the pool's confirmation window.
+pub fn set_provisional_winner(
+    ctx: Context<ASetProvisionalWinner>,
+    input: SetProvisionalWinnerInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let resolver = ctx.accounts.referee.key();
+
+    require!(
+        pool_state.provisional_window_secs > 0,
+        BettingError::NotProvisionalPool
+    );
+    resolution::ensure_unresolved(pool_state)?;
+    require!(
+        !pool_state.has_provisional_outcome(),
+        BettingError::ProvisionalOutcomePending
+    );
+
+    let now = time::now(main_state)?;
+    let referee_no_show = pool_state.check_resolver(&resolver, main_state, now)?;
+    require!(
+        pool_state.has_ended(now),
+        BettingError::BetNotEnded
+    );
+    require!(
+        pool_state.commitments_settled(now),
+        BettingError::CommitmentsPending
+    );
+
+    let is_yes = if pool_state.range_buckets.is_empty() {
+        input.is_yes
+    } else {
+        let value = input.value.ok_or(BettingError::RangeValueRequired)?;
+        pool_state.range_value = value;
+        pool_state.range_outcome(value)?
+    };
+
+    pool_state.provisional_at = now;
+    pool_state.provisional_is_yes = is_yes;
+    pool_state.provisional_by = resolver;
+    // The referee acted now, so that's when their timeliness is judged
+    record_referee_resolution(
+        pool_state,
+        #[cfg(feature = "referee-registry")]
+        &mut ctx.accounts.referee_profile,
+        &resolver,
+        referee_no_show,
+        now,
+    );
+
+    emit!(ProvisionalWinnerEvent {
+        bet_id: input.bet_id,
+        is_yes,
+        resolver,
+        confirms_at: now.saturating_add(pool_state.provisional_window_secs),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SetProvisionalWinnerInput)]
+pub struct ASetProvisionalWinner<'info> {
+    #[account(mut)]
+    pub referee: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[cfg(feature = "referee-registry")]
+    #[account(
+        init_if_needed,
+        payer = referee,
+        space = 8 + RefereeProfile::MAX_SIZE,
+        seeds = [RefereeProfile::PREFIX_SEED, pool_state.referee.as_ref()],
+        bump
+    )]
+    pub referee_profile: Box<Account<'info, RefereeProfile>>,
+
+    pub system_program: Program<'info, System>,
+}
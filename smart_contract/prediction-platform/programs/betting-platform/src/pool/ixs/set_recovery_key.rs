@@ -0,0 +1,62 @@
+use crate::time;
+use crate::{error::BettingError, EntryState, MainState, PoolState, RecoveryKeySetEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SetRecoveryKeyInput {
+    pub bet_id: u64,
+    pub recovery_key: Pubkey, // Pubkey::default() removes it
+}
+
+// Registers the backup key that may take over the entry's claims if the user loses theirs
+// (see initiate_recovery). A recovery the previous key started is dropped with it.
+pub fn set_recovery_key(ctx: Context<ASetRecoveryKey>, input: SetRecoveryKeyInput) -> Result<()> {
+    let entry_state = &mut ctx.accounts.entry_state;
+    require!(
+        input.recovery_key != entry_state.user,
+        BettingError::InvalidRecoveryKey
+    );
+    entry_state.recovery_key = input.recovery_key;
+    entry_state.recovery_initiated_at = 0;
+
+    emit!(RecoveryKeySetEvent {
+        user: entry_state.user,
+        bet_id: input.bet_id,
+        recovery_key: input.recovery_key,
+        timestamp: time::now(&ctx.accounts.main_state)?
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SetRecoveryKeyInput)]
+pub struct ASetRecoveryKey<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Account<'info, EntryState>,
+}
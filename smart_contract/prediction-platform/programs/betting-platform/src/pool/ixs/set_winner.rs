@@ -1,82 +1,617 @@
-use crate::{constants::VAULT_SEED, error::BettingError, CompleteEvent, MainState, PoolState};
+use crate::{
+    annotate_history,
+    constants::{PAYOUT_PER_TOKEN_SCALE, POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    fees::{
+        insurance_surplus, platform_fee_bps, referee_fee, referral_fee_escrow,
+        settle_resolution_fees,
+    },
+    vault::PoolVault,
+    load_history, lock_final_odds,
+    resolution::{self, ResolutionSource},
+    scalar_side_pot, time, winner_profit_pot, CompleteEvent, MainState, PoolHistoryState,
+    PoolNotificationEvent, PoolState, PoolSubscribers, PoolVoidedEvent, ProbabilityPoint,
+    RefereeNoShowEvent, RefereeQueue, SettlementAuditEvent, SettlementReport, TitleIndex,
+    WinnerSide,
+};
+#[cfg(feature = "oracle")]
+use crate::OutcomeOracle;
+#[cfg(feature = "referee-registry")]
+use crate::{constants::REFEREE_ON_TIME_SECS, RefereeProfile};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_instruction;
+use anchor_lang::system_program::{create_account, CreateAccount};
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
 pub struct SetWinnerInput {
     bet_id: u64,
     is_yes: bool,
+    // Optional: declare the question malformed instead of naming a winner. The pool is voided
+    // without fees and every entry takes its deposit back through claim_refund; `is_yes` is
+    // ignored (default: off)
+    invalid: Option<bool>,
 }
 
 pub fn set_winner(ctx: Context<ASetWinner>, input: SetWinnerInput) -> Result<()> {
     let main_state = &mut ctx.accounts.main_state;
     let pool_state = &mut ctx.accounts.pool_state;
+    let referee = ctx.accounts.referee.to_account_info();
+    let invalid = input.invalid.unwrap_or(false);
+    // Range and scalar pools are resolved by value, but can still be declared invalid here
+    require!(
+        invalid || !pool_state.resolves_by_value(),
+        BettingError::RangeValueRequired
+    );
 
-    require!(pool_state.complete.eq(&false), BettingError::BetComplete);
+    // The pool's TitleIndex is closed with the `close` constraint once it resolves
     require!(
-        pool_state.referee.eq(ctx.accounts.referee.key)
-            || main_state.owner.eq(ctx.accounts.referee.key),
-        BettingError::Unauthorized
+        ctx.accounts.title_index.is_some() == pool_state.title_indexed,
+        BettingError::TitleIndexMismatch
     );
-    // If the market has a fixed end time (>= 0), ensure it has ended before resolving.
-    // A negative end_timestamp (e.g. -1) denotes an open-ended market that can be
-    // resolved by the referee at any arbitrary moment.
-    if pool_state.end_timestamp >= 0 {
-        require!(
-            pool_state.end_timestamp < Clock::get()?.unix_timestamp,
-            BettingError::BetNotEnded
-        );
-    }
 
-    let referee = ctx.accounts.referee.to_account_info();
+    // A pool short of its minimum participants refunds whatever winner the referee named
+    let short_of_participants = !invalid && pool_state.short_of_participants();
+    let refund = invalid || short_of_participants;
+    let platform_payout = if invalid {
+        let now = time::now(main_state)?;
+        resolve_invalid(
+            pool_state,
+            main_state,
+            #[cfg(feature = "referee-registry")]
+            &mut ctx.accounts.referee_profile,
+            referee.key,
+            now,
+        )?;
+        0
+    } else if short_of_participants {
+        let now = time::now(main_state)?;
+        resolve_short_of_participants(
+            pool_state,
+            main_state,
+            #[cfg(feature = "referee-registry")]
+            &mut ctx.accounts.referee_profile,
+            referee.key,
+            now,
+        )?;
+        0
+    } else {
+        resolve_pool(
+            pool_state,
+            main_state,
+            #[cfg(feature = "referee-registry")]
+            &mut ctx.accounts.referee_profile,
+            referee.key,
+            input.is_yes,
+        )?
+    };
 
-    pool_state.complete = true;
-    pool_state.winner = if input.is_yes {
-        "yes".to_string()
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        platform_payout,
+    )?;
+
+    let pool_key = pool_state.key();
+    let marker = if refund {
+        ProbabilityPoint::KIND_CANCELLATION
     } else {
-        "no".to_string()
+        ProbabilityPoint::KIND_RESOLUTION
     };
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        marker,
+    )?;
 
-    // Auto-claim platform fee at resolution time based on total reserves to keep fee impact
-    // symmetric across both sides. (Both sides effectively contribute proportionally.)
-    let total_reserve = (pool_state.yes_reserve as u128)
-        .saturating_add(pool_state.no_reserve as u128);
-    let platform_fee = total_reserve
-        .saturating_mul(main_state.platform_fee_percent as u128)
-        / 10000u128;
-    let platform_fee: u64 = platform_fee.min(u64::MAX as u128) as u64; // saturate to u64
-
-    if platform_fee > 0 {
-        let transfer_instruction = system_instruction::transfer(
-            &ctx.accounts.sol_vault.to_account_info().key(),
-            &main_state.owner,
-            platform_fee,
-        );
-
-        anchor_lang::solana_program::program::invoke_signed(
-            &transfer_instruction,
-            &[
-                ctx.accounts.sol_vault.to_account_info(),
-                ctx.accounts.platform_owner.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&[VAULT_SEED.as_bytes(), &[ctx.bumps.sol_vault]]],
-        )?;
+    if let Some(referee_queue) = ctx.accounts.referee_queue.as_mut() {
+        referee_queue.remove(input.bet_id);
     }
 
-    // Mark platform fee claimed
-    pool_state.platform_fee_claimed = true;
+    // Publish the outcome for other programs
+    #[cfg(feature = "oracle")]
+    publish_outcome(&mut ctx.accounts.outcome_oracle, pool_state, pool_key);
+    write_settlement_report(
+        &mut ctx.accounts.settlement_report,
+        pool_state,
+        pool_key,
+        referee.key(),
+    );
 
+    // A refunded pool is decided like a voided one, which event replays rely on
+    if refund {
+        emit!(PoolVoidedEvent {
+            bet_id: input.bet_id,
+            voided_by: referee.key(),
+            by_owner: main_state.owner.eq(referee.key),
+            timestamp: pool_state.voided_at
+        });
+        return Ok(());
+    }
     emit!(CompleteEvent {
         referee: referee.key(),
         bet_id: input.bet_id,
-        winner: pool_state.winner.clone(),
+        winner: pool_state.winner,
+        creator_fee_donated: pool_state.creator_fee_donated,
         timestamp: Clock::get()?.unix_timestamp
     });
 
     Ok(())
 }
 
+// Transfers lamports out of the shared vault, e.g. the platform's share of a resolution
+pub(crate) fn pay_from_vault<'info>(
+    sol_vault: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let transfer_instruction = system_instruction::transfer(sol_vault.key, recipient.key, amount);
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &transfer_instruction,
+        &[sol_vault.clone(), recipient.clone(), system_program.clone()],
+        &[&[VAULT_SEED.as_bytes(), &[vault_bump]]],
+    )?;
+    Ok(())
+}
+
+// Resolution shared by set_winner, set_winner_by_value and set_winner_many: runs the resolution
+// guard, finalizes the pool and updates the assigned referee's profile. Returns what to
+// transfer to the owner (see finalize_outcome). Pools with a confirmation window resolve
+// through set_provisional_winner and finalize_resolution instead.
+pub(crate) fn resolve_pool(
+    pool_state: &mut PoolState,
+    main_state: &mut MainState,
+    #[cfg(feature = "referee-registry")]
+    referee_profile: &mut RefereeProfile,
+    resolver: &Pubkey,
+    is_yes: bool,
+) -> Result<u64> {
+    resolution::ensure_unresolved(pool_state)?;
+    let now = time::now(main_state)?;
+    require!(
+        pool_state.provisional_window_secs == 0,
+        BettingError::ProvisionalResolutionRequired
+    );
+    let referee_no_show = pool_state.check_resolver(resolver, main_state, now)?;
+    let source = if resolver.eq(&pool_state.referee) {
+        ResolutionSource::Referee
+    } else {
+        ResolutionSource::Fallback
+    };
+    let platform_payout = finalize_outcome(pool_state, main_state, source, is_yes, now)?;
+    record_referee_resolution(
+        pool_state,
+        #[cfg(feature = "referee-registry")]
+        referee_profile,
+        resolver,
+        referee_no_show,
+        now,
+    );
+
+    Ok(platform_payout)
+}
+
+// set_winner's verdict that the question is malformed, open to whoever could name a winner.
+// The pool goes to refund mode like void_pool, so no fees are fixed or paid and claim_refund
+// returns every deposit. No end-time check: a malformed question can be voided at any time.
+pub(crate) fn resolve_invalid(
+    pool_state: &mut PoolState,
+    main_state: &MainState,
+    #[cfg(feature = "referee-registry")]
+    referee_profile: &mut RefereeProfile,
+    resolver: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    refund_on_resolution(
+        pool_state,
+        main_state,
+        #[cfg(feature = "referee-registry")]
+        referee_profile,
+        resolver,
+        now,
+        ResolutionSource::Invalid,
+    )
+}
+
+// set_winner on an ended pool created with min_participants that drew fewer entries than that,
+// or none on one side: it refunds like an invalid verdict instead of paying fees and profits
+pub(crate) fn resolve_short_of_participants(
+    pool_state: &mut PoolState,
+    main_state: &MainState,
+    #[cfg(feature = "referee-registry")]
+    referee_profile: &mut RefereeProfile,
+    resolver: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        pool_state.has_ended(now),
+        BettingError::BetNotEnded
+    );
+    refund_on_resolution(
+        pool_state,
+        main_state,
+        #[cfg(feature = "referee-registry")]
+        referee_profile,
+        resolver,
+        now,
+        ResolutionSource::TooFewParticipants,
+    )
+}
+
+fn refund_on_resolution(
+    pool_state: &mut PoolState,
+    main_state: &MainState,
+    #[cfg(feature = "referee-registry")]
+    referee_profile: &mut RefereeProfile,
+    resolver: &Pubkey,
+    now: i64,
+    source: ResolutionSource,
+) -> Result<()> {
+    // claim_refund only pays from the SOL vault
+    pool_state.require_sol_denominated()?;
+    resolution::ensure_unresolved(pool_state)?;
+    let referee_no_show = pool_state.check_resolver(resolver, main_state, now)?;
+
+    resolution::claim(pool_state, source)?;
+    pool_state.refund_mode = true;
+    pool_state.winner = WinnerSide::Void;
+    pool_state.voided_at = now;
+    pool_state.resolved_timestamp = now;
+    record_referee_resolution(
+        pool_state,
+        #[cfg(feature = "referee-registry")]
+        referee_profile,
+        resolver,
+        referee_no_show,
+        now,
+    );
+
+    Ok(())
+}
+
+// Books a resolution decision made by `resolver` at `now` against the assigned referee: flags
+// a no-show and, in builds with the referee registry, updates their streak. Only the assigned referee can keep a streak going;
+// council or owner resolutions count as a miss against them.
+pub(crate) fn record_referee_resolution(
+    pool_state: &mut PoolState,
+    #[cfg(feature = "referee-registry")]
+    referee_profile: &mut RefereeProfile,
+    resolver: &Pubkey,
+    referee_no_show: bool,
+    now: i64,
+) {
+    pool_state.referee_no_show = referee_no_show;
+    if referee_no_show {
+        emit!(RefereeNoShowEvent {
+            bet_id: pool_state.bet_id,
+            referee: pool_state.referee,
+            resolved_by: *resolver,
+            referee_deadline: pool_state.referee_deadline,
+            timestamp: now
+        });
+    }
+
+    #[cfg(feature = "referee-registry")]
+    {
+        let on_time = resolver.eq(&pool_state.referee)
+            && (pool_state.referee_deadline == 0 || now <= pool_state.referee_deadline)
+            && (pool_state.end_timestamp < 0
+                || now.saturating_sub(pool_state.end_timestamp) <= REFEREE_ON_TIME_SECS);
+        referee_profile.referee = pool_state.referee;
+        referee_profile.record_resolution(on_time, now);
+    }
+}
+
+// Marks the pool complete with the given outcome and fixes its fees, for any resolution path
+// (referee, council, a confirmed provisional outcome or an unchallenged assertion), which it
+// records as `source` (see resolution.rs). Returns what to transfer to the owner: the platform
+// fee less the referrers' escrow and the referee's fee, plus any insurance premiums not needed
+// to cover losing insured deposits (nothing for SPL pools, whose vault holds no SOL).
+pub(crate) fn finalize_outcome(
+    pool_state: &mut PoolState,
+    main_state: &mut MainState,
+    source: ResolutionSource,
+    is_yes: bool,
+    now: i64,
+) -> Result<u64> {
+    resolution::claim(pool_state, source)?;
+    // If the market has a fixed end time (>= 0), ensure it has ended before resolving.
+    // A negative end_timestamp (e.g. -1) denotes an open-ended market that can be
+    // resolved by the referee at any arbitrary moment.
+    require!(
+        pool_state.has_ended(now),
+        BettingError::BetNotEnded
+    );
+    require!(
+        pool_state.commitments_settled(now),
+        BettingError::CommitmentsPending
+    );
+
+    pool_state.complete = true;
+    pool_state.resolved_timestamp = now;
+    pool_state.winner = WinnerSide::from_is_yes(is_yes);
+    lock_final_odds(pool_state, now);
+
+    // Auto-claim platform fee at resolution time
+    let total_reserve = pool_state.yes_reserve.saturating_add(pool_state.no_reserve);
+    let platform_fee = settle_resolution_fees(
+        pool_state,
+        is_yes,
+        platform_fee_bps(main_state, total_reserve),
+    );
+
+    // Mark platform fee claimed; SPL pools keep it in their token vault for
+    // claim_platform_fee_spl instead
+    pool_state.platform_fee_claimed = !pool_state.is_spl();
+
+    // The winning side's referrers' slice stays in the vault until their entries claim
+    pool_state.referral_fee_escrow =
+        referral_fee_escrow(pool_state, is_yes, main_state.referral_fee_percent);
+    // So does the referee's, when they resolved the pool themselves; SPL pools pay no SOL fees
+    // Provisional, committee, oracle and fallback resolutions earn no referee fee, even when
+    // the referee proposed the provisional outcome: they did not settle the pool alone
+    (pool_state.referee_fee_amount, pool_state.referee_fee_recipient) =
+        if source == ResolutionSource::Referee && !pool_state.is_spl() {
+            (referee_fee(pool_state), pool_state.referee)
+        } else {
+            (0, Pubkey::default())
+        };
+
+    let audit = audit_settlement(pool_state, now)?;
+    emit!(audit);
+
+    // Resolution statistics
+    let market_duration = now.saturating_sub(pool_state.created_timestamp).max(0) as u64;
+    let latency = if pool_state.end_timestamp >= 0 {
+        pool_state.resolution_latency_secs = now.saturating_sub(pool_state.end_timestamp).max(0);
+        Some(pool_state.resolution_latency_secs as u64)
+    } else {
+        None
+    };
+    main_state.record_resolution(market_duration, latency);
+
+    // Subscribers are looked up off-chain; the list account need not exist
+    let (subscribers, _) = Pubkey::find_program_address(
+        &[PoolSubscribers::PREFIX_SEED, &pool_state.bet_id.to_le_bytes()],
+        &crate::ID,
+    );
+    emit!(PoolNotificationEvent {
+        bet_id: pool_state.bet_id,
+        kind: PoolSubscribers::NOTIFY_RESOLUTION,
+        subscribers,
+        timestamp: now
+    });
+
+    if pool_state.is_spl() {
+        return Ok(0);
+    }
+    let platform_fee = platform_fee - pool_state.referral_fee_escrow - pool_state.referee_fee_amount;
+    main_state.accrue_revenue_share(platform_fee);
+    Ok(platform_fee + insurance_surplus(pool_state, is_yes))
+}
+
+// Where every lamport of a just-resolved pool goes (see SettlementAuditEvent). Fails with
+// SettlementNotConserved unless the destinations add up to exactly the reserves, so no
+// resolution can promise more than the pool holds or leave part of it unaccounted for.
+pub(crate) fn audit_settlement(pool_state: &PoolState, now: i64) -> Result<SettlementAuditEvent> {
+    let is_yes = pool_state.winner.is_yes();
+    let (winning_reserve, winning_seed, winning_supply) = if is_yes {
+        (pool_state.yes_reserve, pool_state.seed_yes_liquidity, pool_state.yes_supply)
+    } else {
+        (pool_state.no_reserve, pool_state.seed_no_liquidity, pool_state.no_supply)
+    };
+    let total_reserve =
+        (pool_state.yes_reserve as u128).saturating_add(pool_state.no_reserve as u128);
+    let profit_pot = winner_profit_pot(pool_state, is_yes);
+    let seed_returned = pool_state.reclaimable_seed() as u128;
+    let principal = winning_reserve.saturating_sub(winning_seed) as u128;
+    let (winners_pot, unallocated) = if pool_state.scalar_bounds.is_set() {
+        (scalar_side_pot(pool_state, true) + scalar_side_pot(pool_state, false), 0)
+    } else if winning_supply > 0 {
+        (principal.saturating_add(profit_pot), 0)
+    } else {
+        // No winning token to split the profit: the creator takes back what is left of the
+        // losing seed and the rest waits in the vault for sweep_unclaimed
+        let losing_seed_returned = seed_returned.saturating_sub(winning_seed as u128);
+        (principal, profit_pot.saturating_sub(losing_seed_returned))
+    };
+
+    let destinations = winners_pot
+        .saturating_add(pool_state.creator_fee_accrued as u128)
+        .saturating_add(pool_state.platform_fee_amount as u128)
+        .saturating_add(seed_returned)
+        .saturating_add(unallocated);
+    require!(
+        destinations == total_reserve,
+        BettingError::SettlementNotConserved
+    );
+
+    let insurance_surplus = insurance_surplus(pool_state, is_yes);
+    Ok(SettlementAuditEvent {
+        bet_id: pool_state.bet_id,
+        winner: pool_state.winner,
+        total_reserve: total_reserve as u64,
+        winners_pot: winners_pot as u64,
+        creator_fee: pool_state.creator_fee_accrued,
+        platform_fee: pool_state.platform_fee_amount,
+        referral_fees: pool_state.referral_fee_escrow,
+        referee_fee: pool_state.referee_fee_amount,
+        seed_returned: seed_returned as u64,
+        unallocated: unallocated as u64,
+        insurance_payouts: pool_state.insurance_premiums - insurance_surplus,
+        insurance_surplus,
+        timestamp: now,
+    })
+}
+
+#[cfg(feature = "oracle")]
+// Creates and fills the OutcomeOracle PDA of a freshly resolved pool, for paths that can't
+// use an `init` constraint because the account is only needed conditionally or in a batch.
+// Callers check `oracle_info` against the PDA.
+pub(crate) fn create_outcome_oracle<'info>(
+    program_id: &Pubkey,
+    system_program: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    oracle_info: &AccountInfo<'info>,
+    pool_state: &PoolState,
+    pool_key: Pubkey,
+) -> Result<()> {
+    create_resolution_account(
+        program_id,
+        system_program,
+        payer,
+        oracle_info,
+        OutcomeOracle::PREFIX_SEED,
+        pool_state.bet_id,
+        8 + OutcomeOracle::MAX_SIZE,
+    )?;
+
+    let mut outcome_oracle = OutcomeOracle::default();
+    publish_outcome(&mut outcome_oracle, pool_state, pool_key);
+    outcome_oracle.try_serialize(&mut &mut oracle_info.try_borrow_mut_data()?[..])
+}
+
+// SettlementReport counterpart of create_outcome_oracle. Callers check `report_info` against
+// the PDA.
+pub(crate) fn create_settlement_report<'info>(
+    program_id: &Pubkey,
+    system_program: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    report_info: &AccountInfo<'info>,
+    pool_state: &mut PoolState,
+    pool_key: Pubkey,
+    resolved_by: Pubkey,
+) -> Result<()> {
+    create_resolution_account(
+        program_id,
+        system_program,
+        payer,
+        report_info,
+        SettlementReport::PREFIX_SEED,
+        pool_state.bet_id,
+        8 + SettlementReport::MAX_SIZE,
+    )?;
+
+    let mut report = SettlementReport::default();
+    write_settlement_report(&mut report, pool_state, pool_key, resolved_by);
+    report.try_serialize(&mut &mut report_info.try_borrow_mut_data()?[..])
+}
+
+// Allocates a program-owned PDA seeded by (prefix, bet_id)
+fn create_resolution_account<'info>(
+    program_id: &Pubkey,
+    system_program: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    info: &AccountInfo<'info>,
+    prefix: &[u8],
+    bet_id: u64,
+    space: usize,
+) -> Result<()> {
+    let bet_id_bytes = bet_id.to_le_bytes();
+    let (_, bump) = Pubkey::find_program_address(&[prefix, &bet_id_bytes], program_id);
+
+    create_account(
+        CpiContext::new_with_signer(
+            system_program,
+            CreateAccount {
+                from: payer,
+                to: info.clone(),
+            },
+            &[&[prefix, &bet_id_bytes, &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        program_id,
+    )
+}
+
+// Records the final figures of a freshly resolved pool, and on the pool that it has a report
+// claims must check; the report is never written again
+pub(crate) fn write_settlement_report(
+    report: &mut SettlementReport,
+    pool_state: &mut PoolState,
+    pool_key: Pubkey,
+    resolved_by: Pubkey,
+) {
+    pool_state.has_settlement_report = true;
+    let winner_is_yes = pool_state.winner.is_yes();
+    let winning_supply = if winner_is_yes {
+        pool_state.yes_supply
+    } else {
+        pool_state.no_supply
+    };
+    // A pool declared invalid refunds deposits and premiums, with no profit or surplus
+    let (profit_pot, surplus) = if pool_state.refund_mode {
+        (0, 0)
+    } else {
+        (
+            winner_profit_pot(pool_state, winner_is_yes),
+            insurance_surplus(pool_state, winner_is_yes),
+        )
+    };
+
+    report.version = SettlementReport::VERSION;
+    report.pool = pool_key;
+    report.bet_id = pool_state.bet_id;
+    report.winner_is_yes = winner_is_yes;
+    report.resolved_at = pool_state.resolved_timestamp;
+    report.resolved_by = resolved_by;
+    report.yes_reserve = pool_state.yes_reserve;
+    report.no_reserve = pool_state.no_reserve;
+    report.yes_supply = pool_state.yes_supply;
+    report.no_supply = pool_state.no_supply;
+    report.creator_fee = pool_state.creator_fee_accrued;
+    report.creator_fee_donated = pool_state.creator_fee_donated;
+    report.platform_fee = pool_state.platform_fee_amount;
+    report.insurance_surplus = surplus;
+    report.winner_profit_pot = profit_pot.min(u64::MAX as u128) as u64;
+    report.payout_per_token = if winning_supply == 0 {
+        0
+    } else {
+        (profit_pot.saturating_mul(PAYOUT_PER_TOKEN_SCALE as u128) / winning_supply as u128)
+            .min(u64::MAX as u128) as u64
+    };
+    report.yes_entries = pool_state.yes_entries;
+    report.no_entries = pool_state.no_entries;
+}
+
+#[cfg(feature = "oracle")]
+// Fills in the OutcomeOracle of a freshly resolved pool
+pub(crate) fn publish_outcome(
+    outcome_oracle: &mut OutcomeOracle,
+    pool_state: &PoolState,
+    pool_key: Pubkey,
+) {
+    let (yes_probability, no_probability) = pool_state.current_prices();
+    outcome_oracle.version = OutcomeOracle::VERSION;
+    outcome_oracle.pool = pool_key;
+    outcome_oracle.bet_id = pool_state.bet_id;
+    outcome_oracle.winner = match pool_state.winner {
+        WinnerSide::Yes => OutcomeOracle::WINNER_YES,
+        WinnerSide::Void => OutcomeOracle::WINNER_INVALID,
+        _ => OutcomeOracle::WINNER_NO,
+    };
+    outcome_oracle.resolved_at = pool_state.resolved_timestamp;
+    outcome_oracle.yes_reserve = pool_state.yes_reserve;
+    outcome_oracle.no_reserve = pool_state.no_reserve;
+    outcome_oracle.yes_probability = yes_probability as u64;
+    outcome_oracle.no_probability = no_probability as u64;
+}
+
 #[derive(Accounts)]
 #[instruction(input: SetWinnerInput)]
 pub struct ASetWinner<'info> {
@@ -97,9 +632,27 @@ pub struct ASetWinner<'info> {
             &input.bet_id.to_le_bytes(),
         ],
         bump,
+        has_one = creator,
     )]
     pub pool_state: Box<Account<'info, PoolState>>,
 
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    // Omitted for pools without a title index (linked pools)
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
     #[account(
         mut,
         seeds = [VAULT_SEED.as_bytes()],
@@ -108,8 +661,61 @@ pub struct ASetWinner<'info> {
     /// CHECK: PDA vault only signs to transfer lamports
     pub sol_vault: AccountInfo<'info>,
 
-    #[account(mut, address = main_state.owner)]
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
     pub platform_owner: SystemAccount<'info>,
 
+    #[cfg(feature = "oracle")]
+    #[account(
+        init,
+        payer = referee,
+        space = 8 + OutcomeOracle::MAX_SIZE,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_oracle: Box<Account<'info, OutcomeOracle>>,
+
+    #[account(
+        init,
+        payer = referee,
+        space = 8 + SettlementReport::MAX_SIZE,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Box<Account<'info, SettlementReport>>,
+
+    #[cfg(feature = "referee-registry")]
+    #[account(
+        init_if_needed,
+        payer = referee,
+        space = 8 + RefereeProfile::MAX_SIZE,
+        seeds = [RefereeProfile::PREFIX_SEED, pool_state.referee.as_ref()],
+        bump
+    )]
+    pub referee_profile: Box<Account<'info, RefereeProfile>>,
+
+    // Omitted for pools created with history disabled; receives the resolution marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    // The referee's pending queue, if the pool was queued; the pool leaves it here
+    #[account(
+        mut,
+        seeds = [RefereeQueue::PREFIX_SEED, pool_state.referee.as_ref()],
+        bump
+    )]
+    pub referee_queue: Option<Box<Account<'info, RefereeQueue>>>,
+
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,213 @@
+use crate::{
+    annotate_history,
+    constants::{POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    load_history, resolve_pool,
+    vault::PoolVault,
+    write_settlement_report, CompleteEvent, MainState, PoolHistoryState, PoolState,
+    ProbabilityPoint, RangeResolvedEvent, ScalarResolvedEvent, SettlementReport, TitleIndex,
+};
+#[cfg(feature = "oracle")]
+use crate::{publish_outcome, OutcomeOracle};
+#[cfg(feature = "referee-registry")]
+use crate::RefereeProfile;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct SetWinnerByValueInput {
+    bet_id: u64,
+    value: i64,
+}
+
+// set_winner for range and scalar markets: the resolver submits the actual value, under the
+// same resolution guard as set_winner. In a range market the bucket that contains it decides
+// the outcome; a scalar market records the long side's share and resolves to whichever side
+// takes at least half of the pot.
+pub fn set_winner_by_value(
+    ctx: Context<ASetWinnerByValue>,
+    input: SetWinnerByValueInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let referee = ctx.accounts.referee.to_account_info();
+
+    // The pool's TitleIndex is closed with the `close` constraint once it resolves
+    require!(
+        ctx.accounts.title_index.is_some() == pool_state.title_indexed,
+        BettingError::TitleIndexMismatch
+    );
+
+    let is_yes = if pool_state.scalar_bounds.is_set() {
+        pool_state.scalar_long_bps = pool_state.scalar_bounds.long_bps(input.value);
+        pool_state.scalar_long_bps >= 5_000
+    } else {
+        pool_state.range_outcome(input.value)?
+    };
+    pool_state.range_value = input.value;
+    let platform_payout = resolve_pool(
+        pool_state,
+        main_state,
+        #[cfg(feature = "referee-registry")]
+        &mut ctx.accounts.referee_profile,
+        referee.key,
+        is_yes,
+    )?;
+
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.platform_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        platform_payout,
+    )?;
+
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_RESOLUTION,
+    )?;
+
+    // Publish the outcome for other programs
+    #[cfg(feature = "oracle")]
+    publish_outcome(&mut ctx.accounts.outcome_oracle, pool_state, pool_key);
+    write_settlement_report(
+        &mut ctx.accounts.settlement_report,
+        pool_state,
+        pool_key,
+        referee.key(),
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    if pool_state.scalar_bounds.is_set() {
+        emit!(ScalarResolvedEvent {
+            bet_id: input.bet_id,
+            value: input.value,
+            long_bps: pool_state.scalar_long_bps,
+            timestamp: now
+        });
+    } else {
+        emit!(RangeResolvedEvent {
+            bet_id: input.bet_id,
+            value: input.value,
+            is_yes,
+            timestamp: now
+        });
+    }
+    emit!(CompleteEvent {
+        referee: referee.key(),
+        bet_id: input.bet_id,
+        winner: pool_state.winner,
+        creator_fee_donated: pool_state.creator_fee_donated,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SetWinnerByValueInput)]
+pub struct ASetWinnerByValue<'info> {
+    #[account(mut)]
+    pub referee: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    // Omitted for pools without a title index (linked pools)
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    #[cfg(feature = "oracle")]
+    #[account(
+        init,
+        payer = referee,
+        space = 8 + OutcomeOracle::MAX_SIZE,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome_oracle: Box<Account<'info, OutcomeOracle>>,
+
+    #[account(
+        init,
+        payer = referee,
+        space = 8 + SettlementReport::MAX_SIZE,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_report: Box<Account<'info, SettlementReport>>,
+
+    #[cfg(feature = "referee-registry")]
+    #[account(
+        init_if_needed,
+        payer = referee,
+        space = 8 + RefereeProfile::MAX_SIZE,
+        seeds = [RefereeProfile::PREFIX_SEED, pool_state.referee.as_ref()],
+        bump
+    )]
+    pub referee_profile: Box<Account<'info, RefereeProfile>>,
+
+    // Omitted for pools created with history disabled; receives the resolution marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+
+    pub system_program: Program<'info, System>,
+}
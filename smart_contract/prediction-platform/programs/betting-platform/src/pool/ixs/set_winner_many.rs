@@ -0,0 +1,260 @@
+use crate::{
+    constants::{MAX_BATCH_RESOLUTIONS, POOL_VAULT_SEED, VAULT_SEED},
+    error::BettingError,
+    vault::PoolVault,
+    create_settlement_report, resolve_pool, CompleteEvent, MainState, PoolState,
+    SettlementReport, TitleIndex,
+};
+#[cfg(feature = "oracle")]
+use crate::{create_outcome_oracle, OutcomeOracle};
+#[cfg(feature = "referee-registry")]
+use crate::RefereeProfile;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+#[cfg(feature = "referee-registry")]
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct PoolResolution {
+    pub bet_id: u64,
+    pub is_yes: bool,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct SetWinnerManyInput {
+    pub resolutions: Vec<PoolResolution>,
+}
+
+// Resolves several pools in one transaction (e.g. a tournament bracket round). For every
+// resolution, remaining_accounts must hold the pool's PoolState, its (not yet created)
+// OutcomeOracle (oracle builds only) and SettlementReport PDAs and its referee's RefereeProfile
+// PDA (referee-registry builds only; created here if missing), followed by its TitleIndex PDA
+// and creator when the pool is title-indexed, and by its pool vault when it has its own. Each pool goes through the same guard as set_winner.
+pub fn set_winner_many<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ASetWinnerMany<'info>>,
+    input: SetWinnerManyInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let referee = &ctx.accounts.referee;
+    let mut remaining = ctx.remaining_accounts.iter();
+
+    require!(
+        !input.resolutions.is_empty() && input.resolutions.len() <= MAX_BATCH_RESOLUTIONS,
+        BettingError::InvalidBatch
+    );
+
+    let mut platform_payout_total: u64 = 0;
+
+    for resolution in input.resolutions.iter() {
+        let pool_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+        #[cfg(feature = "oracle")]
+        let oracle_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+        let report_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+        #[cfg(feature = "referee-registry")]
+        let profile_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+        let bet_id_bytes = resolution.bet_id.to_le_bytes();
+
+        let (pool_key, _) = Pubkey::find_program_address(
+            &[PoolState::PREFIX_SEED, &bet_id_bytes],
+            ctx.program_id,
+        );
+        require_keys_eq!(pool_info.key(), pool_key, BettingError::InvalidBatch);
+        let mut pool_state = Account::<PoolState>::try_from(pool_info)?;
+
+        // Mirror set_winner's `init_if_needed` on the referee profile. Pools sharing a referee
+        // pass the same profile, which is written back before the next resolution loads it.
+        #[cfg(feature = "referee-registry")]
+        let mut referee_profile = {
+            let (profile_key, profile_bump) = Pubkey::find_program_address(
+                &[RefereeProfile::PREFIX_SEED, pool_state.referee.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(profile_info.key(), profile_key, BettingError::InvalidBatch);
+            if profile_info.data_is_empty() {
+                let profile_space = 8 + RefereeProfile::MAX_SIZE;
+                create_account(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        CreateAccount {
+                            from: referee.to_account_info(),
+                            to: profile_info.clone(),
+                        },
+                        &[&[
+                            RefereeProfile::PREFIX_SEED,
+                            pool_state.referee.as_ref(),
+                            &[profile_bump],
+                        ]],
+                    ),
+                    Rent::get()?.minimum_balance(profile_space),
+                    profile_space as u64,
+                    ctx.program_id,
+                )?;
+                RefereeProfile::default()
+                    .try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+            }
+            Account::<RefereeProfile>::try_from(profile_info)?
+        };
+
+        require!(
+            !pool_state.resolves_by_value(),
+            BettingError::RangeValueRequired
+        );
+        let platform_payout = resolve_pool(
+            &mut pool_state,
+            main_state,
+            #[cfg(feature = "referee-registry")]
+            &mut referee_profile,
+            referee.key,
+            resolution.is_yes,
+        )?;
+
+        // Create and fill the OutcomeOracle PDA, mirroring set_winner's `init` constraint
+        #[cfg(feature = "oracle")]
+        {
+            let (oracle_key, _) = Pubkey::find_program_address(
+                &[OutcomeOracle::PREFIX_SEED, &bet_id_bytes],
+                ctx.program_id,
+            );
+            require_keys_eq!(oracle_info.key(), oracle_key, BettingError::InvalidBatch);
+            create_outcome_oracle(
+                ctx.program_id,
+                ctx.accounts.system_program.to_account_info(),
+                referee.to_account_info(),
+                oracle_info,
+                &pool_state,
+                pool_key,
+            )?;
+        }
+
+        let (report_key, _) = Pubkey::find_program_address(
+            &[SettlementReport::PREFIX_SEED, &bet_id_bytes],
+            ctx.program_id,
+        );
+        require_keys_eq!(report_info.key(), report_key, BettingError::InvalidBatch);
+        create_settlement_report(
+            ctx.program_id,
+            ctx.accounts.system_program.to_account_info(),
+            referee.to_account_info(),
+            report_info,
+            &mut pool_state,
+            pool_key,
+            referee.key(),
+        )?;
+
+        // Mirror set_winner's `close = creator` on the title index
+        if pool_state.title_indexed {
+            let title_index_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+            let creator_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+            let (title_index_key, _) = Pubkey::find_program_address(
+                &[
+                    TitleIndex::PREFIX_SEED,
+                    pool_state.creator.as_ref(),
+                    pool_state.title_hash.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require_keys_eq!(
+                title_index_info.key(),
+                title_index_key,
+                BettingError::TitleIndexMismatch
+            );
+            require_keys_eq!(
+                creator_info.key(),
+                pool_state.creator,
+                BettingError::TitleIndexMismatch
+            );
+            Account::<TitleIndex>::try_from(title_index_info)?.close(creator_info.clone())?;
+        }
+
+        // Pools with their own vault pay their platform fee from it; legacy pools add theirs
+        // to the batch's single transfer out of the shared vault
+        if pool_state.own_vault {
+            let vault_info = remaining.next().ok_or(BettingError::InvalidBatch)?;
+            let (vault_key, vault_bump) = Pubkey::find_program_address(
+                &[POOL_VAULT_SEED.as_bytes(), &bet_id_bytes],
+                ctx.program_id,
+            );
+            require_keys_eq!(vault_info.key(), vault_key, BettingError::InvalidBatch);
+            PoolVault::select(
+                &pool_state,
+                &ctx.accounts.sol_vault,
+                ctx.bumps.sol_vault,
+                vault_info,
+                vault_bump,
+            )
+            .pay(
+                &mut pool_state,
+                &ctx.accounts.platform_owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                platform_payout,
+            )?;
+        } else {
+            platform_payout_total = platform_payout_total
+                .checked_add(platform_payout)
+                .ok_or(BettingError::MathOverflow)?;
+            pool_state.vault_liabilities =
+                pool_state.vault_liabilities.saturating_sub(platform_payout);
+        }
+
+        pool_state.exit(ctx.program_id)?;
+        #[cfg(feature = "referee-registry")]
+        referee_profile.exit(ctx.program_id)?;
+
+        emit!(CompleteEvent {
+            referee: referee.key(),
+            bet_id: resolution.bet_id,
+            winner: pool_state.winner,
+            creator_fee_donated: pool_state.creator_fee_donated,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+    }
+
+    require!(remaining.next().is_none(), BettingError::InvalidBatch);
+
+    // One transfer for the platform fees of the whole batch
+    if platform_payout_total > 0 {
+        let transfer_instruction = system_instruction::transfer(
+            &ctx.accounts.sol_vault.to_account_info().key(),
+            ctx.accounts.platform_owner.key,
+            platform_payout_total,
+        );
+
+        invoke_signed(
+            &transfer_instruction,
+            &[
+                ctx.accounts.sol_vault.to_account_info(),
+                ctx.accounts.platform_owner.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[VAULT_SEED.as_bytes(), &[ctx.bumps.sol_vault]]],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetWinnerMany<'info> {
+    #[account(mut)]
+    pub referee: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,241 @@
+use crate::{
+    constants::{POOL_VAULT_SEED, VAULT_SEED},
+    create_outcome_oracle, create_settlement_report,
+    error::BettingError,
+    finalize_outcome,
+    resolution::ResolutionSource,
+    time,
+    vault::PoolVault,
+    Assertion, AssertionSettledEvent, CompleteEvent, MainState, OutcomeOracle, PoolState,
+    SettlementReport, TitleIndex,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SettleAssertionInput {
+    bet_id: u64,
+}
+
+// Permissionless. An undisputed assertion past its liveness window resolves the pool with the
+// asserted outcome and returns the bond. A disputed assertion waits for the regular
+// resolution and then pays both bonds to the side it proved right. If the pool can no longer
+// be resolved (refund mode or a migrated vault), every bond goes back to its owner. The
+// assertion account is closed to the asserter in all cases.
+pub fn settle_assertion(
+    ctx: Context<ASettleAssertion>,
+    input: SettleAssertionInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let assertion = &ctx.accounts.assertion;
+    let now = time::now(main_state)?;
+
+    let unresolvable = !pool_state.complete && pool_state.refund_mode;
+    let mut resolved_pool = false;
+    // Lamports moved out of the assertion before it closes to the asserter
+    let (bond_recipient, forfeited) = if assertion.is_disputed() {
+        let disputer = ctx
+            .accounts
+            .disputer
+            .as_ref()
+            .filter(|disputer| disputer.key().eq(&assertion.disputer))
+            .ok_or(BettingError::InvalidDisputer)?;
+        require!(
+            pool_state.complete || unresolvable,
+            BettingError::AssertionAwaitingResolution
+        );
+
+        if unresolvable {
+            (disputer.to_account_info(), assertion.bond)
+        } else if pool_state.winner.is_yes() == assertion.is_yes {
+            (ctx.accounts.asserter.to_account_info(), 0)
+        } else {
+            (disputer.to_account_info(), assertion.bond.saturating_mul(2))
+        }
+    } else if pool_state.complete {
+        // The referee resolved the pool before the liveness window ran out; a wrong
+        // assertion forfeits its bond to the platform
+        if pool_state.winner.is_yes() == assertion.is_yes {
+            (ctx.accounts.asserter.to_account_info(), 0)
+        } else {
+            (ctx.accounts.platform_owner.to_account_info(), assertion.bond)
+        }
+    } else if unresolvable {
+        (ctx.accounts.asserter.to_account_info(), 0)
+    } else {
+        require!(
+            now >= assertion.expires_at,
+            BettingError::AssertionLivenessActive
+        );
+        // The pool's TitleIndex is closed with the `close` constraint once it resolves
+        require!(
+            ctx.accounts.title_index.is_some() == pool_state.title_indexed,
+            BettingError::TitleIndexMismatch
+        );
+
+        let platform_payout = finalize_outcome(
+            pool_state,
+            main_state,
+            ResolutionSource::Assertion,
+            assertion.is_yes,
+            now,
+        )?;
+        PoolVault::select(
+            pool_state,
+            &ctx.accounts.sol_vault,
+            ctx.bumps.sol_vault,
+            &ctx.accounts.pool_vault,
+            ctx.bumps.pool_vault,
+        )
+        .pay(
+            pool_state,
+            &ctx.accounts.platform_owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            platform_payout,
+        )?;
+
+        let pool_key = pool_state.key();
+        let oracle_info = ctx.accounts.outcome_oracle.to_account_info();
+        create_outcome_oracle(
+            ctx.program_id,
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.caller.to_account_info(),
+            &oracle_info,
+            pool_state,
+            pool_key,
+        )?;
+        let report_info = ctx.accounts.settlement_report.to_account_info();
+        create_settlement_report(
+            ctx.program_id,
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.caller.to_account_info(),
+            &report_info,
+            pool_state,
+            pool_key,
+            assertion.asserter,
+        )?;
+
+        emit!(CompleteEvent {
+            referee: assertion.asserter,
+            bet_id: input.bet_id,
+            winner: pool_state.winner,
+            creator_fee_donated: pool_state.creator_fee_donated,
+            timestamp: now
+        });
+
+        resolved_pool = true;
+        (ctx.accounts.asserter.to_account_info(), 0)
+    };
+
+    if forfeited > 0 {
+        ctx.accounts.assertion.sub_lamports(forfeited)?;
+        bond_recipient.add_lamports(forfeited)?;
+    }
+
+    emit!(AssertionSettledEvent {
+        bet_id: input.bet_id,
+        asserter: assertion.asserter,
+        is_yes: assertion.is_yes,
+        resolved_pool,
+        bond_recipient: bond_recipient.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SettleAssertionInput)]
+pub struct ASettleAssertion<'info> {
+    // Pays the OutcomeOracle and SettlementReport rent when the assertion resolves the pool
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Receives the TitleIndex rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    // Only needed when the assertion resolves a pool with a title index
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(
+        mut,
+        close = asserter,
+        has_one = asserter,
+        seeds = [Assertion::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub assertion: Box<Account<'info, Assertion>>,
+
+    #[account(mut)]
+    pub asserter: SystemAccount<'info>,
+
+    // Required once the assertion is disputed
+    #[account(mut)]
+    pub disputer: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut, address = main_state.platform_fee_recipient())]
+    pub platform_owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [OutcomeOracle::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: created by create_outcome_oracle when the assertion resolves the pool
+    pub outcome_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SettlementReport::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: created by create_settlement_report when the assertion resolves the pool
+    pub settlement_report: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
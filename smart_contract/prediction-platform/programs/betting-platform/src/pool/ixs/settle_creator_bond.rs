@@ -0,0 +1,111 @@
+use crate::constants::{POOL_VAULT_SEED, TREASURY_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{error::BettingError, time, CreatorBondSettledEvent, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SettleCreatorBondInput {
+    bet_id: u64,
+}
+
+// Permissionless once the pool is decided: pays the bond back to whoever posted it (the pool's
+// rent payer), or into the treasury PDA when the pool missed its deadlines (see
+// creator_bond_slashed)
+pub fn settle_creator_bond(
+    ctx: Context<ASettleCreatorBond>,
+    input: SettleCreatorBondInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(
+        pool_state.complete || pool_state.refund_mode,
+        BettingError::BetNotComplete
+    );
+    require!(
+        pool_state.creator_bond > 0 && !pool_state.creator_bond_settled,
+        BettingError::AlreadyClaimed
+    );
+
+    // Mark as settled first to prevent reentrancy
+    pool_state.creator_bond_settled = true;
+    let amount = pool_state.creator_bond;
+    let slashed = pool_state.creator_bond_slashed();
+    let recipient = if slashed {
+        ctx.accounts.treasury.to_account_info()
+    } else {
+        ctx.accounts.rent_payer.to_account_info()
+    };
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &recipient,
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    emit!(CreatorBondSettledEvent {
+        bet_id: input.bet_id,
+        creator: pool_state.creator,
+        recipient: recipient.key(),
+        amount,
+        slashed,
+        timestamp: time::now(&ctx.accounts.main_state)?
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SettleCreatorBondInput)]
+pub struct ASettleCreatorBond<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+        has_one = rent_payer
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Posted the bond with the pool's rent: the creator, or open_linked_pool's caller
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA treasury; only receives lamports here
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,87 @@
+use crate::{error::BettingError, NotificationPrefs, PoolState, PoolSubscribers};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SubscribePoolInput {
+    bet_id: u64,
+}
+
+// Adds the signer to the pool's subscriber list. Only users with NotificationPrefs can
+// subscribe, so every listed key resolves to an endpoint.
+pub fn subscribe_pool(ctx: Context<ASubscribePool>, _input: SubscribePoolInput) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let subscribers = &mut ctx.accounts.pool_subscribers;
+    let user = ctx.accounts.user.key();
+    require!(!pool_state.complete, BettingError::BetComplete);
+    require!(
+        !subscribers.subscribers.contains(&user),
+        BettingError::AlreadySubscribed
+    );
+    require!(
+        subscribers.subscribers.len() < PoolSubscribers::MAX_SUBSCRIBERS,
+        BettingError::SubscriberListFull
+    );
+
+    subscribers.pool = pool_state.key();
+    subscribers.bet_id = pool_state.bet_id;
+    subscribers.subscribers.push(user);
+
+    Ok(())
+}
+
+pub fn unsubscribe_pool(ctx: Context<AUnsubscribePool>, _input: SubscribePoolInput) -> Result<()> {
+    let subscribers = &mut ctx.accounts.pool_subscribers;
+    let user = ctx.accounts.user.key();
+    let position = subscribers
+        .subscribers
+        .iter()
+        .position(|subscriber| subscriber.eq(&user))
+        .ok_or(BettingError::NotSubscribed)?;
+    subscribers.subscribers.swap_remove(position);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SubscribePoolInput)]
+pub struct ASubscribePool<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [NotificationPrefs::PREFIX_SEED, user.key().as_ref()],
+        bump,
+        has_one = user,
+    )]
+    pub notification_prefs: Box<Account<'info, NotificationPrefs>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PoolSubscribers::MAX_SIZE,
+        seeds = [PoolSubscribers::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_subscribers: Box<Account<'info, PoolSubscribers>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(input: SubscribePoolInput)]
+pub struct AUnsubscribePool<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PoolSubscribers::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_subscribers: Box<Account<'info, PoolSubscribers>>,
+}
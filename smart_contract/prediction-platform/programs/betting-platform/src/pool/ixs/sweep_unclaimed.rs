@@ -0,0 +1,93 @@
+use crate::constants::{POOL_VAULT_SEED, TREASURY_SEED, VAULT_SEED};
+use crate::vault::PoolVault;
+use crate::{error::BettingError, time, MainState, PoolState, UnclaimedSweptEvent};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SweepUnclaimedInput {
+    bet_id: u64,
+}
+
+// Once a resolved pool's claim deadline passes, the owner moves whatever its winners (and
+// insured losers) left unclaimed into the treasury PDA, so the vault doesn't hold it forever.
+// The creator's fee and seed stay behind for their own instructions. Late entries become
+// closable through close_entry.
+pub fn sweep_unclaimed(ctx: Context<ASweepUnclaimed>, input: SweepUnclaimedInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.require_sol_denominated()?;
+    require!(!pool_state.unclaimed_swept, BettingError::AlreadyClaimed);
+    let now = time::now(&ctx.accounts.main_state)?;
+    require!(pool_state.claims_closed(now), BettingError::ClaimWindowOpen);
+
+    let amount = pool_state.unclaimed_winnings();
+    pool_state.unclaimed_swept = true;
+    PoolVault::select(
+        pool_state,
+        &ctx.accounts.sol_vault,
+        ctx.bumps.sol_vault,
+        &ctx.accounts.pool_vault,
+        ctx.bumps.pool_vault,
+    )
+    .pay(
+        pool_state,
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    emit!(UnclaimedSweptEvent {
+        bet_id: input.bet_id,
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        claim_deadline: pool_state.claim_deadline().unwrap_or_default(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: SweepUnclaimedInput)]
+pub struct ASweepUnclaimed<'info> {
+    #[account(address = main_state.owner @ BettingError::Unauthorized)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA vault only signs to transfer lamports
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED.as_bytes(), &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: The pool's own vault; PoolVault picks it over sol_vault for pools that have one
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA treasury; only receives lamports here
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
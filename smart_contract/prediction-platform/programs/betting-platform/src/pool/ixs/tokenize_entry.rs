@@ -0,0 +1,138 @@
+use crate::constants::OUTCOME_MINT_SEED;
+use crate::{error::BettingError, EntryState, EntryTokenizedEvent, PoolState};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TokenizeEntryInput {
+    bet_id: u64,
+}
+
+// Converts the user's whole position into SPL outcome tokens of its side, one token per unit
+// of token_balance. The entry is emptied, so from here on the position is redeemed by
+// whoever holds the tokens (claim_with_tokens) instead of through the entry.
+pub fn tokenize_entry(ctx: Context<ATokenizeEntry>, input: TokenizeEntryInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let entry_state = &mut ctx.accounts.entry_state;
+
+    require!(pool_state.outcome_tokens, BettingError::OutcomeTokensDisabled);
+    require!(!entry_state.is_claimed, BettingError::AlreadyClaimed);
+    // Insurance and installments stay tied to the entry, so those entries keep it
+    require!(
+        entry_state.insured_amount == 0 && entry_state.installments_claimed == 0,
+        BettingError::EntryNotTokenizable
+    );
+    require!(entry_state.token_balance > 0, BettingError::InvalidBet);
+
+    let amount = entry_state.token_balance;
+    let principal = entry_state.deposited_sol_amount;
+    let is_yes = entry_state.is_yes;
+
+    let bet_id_bytes = input.bet_id.to_le_bytes();
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.outcome_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+            &[&[
+                PoolState::PREFIX_SEED,
+                &bet_id_bytes,
+                &[ctx.bumps.pool_state],
+            ]],
+        ),
+        amount,
+    )?;
+
+    if is_yes {
+        pool_state.tokenized_yes_supply = pool_state
+            .tokenized_yes_supply
+            .checked_add(amount)
+            .ok_or(BettingError::MathOverflow)?;
+        pool_state.tokenized_yes_principal = pool_state
+            .tokenized_yes_principal
+            .checked_add(principal)
+            .ok_or(BettingError::MathOverflow)?;
+    } else {
+        pool_state.tokenized_no_supply = pool_state
+            .tokenized_no_supply
+            .checked_add(amount)
+            .ok_or(BettingError::MathOverflow)?;
+        pool_state.tokenized_no_principal = pool_state
+            .tokenized_no_principal
+            .checked_add(principal)
+            .ok_or(BettingError::MathOverflow)?;
+    }
+
+    entry_state.token_balance = 0;
+    entry_state.deposited_sol_amount = 0;
+
+    emit!(EntryTokenizedEvent {
+        user: ctx.accounts.user.key(),
+        bet_id: input.bet_id,
+        is_yes,
+        token_amount: amount,
+        principal,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: TokenizeEntryInput)]
+pub struct ATokenizeEntry<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Mint authority of both outcome mints
+    #[account(
+        mut,
+        seeds =[
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub entry_state: Box<Account<'info, EntryState>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        mint::decimals = 0,
+        mint::authority = pool_state,
+        seeds = [
+            OUTCOME_MINT_SEED.as_bytes(),
+            &input.bet_id.to_le_bytes(),
+            &[entry_state.is_yes as u8],
+        ],
+        bump
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = outcome_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,176 @@
+use crate::resolution;
+use crate::time;
+use crate::{error::BettingError, EntryState, EntryTransferredEvent, MainState, PoolState};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TransferEntryInput {
+    pub bet_id: u64,
+}
+
+// Moves the user's whole position in an unresolved pool to another wallet's entry, created
+// if needed, e.g. when rotating wallets. The source entry is left empty.
+pub fn transfer_entry(ctx: Context<ATransferEntry>, input: TransferEntryInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user = ctx.accounts.user.key();
+    let recipient = ctx.accounts.recipient.key();
+
+    require!(!ctx.accounts.main_state.paused, BettingError::ProtocolPaused);
+    require!(user != recipient, BettingError::InvalidEntryTransfer);
+    resolution::ensure_unresolved(pool_state)?;
+    require!(
+        !pool_state.has_provisional_outcome(),
+        BettingError::ProvisionalOutcomePending
+    );
+    // A gated pool's positions stay with holders of its gate mint
+    pool_state.check_gate(
+        ctx.accounts
+            .gate_token_account
+            .as_ref()
+            .map(|account| account.amount),
+    )?;
+
+    let destination_entry = &mut ctx.accounts.destination_entry;
+    // Counted once, when the account is created; close_entry counts it out
+    if destination_entry.user.eq(&Pubkey::default()) {
+        pool_state.open_entries = pool_state
+            .open_entries
+            .checked_add(1)
+            .ok_or(BettingError::MathOverflow)?;
+        destination_entry.user = recipient;
+        destination_entry.bet_id = input.bet_id;
+    }
+
+    let source_entry = &mut ctx.accounts.source_entry;
+    let token_amount = source_entry.token_balance;
+    let deposited_amount = source_entry.deposited_sol_amount;
+    move_position(pool_state, source_entry, destination_entry)?;
+
+    emit!(EntryTransferredEvent {
+        bet_id: input.bet_id,
+        from: user,
+        to: recipient,
+        is_yes: destination_entry.is_yes,
+        token_amount,
+        deposited_amount,
+        timestamp: time::now(&ctx.accounts.main_state)?
+    });
+
+    Ok(())
+}
+
+// Folds the source entry's position into the destination's and empties the source. Both must
+// hold the same side and referrer, unless the destination holds nothing yet and takes the
+// source's; two positions merged into one count as one entry of their side and must fit the
+// pool's per-user cap. The creator fee exemption travels with the position: a merged entry keeps
+// it only if both halves had it.
+pub(crate) fn move_position(
+    pool_state: &mut PoolState,
+    source: &mut EntryState,
+    destination: &mut EntryState,
+) -> Result<()> {
+    require!(source.token_balance > 0, BettingError::InvalidBet);
+    require!(!source.is_claimed, BettingError::AlreadyClaimed);
+
+    let merging = destination.deposit_count > 0;
+    if merging {
+        require!(
+            destination.is_yes == source.is_yes && destination.referrer == source.referrer,
+            BettingError::InvalidEntryTransfer
+        );
+        pool_state.check_user_cap(destination.deposited_sol_amount, source.deposited_sol_amount)?;
+        destination.creator_fee_exempt &= source.creator_fee_exempt;
+        let entries = if source.is_yes {
+            &mut pool_state.yes_entries
+        } else {
+            &mut pool_state.no_entries
+        };
+        *entries = entries.saturating_sub(1);
+    } else {
+        destination.is_yes = source.is_yes;
+        destination.referrer = source.referrer;
+        destination.referral_credited = source.referral_credited;
+        destination.creator_fee_exempt = source.creator_fee_exempt;
+    }
+
+    let add = |to: &mut u64, amount: u64| -> Result<()> {
+        *to = to.checked_add(amount).ok_or(BettingError::MathOverflow)?;
+        Ok(())
+    };
+    add(&mut destination.deposited_sol_amount, source.deposited_sol_amount)?;
+    add(&mut destination.token_balance, source.token_balance)?;
+    add(&mut destination.creator_fee_paid, source.creator_fee_paid)?;
+    add(&mut destination.insured_amount, source.insured_amount)?;
+    add(&mut destination.insurance_premium_paid, source.insurance_premium_paid)?;
+    destination.deposit_count = destination
+        .deposit_count
+        .saturating_add(source.deposit_count);
+
+    source.deposited_sol_amount = 0;
+    source.token_balance = 0;
+    source.creator_fee_paid = 0;
+    source.insured_amount = 0;
+    source.insurance_premium_paid = 0;
+    source.deposit_count = 0;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: TransferEntryInput)]
+pub struct ATransferEntry<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &user.key().to_bytes()
+        ],
+        bump
+    )]
+    pub source_entry: Box<Account<'info, EntryState>>,
+
+    /// CHECK: Any wallet; only used to derive the destination entry
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + EntryState::MAX_SIZE,
+        seeds = [
+            EntryState::PREFIX_SEED,
+            &pool_state.key().to_bytes(),
+            &recipient.key().to_bytes()
+        ],
+        bump
+    )]
+    pub destination_entry: Box<Account<'info, EntryState>>,
+
+    // Token-gated pools only: the recipient's token account for the pool's gate mint
+    #[account(
+        token::mint = pool_state.gate_mint,
+        token::authority = recipient,
+    )]
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
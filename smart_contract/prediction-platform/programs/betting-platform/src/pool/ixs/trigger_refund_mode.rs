@@ -0,0 +1,78 @@
+use crate::resolution::{self, ResolutionSource};
+use crate::time;
+use crate::{
+    annotate_history, error::BettingError, load_history, MainState, PoolHistoryState, PoolState,
+    ProbabilityPoint, RefundModeEvent, WinnerSide,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct TriggerRefundModeInput {
+    pub bet_id: u64,
+}
+
+// Last rung of the escalation ladder: once the refund or resolution deadline passes without a
+// resolution, anyone can switch the pool into refund mode so every bettor gets their deposit
+// back
+pub fn trigger_refund_mode(
+    ctx: Context<ATriggerRefundMode>,
+    _input: TriggerRefundModeInput,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+
+    resolution::ensure_unresolved(pool_state)?;
+    let now = time::now(&ctx.accounts.main_state)?;
+    require!(
+        pool_state.resolution_window_closed(now),
+        BettingError::RefundDeadlineNotReached
+    );
+
+    resolution::claim(pool_state, ResolutionSource::RefundDeadline)?;
+    pool_state.refund_mode = true;
+    pool_state.winner = WinnerSide::Void;
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_CANCELLATION,
+    )?;
+
+    emit!(RefundModeEvent {
+        bet_id: pool_state.bet_id,
+        triggered_by: ctx.accounts.caller.key(),
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: TriggerRefundModeInput)]
+pub struct ATriggerRefundMode<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Omitted for pools created with history disabled; receives the cancellation marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+}
@@ -1,5 +1,11 @@
-use crate::{error::BettingError, MainState, PoolState};
+use crate::{
+    error::BettingError,
+    time,
+    validation::{validate_description, validate_title},
+    MainState, PoolState, RefereeAssignedEvent, TitleIndex, UpdatePoolEvent,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
 pub struct UpdatePoolInput {
@@ -8,12 +14,16 @@ pub struct UpdatePoolInput {
     pub description: Option<String>,
     pub end_timestamp: Option<i64>,
     pub referee: Option<Pubkey>,
+    // Exposure caps (0 removes one); the owner's values lock them against the creator
+    pub max_pool_reserve: Option<u64>,
+    pub max_user_deposit: Option<u64>,
 }
 
 pub fn update_pool(ctx: Context<AUpdatePool>, input: UpdatePoolInput) -> Result<()> {
     let main_state = &ctx.accounts.main_state;
-    let pool_state = &mut ctx.accounts.pool_state;
+    let pool_state = &ctx.accounts.pool_state;
     let updater = &ctx.accounts.updater;
+    let before = UpdatableFields::of(pool_state);
 
     // Only pool creator or main state owner can update the pool
     require!(
@@ -26,47 +36,218 @@ pub fn update_pool(ctx: Context<AUpdatePool>, input: UpdatePoolInput) -> Result<
         !pool_state.complete,
         BettingError::BetComplete
     );
+    require!(!pool_state.refund_mode, BettingError::PoolInRefundMode);
+    require!(
+        !pool_state.has_provisional_outcome(),
+        BettingError::ProvisionalOutcomePending
+    );
+    let now = time::now(main_state)?;
 
     // Update title if provided
     if let Some(title) = input.title {
-        require!(
-            title.len() <= 100,
-            BettingError::TitleTooLong
-        );
-        require!(
-            !title.is_empty(),
-            BettingError::TitleEmpty
-        );
-        pool_state.title = title;
+        validate_title(&title)?;
+        let title_hash = TitleIndex::title_hash(&title);
+        if pool_state.title_indexed && title_hash != pool_state.title_hash {
+            move_title_index(&ctx, title_hash)?;
+            ctx.accounts.pool_state.title_hash = title_hash;
+        }
+        ctx.accounts.pool_state.title = title;
     }
 
+    let pool_state = &mut ctx.accounts.pool_state;
+
     // Update description if provided
     if let Some(description) = input.description {
-        require!(
-            description.len() <= 500,
-            BettingError::DescriptionTooLong
-        );
-        require!(
-            !description.is_empty(),
-            BettingError::DescriptionEmpty
-        );
+        validate_description(&description)?;
         pool_state.description = description;
     }
 
     // Update other parameters if provided
     if let Some(end_timestamp) = input.end_timestamp {
+        require!(!pool_state.locked_on_start, BettingError::PoolLockedOnStart);
         pool_state.end_timestamp = end_timestamp;
+        pool_state.check_split_deadlines()?;
     }
 
     if let Some(referee) = input.referee {
-        pool_state.referee = referee;
+        if referee.ne(&pool_state.referee) {
+            // A committee pool's referee role stays with one of its members
+            require!(
+                !pool_state.has_committee() || pool_state.committee_seat(&referee).is_some(),
+                BettingError::NotCommitteeMember
+            );
+            emit!(RefereeAssignedEvent {
+                bet_id: input.bet_id,
+                previous_referee: pool_state.referee,
+                referee,
+                timestamp: now
+            });
+            pool_state.referee = referee;
+            pool_state.referee_accepted = false;
+            pool_state.check_self_referee_cap(&ctx.accounts.main_state)?;
+        }
     }
 
+    // Update exposure caps if provided
+    let by_owner = ctx.accounts.main_state.owner.eq(&ctx.accounts.updater.key());
+    pool_state.set_exposure_caps(input.max_pool_reserve, input.max_user_deposit, by_owner)?;
+
     // min_buy_amount removed
 
+    let updater = ctx.accounts.updater.key();
+    if let Some(event) = record_pool_update(&before, &mut ctx.accounts.pool_state, updater, now) {
+        emit!(event);
+    }
+
     Ok(())
 }
 
+// The fields update_pool may change, as they were before an update
+pub(crate) struct UpdatableFields {
+    title: String,
+    description: String,
+    end_timestamp: i64,
+    referee: Pubkey,
+    max_pool_reserve: u64,
+    max_user_deposit: u64,
+}
+
+impl UpdatableFields {
+    pub(crate) fn of(pool_state: &PoolState) -> Self {
+        Self {
+            title: pool_state.title.clone(),
+            description: pool_state.description.clone(),
+            end_timestamp: pool_state.end_timestamp,
+            referee: pool_state.referee,
+            max_pool_reserve: pool_state.max_pool_reserve,
+            max_user_deposit: pool_state.max_user_deposit,
+        }
+    }
+}
+
+// (old, new) of a field that changed, (None, None) otherwise
+fn change<T: PartialEq + Clone>(before: &T, after: &T) -> (Option<T>, Option<T>) {
+    if before == after {
+        (None, None)
+    } else {
+        (Some(before.clone()), Some(after.clone()))
+    }
+}
+
+// Stamps the pool's audit trail and describes the update if it changed any field; an update
+// that only restated current values leaves no trace
+pub(crate) fn record_pool_update(
+    before: &UpdatableFields,
+    pool_state: &mut PoolState,
+    updater: Pubkey,
+    now: i64,
+) -> Option<UpdatePoolEvent> {
+    let (previous_title, title) = change(&before.title, &pool_state.title);
+    let (previous_description, description) =
+        change(&before.description, &pool_state.description);
+    let (previous_end_timestamp, end_timestamp) =
+        change(&before.end_timestamp, &pool_state.end_timestamp);
+    let (previous_referee, referee) = change(&before.referee, &pool_state.referee);
+    let (previous_max_pool_reserve, max_pool_reserve) =
+        change(&before.max_pool_reserve, &pool_state.max_pool_reserve);
+    let (previous_max_user_deposit, max_user_deposit) =
+        change(&before.max_user_deposit, &pool_state.max_user_deposit);
+
+    let changed = title.is_some()
+        || description.is_some()
+        || end_timestamp.is_some()
+        || referee.is_some()
+        || max_pool_reserve.is_some()
+        || max_user_deposit.is_some();
+    if !changed {
+        return None;
+    }
+    pool_state.last_updated_timestamp = now;
+    pool_state.update_count = pool_state.update_count.saturating_add(1);
+
+    Some(UpdatePoolEvent {
+        bet_id: pool_state.bet_id,
+        updater,
+        previous_title,
+        title,
+        previous_description,
+        description,
+        previous_end_timestamp,
+        end_timestamp,
+        previous_referee,
+        referee,
+        previous_max_pool_reserve,
+        max_pool_reserve,
+        previous_max_user_deposit,
+        max_user_deposit,
+        update_count: pool_state.update_count,
+        timestamp: now,
+    })
+}
+
+// Re-keys the pool's TitleIndex to a new title: creates the index for the new title (paid by
+// the updater) and closes the old one into the updater
+fn move_title_index(ctx: &Context<AUpdatePool>, title_hash: [u8; 32]) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let updater = &ctx.accounts.updater;
+    let title_index = ctx
+        .accounts
+        .title_index
+        .as_ref()
+        .ok_or(BettingError::TitleIndexMismatch)?;
+    let new_title_index = ctx
+        .accounts
+        .new_title_index
+        .as_ref()
+        .ok_or(BettingError::TitleIndexMismatch)?;
+
+    let (new_key, new_bump) = Pubkey::find_program_address(
+        &[
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            &title_hash,
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        new_title_index.key(),
+        new_key,
+        BettingError::TitleIndexMismatch
+    );
+    require!(
+        new_title_index.data_is_empty(),
+        BettingError::DuplicateMarket
+    );
+
+    let space = 8 + TitleIndex::MAX_SIZE;
+    create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: updater.to_account_info(),
+                to: new_title_index.to_account_info(),
+            },
+            &[&[
+                TitleIndex::PREFIX_SEED,
+                pool_state.creator.as_ref(),
+                &title_hash,
+                &[new_bump],
+            ]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        ctx.program_id,
+    )?;
+    TitleIndex {
+        creator: pool_state.creator,
+        pool: pool_state.key(),
+        bet_id: pool_state.bet_id,
+    }
+    .try_serialize(&mut &mut new_title_index.try_borrow_mut_data()?[..])?;
+
+    title_index.close(updater.to_account_info())
+}
+
 #[derive(Accounts)]
 #[instruction(input: UpdatePoolInput)]
 pub struct AUpdatePool<'info> {
@@ -88,4 +269,23 @@ pub struct AUpdatePool<'info> {
         bump
     )]
     pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Only needed to change the title of a title-indexed pool: the current index, closed into
+    // the updater, and the not yet created index for the new title
+    #[account(
+        mut,
+        seeds = [
+            TitleIndex::PREFIX_SEED,
+            pool_state.creator.as_ref(),
+            pool_state.title_hash.as_ref(),
+        ],
+        bump
+    )]
+    pub title_index: Option<Box<Account<'info, TitleIndex>>>,
+
+    #[account(mut)]
+    /// CHECK: checked against the new title's TitleIndex PDA in the handler
+    pub new_title_index: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
 }
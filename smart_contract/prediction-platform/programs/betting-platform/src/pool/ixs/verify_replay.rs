@@ -0,0 +1,32 @@
+use crate::error::BettingError;
+use crate::replay::PoolReplay;
+use crate::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct VerifyReplayInput {
+    pub bet_id: u64,
+    // replay_hash of the indexer's replay; None only reads the on-chain hash
+    pub expected_hash: Option<[u8; 32]>,
+}
+
+// Hashes the replayable part of a pool (see replay.rs) for comparison with an off-chain
+// replay of its events. Returns the hash, failing with ReplayMismatch when it differs from
+// `expected_hash`; call it with `.view()` to compare without a transaction.
+pub fn verify_replay(ctx: Context<AVerifyReplay>, input: VerifyReplayInput) -> Result<[u8; 32]> {
+    let replay_hash = PoolReplay::of(&ctx.accounts.pool_state).replay_hash();
+    if let Some(expected_hash) = input.expected_hash {
+        require!(replay_hash == expected_hash, BettingError::ReplayMismatch);
+    }
+    Ok(replay_hash)
+}
+
+#[derive(Accounts)]
+#[instruction(input: VerifyReplayInput)]
+pub struct AVerifyReplay<'info> {
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
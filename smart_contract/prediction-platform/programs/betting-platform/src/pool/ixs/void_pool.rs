@@ -0,0 +1,84 @@
+use crate::resolution::{self, ResolutionSource};
+use crate::time;
+use crate::{
+    annotate_history, error::BettingError, load_history, MainState, PoolHistoryState, PoolState,
+    PoolVoidedEvent, ProbabilityPoint, WinnerSide,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct VoidPoolInput {
+    pub bet_id: u64,
+}
+
+// Gives up on resolving a pool: it enters refund mode, so every entry can take its deposit
+// back through claim_refund. The owner may void at any time; anyone may once VOID_GRACE_SECS
+// have passed since the end without a resolution, so funds never stay stuck in the vault.
+pub fn void_pool(ctx: Context<AVoidPool>, _input: VoidPoolInput) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    // claim_refund only pays from the SOL vault
+    pool_state.require_sol_denominated()?;
+
+    resolution::ensure_unresolved(pool_state)?;
+    let now = time::now(&ctx.accounts.main_state)?;
+    let caller = ctx.accounts.caller.key();
+    let by_owner = ctx.accounts.main_state.owner.eq(&caller);
+    if !by_owner {
+        require!(
+            pool_state.public_void_at().is_some_and(|void_at| now >= void_at),
+            BettingError::VoidGraceNotElapsed
+        );
+    }
+
+    resolution::claim(pool_state, ResolutionSource::Voided)?;
+    pool_state.refund_mode = true;
+    pool_state.winner = WinnerSide::Void;
+    pool_state.voided_at = now;
+    pool_state.resolved_timestamp = now;
+    let pool_key = pool_state.key();
+    annotate_history(
+        pool_state,
+        load_history(&ctx.accounts.history_state)?.as_deref_mut(),
+        pool_key,
+        ProbabilityPoint::KIND_CANCELLATION,
+    )?;
+
+    emit!(PoolVoidedEvent {
+        bet_id: pool_state.bet_id,
+        voided_by: caller,
+        by_owner,
+        timestamp: now
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: VoidPoolInput)]
+pub struct AVoidPool<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            &input.bet_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    // Omitted for pools created with history disabled; receives the cancellation marker
+    #[account(
+        mut,
+        seeds = [PoolHistoryState::PREFIX_SEED, &input.bet_id.to_le_bytes()],
+        bump
+    )]
+    pub history_state: Option<AccountLoader<'info, PoolHistoryState>>,
+}
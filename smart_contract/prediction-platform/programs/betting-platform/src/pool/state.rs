@@ -1,76 +1,1756 @@
-use anchor_lang::prelude::*;
-
-#[account]
-pub struct PoolState {
-    pub creator: Pubkey,
-    pub bet_id: u64,
-    pub initial_price: u64,
-    pub scale_factor: u64,
-
-    pub total_supply: u64,
-    pub total_reserve: u64,
-    pub yes_supply: u64,
-    pub yes_reserve: u64,
-    pub no_supply: u64,
-    pub no_reserve: u64,
-
-    pub end_timestamp: i64,
-    // New: block time when the pool was created
-    pub created_timestamp: i64,
-    pub referee: Pubkey,
-
-    pub title: String,       // Bet title (max 100 chars)
-    pub description: String, // Bet description (max 500 chars)
-    pub share_uuid: String,  // Unique identifier for shareable link (max 50 chars)
-
-    pub winner: String,
-    pub complete: bool,
-    pub creator_fee_claimed: bool, // Track if creator has claimed their fee
-    pub platform_fee_claimed: bool, // Track if platform has claimed its fee
-}
-
-impl PoolState {
-    pub const MAX_SIZE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + (4 + 100) + (4 + 500) + (4 + 50) + (4 + 50) + 1 + 1 + 1; // ~852 bytes
-    pub const PREFIX_SEED: &'static [u8] = b"pool";
-}
-
-// Probability history for a pool (market)
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
-pub struct ProbabilityPoint {
-    pub timestamp: i64,      // block time when recorded
-    pub yes_reserve: u64,    // lamports on YES side at this time
-    pub no_reserve: u64,     // lamports on NO side at this time
-}
-
-#[account]
-pub struct PoolHistoryState {
-    pub pool: Pubkey,                // reference to the pool
-    pub bet_id: u64,                 // convenience: same as pool.bet_id
-    pub points: Vec<ProbabilityPoint>, // chronological series of points
-}
-
-impl PoolHistoryState {
-    // Each ProbabilityPoint = 24 bytes (i64 + u64 + u64)
-    // 40 points * 24 = 960 bytes; overhead (pool 32 + bet_id 8 + vec len 4) = 44 bytes; total
-    // data size (excluding 8-byte discriminator) ≈ 1004 bytes.
-    pub const MAX_POINTS: usize = 40;
-    pub const PREFIX_SEED: &'static [u8] = b"history";
-
-    // discriminator (8) + pool(32) + bet_id(8) + vec len(4) + points
-    pub const MAX_SIZE: usize = 32 + 8 + 4 + (Self::MAX_POINTS * core::mem::size_of::<ProbabilityPoint>());
-}
-
-#[account]
-pub struct EntryState {
-    pub user: Pubkey,
-    pub bet_id: u64,
-    pub deposited_sol_amount: u64,
-    pub token_balance: u64,
-    pub is_yes: bool,
-    pub is_claimed: bool,
-}
-
-impl EntryState {
-    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
-    pub const PREFIX_SEED: &'static [u8] = b"entry";
-}
+use crate::{constants::{COMMIT_REVEAL_WINDOW_SECS, CREATION_BUCKETS, CREATION_BUCKET_SECS, DOMINANT_HOLDER_BPS, MAX_COMMITTEE_REFEREES, MAX_OUTCOMES, MAX_OUTCOME_NAME_LEN, MAX_RANGE_BUCKETS, RECOVERY_CHALLENGE_SECS, STATE_HASH_PRECISION, UNREVEALED_FORFEIT_BPS, VOID_GRACE_SECS, VOLUME_BUCKETS, VOLUME_BUCKET_SECS, WHALE_DEPOSIT_BPS}, error::BettingError, implied_prices, MainState};
+use crate::fees::bps_of;
+use crate::layout::Reserved;
+use crate::pricing::lmsr_prices;
+use crate::resolution::ResolutionSource;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+
+// A pool's outcome. Void pools were given up on and refund every deposit (see refund_mode).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum WinnerSide {
+    #[default]
+    Unresolved,
+    Yes,
+    No,
+    Void,
+}
+
+impl WinnerSide {
+    pub fn from_is_yes(is_yes: bool) -> Self {
+        if is_yes {
+            Self::Yes
+        } else {
+            Self::No
+        }
+    }
+
+    pub fn is_yes(&self) -> bool {
+        *self == Self::Yes
+    }
+}
+
+// How deposits are priced into tokens: the ratio of reserves padded with a virtual reserve
+// (see implied_prices), or the Logarithmic Market Scoring Rule over the tokens outstanding
+// (see pricing.rs)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum PricingModel {
+    #[default]
+    Ratio,
+    Lmsr,
+}
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct PoolState {
+    pub creator: Pubkey,
+    pub bet_id: u64,
+    pub initial_price: u64,
+    pub scale_factor: u64,
+
+    pub total_supply: u64,
+    pub total_reserve: u64,
+    pub yes_supply: u64,
+    pub yes_reserve: u64,
+    pub no_supply: u64,
+    pub no_reserve: u64,
+
+    pub end_timestamp: i64,
+    // New: block time when the pool was created
+    pub created_timestamp: i64,
+    pub referee: Pubkey,
+
+    #[max_len(100)]
+    pub title: String, // Bet title
+    #[max_len(500)]
+    pub description: String, // Bet description
+    #[max_len(50)]
+    pub share_uuid: String, // Unique identifier for shareable link
+
+    pub winner: WinnerSide,
+    pub complete: bool,
+    pub creator_fee_claimed: bool, // Track if creator has claimed their fee
+    pub platform_fee_claimed: bool, // Track if platform has claimed its fee
+
+    pub commit_reveal: bool,       // Deposits must go through commit_deposit/reveal_deposit
+    pub pending_commitments: u64,  // Commitments that are neither revealed nor refunded yet
+
+    pub history_pruned: bool, // Set once old history points have been dropped (see HistoryPrunedEvent)
+
+    // Annuity-style payouts: winners are paid in `installment_count` equal parts, one unlocking
+    // every `installment_period` seconds after resolution (count <= 1 means a single payout)
+    pub installment_count: u8,
+    pub installment_period: i64,
+    pub resolved_timestamp: i64, // block time of set_winner or void_pool (0 while unresolved)
+
+    // Display metadata for quantity markets (e.g. "BTC above $100k"); empty symbol = none
+    #[max_len(10)]
+    pub unit_symbol: String,
+    pub decimals: u8,
+
+    // Display labels for the YES/NO sides (e.g. "Team A" / "Team B"); empty = "Yes"/"No"
+    #[max_len(30)]
+    pub side_a_label: String, // maps to YES
+    #[max_len(30)]
+    pub side_b_label: String, // maps to NO
+
+    // Creator fee accrues on every deposit at the rate snapshotted at creation, so the
+    // creator's earnings are visible in real time and payout never depends on final reserves
+    pub creator_fee_bps: u64,
+    pub creator_fee_accrued: u64,
+
+    // Maximum the vault may owe this pool's winners (0 = uncapped); enforced on every deposit
+    pub liability_cap: u64,
+
+    pub history_disabled: bool, // No PoolHistoryState; probability history comes from events only
+
+    // The creator's own entry does not pay the creator fee (they would only be paying themselves)
+    pub waive_creator_self_fee: bool,
+
+    // Platform fee taken at resolution; claims reserve exactly this amount instead of
+    // re-reading the (mutable) rate from MainState
+    pub platform_fee_amount: u64,
+
+    // Escalation ladder (0 = not set): the referee resolves alone until `referee_deadline`,
+    // then the owner/ops council may step in; past `refund_deadline` nobody can resolve and
+    // anyone may switch the pool into refund mode, returning every deposit
+    pub referee_deadline: i64,
+    pub refund_deadline: i64,
+    pub refund_mode: bool,
+
+    // Funds live in the pool's own pool-vault PDA (see vault::PoolVault): set at creation for
+    // SOL pools, or by migrate_vault for pools that started in the legacy shared sol-vault
+    pub own_vault: bool,
+
+    // resolved_timestamp - end_timestamp, floored at 0; stays 0 for open-ended pools
+    pub resolution_latency_secs: i64,
+
+    // Share of the creator fee (bps) handed back to winners at resolution, and the amount
+    // that moved; it is subtracted from creator_fee_accrued so it lands in the profit pot
+    pub creator_donation_bps: u64,
+    pub creator_fee_donated: u64,
+
+    // Insurance sub-pool: premiums collected (held in the vault on top of the reserves) and
+    // the insured principal on each side
+    pub insurance_premiums: u64,
+    pub insured_yes: u64,
+    pub insured_no: u64,
+
+    // Deposits a single entry may make (0 = unlimited), so micro-deposit spam can't bloat
+    // per-entry stats
+    pub max_deposits_per_entry: u32,
+
+    // Set when the council resolved because the referee missed `referee_deadline`; a
+    // no-show referee forfeits any referee fee to the winners
+    pub referee_no_show: bool,
+
+    // Pools opened through create_pool hold a TitleIndex keyed by (creator, title_hash) until
+    // they resolve; linked pools are not indexed
+    pub title_indexed: bool,
+    pub title_hash: [u8; 32],
+
+    // Anyone may resolve the pool by asserting an outcome with a bond (see Assertion)
+    pub optimistic_resolution: bool,
+
+    // Rent telemetry for treasury reconciliation: who funded the pool's accounts, the lamports
+    // they locked at creation, and how much has been handed back as accounts closed
+    pub rent_payer: Pubkey,
+    pub pool_rent_lamports: u64,
+    pub history_rent_lamports: u64, // 0 when history is disabled
+    pub rent_reclaimed_lamports: u64,
+
+    // SPL outcome tokens: entries may be converted into fungible tokens of their side's mint,
+    // redeemable by any holder via claim_with_tokens. The totals track the tokenized slice
+    // of each side so token redemptions pay the same principal + profit as entry claims.
+    pub outcome_tokens: bool,
+    pub tokenized_yes_supply: u64,
+    pub tokenized_yes_principal: u64,
+    pub tokenized_no_supply: u64,
+    pub tokenized_no_principal: u64,
+
+    // Range markets ask a numeric question; the referee submits the actual value and the
+    // bucket containing it decides the outcome. Empty = regular YES/NO market.
+    #[max_len(MAX_RANGE_BUCKETS)]
+    pub range_buckets: Vec<RangeBucket>,
+    pub range_value: i64, // submitted value (0 until a range or scalar market resolves)
+
+    // Payout preview refreshed on every deposit: profit per winning token (scaled by
+    // PAYOUT_PER_TOKEN_SCALE) if that side won now, see fees::refresh_payout_projection
+    pub projected_payout_per_token_yes: u64,
+    pub projected_payout_per_token_no: u64,
+
+    // A referee handed the pool through update_pool must accept_referee_role before they can
+    // resolve it; the referee chosen at creation starts out accepted
+    pub referee_accepted: bool,
+
+    // Entries holding a position on each side, counted at their first deposit
+    pub yes_entries: u32,
+    pub no_entries: u32,
+
+    // Seconds after resolution winners have to claim, fixed at creation from the creator's
+    // claim_deadline_override or MainState's default (see claim_deadline)
+    pub claim_window_secs: i64,
+
+    // Two-phase resolution (0 = single-call set_winner): set_provisional_winner locks trading
+    // and records the outcome, which anyone may finalize once `provisional_window_secs` pass.
+    // A participant's dispute in the meantime hands the decision to the owner.
+    pub provisional_window_secs: i64,
+    pub provisional_at: i64, // 0 while no provisional outcome is pending
+    pub provisional_is_yes: bool,
+    pub provisional_by: Pubkey,
+    pub provisional_disputed: bool,
+
+    // Oracle-bound markets: the StartFeed reporting when the underlying event begins
+    // (Pubkey::default() = none). crank_lock_on_start ends trading once it reports a start,
+    // after which the end time can no longer be moved.
+    pub start_feed: Pubkey,
+    pub locked_on_start: bool,
+
+    // SPL-denominated pools (create_pool_spl) hold this mint in a vault ATA owned by the pool
+    // PDA and book every amount in its base units; Pubkey::default() = native SOL. Each kind
+    // of pool only moves funds through its own instructions (deposit vs deposit_spl, ...).
+    pub token_mint: Pubkey,
+
+    // Block time void_pool switched the pool into refund mode (0 = not voided); refund mode
+    // reached through trigger_refund_mode leaves it at 0
+    pub voided_at: i64,
+
+    // Promoted markets the owner backs with a PoolGuarantee escrow; claims draw from it when
+    // the vault alone cannot pay a winner
+    pub guaranteed: bool,
+
+    // Pools that resolve from a Pyth price feed through resolve_with_oracle (feed =
+    // Pubkey::default() for referee-only pools). The referee can still resolve them.
+    pub price_condition: PriceCondition,
+
+    // Latest reveal deadline of any commitment (see commitments_settled)
+    pub latest_reveal_deadline: i64,
+
+    // Rent reclaim: entries not yet closed through close_entry, and whether close_history
+    // ran. close_pool waits for both (see is_fully_settled).
+    pub open_entries: u32,
+    pub history_closed: bool,
+
+    // Vault ledger of SOL pools: lamports the vault holds on this pool's behalf, i.e. deposits,
+    // premiums and escrowed commitments in, minus every PoolVault::pay out (see
+    // get_vault_health). Pools opened before the ledger only count what moved since.
+    pub vault_liabilities: u64,
+
+    // Creator-seeded liquidity: lamports the creator put on each side at creation. They sit in
+    // the reserves (and so in pricing) without minting tokens; see reclaimable_seed for what
+    // the creator gets back.
+    pub seed_yes_liquidity: u64,
+    pub seed_no_liquidity: u64,
+    pub seed_reclaimed: bool,
+
+    // Meta-markets (create_meta_pool) resolve from another pool's state through
+    // resolve_meta_market; unset for every other pool
+    pub meta_condition: MetaCondition,
+
+    // Scalar markets (unset bounds = not scalar): the referee submits a value through
+    // set_winner_by_value and both sides share the pot, long (YES) taking scalar_long_bps of
+    // it (see scalar_side_pot)
+    pub scalar_bounds: ScalarBounds,
+    pub scalar_long_bps: u64,
+
+    // Which path decided the pool (see resolution.rs)
+    pub resolution_source: ResolutionSource,
+
+    // sweep_unclaimed moved what winners left unclaimed past the claim deadline to the treasury
+    pub unclaimed_swept: bool,
+
+    // Referrals: deposits made through entries with a referrer, per side, and the part of the
+    // platform fee held back at resolution for the winning side's referrers
+    pub referred_yes: u64,
+    pub referred_no: u64,
+    pub referral_fee_escrow: u64,
+
+    // Scheduled open (0 = open at creation): before `opens_at` deposits can only be queued as
+    // PendingDeposits, which execute_pending_deposit places in queue order once the pool opens.
+    // Regular deposits wait until every queued one has been placed or refunded.
+    pub opens_at: i64,
+    pub waitlist_queued: u64,   // PendingDeposits ever queued; the next one's position
+    pub waitlist_executed: u64, // PendingDeposits placed or refunded; the head's position
+
+    // Referee committee (committee_size = 0: the referee decides alone). Members vote through
+    // propose_winner and the pool resolves once `committee_threshold` of them back the same
+    // outcome; the referee is a member and loses the solo set_winner path.
+    pub referee_committee: [Pubkey; MAX_COMMITTEE_REFEREES],
+    pub committee_size: u8,
+    pub committee_threshold: u8,
+
+    // Closing line of a fixed-end pool, taken by the first interaction after trading stopped
+    // (see record_final_odds); unset until then and for open-ended pools that never lock
+    pub final_odds: FinalOdds,
+
+    // Token-gated pools take entries and deposits only from holders of at least
+    // `gate_min_amount` of `gate_mint` (default key = open to everyone)
+    pub gate_mint: Pubkey,
+    pub gate_min_amount: u64,
+
+    // Entries needed for set_winner to pay out; short of them, or with one side empty, the
+    // pool refunds instead (0 = no minimum)
+    pub min_participants: u32,
+
+    // Exposure limits (0 = uncapped): what the reserves and one entry's deposits may grow to
+    // through deposit. Set by the creator unless the owner overrode them, which locks them.
+    pub max_pool_reserve: u64,
+    pub max_user_deposit: u64,
+    pub exposure_caps_by_owner: bool,
+
+    // The referee's cut of the platform fee (bps of the fee), fixed at creation; what it came
+    // to when the referee resolved the pool, held in the vault until claim_referee_fee
+    pub referee_fee_bps: u64,
+    pub referee_fee_amount: u64,
+    pub referee_fee_claimed: bool,
+
+    // Pricing of deposits, fixed at creation; lmsr_liquidity is the LMSR's `b` (0 for Ratio)
+    pub pricing_model: PricingModel,
+    pub lmsr_liquidity: u64,
+
+    // Audit trail of update_pool: when it last changed a field and how many updates did
+    pub last_updated_timestamp: i64,
+    pub update_count: u32,
+
+    // Split deadlines (0 = not set): deposits stop at trading_end_timestamp rather than
+    // end_timestamp, and past resolution_deadline nobody may resolve and anyone may trigger
+    // refund mode, as past refund_deadline but without a referee deadline before it
+    pub trading_end_timestamp: i64,
+    pub resolution_deadline: i64,
+
+    // Anti-spam bond escrowed in the vault at creation (MainState's creator_bond_lamports then),
+    // back to the rent payer who posted it once the pool is decided on time or to the treasury
+    // if it missed its deadlines (see creator_bond_slashed); settled by settle_creator_bond
+    pub creator_bond: u64,
+    pub creator_bond_settled: bool,
+
+    // Who earned referee_fee_amount: the referee as of their own resolution, snapshotted so the
+    // fee is paid to the key that resolved and not to whoever holds the role at claim time
+    pub referee_fee_recipient: Pubkey,
+
+    // Opened with a PoolSummary (create_pool), which every deposit must then update; pools
+    // without one predate it or were opened another way
+    pub has_summary: bool,
+
+    // Resolved with a SettlementReport (see write_settlement_report), which claims must then
+    // check against; pools resolved before reports existed have none
+    pub has_settlement_report: bool,
+
+    // Room for later fields (see layout.rs); new fields go above and shrink it
+    pub reserved: Reserved<{ PoolState::RESERVED }>,
+}
+
+impl PoolState {
+    pub const RESERVED: usize = 34;
+    pub const MAX_SIZE: usize = Self::INIT_SPACE;
+    pub const PREFIX_SEED: &'static [u8] = b"pool";
+
+    // Outcome a range market resolves to for `value`
+    pub fn range_outcome(&self, value: i64) -> Result<bool> {
+        require!(!self.range_buckets.is_empty(), BettingError::NotRangeMarket);
+        self.range_buckets
+            .iter()
+            .find(|bucket| bucket.contains(value))
+            .map(|bucket| bucket.is_yes)
+            .ok_or(error!(BettingError::ValueOutsideRange))
+    }
+
+    // Snapshots the reserves and implied prices once a fixed-end pool stopped trading, at its
+    // end time or at resolution, whichever the pool sees first. Later flows (late reveals,
+    // refunds) no longer move the closing line. Returns the snapshot if this call took it.
+    pub fn record_final_odds(&mut self, now: i64) -> Option<FinalOdds> {
+        let closed = self.trading_end() >= 0 && (now >= self.trading_end() || self.complete);
+        if !closed || self.final_odds.is_recorded() {
+            return None;
+        }
+        let (yes_price, no_price) = self.current_prices();
+        self.final_odds = FinalOdds {
+            yes_reserve: self.yes_reserve,
+            no_reserve: self.no_reserve,
+            yes_price: yes_price as u64,
+            no_price: no_price as u64,
+            recorded_at: now,
+        };
+        Some(self.final_odds)
+    }
+
+    // Implied (yes, no) prices scaled by PRICE_SCALE under the pool's pricing model
+    pub fn current_prices(&self) -> (u128, u128) {
+        match self.pricing_model {
+            PricingModel::Ratio => implied_prices(self.yes_reserve, self.no_reserve),
+            PricingModel::Lmsr => lmsr_prices(self.yes_supply, self.no_supply, self.lmsr_liquidity),
+        }
+    }
+
+    // Whether set_winner must refund the pool rather than name a winner (see min_participants)
+    pub fn short_of_participants(&self) -> bool {
+        self.min_participants > 0
+            && (self.yes_entries.saturating_add(self.no_entries) < self.min_participants
+                || self.yes_entries == 0
+                || self.no_entries == 0)
+    }
+
+    // Fails if a deposit of `amount` into an entry holding `entry_deposited` breaks a cap
+    pub fn check_exposure_caps(&self, entry_deposited: u64, amount: u64) -> Result<()> {
+        if self.max_pool_reserve > 0 {
+            let reserve = self
+                .yes_reserve
+                .saturating_add(self.no_reserve)
+                .saturating_add(amount);
+            require!(
+                reserve <= self.max_pool_reserve,
+                BettingError::PoolCapExceeded
+            );
+        }
+        self.check_user_cap(entry_deposited, amount)
+    }
+
+    // The per-entry half of check_exposure_caps, for stake that moves between entries without
+    // adding to the pool's reserves
+    pub fn check_user_cap(&self, entry_deposited: u64, amount: u64) -> Result<()> {
+        if self.max_user_deposit > 0 {
+            require!(
+                entry_deposited.saturating_add(amount) <= self.max_user_deposit,
+                BettingError::UserCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    // update_pool's cap changes; once the owner set them the creator can no longer change them
+    pub fn set_exposure_caps(
+        &mut self,
+        max_pool_reserve: Option<u64>,
+        max_user_deposit: Option<u64>,
+        by_owner: bool,
+    ) -> Result<()> {
+        if max_pool_reserve.is_none() && max_user_deposit.is_none() {
+            return Ok(());
+        }
+        require!(
+            by_owner || !self.exposure_caps_by_owner,
+            BettingError::ExposureCapsSetByOwner
+        );
+        if let Some(cap) = max_pool_reserve {
+            self.max_pool_reserve = cap;
+        }
+        if let Some(cap) = max_user_deposit {
+            self.max_user_deposit = cap;
+        }
+        self.exposure_caps_by_owner |= by_owner;
+        Ok(())
+    }
+
+    pub fn is_gated(&self) -> bool {
+        self.gate_mint != Pubkey::default()
+    }
+
+    // `gate_balance` is what the user holds in the gate token account they passed, if any
+    pub fn check_gate(&self, gate_balance: Option<u64>) -> Result<()> {
+        if !self.is_gated() {
+            return Ok(());
+        }
+        let balance = gate_balance.ok_or(error!(BettingError::GateTokenRequired))?;
+        require!(
+            balance >= self.gate_min_amount,
+            BettingError::GateBalanceTooLow
+        );
+        Ok(())
+    }
+
+    // Deposits must update the pool's summary if it has one; only pools without one may
+    // leave it out
+    pub fn check_summary(&self, summary_passed: bool) -> Result<()> {
+        require!(
+            summary_passed || !self.has_summary,
+            BettingError::PoolSummaryRequired
+        );
+        Ok(())
+    }
+
+    // Claims must be checked against the pool's SettlementReport if it has one; only pools
+    // resolved without one may leave it out
+    pub fn check_settlement_report_passed(&self, report_passed: bool) -> Result<()> {
+        require!(
+            report_passed || !self.has_settlement_report,
+            BettingError::SettlementReportRequired
+        );
+        Ok(())
+    }
+
+    // Range and scalar markets resolve from a value only set_winner_by_value submits
+    pub fn resolves_by_value(&self) -> bool {
+        !self.range_buckets.is_empty() || self.scalar_bounds.is_set()
+    }
+
+    pub fn is_spl(&self) -> bool {
+        self.token_mint.ne(&Pubkey::default())
+    }
+
+    // Nothing refers to the pool any more: it was resolved or refunded, and every entry, fee,
+    // history and guarantee around it is settled, so close_pool may return its rent
+    pub fn is_fully_settled(&self) -> bool {
+        let fees_settled = !self.complete
+            || ((self.creator_fee_claimed || self.creator_fee_accrued == 0)
+                && self.platform_fee_claimed
+                && self.unclaimed_referee_fee() == 0);
+        (self.complete || self.refund_mode)
+            && self.open_entries == 0
+            && fees_settled
+            && (self.history_disabled || self.history_closed)
+            && !self.guaranteed
+            && self.reclaimable_seed() == 0
+            && self.unsettled_creator_bond() == 0
+            && self.waitlist_drained()
+    }
+
+    pub fn has_committee(&self) -> bool {
+        self.committee_size > 0
+    }
+
+    // Index of `member` on the referee committee, if they sit on it
+    pub fn committee_seat(&self, member: &Pubkey) -> Option<usize> {
+        self.referee_committee[..self.committee_size as usize]
+            .iter()
+            .position(|seat| seat.eq(member))
+    }
+
+    pub fn is_open(&self, now: i64) -> bool {
+        now >= self.opens_at
+    }
+
+    pub fn waitlist_drained(&self) -> bool {
+        self.waitlist_executed == self.waitlist_queued
+    }
+
+    // Deposits outside the waitlist: the pool must be open and every queued deposit ahead of
+    // them placed, so the waitlist converts at the opening odds
+    pub fn require_open_for_deposits(&self, now: i64) -> Result<()> {
+        require!(self.is_open(now), BettingError::PoolNotOpen);
+        require!(self.waitlist_drained(), BettingError::WaitlistPending);
+        Ok(())
+    }
+
+    // Last moment to claim winnings or insurance: claim_window_secs after the final installment
+    // falls due. None before resolution and for pools created without a claim window.
+    pub fn claim_deadline(&self) -> Option<i64> {
+        if !self.complete || self.claim_window_secs <= 0 {
+            return None;
+        }
+        let installments = i64::from(self.installment_count.max(1) - 1);
+        Some(
+            self.resolved_timestamp
+                .saturating_add(self.installment_period.saturating_mul(installments))
+                .saturating_add(self.claim_window_secs),
+        )
+    }
+
+    pub fn claims_closed(&self, now: i64) -> bool {
+        self.claim_deadline().is_some_and(|deadline| now >= deadline)
+    }
+
+    // What sweep_unclaimed takes once claims close: everything the vault still holds for the
+    // pool except the creator's and referee's fees and the seed, which were never winnings
+    pub fn unclaimed_winnings(&self) -> u64 {
+        let creator_fee = if self.creator_fee_claimed {
+            0
+        } else {
+            self.creator_fee_accrued
+        };
+        self.vault_liabilities
+            .saturating_sub(creator_fee)
+            .saturating_sub(self.unclaimed_referee_fee())
+            .saturating_sub(self.reclaimable_seed())
+            .saturating_sub(self.unsettled_creator_bond())
+    }
+
+    pub fn unsettled_creator_bond(&self) -> u64 {
+        if self.creator_bond_settled {
+            0
+        } else {
+            self.creator_bond
+        }
+    }
+
+    // The bond is forfeit when the pool was abandoned: nobody resolved it before its refund or
+    // resolution deadline, it was voided once anyone could (public_void_at) or a deadline had
+    // passed, or the council had to resolve it in the no-show referee's place. A pool decided
+    // on time keeps it, whoever decided it, as do the owner voiding a live pool and refunds for
+    // an invalid or thin market.
+    pub fn creator_bond_slashed(&self) -> bool {
+        match self.resolution_source {
+            ResolutionSource::RefundDeadline => true,
+            ResolutionSource::Voided => {
+                self.public_void_at()
+                    .is_some_and(|void_at| self.voided_at >= void_at)
+                    || self.resolution_window_closed(self.voided_at)
+            }
+            _ => self.referee_no_show,
+        }
+    }
+
+    pub fn unclaimed_referee_fee(&self) -> u64 {
+        if self.referee_fee_claimed {
+            0
+        } else {
+            self.referee_fee_amount
+        }
+    }
+
+    // Seed liquidity the creator may take back through reclaim_seed_liquidity. A refunded pool
+    // returns all of it. A resolved one returns the winning side's seed, which no token claims;
+    // the losing side's seed funds the winners' profit unless nobody backed the winner, in
+    // which case whatever of it the fees left is returned too.
+    pub fn reclaimable_seed(&self) -> u64 {
+        if self.seed_reclaimed {
+            return 0;
+        }
+        if self.refund_mode {
+            return self.seed_yes_liquidity.saturating_add(self.seed_no_liquidity);
+        }
+        if !self.complete {
+            return 0;
+        }
+        let (winning_seed, losing_seed, losing_reserve, winning_supply) = if self.winner.is_yes() {
+            (self.seed_yes_liquidity, self.seed_no_liquidity, self.no_reserve, self.yes_supply)
+        } else {
+            (self.seed_no_liquidity, self.seed_yes_liquidity, self.yes_reserve, self.no_supply)
+        };
+        let unclaimed_losing_seed = if winning_supply == 0 {
+            let left_after_fees = losing_reserve
+                .saturating_sub(self.creator_fee_accrued)
+                .saturating_sub(self.platform_fee_amount);
+            losing_seed.min(left_after_fees)
+        } else {
+            0
+        };
+        winning_seed.saturating_add(unclaimed_losing_seed)
+    }
+
+    // Guard for the native SOL paths (deposit, claim, fee claims, ...)
+    pub fn require_sol_denominated(&self) -> Result<()> {
+        require!(!self.is_spl(), BettingError::SplPoolRequiresTokenPath);
+        Ok(())
+    }
+
+    // Earliest time anyone (not just the owner) may void the unresolved pool; open-ended
+    // pools have no end to count from, so only the owner can void them
+    pub fn public_void_at(&self) -> Option<i64> {
+        (self.end_timestamp >= 0).then(|| self.end_timestamp.saturating_add(VOID_GRACE_SECS))
+    }
+
+    // Trading stops as soon as a provisional outcome is recorded
+    pub fn has_provisional_outcome(&self) -> bool {
+        self.provisional_at != 0
+    }
+
+    // Unrevealed commitments hold resolution back until their reveal window closes, so a
+    // resolved pool never strands a commitment that could still have been revealed
+    pub fn commitments_settled(&self, now: i64) -> bool {
+        self.pending_commitments == 0 || now > self.latest_reveal_deadline
+    }
+
+    // True once a fixed-end market reached its trading end, the same instant trading_closed
+    // stops deposits. Never earlier: a lagging cluster clock only delays the end, while letting
+    // resolution in before it would settle a market that still takes bets. Open-ended markets
+    // (negative end) are never time-gated.
+    pub fn has_ended(&self, now: i64) -> bool {
+        let trading_end = self.trading_end();
+        trading_end < 0 || now >= trading_end
+    }
+
+    // When deposits stop: the trading deadline if the pool has one, its end time otherwise
+    // (negative for open-ended pools, which trade until resolved)
+    pub fn trading_end(&self) -> i64 {
+        if self.trading_end_timestamp != 0 {
+            self.trading_end_timestamp
+        } else {
+            self.end_timestamp
+        }
+    }
+
+    pub fn trading_closed(&self, now: i64) -> bool {
+        self.trading_end() >= 0 && now >= self.trading_end()
+    }
+
+    // Reveals close with trading: a side picked after the last trade would be a free option on
+    // the reserves recorded at commit time
+    pub fn reveal_deadline(&self, now: i64) -> i64 {
+        let deadline = now + COMMIT_REVEAL_WINDOW_SECS;
+        if self.trading_end() >= 0 {
+            deadline.min(self.trading_end())
+        } else {
+            deadline
+        }
+    }
+
+    // Trading stops by the pool's end at the latest, and its resolution deadline comes after
+    // both that and any referee deadline
+    pub fn check_split_deadlines(&self) -> Result<()> {
+        if self.trading_end_timestamp != 0 {
+            require!(
+                self.trading_end_timestamp > 0
+                    && (self.end_timestamp < 0 || self.trading_end_timestamp <= self.end_timestamp),
+                BettingError::InvalidResolutionDeadlines
+            );
+        }
+        if self.resolution_deadline != 0 {
+            let latest = self.trading_end().max(self.end_timestamp).max(self.referee_deadline);
+            require!(
+                self.resolution_deadline > latest.max(0),
+                BettingError::InvalidResolutionDeadlines
+            );
+        }
+        Ok(())
+    }
+
+    // Whether the earlier of refund_deadline and resolution_deadline passed: nobody may
+    // resolve the pool any more and anyone may switch it into refund mode
+    pub fn resolution_window_closed(&self, now: i64) -> bool {
+        [self.refund_deadline, self.resolution_deadline]
+            .into_iter()
+            .any(|deadline| deadline > 0 && now >= deadline)
+    }
+
+    // Largest total the vault could have to pay out to winners across both outcomes.
+    // Winners split everything except fees; the platform fee is left out because it can be
+    // configured away, which keeps this bound conservative.
+    pub fn worst_case_payout(&self) -> u64 {
+        let total_reserve = self.yes_reserve.saturating_add(self.no_reserve);
+        total_reserve.saturating_sub(self.creator_fee_accrued)
+    }
+
+    // Self-refereed markets are fine between friends but not at scale: a pool its creator
+    // referees may not hold more than MainState.self_referee_reserve_cap
+    pub fn check_self_referee_cap(&self, main_state: &MainState) -> Result<()> {
+        let cap = main_state.self_referee_reserve_cap;
+        if cap > 0 && self.creator == self.referee {
+            require!(
+                self.yes_reserve.saturating_add(self.no_reserve) <= cap,
+                BettingError::SelfRefereeCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    // Odds snapshot clients quote with a deposit: sha256(yes || no) over the reserves rounded
+    // down to STATE_HASH_PRECISION lamports, each as u64 little-endian
+    pub fn state_hash(&self) -> [u8; 32] {
+        let yes = self.yes_reserve / STATE_HASH_PRECISION;
+        let no = self.no_reserve / STATE_HASH_PRECISION;
+        hashv(&[&yes.to_le_bytes(), &no.to_le_bytes()]).to_bytes()
+    }
+
+    // Resolution guard implementing the escalation ladder. Pools without a referee deadline
+    // keep the original rule: the referee or the owner may resolve at any time. Returns true
+    // when the resolver is stepping in for a referee who missed their deadline.
+    pub fn check_resolver(
+        &self,
+        resolver: &Pubkey,
+        main_state: &MainState,
+        now: i64,
+    ) -> Result<bool> {
+        require!(!self.refund_mode, BettingError::PoolInRefundMode);
+        if self.referee.eq(resolver) && !self.referee_accepted && !main_state.owner.eq(resolver) {
+            return err!(BettingError::RefereeNotAccepted);
+        }
+        let is_referee = self.referee.eq(resolver) && self.referee_accepted;
+        // A committee pool's referee only decides together with the committee; the owner and
+        // council fallbacks below still apply
+        require!(
+            !(is_referee && self.has_committee()),
+            BettingError::CommitteeResolutionRequired
+        );
+        require!(
+            !self.resolution_window_closed(now),
+            BettingError::ResolutionWindowClosed
+        );
+        if self.referee_deadline == 0 {
+            require!(
+                is_referee || main_state.owner.eq(resolver),
+                BettingError::Unauthorized
+            );
+            return Ok(false);
+        }
+
+        if is_referee {
+            return Ok(false);
+        }
+        require!(main_state.is_ops_admin(resolver), BettingError::Unauthorized);
+        require!(now >= self.referee_deadline, BettingError::RefereeWindowActive);
+        Ok(true)
+    }
+}
+
+// One point of a pool's probability chart: its time as an offset from the history's start
+// and YES's share of the reserves, which is all a chart plots. Packed into 8 bytes so the ring
+// holds three times the points the Vec-backed history did (25 bytes each) in the same space.
+#[zero_copy]
+#[derive(Debug, Default)]
+pub struct ProbabilityPoint {
+    pub offset_secs: u32,   // seconds since PoolHistoryState::started_at
+    pub yes_share_bps: u16, // yes_reserve / (yes_reserve + no_reserve); 5_000 while both are 0
+    pub kind: u8,           // ProbabilityPoint::KIND_*, so charts can draw event markers
+    pub padding: u8,
+}
+
+impl ProbabilityPoint {
+    pub const SIZE: usize = 4 + 2 + 1 + 1;
+
+    pub const KIND_SNAPSHOT: u8 = 0; // creation or a regular deposit
+    pub const KIND_LARGE_DEPOSIT: u8 = 1; // deposit of at least WHALE_DEPOSIT_BPS of the pool
+    pub const KIND_LOCK: u8 = 2; // trading locked at event start
+    pub const KIND_RESOLUTION: u8 = 3;
+    pub const KIND_CANCELLATION: u8 = 4; // pool switched to refund mode
+
+    pub fn yes_share_bps(yes_reserve: u64, no_reserve: u64) -> u16 {
+        let total = yes_reserve as u128 + no_reserve as u128;
+        if total == 0 {
+            return 5_000;
+        }
+        (yes_reserve as u128 * 10_000 / total) as u16
+    }
+}
+
+// Probability history for a pool (market): a ring buffer of its latest MAX_POINTS points.
+// Zero-copy, so recording a point writes one slot in place rather than deserializing the
+// series and shifting it; once full, each new point overwrites the oldest.
+#[account(zero_copy)]
+pub struct PoolHistoryState {
+    pub pool: Pubkey,    // reference to the pool
+    pub bet_id: u64,     // convenience: same as pool.bet_id
+    pub started_at: i64, // time points are offset from (the pool's creation)
+    pub head: u16,       // slot the next point is written to
+    pub len: u16,        // points recorded, at most MAX_POINTS
+    pub padding: [u8; 4],
+    pub points: [ProbabilityPoint; PoolHistoryState::MAX_POINTS],
+}
+
+impl PoolHistoryState {
+    // 120 points * 8 = 960 bytes plus a 56-byte header: 1016 bytes, within the 1044 the
+    // Vec-backed history of 40 points took
+    pub const MAX_POINTS: usize = 120;
+    pub const PREFIX_SEED: &'static [u8] = b"history";
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+
+    // Data size (after the discriminator) of history accounts created before the ring buffer:
+    // pool, bet_id and a Borsh Vec of up to 40 points of 25 bytes (see load_history)
+    pub const LEGACY_SIZE: usize = 32 + 8 + 4 + 40 * 25;
+
+    // Starts an empty chart for the pool at `now`
+    pub fn reset(&mut self, pool: Pubkey, bet_id: u64, now: i64) {
+        self.pool = pool;
+        self.bet_id = bet_id;
+        self.started_at = now;
+        self.head = 0;
+        self.len = 0;
+    }
+
+    // Records a point in the next slot, returning the oldest one when it had to be overwritten
+    pub fn push(
+        &mut self,
+        timestamp: i64,
+        yes_reserve: u64,
+        no_reserve: u64,
+        kind: u8,
+    ) -> Option<ProbabilityPoint> {
+        let point = ProbabilityPoint {
+            offset_secs: timestamp
+                .saturating_sub(self.started_at)
+                .clamp(0, u32::MAX as i64) as u32,
+            yes_share_bps: ProbabilityPoint::yes_share_bps(yes_reserve, no_reserve),
+            kind,
+            padding: 0,
+        };
+        let slot = self.head as usize % Self::MAX_POINTS;
+        let overwritten = (self.len as usize == Self::MAX_POINTS).then_some(self.points[slot]);
+        self.points[slot] = point;
+        self.head = ((slot + 1) % Self::MAX_POINTS) as u16;
+        if overwritten.is_none() {
+            self.len += 1;
+        }
+        overwritten
+    }
+
+    pub fn timestamp_of(&self, point: &ProbabilityPoint) -> i64 {
+        self.started_at.saturating_add(point.offset_secs as i64)
+    }
+
+    // Recorded points, oldest first
+    pub fn chronological(&self) -> impl Iterator<Item = &ProbabilityPoint> {
+        let len = self.len as usize;
+        let oldest = (self.head as usize + Self::MAX_POINTS - len) % Self::MAX_POINTS;
+        (0..len).map(move |i| &self.points[(oldest + i) % Self::MAX_POINTS])
+    }
+}
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct EntryState {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub deposited_sol_amount: u64,
+    pub token_balance: u64,
+    pub is_yes: bool,
+    pub is_claimed: bool,
+    pub installments_claimed: u8, // installment pools only; is_claimed flips once all are paid
+
+    // Ring buffer of the last client nonces used for deposits (0 = empty slot), so wallet
+    // retries of the same deposit are rejected instead of charging the user twice
+    pub recent_nonces: [u64; EntryState::NONCE_HISTORY],
+    pub nonce_cursor: u8,
+
+    pub creator_fee_exempt: bool, // creator's own entry in a pool with waive_creator_self_fee
+    pub creator_fee_paid: u64,    // creator fee accrued from this entry's deposits
+
+    pub insured_amount: u64,         // principal covered by insurance
+    pub insurance_premium_paid: u64, // refunded with the deposit if the pool is refunded
+    pub insurance_claimed: bool,
+
+    // Deposits are folded into the running totals above rather than stored one by one, so
+    // any number of them compacts to the same size with the weighted average price
+    // (deposited_sol_amount / token_balance) preserved; only the count is kept
+    pub deposit_count: u32,
+
+    pub claimed_at: i64, // time of the latest payout or refund (0 = never)
+
+    // Who brought the user in, fixed by their first deposit (Pubkey::default() = nobody), and
+    // whether the referrer was credited their slice of the platform fee (see credit_referral)
+    pub referrer: Pubkey,
+    pub referral_credited: bool,
+
+    // Social recovery: a backup key the user registered (Pubkey::default() = none), which may
+    // take over the entry's claims once a recovery it started outlasts the challenge window
+    pub recovery_key: Pubkey,
+    pub recovery_initiated_at: i64, // 0 = no recovery pending
+
+    pub reserved: Reserved<{ EntryState::RESERVED }>, // headroom, see layout.rs
+}
+
+impl EntryState {
+    pub const NONCE_HISTORY: usize = 8;
+    pub const RESERVED: usize = 61;
+    pub const MAX_SIZE: usize = Self::INIT_SPACE;
+    pub const PREFIX_SEED: &'static [u8] = b"entry";
+
+    // Nothing is left to pay out on this entry: its refund, winnings or insurance were taken,
+    // or it never had any (an empty or uninsured losing entry)
+    pub fn is_settled(&self, pool_state: &PoolState) -> bool {
+        if pool_state.refund_mode {
+            return self.is_claimed || self.deposited_sol_amount == 0;
+        }
+        if !pool_state.complete {
+            return false;
+        }
+        // Whatever the entry left unclaimed went to the treasury
+        if pool_state.unclaimed_swept {
+            return true;
+        }
+        // Both sides of a scalar market hold a share of the pot
+        if self.is_yes == pool_state.winner.is_yes() || pool_state.scalar_bounds.is_set() {
+            self.is_claimed || self.token_balance == 0
+        } else {
+            self.insured_amount == 0 || self.insurance_claimed
+        }
+    }
+
+    pub fn record_client_nonce(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce != 0, BettingError::InvalidClientNonce);
+        require!(
+            !self.recent_nonces.contains(&nonce),
+            BettingError::DuplicateDeposit
+        );
+        let slot = self.nonce_cursor as usize % Self::NONCE_HISTORY;
+        self.recent_nonces[slot] = nonce;
+        self.nonce_cursor = ((slot + 1) % Self::NONCE_HISTORY) as u8;
+        Ok(())
+    }
+
+    // When a pending recovery may start paying out to the recovery key
+    pub fn recovery_unlocks_at(&self) -> i64 {
+        self.recovery_initiated_at.saturating_add(RECOVERY_CHALLENGE_SECS)
+    }
+
+    // The signer may claim in the user's place: it is the registered recovery key and the
+    // recovery it started has run the whole challenge window without the user cancelling it
+    pub fn check_recovery(&self, recovery_key: &Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.recovery_key != Pubkey::default() && self.recovery_key == *recovery_key,
+            BettingError::NotRecoveryKey
+        );
+        require!(self.recovery_initiated_at != 0, BettingError::NoRecoveryPending);
+        require!(now >= self.recovery_unlocks_at(), BettingError::RecoveryChallengeActive);
+        Ok(())
+    }
+}
+
+// Sealed deposit for commit-reveal pools. The side is hidden behind a hash until reveal, while the
+// reserves at commit time are recorded so the bet is priced at the odds the user saw.
+#[account]
+pub struct CommitmentState {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub commitment_hash: [u8; 32], // hash(is_yes as u8 || salt)
+    pub amount: u64,               // lamports locked in the vault at commit time
+    pub yes_reserve: u64,          // pool reserves recorded at commit time
+    pub no_reserve: u64,
+    pub committed_timestamp: i64,
+    pub reveal_deadline: i64,
+}
+
+impl CommitmentState {
+    pub const MAX_SIZE: usize = 32 + 8 + 32 + 8 + 8 + 8 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"commitment";
+
+    // A commitment is refunded once it can no longer be revealed: either the reveal window
+    // has passed or the pool was resolved (provisionally, or refunded) in the meantime
+    pub fn is_refundable(&self, pool_state: &PoolState, now: i64) -> bool {
+        pool_state.complete
+            || pool_state.has_provisional_outcome()
+            || pool_state.refund_mode
+            || now > self.reveal_deadline
+    }
+
+    // What a refund keeps back: UNREVEALED_FORFEIT_BPS of the amount when the user let the
+    // reveal window lapse, so sitting on a sealed side and walking away isn't a free option.
+    // Nothing when the pool itself cut the window short by being refunded or decided first.
+    pub fn forfeit(&self, pool_state: &PoolState) -> u64 {
+        let cut_short = pool_state.refund_mode
+            || (pool_state.has_provisional_outcome()
+                && pool_state.provisional_at <= self.reveal_deadline)
+            || (pool_state.complete && pool_state.resolved_timestamp <= self.reveal_deadline);
+        if cut_short {
+            0
+        } else {
+            bps_of(self.amount as u128, UNREVEALED_FORFEIT_BPS) as u64
+        }
+    }
+}
+
+// A deposit queued before its pool opens. The lamports sit in this account on top of its rent
+// until execute_pending_deposit places them (or hands them back) at its turn in the queue.
+#[account]
+#[derive(Default)]
+pub struct PendingDeposit {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub position: u64, // place in the pool's waitlist, from 0
+    pub is_yes: bool,
+    pub amount: u64, // lamports escrowed here
+    pub queued_at: i64,
+}
+
+impl PendingDeposit {
+    pub const MAX_SIZE: usize = 32 + 8 + 8 + 1 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"pending_deposit";
+}
+
+// Votes of a pool's referee committee (see PoolState::referee_committee), one per member.
+// propose_winner resolves the pool with the vote that brings either side to the threshold.
+#[account]
+#[derive(Default)]
+pub struct ResolutionState {
+    pub bet_id: u64,
+    pub yes_votes: u8,
+    pub no_votes: u8,
+    pub voted: u8, // bit i set once committee seat i has voted
+    pub last_vote_at: i64,
+}
+
+impl ResolutionState {
+    pub const MAX_SIZE: usize = 8 + 1 + 1 + 1 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"resolution_state";
+
+    // Records `seat`'s vote; returns the votes now backing `is_yes`
+    pub fn record_vote(&mut self, seat: usize, is_yes: bool, now: i64) -> Result<u8> {
+        let bit = 1u8 << seat;
+        require!(self.voted & bit == 0, BettingError::AlreadyVoted);
+        self.voted |= bit;
+        self.last_vote_at = now;
+        let votes = if is_yes {
+            &mut self.yes_votes
+        } else {
+            &mut self.no_votes
+        };
+        *votes += 1;
+        Ok(*votes)
+    }
+}
+
+// Resolved outcome published for other programs. The layout is append-only: fields are never
+// reordered or removed, and `version` is bumped whenever fields are appended.
+#[cfg(feature = "oracle")]
+#[account]
+#[derive(Default)]
+pub struct OutcomeOracle {
+    pub version: u8,
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub winner: u8, // OutcomeOracle::WINNER_YES, WINNER_NO or WINNER_INVALID
+    pub resolved_at: i64,
+    pub yes_reserve: u64,     // final reserves at resolution
+    pub no_reserve: u64,
+    pub yes_probability: u64, // final implied probabilities, scaled by 1e9
+    pub no_probability: u64,
+}
+
+#[cfg(feature = "oracle")]
+impl OutcomeOracle {
+    pub const VERSION: u8 = 1;
+    pub const WINNER_YES: u8 = 1;
+    pub const WINNER_NO: u8 = 2;
+    pub const WINNER_INVALID: u8 = 3; // the referee declared the question malformed
+    pub const MAX_SIZE: usize = 1 + 32 + 8 + 1 + 8 + 8 + 8 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"outcome";
+}
+
+// Immutable settlement artifact written once at resolution and never touched again, so
+// auditors can check every claim against it even if PoolState is later archived or shrunk.
+// Append-only like OutcomeOracle.
+#[account]
+#[derive(Default)]
+pub struct SettlementReport {
+    pub version: u8,
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub winner_is_yes: bool,
+    pub resolved_at: i64,
+    pub resolved_by: Pubkey,
+    pub yes_reserve: u64, // final reserves and supplies
+    pub no_reserve: u64,
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    pub creator_fee: u64, // owed to the creator, after the donation
+    pub creator_fee_donated: u64,
+    pub platform_fee: u64,
+    pub insurance_surplus: u64,
+    pub winner_profit_pot: u64,
+    pub payout_per_token: u64, // profit per winning token, scaled by PAYOUT_PER_TOKEN_SCALE
+    pub yes_entries: u32,
+    pub no_entries: u32,
+}
+
+impl SettlementReport {
+    pub const VERSION: u8 = 1;
+    pub const MAX_SIZE: usize = 1 + 32 + 8 + 1 + 8 + 32 + 8 * 4 + 8 * 6 + 4 + 4;
+    pub const PREFIX_SEED: &'static [u8] = b"settlement";
+}
+
+// Users who asked to be notified about a pool; every PoolNotificationEvent names this account
+// so off-chain notifiers can deliver alerts deterministically
+#[account]
+#[derive(Default)]
+pub struct PoolSubscribers {
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub subscribers: Vec<Pubkey>, // max MAX_SUBSCRIBERS
+    pub lock_notified: bool,
+}
+
+impl PoolSubscribers {
+    pub const MAX_SUBSCRIBERS: usize = 32;
+    pub const MAX_SIZE: usize = 32 + 8 + (4 + 32 * Self::MAX_SUBSCRIBERS) + 1;
+    pub const PREFIX_SEED: &'static [u8] = b"subscribers";
+
+    // PoolNotificationEvent kinds
+    pub const NOTIFY_LOCK: u8 = 1;
+    pub const NOTIFY_RESOLUTION: u8 = 2;
+}
+
+// Pre-funded balance a user bets from with deposit_from_balance instead of transferring on
+// every deposit. `balance` excludes the account's rent, which is only returned on close.
+#[account]
+#[derive(Default)]
+pub struct BetBalance {
+    pub user: Pubkey,
+    pub balance: u64,
+    pub total_funded: u64,
+    pub total_bet: u64,
+}
+
+impl BetBalance {
+    pub const MAX_SIZE: usize = 32 + 8 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"bet-balance";
+}
+
+// Lamports the owner escrows behind a guaranteed pool (held by this account on top of its
+// rent). `balance` is what claims can still draw; the owner takes it back with
+// release_guarantee once the claim window has closed or the pool was refunded.
+#[account]
+#[derive(Default)]
+pub struct PoolGuarantee {
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub balance: u64,
+    pub total_funded: u64,
+    pub total_drawn: u64,
+}
+
+impl PoolGuarantee {
+    pub const MAX_SIZE: usize = 32 + 8 + 8 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"guarantee";
+}
+
+// Risk indicators for frontends, updated on every deposit so thin or whale-dominated markets
+// can be flagged without indexing. create_pool opens one with every pool, paid by the creator,
+// and records it in has_summary; pools opened any other way, or before it did, have none and
+// their deposits skip it.
+#[account]
+#[derive(Default)]
+pub struct PoolSummary {
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub total_deposited: u64,
+    pub depositor_count: u32,
+    pub sum_sq_entry_deposits: u128, // sum of each entry's deposits squared
+    pub herfindahl_bps: u64,         // concentration: 10_000 = a single depositor
+    pub top_depositor: Pubkey,
+    pub top_entry_deposit: u64,
+    pub top_depositor_bps: u64, // share of the pool held by the top depositor
+    pub largest_deposit: u64,
+    pub risk_flags: u8, // PoolSummary::FLAG_*
+
+    // Rolling 24h volume for trending/ranking: one bucket per VOLUME_BUCKET_SECS slot, indexed
+    // by slot % VOLUME_BUCKETS. `volume_24h` is the window total as of `volume_slot` (the slot
+    // of the latest deposit); readers at a later time should use volume_24h_at.
+    pub volume_buckets: [u64; VOLUME_BUCKETS],
+    pub volume_slot: i64,
+    pub volume_24h: u64,
+}
+
+impl PoolSummary {
+    // Both flags are recomputed on every deposit, so early deposits in a growing pool stop
+    // counting once they are diluted
+    pub const FLAG_WHALE_DEPOSIT: u8 = 1; // largest single deposit >= WHALE_DEPOSIT_BPS
+    pub const FLAG_DOMINANT_HOLDER: u8 = 2; // top depositor >= DOMINANT_HOLDER_BPS
+    pub const MAX_SIZE: usize = 32 + 8 + 8 + 4 + 16 + 8 + 32 + 8 + 8 + 8 + 1
+        + 8 * VOLUME_BUCKETS + 8 + 8; // volume_buckets, volume_slot, volume_24h
+    pub const PREFIX_SEED: &'static [u8] = b"summary";
+
+    // Adds `amount` to the bucket of `now`, clearing buckets that fell out of the window
+    pub fn record_volume(&mut self, amount: u64, now: i64) {
+        let slot = now.div_euclid(VOLUME_BUCKET_SECS);
+        let elapsed = slot.saturating_sub(self.volume_slot);
+        if elapsed >= VOLUME_BUCKETS as i64 || self.volume_slot == 0 {
+            self.volume_buckets = [0; VOLUME_BUCKETS];
+        } else {
+            for stale in 1..=elapsed {
+                self.volume_buckets[Self::bucket(self.volume_slot + stale)] = 0;
+            }
+        }
+        self.volume_slot = self.volume_slot.max(slot);
+        // A clock that stepped back still lands in a bucket inside the window
+        let bucket = Self::bucket(slot.max(self.volume_slot - (VOLUME_BUCKETS as i64 - 1)));
+        self.volume_buckets[bucket] = self.volume_buckets[bucket].saturating_add(amount);
+        self.volume_24h = self.volume_24h_at(now);
+    }
+
+    // Volume of the VOLUME_BUCKETS slots ending at `now`
+    pub fn volume_24h_at(&self, now: i64) -> u64 {
+        let slot = now.div_euclid(VOLUME_BUCKET_SECS);
+        let window = VOLUME_BUCKETS as i64 - 1;
+        // Only slots inside both the window and the buckets' own span still hold their volume
+        let oldest = (slot - window).max(self.volume_slot - window);
+        (oldest..=slot.min(self.volume_slot))
+            .map(|s| self.volume_buckets[Self::bucket(s)])
+            .fold(0u64, |total, v| total.saturating_add(v))
+    }
+
+    fn bucket(slot: i64) -> usize {
+        slot.rem_euclid(VOLUME_BUCKETS as i64) as usize
+    }
+
+    // `entry_before` is the entry's deposited total before this deposit of `amount`
+    pub fn record_deposit(&mut self, user: Pubkey, entry_before: u64, amount: u64) {
+        let entry_after = entry_before + amount;
+        if entry_before == 0 {
+            self.depositor_count += 1;
+        }
+        self.total_deposited += amount;
+        self.sum_sq_entry_deposits = self.sum_sq_entry_deposits
+            - (entry_before as u128) * (entry_before as u128)
+            + (entry_after as u128) * (entry_after as u128);
+        if entry_after > self.top_entry_deposit {
+            self.top_entry_deposit = entry_after;
+            self.top_depositor = user;
+        }
+        self.largest_deposit = self.largest_deposit.max(amount);
+
+        let total = self.total_deposited as u128;
+        self.herfindahl_bps = (self.sum_sq_entry_deposits * 10_000 / (total * total)) as u64;
+        self.top_depositor_bps = (self.top_entry_deposit as u128 * 10_000 / total) as u64;
+
+        self.risk_flags = 0;
+        if (self.largest_deposit as u128) * 10_000 >= total * WHALE_DEPOSIT_BPS as u128 {
+            self.risk_flags |= Self::FLAG_WHALE_DEPOSIT;
+        }
+        if self.top_depositor_bps >= DOMINANT_HOLDER_BPS {
+            self.risk_flags |= Self::FLAG_DOMINANT_HOLDER;
+        }
+    }
+}
+
+// Live odds feed for external consumers, refreshed by the permissionless publish_odds_feed.
+// Values follow the common oracle convention `value = price * 10^expo`, with a publish time
+// and slot so consumers can apply their own staleness checks. Append-only like OutcomeOracle.
+#[cfg(feature = "oracle")]
+#[account]
+#[derive(Default)]
+pub struct OddsFeed {
+    pub version: u8,
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub status: u8, // OddsFeed::STATUS_*
+    pub expo: i32,
+    pub yes_price: i64, // implied probabilities
+    pub no_price: i64,
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+    pub publish_time: i64,
+    pub publish_slot: u64,
+}
+
+#[cfg(feature = "oracle")]
+impl OddsFeed {
+    pub const VERSION: u8 = 1;
+    pub const EXPO: i32 = -9; // prices are scaled by 1e9, like OutcomeOracle probabilities
+    pub const STATUS_TRADING: u8 = 1;
+    pub const STATUS_RESOLVED: u8 = 2;
+    pub const STATUS_REFUNDING: u8 = 3;
+    pub const MAX_SIZE: usize = 1 + 32 + 8 + 1 + 4 + 8 + 8 + 8 + 8 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"odds-feed";
+}
+
+// Language-tagged title/description pair for international frontends
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LocalizedText {
+    pub language: String,    // BCP-47 tag, e.g. "es" or "pt-BR" (max 8 bytes)
+    pub title: String,       // max 100 bytes, same limit as PoolState.title
+    pub description: String, // max 500 bytes, same limit as PoolState.description
+}
+
+impl LocalizedText {
+    pub const MAX_LANGUAGE_LEN: usize = 8;
+    pub const MAX_SIZE: usize = (4 + Self::MAX_LANGUAGE_LEN) + (4 + 100) + (4 + 500);
+}
+
+// Optional side account holding official translations of a pool's title/description
+#[account]
+pub struct PoolMetadata {
+    pub pool: Pubkey,
+    pub bet_id: u64,
+    pub translations: Vec<LocalizedText>,
+}
+
+impl PoolMetadata {
+    pub const MAX_TRANSLATIONS: usize = 3;
+    pub const MAX_SIZE: usize = 32 + 8 + 4 + Self::MAX_TRANSLATIONS * LocalizedText::MAX_SIZE;
+    pub const PREFIX_SEED: &'static [u8] = b"metadata";
+}
+
+// Successor market that opens once the parent pool resolves to `on_yes` (e.g. bracket
+// progression). The child inherits the parent's referee and market settings.
+#[account]
+pub struct PoolLink {
+    pub parent_bet_id: u64,
+    pub on_yes: bool,    // outcome of the parent that opens the child
+    pub creator: Pubkey, // parent creator; becomes the child's creator
+    pub title: String,       // max 100 bytes
+    pub description: String, // max 500 bytes
+    // Child end = parent resolution time + duration; negative = open-ended child
+    pub duration_secs: i64,
+    pub opened: bool,
+    pub child_bet_id: u64, // set once opened
+}
+
+impl PoolLink {
+    pub const MAX_SIZE: usize = 8 + 1 + 32 + (4 + 100) + (4 + 500) + 8 + 1 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"link";
+}
+
+// Marks a title as taken among a creator's active pools. Created by create_pool, moved by
+// update_pool when the title changes and closed (rent back to the creator) at resolution.
+#[account]
+pub struct TitleIndex {
+    pub creator: Pubkey,
+    pub pool: Pubkey,
+    pub bet_id: u64,
+}
+
+impl TitleIndex {
+    pub const MAX_SIZE: usize = 32 + 32 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"title-index";
+
+    // Titles can exceed the 32-byte seed limit, so the index is keyed by their hash
+    pub fn title_hash(title: &str) -> [u8; 32] {
+        hash(title.as_bytes()).to_bytes()
+    }
+}
+
+// A creator's pool creations over the last CREATION_BUCKETS slots (24h), bucketed like
+// PoolSummary's rolling volume: one count per CREATION_BUCKET_SECS slot, indexed by
+// slot % CREATION_BUCKETS. Created by the creator's first pool and kept for good.
+#[account]
+#[derive(Default)]
+pub struct CreatorRateLimit {
+    pub creator: Pubkey,
+    pub buckets: [u32; CREATION_BUCKETS],
+    pub latest_slot: i64, // slot of the latest creation
+}
+
+impl CreatorRateLimit {
+    pub const MAX_SIZE: usize = 32 + 4 * CREATION_BUCKETS + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"creator-rate-limit";
+
+    // Counts a new pool at `now`, unless `max_per_day` pools were already created in the
+    // window (0 = unlimited)
+    pub fn record_creation(&mut self, max_per_day: u64, now: i64) -> Result<()> {
+        if max_per_day > 0 {
+            require!(
+                self.created_in_window(now) < max_per_day,
+                BettingError::CreationRateLimited
+            );
+        }
+        let slot = now.div_euclid(CREATION_BUCKET_SECS);
+        if slot > self.latest_slot {
+            let elapsed = (slot - self.latest_slot).min(CREATION_BUCKETS as i64);
+            for stale in (slot - elapsed + 1)..=slot {
+                self.buckets[Self::bucket(stale)] = 0;
+            }
+            self.latest_slot = slot;
+        }
+        // A clock that stepped back still lands in a bucket inside the window
+        let bucket = Self::bucket(slot.max(self.latest_slot - (CREATION_BUCKETS as i64 - 1)));
+        self.buckets[bucket] = self.buckets[bucket].saturating_add(1);
+        Ok(())
+    }
+
+    // Pools created in the CREATION_BUCKETS slots ending at `now`
+    pub fn created_in_window(&self, now: i64) -> u64 {
+        let slot = now.div_euclid(CREATION_BUCKET_SECS);
+        let window = CREATION_BUCKETS as i64 - 1;
+        let oldest = (slot - window).max(self.latest_slot - window);
+        (oldest..=slot.min(self.latest_slot))
+            .map(|s| self.buckets[Self::bucket(s)] as u64)
+            .sum()
+    }
+
+    fn bucket(slot: i64) -> usize {
+        slot.rem_euclid(CREATION_BUCKETS as i64) as usize
+    }
+}
+
+// A bonded dispute of a provisional outcome (see dispute_provisional_winner). The bond stays
+// escrowed here until the owner rules through finalize_resolution: it goes back to the
+// disputer if the owner overturns the outcome and to the platform if they confirm it. The
+// account closes to the disputer either way.
+#[account]
+#[derive(Default)]
+pub struct ResolutionDispute {
+    pub bet_id: u64,
+    pub disputer: Pubkey,
+    pub is_yes: bool, // the disputed provisional outcome
+    pub bond: u64,
+    pub disputed_at: i64,
+}
+
+impl ResolutionDispute {
+    pub const MAX_SIZE: usize = 8 + 32 + 1 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"resolution-dispute";
+}
+
+// Optimistic-oracle resolution: an outcome asserted with a bond becomes final once it survives
+// ASSERTION_LIVENESS_SECS unchallenged. A dispute posts a matching bond and escalates the pool
+// to the regular referee/council resolution; whoever turns out right takes both bonds. Bonds
+// are escrowed in this account, which is closed to the asserter on settlement.
+#[cfg(feature = "oracle")]
+#[account]
+#[derive(Default)]
+pub struct Assertion {
+    pub bet_id: u64,
+    pub asserter: Pubkey,
+    pub is_yes: bool,
+    pub bond: u64,
+    pub asserted_at: i64,
+    pub expires_at: i64,
+    pub disputer: Pubkey, // Pubkey::default() while undisputed
+    pub disputed_at: i64,
+}
+
+#[cfg(feature = "oracle")]
+impl Assertion {
+    pub const MAX_SIZE: usize = 8 + 32 + 1 + 8 + 8 + 8 + 32 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"assertion";
+
+    pub fn is_disputed(&self) -> bool {
+        self.disputer.ne(&Pubkey::default())
+    }
+}
+
+// A bucket of a range market: values in [lower, upper) resolve the pool to `is_yes`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct RangeBucket {
+    pub lower: i64,
+    pub upper: i64, // exclusive
+    pub is_yes: bool,
+}
+
+impl RangeBucket {
+    pub fn contains(&self, value: i64) -> bool {
+        self.lower <= value && value < self.upper
+    }
+
+    // Buckets must be non-empty, sorted and non-overlapping, and map to both outcomes (a
+    // range market with one outcome is decided before it opens). Gaps are allowed; a value
+    // falling in one cannot resolve the pool.
+    pub fn validate_all(buckets: &[RangeBucket]) -> Result<()> {
+        require!(
+            (2..=MAX_RANGE_BUCKETS).contains(&buckets.len())
+                && buckets.iter().all(|bucket| bucket.lower < bucket.upper)
+                && buckets.windows(2).all(|pair| pair[0].upper <= pair[1].lower)
+                && buckets.iter().any(|bucket| bucket.is_yes)
+                && buckets.iter().any(|bucket| !bucket.is_yes),
+            BettingError::InvalidRangeBuckets
+        );
+        Ok(())
+    }
+}
+
+// Reserves and implied prices (scaled by PRICE_SCALE) of a pool when trading stopped
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FinalOdds {
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+    pub yes_price: u64,
+    pub no_price: u64,
+    pub recorded_at: i64, // 0 = not recorded yet
+}
+
+impl FinalOdds {
+    pub fn is_recorded(&self) -> bool {
+        self.recorded_at != 0
+    }
+}
+
+// Bounds of a scalar market. A value at or below `min` hands the whole pot to the short (NO)
+// side, one at or above `max` to the long (YES) side, and values in between split it
+// linearly. Unset while min >= max.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct ScalarBounds {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl ScalarBounds {
+    pub fn is_set(&self) -> bool {
+        self.min < self.max
+    }
+
+    // The long side's share of the pot for `value`, in bps
+    pub fn long_bps(&self, value: i64) -> u64 {
+        let value = value.clamp(self.min, self.max) as i128;
+        let span = self.max as i128 - self.min as i128;
+        ((value - self.min as i128) * 10_000 / span) as u64
+    }
+}
+
+// Price-feed resolution: the pool resolves YES when the feed's aggregate price compares to
+// `threshold` as `comparison` says, NO otherwise. `threshold` is in the feed's fixed-point
+// units, so `expo` must match the feed's exponent.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct PriceCondition {
+    pub feed: Pubkey,
+    pub comparison: u8, // PriceCondition::ABOVE, ...
+    pub threshold: i64,
+    pub expo: i32,
+}
+
+impl PriceCondition {
+    pub const ABOVE: u8 = 0;
+    pub const AT_OR_ABOVE: u8 = 1;
+    pub const BELOW: u8 = 2;
+    pub const AT_OR_BELOW: u8 = 3;
+
+    pub fn is_set(&self) -> bool {
+        self.feed.ne(&Pubkey::default())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.is_set() && self.comparison <= Self::AT_OR_BELOW,
+            BettingError::InvalidPriceCondition
+        );
+        Ok(())
+    }
+
+    pub fn is_met(&self, price: i64) -> Result<bool> {
+        match self.comparison {
+            Self::ABOVE => Ok(price > self.threshold),
+            Self::AT_OR_ABOVE => Ok(price >= self.threshold),
+            Self::BELOW => Ok(price < self.threshold),
+            Self::AT_OR_BELOW => Ok(price <= self.threshold),
+            _ => err!(BettingError::InvalidPriceCondition),
+        }
+    }
+}
+
+// Meta-market resolution: the pool bets on another pool's own state. It resolves YES when the
+// target's `metric` compares to `threshold` as `comparison` says (PriceCondition's constants),
+// read once the target stopped trading for good (resolved or refunded), when the reserves and
+// participant counts it reports can no longer move.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct MetaCondition {
+    pub target_bet_id: u64,
+    pub metric: u8, // MetaCondition::TOTAL_RESERVE, ...
+    pub comparison: u8,
+    pub threshold: u64,
+    pub enabled: bool, // bet id 0 is a real pool, so the target id can't double as the flag
+}
+
+impl MetaCondition {
+    pub const TOTAL_RESERVE: u8 = 0;
+    pub const YES_RESERVE: u8 = 1;
+    pub const NO_RESERVE: u8 = 2;
+    pub const PARTICIPANTS: u8 = 3; // entries holding a position on either side
+
+    pub fn is_set(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.enabled
+                && self.metric <= Self::PARTICIPANTS
+                && self.comparison <= PriceCondition::AT_OR_BELOW,
+            BettingError::InvalidMetaCondition
+        );
+        Ok(())
+    }
+
+    pub fn value_of(&self, target: &PoolState) -> Result<u64> {
+        match self.metric {
+            Self::TOTAL_RESERVE => Ok(target.yes_reserve.saturating_add(target.no_reserve)),
+            Self::YES_RESERVE => Ok(target.yes_reserve),
+            Self::NO_RESERVE => Ok(target.no_reserve),
+            Self::PARTICIPANTS => Ok(target.yes_entries.saturating_add(target.no_entries) as u64),
+            _ => err!(BettingError::InvalidMetaCondition),
+        }
+    }
+
+    pub fn is_met(&self, value: u64) -> Result<bool> {
+        match self.comparison {
+            PriceCondition::ABOVE => Ok(value > self.threshold),
+            PriceCondition::AT_OR_ABOVE => Ok(value >= self.threshold),
+            PriceCondition::BELOW => Ok(value < self.threshold),
+            PriceCondition::AT_OR_BELOW => Ok(value <= self.threshold),
+            _ => err!(BettingError::InvalidMetaCondition),
+        }
+    }
+}
+
+// Multi-outcome (N-way) market: 2..=MAX_OUTCOMES named outcomes indexed by u8, each with its
+// own reserve and token supply. Priced, fee'd and settled like a PoolState (principal plus a
+// token-weighted share of the losing reserves after fees), sharing its bet ids and SOL vault.
+#[account]
+#[derive(Default)]
+pub struct OutcomeMarket {
+    pub bet_id: u64,
+    pub creator: Pubkey,
+    pub referee: Pubkey,
+    pub title: String, // max 100 bytes
+    pub end_timestamp: i64, // negative = open-ended
+    pub created_timestamp: i64,
+
+    pub outcomes: Vec<String>, // outcome names, max MAX_OUTCOME_NAME_LEN bytes each
+    pub reserves: Vec<u64>,    // per outcome, same order as `outcomes`
+    pub supplies: Vec<u64>,
+
+    pub creator_fee_bps: u64,
+    pub creator_fee_accrued: u64, // fixed (clamped to the losing reserves) at resolution
+    pub platform_fee_amount: u64,
+
+    pub complete: bool,
+    pub winner: u8, // index into `outcomes`, meaningful once complete
+    pub resolved_timestamp: i64,
+
+    // Creator bond escrowed in the shared vault at creation, settled by set_outcome_winner
+    pub creator_bond: u64,
+}
+
+impl OutcomeMarket {
+    pub const MAX_SIZE: usize = 8 + 32 + 32 // bet_id, creator, referee
+        + (4 + 100) + 8 + 8 // title, end_timestamp, created_timestamp
+        + (4 + MAX_OUTCOMES * (4 + MAX_OUTCOME_NAME_LEN)) // outcomes
+        + (4 + MAX_OUTCOMES * 8) + (4 + MAX_OUTCOMES * 8) // reserves, supplies
+        + 8 + 8 + 8 // creator_fee_bps, creator_fee_accrued, platform_fee_amount
+        + 1 + 1 + 8 // complete, winner, resolved_timestamp
+        + 8; // creator_bond
+    pub const PREFIX_SEED: &'static [u8] = b"outcome-market";
+
+    pub fn total_reserve(&self) -> u64 {
+        self.reserves.iter().fold(0u64, |total, reserve| total.saturating_add(*reserve))
+    }
+
+    // Same rule as PoolState::has_ended
+    pub fn has_ended(&self, now: i64) -> bool {
+        self.end_timestamp < 0 || now >= self.end_timestamp
+    }
+
+    pub fn check_outcome(&self, outcome: u8) -> Result<usize> {
+        require!(
+            (outcome as usize) < self.outcomes.len(),
+            BettingError::InvalidOutcomeIndex
+        );
+        Ok(outcome as usize)
+    }
+
+    // Profit split among the winning outcome's tokens: every other outcome's reserve after
+    // fees (see winner_profit_pot)
+    pub fn winner_profit_pot(&self) -> u128 {
+        let winning_reserve = self.reserves[self.winner as usize];
+        (self.total_reserve() as u128)
+            .saturating_sub(winning_reserve as u128)
+            .saturating_sub(self.creator_fee_accrued as u128)
+            .saturating_sub(self.platform_fee_amount as u128)
+    }
+}
+
+// A user's position in an OutcomeMarket; like an EntryState it holds a single outcome
+#[account]
+#[derive(Default)]
+pub struct OutcomeEntry {
+    pub user: Pubkey,
+    pub bet_id: u64,
+    pub outcome: u8,
+    pub deposited_sol_amount: u64,
+    pub token_balance: u64,
+    pub is_claimed: bool,
+    pub claimed_at: i64,
+}
+
+impl OutcomeEntry {
+    pub const MAX_SIZE: usize = 32 + 8 + 1 + 8 + 8 + 1 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"outcome-entry";
+}
+
+// Event-start report for oracle-bound markets, written by an oracle operator through
+// publish_event_start. One feed per (authority, feed_id), e.g. one per fixture.
+#[cfg(feature = "oracle")]
+#[account]
+#[derive(Default)]
+pub struct StartFeed {
+    pub authority: Pubkey,
+    pub feed_id: u64,
+    pub started_at: i64, // 0 until the event has started
+    pub updated_at: i64,
+}
+
+#[cfg(feature = "oracle")]
+impl StartFeed {
+    pub const MAX_SIZE: usize = 32 + 8 + 8 + 8;
+    pub const PREFIX_SEED: &'static [u8] = b"start-feed";
+
+    pub fn has_started(&self, now: i64) -> bool {
+        self.started_at != 0 && self.started_at <= now
+    }
+}
@@ -0,0 +1,130 @@
+//! Logarithmic Market Scoring Rule pricing, the alternative to the ratio-with-virtual-reserve
+//! pricing in deposit.rs (see PricingModel). An LMSR pool prices each side from the tokens
+//! outstanding on both sides and its liquidity parameter `b`: the cost of holding q_yes and
+//! q_no tokens is C = b * ln(e^(q_yes/b) + e^(q_no/b)), a deposit buys the tokens that raise C
+//! by its amount, and the YES price is e^(q_yes/b) / (e^(q_yes/b) + e^(q_no/b)). Larger `b`
+//! moves the price less per lamport.
+//!
+//! Tokens only weight the winners' share of the losing reserve (see claim.rs), so the pool
+//! never underwrites the market maker's loss the way a classic LMSR does.
+
+use crate::error::BettingError;
+use crate::PRICE_SCALE;
+use anchor_lang::prelude::*;
+
+// Fixed-point precision of the ln/exp helpers (1e18)
+pub const WAD: i128 = 1_000_000_000_000_000_000;
+const LN2_WAD: i128 = 693_147_180_559_945_309;
+
+// e^x underflows WAD precision below this
+const EXP_MIN_WAD: i128 = -42 * WAD;
+
+// e^(x / WAD) scaled by WAD, for x <= 0. Reduces x to k * ln 2 + r with r in (-ln 2, 0] and
+// sums the Taylor series of e^r, which converges within a few dozen terms there.
+pub fn exp_neg_wad(x: i128) -> u128 {
+    debug_assert!(x <= 0);
+    if x < EXP_MIN_WAD {
+        return 0;
+    }
+    let k = x / LN2_WAD;
+    let r = x - k * LN2_WAD;
+
+    let mut sum = WAD;
+    let mut term = WAD;
+    let mut n = 1;
+    while term != 0 {
+        term = term * r / WAD / n;
+        sum += term;
+        n += 1;
+    }
+    (sum as u128) >> (-k as u32)
+}
+
+// ln(x / WAD) scaled by WAD, for x > 0. Normalizes x to m * 2^k with m in [1, 2) and sums
+// ln(m) = 2 * atanh((m - 1) / (m + 1)), whose argument stays below 1/3.
+pub fn ln_wad(x: u128) -> Result<i128> {
+    require!(x > 0, BettingError::MathOverflow);
+    let one = WAD as u128;
+    let (mut m, mut k) = (x, 0i128);
+    while m >= 2 * one {
+        m >>= 1;
+        k += 1;
+    }
+    while m < one {
+        m <<= 1;
+        k -= 1;
+    }
+
+    let m = m as i128;
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z_squared = z * z / WAD;
+    let mut sum = 0;
+    let mut term = z;
+    let mut n = 1;
+    while term != 0 {
+        sum += term / n;
+        term = term * z_squared / WAD;
+        n += 2;
+    }
+    Ok(2 * sum + k * LN2_WAD)
+}
+
+// (q_no - q_yes) / b scaled by WAD: how far YES trails NO in units of liquidity
+fn lead_of_no(yes_supply: u64, no_supply: u64, liquidity: u64) -> i128 {
+    (no_supply as i128 - yes_supply as i128) * WAD / liquidity as i128
+}
+
+// LMSR (yes, no) prices scaled by PRICE_SCALE; they sum to exactly PRICE_SCALE
+pub fn lmsr_prices(yes_supply: u64, no_supply: u64, liquidity: u64) -> (u128, u128) {
+    // p_yes = 1 / (1 + e^d), evaluated with a non-positive exponent either way
+    let d = lead_of_no(yes_supply, no_supply, liquidity);
+    let one = WAD as u128;
+    let yes_wad = if d <= 0 {
+        one * one / (one + exp_neg_wad(d))
+    } else {
+        let e = exp_neg_wad(-d);
+        e * one / (one + e)
+    };
+    let scale = one / PRICE_SCALE;
+    let yes_price = yes_wad / scale;
+    (yes_price, PRICE_SCALE - yes_price)
+}
+
+// Tokens a deposit of `amount` buys on one side of an LMSR pool holding the given supplies.
+// Solving C(q + delta) - C(q) = amount for delta gives, with u = amount / b and d the other
+// side's lead over this one in units of b,
+//     delta = amount + b * ln(1 + e^d * (1 - e^-u)),
+// never less than the amount itself since no price exceeds 1.
+pub fn lmsr_token_amount(
+    amount: u64,
+    is_yes: bool,
+    yes_supply: u64,
+    no_supply: u64,
+    liquidity: u64,
+) -> Result<u64> {
+    require!(liquidity > 0, BettingError::InvalidLmsrLiquidity);
+    let d = if is_yes {
+        lead_of_no(yes_supply, no_supply, liquidity)
+    } else {
+        lead_of_no(no_supply, yes_supply, liquidity)
+    };
+    let u = amount as i128 * WAD / liquidity as i128;
+    let bought = WAD - exp_neg_wad(-u) as i128; // 1 - e^-u
+
+    // ln(1 + e^d * bought), or d + ln(e^-d + bought) when e^d would overflow
+    let log_term = if d <= 0 {
+        ln_wad((WAD + exp_neg_wad(d) as i128 * bought / WAD) as u128)?
+    } else {
+        let inner = (exp_neg_wad(-d) as i128 + bought).max(1);
+        d + ln_wad(inner as u128)?
+    }
+    .max(0);
+
+    let extra = (liquidity as i128)
+        .checked_mul(log_term)
+        .ok_or(error!(BettingError::MathOverflow))?
+        / WAD;
+    (amount as i128 + extra)
+        .try_into()
+        .map_err(|_| error!(BettingError::MathOverflow))
+}
@@ -0,0 +1,62 @@
+//! Reader for Pyth price accounts (the v2 on-chain layout), used by `resolve_with_oracle`.
+//!
+//! Only the header and the aggregate price are read:
+//! `magic u32 @0 | version u32 @4 | type u32 @8 | expo i32 @20 | timestamp i64 @96 |
+//!  agg.price i64 @208 | agg.conf u64 @216 | agg.status u32 @224`
+
+use crate::error::BettingError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+// Pyth oracle program on mainnet-beta; it owns every price account it publishes
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+pub const MAGIC: u32 = 0xa1b2_c3d4;
+pub const VERSION: u32 = 2;
+pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const STATUS_TRADING: u32 = 1;
+
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+// End of the aggregate price info; the publisher components that follow are not read
+pub const MIN_ACCOUNT_LEN: usize = 240;
+
+// Aggregate price of a feed: `price * 10^expo` in the feed's quote currency
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+// Reads the aggregate price of a Pyth-owned price account
+pub fn read_price(feed: &AccountInfo) -> Result<PythPrice> {
+    require_keys_eq!(*feed.owner, PYTH_PROGRAM_ID, BettingError::InvalidPriceFeed);
+    let data = feed.try_borrow_data()?;
+    parse_price(&data)
+}
+
+// Parses price account data; only a currently trading aggregate counts as a price
+pub fn parse_price(data: &[u8]) -> Result<PythPrice> {
+    require!(data.len() >= MIN_ACCOUNT_LEN, BettingError::InvalidPriceFeed);
+    let read_u32 = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+    let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+    require!(
+        read_u32(0) == MAGIC
+            && read_u32(4) == VERSION
+            && read_u32(8) == ACCOUNT_TYPE_PRICE
+            && read_u32(AGG_STATUS_OFFSET) == STATUS_TRADING,
+        BettingError::InvalidPriceFeed
+    );
+
+    Ok(PythPrice {
+        price: read_u64(AGG_PRICE_OFFSET) as i64,
+        conf: read_u64(AGG_CONF_OFFSET),
+        expo: read_u32(EXPO_OFFSET) as i32,
+        publish_time: read_u64(TIMESTAMP_OFFSET) as i64,
+    })
+}
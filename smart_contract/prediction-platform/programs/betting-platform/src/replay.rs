@@ -0,0 +1,183 @@
+//! Deterministic replay of a pool from its event stream.
+//!
+//! Every change to a pool's positions and fate is announced by an event: the CreateEvent
+//! seeds the reserves, each DepositEvent mints tokens against them, and a CompleteEvent,
+//! RefundModeEvent or PoolVoidedEvent decides the pool. Folding those events in emission
+//! order through `replay` rebuilds the pool's `PoolReplay` without reading the account, so an
+//! indexer can check its own view of a pool against the chain:
+//!
+//! `replay_hash = sha256(borsh(PoolReplay))`
+//!
+//! verify_replay computes the same hash from the live PoolState, so comparing the two
+//! needs no trust in whoever served the events.
+
+use crate::error::BettingError;
+use crate::{
+    CompleteEvent, CreateEvent, DepositEvent, PoolLockedOnStartEvent, PoolState, PoolVoidedEvent,
+    RefundModeEvent, WinnerSide,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Discriminator;
+
+// The part of PoolState its events determine
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolReplay {
+    pub bet_id: u64,
+    pub total_supply: u64,
+    pub total_reserve: u64,
+    pub yes_supply: u64,
+    pub yes_reserve: u64,
+    pub no_supply: u64,
+    pub no_reserve: u64,
+    pub insurance_premiums: u64,
+    pub locked_on_start: bool,
+    pub complete: bool,
+    pub refund_mode: bool,
+    pub winner: WinnerSide,
+}
+
+// The events replay consumes; every other event leaves PoolReplay unchanged
+pub enum PoolEvent {
+    Create(Box<CreateEvent>),
+    Deposit(DepositEvent),
+    LockedOnStart(PoolLockedOnStartEvent),
+    Complete(CompleteEvent),
+    RefundMode(RefundModeEvent),
+    Voided(PoolVoidedEvent),
+}
+
+impl PoolEvent {
+    // Decodes an event as logged by `emit!` (discriminator followed by the Borsh body, i.e.
+    // the base64-decoded "Program data:" line). Ok(None) for events replay does not consume.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        fn body<T: AnchorDeserialize>(mut data: &[u8]) -> Result<T> {
+            T::deserialize(&mut data).map_err(|_| error!(BettingError::InvalidReplayEvent))
+        }
+        let event = if let Some(data) = data.strip_prefix(CreateEvent::DISCRIMINATOR) {
+            Self::Create(Box::new(body(data)?))
+        } else if let Some(data) = data.strip_prefix(DepositEvent::DISCRIMINATOR) {
+            Self::Deposit(body(data)?)
+        } else if let Some(data) = data.strip_prefix(PoolLockedOnStartEvent::DISCRIMINATOR) {
+            Self::LockedOnStart(body(data)?)
+        } else if let Some(data) = data.strip_prefix(CompleteEvent::DISCRIMINATOR) {
+            Self::Complete(body(data)?)
+        } else if let Some(data) = data.strip_prefix(RefundModeEvent::DISCRIMINATOR) {
+            Self::RefundMode(body(data)?)
+        } else if let Some(data) = data.strip_prefix(PoolVoidedEvent::DISCRIMINATOR) {
+            Self::Voided(body(data)?)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(event))
+    }
+
+    pub fn bet_id(&self) -> u64 {
+        match self {
+            Self::Create(event) => event.bet_id,
+            Self::Deposit(event) => event.bet_id,
+            Self::LockedOnStart(event) => event.bet_id,
+            Self::Complete(event) => event.bet_id,
+            Self::RefundMode(event) => event.bet_id,
+            Self::Voided(event) => event.bet_id,
+        }
+    }
+}
+
+impl PoolReplay {
+    const SERIALIZED_LEN: usize = 8 * 8 + 3 + 1;
+
+    pub fn of(pool_state: &PoolState) -> Self {
+        Self {
+            bet_id: pool_state.bet_id,
+            total_supply: pool_state.total_supply,
+            total_reserve: pool_state.total_reserve,
+            yes_supply: pool_state.yes_supply,
+            yes_reserve: pool_state.yes_reserve,
+            no_supply: pool_state.no_supply,
+            no_reserve: pool_state.no_reserve,
+            insurance_premiums: pool_state.insurance_premiums,
+            locked_on_start: pool_state.locked_on_start,
+            complete: pool_state.complete,
+            refund_mode: pool_state.refund_mode,
+            winner: pool_state.winner,
+        }
+    }
+
+    // Starts the replay of the pool a CreateEvent announced, holding its seed liquidity
+    pub fn created(create: &CreateEvent) -> Result<Self> {
+        Ok(Self {
+            bet_id: create.bet_id,
+            total_reserve: add(create.initial_yes_liquidity, create.initial_no_liquidity)?,
+            yes_reserve: create.initial_yes_liquidity,
+            no_reserve: create.initial_no_liquidity,
+            ..Default::default()
+        })
+    }
+
+    pub fn replay_hash(&self) -> [u8; 32] {
+        let mut data = Vec::with_capacity(Self::SERIALIZED_LEN);
+        // Writing into a Vec cannot fail
+        let _ = self.serialize(&mut data);
+        hash(&data).to_bytes()
+    }
+
+    fn is_decided(&self) -> bool {
+        self.complete || self.refund_mode
+    }
+
+    // Applies the event following those already replayed. Events of another pool fail with
+    // ReplayWrongPool; events the program could not have emitted at this point of the
+    // stream (a second CreateEvent, a deposit into a decided pool, ...) fail with
+    // ReplayOutOfOrder.
+    pub fn apply(&mut self, event: &PoolEvent) -> Result<()> {
+        require!(event.bet_id() == self.bet_id, BettingError::ReplayWrongPool);
+        require!(!self.is_decided(), BettingError::ReplayOutOfOrder);
+        match event {
+            PoolEvent::Create(_) => return err!(BettingError::ReplayOutOfOrder),
+            PoolEvent::Deposit(deposit) => {
+                self.total_supply = add(self.total_supply, deposit.token_amount)?;
+                self.total_reserve = add(self.total_reserve, deposit.sol_amount)?;
+                if deposit.is_yes {
+                    self.yes_supply = add(self.yes_supply, deposit.token_amount)?;
+                    self.yes_reserve = add(self.yes_reserve, deposit.sol_amount)?;
+                } else {
+                    self.no_supply = add(self.no_supply, deposit.token_amount)?;
+                    self.no_reserve = add(self.no_reserve, deposit.sol_amount)?;
+                }
+                self.insurance_premiums =
+                    add(self.insurance_premiums, deposit.insurance_premium)?;
+            }
+            PoolEvent::LockedOnStart(_) => {
+                require!(!self.locked_on_start, BettingError::ReplayOutOfOrder);
+                self.locked_on_start = true;
+            }
+            PoolEvent::Complete(complete) => {
+                self.complete = true;
+                self.winner = complete.winner;
+            }
+            PoolEvent::RefundMode(_) | PoolEvent::Voided(_) => {
+                self.refund_mode = true;
+                self.winner = WinnerSide::Void;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or(error!(BettingError::MathOverflow))
+}
+
+// Rebuilds a pool from its events in emission order, the first being its CreateEvent
+pub fn replay<'a>(events: impl IntoIterator<Item = &'a PoolEvent>) -> Result<PoolReplay> {
+    let mut events = events.into_iter();
+    let Some(PoolEvent::Create(create)) = events.next() else {
+        return err!(BettingError::ReplayOutOfOrder);
+    };
+    let mut pool = PoolReplay::created(create)?;
+    for event in events {
+        pool.apply(event)?;
+    }
+    Ok(pool)
+}
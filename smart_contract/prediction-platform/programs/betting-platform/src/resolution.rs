@@ -0,0 +1,55 @@
+//! Exactly-once resolution. Every path that decides a pool's fate, whether it names a winner
+//! or sends the pool to refund mode, claims it here first, so of two racing paths only the
+//! first lands and the pool remembers which one it was.
+
+use crate::{error::BettingError, PoolState};
+use anchor_lang::prelude::*;
+
+// The path that decided a pool (PoolState::resolution_source). Pools decided before the
+// field existed read Unresolved but are still caught by their complete/refund_mode flags.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum ResolutionSource {
+    #[default]
+    Unresolved,
+    Referee,            // the assigned referee, through set_winner and its variants
+    Fallback,           // the owner or a council admin resolving in the referee's place
+    Oracle,             // resolve_with_oracle
+    Provisional,        // finalize_resolution on an undisputed provisional outcome
+    DisputeRuling,      // the owner's ruling on a disputed provisional outcome
+    Assertion,          // settle_assertion on an unchallenged assertion
+    MetaMarket,         // resolve_meta_market
+    RefundDeadline,     // trigger_refund_mode
+    Voided,             // void_pool
+    Committee,          // propose_winner, once a referee committee's threshold agreed
+    Invalid,            // set_winner declaring the question malformed
+    TooFewParticipants, // set_winner on a pool short of its min_participants
+}
+
+impl ResolutionSource {
+    pub fn is_refund(&self) -> bool {
+        matches!(
+            self,
+            Self::RefundDeadline | Self::Voided | Self::Invalid | Self::TooFewParticipants
+        )
+    }
+}
+
+// Fails with AlreadyResolved once any path decided the pool. Paths that only start a
+// resolution (a provisional outcome, an assertion) check this without claiming the pool.
+pub fn ensure_unresolved(pool_state: &PoolState) -> Result<()> {
+    require!(
+        !pool_state.complete
+            && !pool_state.refund_mode
+            && pool_state.resolution_source == ResolutionSource::Unresolved,
+        BettingError::AlreadyResolved
+    );
+    Ok(())
+}
+
+// Claims the pool's one resolution for `source`. Callers then set complete or refund_mode
+// in the same instruction.
+pub fn claim(pool_state: &mut PoolState, source: ResolutionSource) -> Result<()> {
+    ensure_unresolved(pool_state)?;
+    pool_state.resolution_source = source;
+    Ok(())
+}
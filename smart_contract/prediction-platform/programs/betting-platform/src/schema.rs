@@ -0,0 +1,80 @@
+//! Stable layout registry for indexers. Anchor accounts are Borsh-encoded behind an 8-byte
+//! discriminator, so every field up to the first variable-length one (a String or Vec) sits at
+//! a fixed offset; those are the ones listed here, computed from the field sizes so they can't
+//! drift from one another. tests::schema checks every offset against a serialized account.
+//! Bump SCHEMA_VERSION whenever any of these move or an account's length changes, and
+//! indexers compare it with get_schema_version before decoding.
+
+use crate::{EntryState, MainState, PoolState};
+use anchor_lang::Discriminator;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+const PUBKEY: usize = 32;
+const DISCRIMINATOR_LEN: usize = 8;
+
+pub mod main_state {
+    use super::*;
+
+    pub const DISCRIMINATOR: &[u8] = MainState::DISCRIMINATOR;
+    pub const LEN: usize = DISCRIMINATOR_LEN + MainState::MAX_SIZE;
+
+    pub const INITIALIZED: usize = DISCRIMINATOR_LEN;
+    pub const OWNER: usize = INITIALIZED + 1;
+    pub const SCALE_FACTOR: usize = OWNER + PUBKEY;
+    pub const INITIAL_PRICE: usize = SCALE_FACTOR + 8;
+    pub const CURRENT_BET_ID: usize = INITIAL_PRICE + 8;
+    pub const CREATOR_FEE_PERCENT: usize = CURRENT_BET_ID + 8;
+    pub const PLATFORM_FEE_PERCENT: usize = CREATOR_FEE_PERCENT + 8;
+    pub const RETIRED: usize = PLATFORM_FEE_PERCENT + 8; // former clock tolerance, unused
+    pub const FEE_ADMIN: usize = RETIRED + 8;
+    pub const OPS_ADMIN: usize = FEE_ADMIN + PUBKEY;
+    pub const PAUSED: usize = OPS_ADMIN + PUBKEY;
+}
+
+pub mod pool_state {
+    use super::*;
+
+    pub const DISCRIMINATOR: &[u8] = PoolState::DISCRIMINATOR;
+    pub const LEN: usize = DISCRIMINATOR_LEN + PoolState::MAX_SIZE;
+
+    pub const CREATOR: usize = DISCRIMINATOR_LEN;
+    pub const BET_ID: usize = CREATOR + PUBKEY;
+    pub const INITIAL_PRICE: usize = BET_ID + 8;
+    pub const SCALE_FACTOR: usize = INITIAL_PRICE + 8;
+    pub const TOTAL_SUPPLY: usize = SCALE_FACTOR + 8;
+    pub const TOTAL_RESERVE: usize = TOTAL_SUPPLY + 8;
+    pub const YES_SUPPLY: usize = TOTAL_RESERVE + 8;
+    pub const YES_RESERVE: usize = YES_SUPPLY + 8;
+    pub const NO_SUPPLY: usize = YES_RESERVE + 8;
+    pub const NO_RESERVE: usize = NO_SUPPLY + 8;
+    pub const END_TIMESTAMP: usize = NO_RESERVE + 8;
+    pub const CREATED_TIMESTAMP: usize = END_TIMESTAMP + 8;
+    pub const REFEREE: usize = CREATED_TIMESTAMP + 8;
+    // The title's u32 length prefix; every later field moves with the pool's strings
+    pub const TITLE: usize = REFEREE + PUBKEY;
+}
+
+pub mod entry_state {
+    use super::*;
+
+    pub const DISCRIMINATOR: &[u8] = EntryState::DISCRIMINATOR;
+    pub const LEN: usize = DISCRIMINATOR_LEN + EntryState::MAX_SIZE;
+
+    pub const USER: usize = DISCRIMINATOR_LEN;
+    pub const BET_ID: usize = USER + PUBKEY;
+    pub const DEPOSITED_SOL_AMOUNT: usize = BET_ID + 8;
+    pub const TOKEN_BALANCE: usize = DEPOSITED_SOL_AMOUNT + 8;
+    pub const IS_YES: usize = TOKEN_BALANCE + 8;
+    pub const IS_CLAIMED: usize = IS_YES + 1;
+    pub const INSTALLMENTS_CLAIMED: usize = IS_CLAIMED + 1;
+    pub const RECENT_NONCES: usize = INSTALLMENTS_CLAIMED + 1;
+    pub const NONCE_CURSOR: usize = RECENT_NONCES + 8 * EntryState::NONCE_HISTORY;
+    pub const CREATOR_FEE_EXEMPT: usize = NONCE_CURSOR + 1;
+    pub const CREATOR_FEE_PAID: usize = CREATOR_FEE_EXEMPT + 1;
+    pub const INSURED_AMOUNT: usize = CREATOR_FEE_PAID + 8;
+    pub const INSURANCE_PREMIUM_PAID: usize = INSURED_AMOUNT + 8;
+    pub const INSURANCE_CLAIMED: usize = INSURANCE_PREMIUM_PAID + 8;
+    pub const DEPOSIT_COUNT: usize = INSURANCE_CLAIMED + 1;
+    pub const CLAIMED_AT: usize = DEPOSIT_COUNT + 4;
+}
@@ -0,0 +1,19 @@
+//! Deterministic share identifiers for pool links.
+//!
+//! `share_uuid = base58(sha256(program_id || bet_id_le))[..SHARE_ID_LEN]`
+//!
+//! Clients can derive the link for any bet_id without reading the pool account; the
+//! constants below are exported in the IDL for that purpose.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+#[constant]
+pub const SHARE_ID_LEN: u8 = 12;
+
+pub fn derive_share_uuid(program_id: &Pubkey, bet_id: u64) -> String {
+    // Hash implements Display as base58
+    let mut share_uuid = hashv(&[program_id.as_ref(), &bet_id.to_le_bytes()]).to_string();
+    share_uuid.truncate(SHARE_ID_LEN as usize);
+    share_uuid
+}
@@ -0,0 +1,205 @@
+//! Lamport-accurate accounting: random deposit sequences are resolved and then claimed in
+//! random orders against a simulated per-pool vault.
+
+use crate::fees::settle_resolution_fees;
+use crate::{apply_deposit, claimed_principal, settle_claim, EntryState, PoolState, WinnerSide};
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+
+const BETTORS: usize = 8;
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+#[derive(Debug)]
+struct Scenario {
+    creator_fee_bps: u64,
+    creator_donation_bps: u64,
+    platform_fee_bps: u64,
+    sides: Vec<bool>,
+    deposits: Vec<(usize, u64)>,
+    winner: bool,
+    claim_order: Vec<usize>,
+    creator_claim_at: usize,
+}
+
+fn scenario() -> impl Strategy<Value = Scenario> {
+    (
+        0u64..=500,
+        0u64..=10_000,
+        0u64..=500,
+        proptest::collection::vec(any::<bool>(), BETTORS),
+        proptest::collection::vec((0..BETTORS, 1_000u64..=50 * LAMPORTS_PER_SOL), 1..40),
+        any::<bool>(),
+        Just((0..BETTORS).collect::<Vec<_>>()).prop_shuffle(),
+        0..=BETTORS,
+    )
+        .prop_map(
+            |(
+                creator_fee_bps,
+                creator_donation_bps,
+                platform_fee_bps,
+                sides,
+                deposits,
+                winner,
+                claim_order,
+                at,
+            )| {
+                Scenario {
+                    creator_fee_bps,
+                    creator_donation_bps,
+                    platform_fee_bps,
+                    sides,
+                    deposits,
+                    winner,
+                    claim_order,
+                    creator_claim_at: at,
+                }
+            },
+        )
+}
+
+struct Outcome {
+    total_deposits: u64,
+    total_paid: u64,
+    winners: u64,
+    vault: u64,
+}
+
+// Runs deposits, resolution and claims, panicking if a winning claim fails or the vault
+// would go negative at any point
+fn run(s: &Scenario) -> Outcome {
+    let mut pool = PoolState {
+        creator_fee_bps: s.creator_fee_bps,
+        creator_donation_bps: s.creator_donation_bps,
+        installment_count: 1,
+        history_disabled: true,
+        ..Default::default()
+    };
+    let mut entries: Vec<EntryState> = (0..BETTORS).map(|_| EntryState::default()).collect();
+    let mut vault: u64 = 0;
+    let mut total_deposits: u64 = 0;
+
+    for &(bettor, amount) in &s.deposits {
+        let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(
+            &mut pool,
+            &mut entries[bettor],
+            None,
+            Pubkey::default(),
+            amount,
+            s.sides[bettor],
+            pricing_reserves,
+        )
+        .expect("deposit failed");
+        vault += amount;
+        total_deposits += amount;
+    }
+
+    // set_winner
+    pool.complete = true;
+    pool.winner = WinnerSide::from_is_yes(s.winner);
+    let platform_fee = settle_resolution_fees(&mut pool, s.winner, s.platform_fee_bps);
+    let mut total_paid = platform_fee;
+    vault = vault.checked_sub(platform_fee).expect("vault underflow on platform fee");
+
+    let mut winners = 0;
+    for (position, &bettor) in s.claim_order.iter().enumerate() {
+        if position == s.creator_claim_at {
+            let creator_fee = pool.creator_fee_accrued;
+            vault = vault.checked_sub(creator_fee).expect("vault underflow on creator fee");
+            total_paid += creator_fee;
+        }
+        let entry = &mut entries[bettor];
+        if entry.token_balance == 0 || entry.is_yes != s.winner {
+            continue;
+        }
+        let amount = settle_claim(&pool, entry, 0).expect("winning claim failed");
+        vault = vault.checked_sub(amount).expect("vault underflow on claim");
+        total_paid += amount;
+        winners += 1;
+    }
+    if s.creator_claim_at == s.claim_order.len() {
+        let creator_fee = pool.creator_fee_accrued;
+        vault = vault.checked_sub(creator_fee).expect("vault underflow on creator fee");
+        total_paid += creator_fee;
+    }
+
+    Outcome {
+        total_deposits,
+        total_paid,
+        winners,
+        vault,
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn payouts_and_fees_account_for_every_deposit(s in scenario()) {
+        let winning_side_funded = s
+            .deposits
+            .iter()
+            .any(|&(bettor, _)| s.sides[bettor] == s.winner);
+        prop_assume!(winning_side_funded);
+
+        let outcome = run(&s);
+
+        // Each winner's profit share is floored, so at most one lamport per winner stays behind
+        prop_assert_eq!(outcome.total_paid + outcome.vault, outcome.total_deposits);
+        prop_assert!(
+            outcome.vault < outcome.winners,
+            "dust {} exceeds bound for {} winners",
+            outcome.vault,
+            outcome.winners
+        );
+    }
+
+    #[test]
+    fn claim_order_does_not_change_payouts(s in scenario()) {
+        let winning_side_funded = s
+            .deposits
+            .iter()
+            .any(|&(bettor, _)| s.sides[bettor] == s.winner);
+        prop_assume!(winning_side_funded);
+
+        let reversed = Scenario {
+            claim_order: s.claim_order.iter().rev().copied().collect(),
+            creator_claim_at: BETTORS - s.creator_claim_at,
+            sides: s.sides.clone(),
+            deposits: s.deposits.clone(),
+            ..s
+        };
+        prop_assert_eq!(run(&s).total_paid, run(&reversed).total_paid);
+    }
+}
+
+#[test]
+fn claim_events_split_installments_into_principal_and_profit() {
+    let mut pool = PoolState {
+        installment_count: 3,
+        installment_period: 10,
+        history_disabled: true,
+        ..Default::default()
+    };
+    let (mut winner, mut loser) = (EntryState::default(), EntryState::default());
+    for (entry, amount, is_yes) in [(&mut winner, 1_000, true), (&mut loser, 500, false)] {
+        let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(&mut pool, entry, None, Pubkey::default(), amount, is_yes, pricing_reserves)
+            .expect("deposit failed");
+    }
+    pool.complete = true;
+    pool.winner = WinnerSide::Yes;
+
+    let (mut principal, mut total) = (0, 0);
+    for now in [0, 10, 20] {
+        let installments_before = winner.installments_claimed;
+        let paid = settle_claim(&pool, &mut winner, now).expect("installment claim failed");
+        let paid_principal = claimed_principal(&pool, &winner, installments_before);
+        assert!(paid_principal <= paid);
+        principal += paid_principal;
+        total += paid;
+    }
+    // The sole winner takes back the deposit plus the whole losing side
+    assert_eq!(principal, 1_000);
+    assert_eq!(total, 1_500);
+}
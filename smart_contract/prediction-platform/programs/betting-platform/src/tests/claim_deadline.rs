@@ -0,0 +1,89 @@
+//! Winners have until the pool's claim deadline; what they leave behind can then be swept.
+
+use crate::error::BettingError;
+use crate::{settle_claim, EntryState, PoolState, WinnerSide};
+use anchor_lang::error::Error;
+
+const RESOLVED_AT: i64 = 1_750_000_000;
+const WINDOW: i64 = 86_400;
+
+fn resolved_pool() -> PoolState {
+    PoolState {
+        complete: true,
+        winner: WinnerSide::Yes,
+        resolved_timestamp: RESOLVED_AT,
+        claim_window_secs: WINDOW,
+        yes_supply: 1_000,
+        yes_reserve: 1_000,
+        no_reserve: 1_000,
+        installment_count: 1,
+        ..Default::default()
+    }
+}
+
+fn winner() -> EntryState {
+    EntryState {
+        deposited_sol_amount: 1_000,
+        token_balance: 1_000,
+        is_yes: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn the_deadline_follows_the_last_installment() {
+    let pool = resolved_pool();
+    assert_eq!(pool.claim_deadline(), Some(RESOLVED_AT + WINDOW));
+    assert!(!pool.claims_closed(RESOLVED_AT + WINDOW - 1));
+    assert!(pool.claims_closed(RESOLVED_AT + WINDOW));
+
+    let annuity = PoolState {
+        installment_count: 4,
+        installment_period: 1_000,
+        ..resolved_pool()
+    };
+    assert_eq!(annuity.claim_deadline(), Some(RESOLVED_AT + 3_000 + WINDOW));
+
+    // Unresolved pools and pools created without a window never close
+    let trading = PoolState {
+        complete: false,
+        ..resolved_pool()
+    };
+    let legacy = PoolState {
+        claim_window_secs: 0,
+        ..resolved_pool()
+    };
+    for pool in [trading, legacy] {
+        assert_eq!(pool.claim_deadline(), None);
+        assert!(!pool.claims_closed(i64::MAX));
+    }
+}
+
+#[test]
+fn claims_stop_at_the_deadline() {
+    let pool = resolved_pool();
+    assert_eq!(
+        settle_claim(&pool, &mut winner(), RESOLVED_AT + WINDOW).unwrap_err(),
+        Error::from(BettingError::ClaimWindowClosed)
+    );
+    assert_eq!(settle_claim(&pool, &mut winner(), RESOLVED_AT + WINDOW - 1).unwrap(), 2_000);
+}
+
+#[test]
+fn sweeps_leave_the_creator_their_fee_and_seed() {
+    let mut pool = PoolState {
+        vault_liabilities: 5_000,
+        creator_fee_accrued: 300,
+        seed_yes_liquidity: 200,
+        ..resolved_pool()
+    };
+    assert_eq!(pool.unclaimed_winnings(), 5_000 - 300 - 200);
+    pool.creator_fee_claimed = true;
+    pool.seed_reclaimed = true;
+    assert_eq!(pool.unclaimed_winnings(), 5_000);
+
+    // Swept entries have nothing left to claim and may be closed
+    assert!(!winner().is_settled(&pool));
+    pool.unclaimed_swept = true;
+    assert!(winner().is_settled(&pool));
+}
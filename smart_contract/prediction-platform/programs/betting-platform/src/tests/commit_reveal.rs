@@ -0,0 +1,106 @@
+//! Commit-reveal pools resolve only once no commitment can still be revealed, and every
+//! commitment left over is refundable from then on, less a forfeit if it simply lapsed.
+
+use crate::constants::COMMIT_REVEAL_WINDOW_SECS;
+use crate::{CommitmentState, PoolState};
+
+const NOW: i64 = 1_750_000_000;
+
+fn commitment(reveal_deadline: i64) -> CommitmentState {
+    CommitmentState {
+        user: Default::default(),
+        bet_id: 1,
+        commitment_hash: [0; 32],
+        amount: 1_000,
+        yes_reserve: 0,
+        no_reserve: 0,
+        committed_timestamp: reveal_deadline - 300,
+        reveal_deadline,
+    }
+}
+
+#[test]
+fn resolution_waits_for_the_last_reveal_deadline() {
+    let mut pool = PoolState {
+        commit_reveal: true,
+        ..Default::default()
+    };
+    assert!(pool.commitments_settled(NOW));
+
+    pool.pending_commitments = 2;
+    pool.latest_reveal_deadline = NOW + 10;
+    assert!(!pool.commitments_settled(NOW));
+    assert!(!pool.commitments_settled(NOW + 10));
+    assert!(pool.commitments_settled(NOW + 11));
+}
+
+#[test]
+fn unrevealable_commitments_are_refundable() {
+    let open = PoolState::default();
+    assert!(!commitment(NOW).is_refundable(&open, NOW));
+    assert!(commitment(NOW).is_refundable(&open, NOW + 1));
+
+    for pool in [
+        PoolState {
+            complete: true,
+            ..Default::default()
+        },
+        PoolState {
+            provisional_at: NOW - 1,
+            ..Default::default()
+        },
+        PoolState {
+            refund_mode: true,
+            ..Default::default()
+        },
+    ] {
+        assert!(commitment(NOW + 300).is_refundable(&pool, NOW));
+    }
+}
+
+#[test]
+fn reveals_close_with_trading() {
+    let open_ended = PoolState {
+        end_timestamp: -1,
+        ..Default::default()
+    };
+    assert_eq!(open_ended.reveal_deadline(NOW), NOW + COMMIT_REVEAL_WINDOW_SECS);
+
+    let ending = PoolState {
+        end_timestamp: NOW + 10,
+        ..Default::default()
+    };
+    assert_eq!(ending.reveal_deadline(NOW), NOW + 10);
+    assert!(ending.trading_closed(ending.reveal_deadline(NOW)));
+}
+
+#[test]
+fn only_lapsed_commitments_pay_the_forfeit() {
+    // 2% of 1_000
+    assert_eq!(commitment(NOW).forfeit(&PoolState::default()), 20);
+
+    for pool in [
+        PoolState {
+            complete: true,
+            resolved_timestamp: NOW,
+            ..Default::default()
+        },
+        PoolState {
+            provisional_at: NOW - 1,
+            ..Default::default()
+        },
+        PoolState {
+            refund_mode: true,
+            ..Default::default()
+        },
+    ] {
+        assert_eq!(commitment(NOW).forfeit(&pool), 0);
+    }
+
+    let decided_late = PoolState {
+        complete: true,
+        resolved_timestamp: NOW + 1,
+        ..Default::default()
+    };
+    assert_eq!(commitment(NOW).forfeit(&decided_late), 20);
+}
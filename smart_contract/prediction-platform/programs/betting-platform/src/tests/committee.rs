@@ -0,0 +1,84 @@
+//! Referee committees: who may sit on one, how votes add up, and that the referee can no
+//! longer resolve alone.
+
+use crate::constants::MAX_COMMITTEE_REFEREES;
+use crate::error::BettingError;
+use crate::{referee_committee, MainState, PoolState, ResolutionState};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn committee_pool(members: &[Pubkey], threshold: u8) -> PoolState {
+    let (referee_committee, committee_size, committee_threshold) =
+        referee_committee(members, &members[0], Some(threshold)).unwrap();
+    PoolState {
+        referee: members[0],
+        referee_accepted: true,
+        referee_committee,
+        committee_size,
+        committee_threshold,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn the_threshold_defaults_to_a_simple_majority() {
+    for (size, majority) in [(2, 2), (3, 2), (4, 3), (5, 3)] {
+        let members: Vec<Pubkey> = (1..=size).map(key).collect();
+        let (_, committee_size, threshold) = referee_committee(&members, &key(1), None).unwrap();
+        assert_eq!((committee_size, threshold), (size, majority));
+    }
+}
+
+#[test]
+fn malformed_committees_are_rejected() {
+    let invalid = Some(Error::from(BettingError::InvalidRefereeCommittee));
+    let six: Vec<Pubkey> = (1..=MAX_COMMITTEE_REFEREES as u8 + 1).map(key).collect();
+    assert_eq!(referee_committee(&[key(1)], &key(1), None).err(), invalid);
+    assert_eq!(referee_committee(&six, &key(1), None).err(), invalid);
+    // The referee must sit on it, once, and the threshold must be reachable
+    assert_eq!(referee_committee(&[key(2), key(3)], &key(1), None).err(), invalid);
+    assert_eq!(referee_committee(&[key(1), key(2), key(1)], &key(1), None).err(), invalid);
+    assert_eq!(referee_committee(&[key(1), key(2)], &key(1), Some(3)).err(), invalid);
+    assert_eq!(referee_committee(&[key(1), key(2)], &key(1), Some(0)).err(), invalid);
+}
+
+#[test]
+fn only_members_have_a_seat() {
+    let pool = committee_pool(&[key(1), key(2), key(3)], 2);
+    assert_eq!(pool.committee_seat(&key(3)), Some(2));
+    assert_eq!(pool.committee_seat(&key(4)), None);
+    // Unused seats hold the default key, which is nobody's seat
+    assert_eq!(pool.committee_seat(&Pubkey::default()), None);
+}
+
+#[test]
+fn votes_count_once_per_member() {
+    let mut votes = ResolutionState::default();
+    assert_eq!(votes.record_vote(0, true, 10).unwrap(), 1);
+    assert_eq!(votes.record_vote(2, false, 11).unwrap(), 1);
+    assert_eq!(
+        votes.record_vote(0, false, 12).err(),
+        Some(Error::from(BettingError::AlreadyVoted))
+    );
+    assert_eq!(votes.record_vote(1, true, 13).unwrap(), 2);
+    assert_eq!((votes.yes_votes, votes.no_votes, votes.voted), (2, 1, 0b111));
+}
+
+#[test]
+fn the_referee_of_a_committee_pool_cannot_resolve_alone() {
+    let pool = committee_pool(&[key(1), key(2)], 2);
+    let main_state = MainState {
+        owner: key(9),
+        ..Default::default()
+    };
+    assert_eq!(
+        pool.check_resolver(&key(1), &main_state, 0).err(),
+        Some(Error::from(BettingError::CommitteeResolutionRequired))
+    );
+    // The owner keeps the fallback they have over any referee
+    assert!(pool.check_resolver(&key(9), &main_state, 0).is_ok());
+}
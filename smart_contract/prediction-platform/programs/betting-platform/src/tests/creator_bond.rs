@@ -0,0 +1,78 @@
+//! Creator bonds: returned once the pool is decided on time, slashed when it missed its
+//! deadlines, and held back from everything else the vault pays out until then.
+
+use crate::constants::VOID_GRACE_SECS;
+use crate::resolution::ResolutionSource;
+use crate::{PoolState, WinnerSide};
+
+const BOND: u64 = 50_000_000;
+
+fn decided_pool(source: ResolutionSource) -> PoolState {
+    let refund = source.is_refund();
+    PoolState {
+        creator_bond: BOND,
+        resolution_source: source,
+        complete: !refund,
+        refund_mode: refund,
+        winner: if refund { WinnerSide::Void } else { WinnerSide::Yes },
+        history_disabled: true,
+        creator_fee_claimed: true,
+        platform_fee_claimed: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn pools_that_missed_their_deadlines_forfeit_the_bond() {
+    assert!(decided_pool(ResolutionSource::RefundDeadline).creator_bond_slashed());
+    for source in [
+        ResolutionSource::Referee,
+        ResolutionSource::Fallback,
+        ResolutionSource::Oracle,
+        ResolutionSource::Committee,
+        ResolutionSource::Invalid,
+        ResolutionSource::TooFewParticipants,
+    ] {
+        assert!(!decided_pool(source).creator_bond_slashed(), "{source:?}");
+    }
+}
+
+#[test]
+fn only_a_no_show_fallback_forfeits_the_bond() {
+    // The owner resolving a pool without a referee deadline is on time
+    let mut pool = decided_pool(ResolutionSource::Fallback);
+    assert!(!pool.creator_bond_slashed());
+    pool.referee_no_show = true;
+    assert!(pool.creator_bond_slashed());
+}
+
+#[test]
+fn a_void_forfeits_the_bond_once_anyone_could_void() {
+    let mut pool = decided_pool(ResolutionSource::Voided);
+    pool.end_timestamp = 1_000;
+    pool.voided_at = 1_000 + VOID_GRACE_SECS - 1;
+    assert!(!pool.creator_bond_slashed());
+    pool.voided_at = 1_000 + VOID_GRACE_SECS;
+    assert!(pool.creator_bond_slashed());
+
+    // Open-ended pools only forfeit past a deadline
+    pool.end_timestamp = -1;
+    pool.resolution_deadline = 2_000;
+    pool.voided_at = 1_999;
+    assert!(!pool.creator_bond_slashed());
+    pool.voided_at = 2_000;
+    assert!(pool.creator_bond_slashed());
+}
+
+#[test]
+fn an_unsettled_bond_is_neither_swept_nor_settled() {
+    let mut pool = decided_pool(ResolutionSource::Referee);
+    pool.vault_liabilities = BOND + 1_000;
+    assert_eq!(pool.unclaimed_winnings(), 1_000);
+    assert!(!pool.is_fully_settled());
+
+    pool.creator_bond_settled = true;
+    pool.vault_liabilities = 1_000;
+    assert_eq!(pool.unclaimed_winnings(), 1_000);
+    assert!(pool.is_fully_settled());
+}
@@ -0,0 +1,117 @@
+//! transfer_entry: a position moves whole into another wallet's entry.
+
+use crate::error::BettingError;
+use crate::{move_position, EntryState, PoolState};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+fn entry(is_yes: bool, deposited: u64, tokens: u64) -> EntryState {
+    EntryState {
+        is_yes,
+        deposited_sol_amount: deposited,
+        token_balance: tokens,
+        creator_fee_paid: deposited / 100,
+        deposit_count: 1,
+        ..Default::default()
+    }
+}
+
+fn pool() -> PoolState {
+    PoolState {
+        yes_entries: 2,
+        no_entries: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn an_empty_destination_takes_the_whole_position() {
+    let mut pool = pool();
+    let mut source = entry(false, 3_000, 5_000);
+    source.referrer = Pubkey::new_from_array([7; 32]);
+    let mut destination = EntryState::default();
+
+    move_position(&mut pool, &mut source, &mut destination).unwrap();
+    assert!(!destination.is_yes);
+    assert_eq!(destination.referrer, Pubkey::new_from_array([7; 32]));
+    assert_eq!(
+        (destination.deposited_sol_amount, destination.token_balance, destination.creator_fee_paid),
+        (3_000, 5_000, 30)
+    );
+    assert_eq!((source.deposited_sol_amount, source.token_balance, source.deposit_count), (0, 0, 0));
+    // The position changed hands, the side kept its bettor
+    assert_eq!((pool.yes_entries, pool.no_entries), (2, 1));
+}
+
+#[test]
+fn merging_into_a_position_on_the_same_side_counts_one_entry() {
+    let mut pool = pool();
+    let mut source = entry(true, 1_000, 1_500);
+    let mut destination = entry(true, 2_000, 2_500);
+
+    move_position(&mut pool, &mut source, &mut destination).unwrap();
+    assert_eq!((destination.deposited_sol_amount, destination.token_balance), (3_000, 4_000));
+    assert_eq!(destination.deposit_count, 2);
+    assert_eq!(pool.yes_entries, 1);
+}
+
+#[test]
+fn positions_on_opposite_sides_or_referrers_do_not_merge() {
+    let mut source = entry(true, 1_000, 1_500);
+    let mut other_side = entry(false, 2_000, 2_500);
+    assert_eq!(
+        move_position(&mut pool(), &mut source, &mut other_side).err(),
+        Some(Error::from(BettingError::InvalidEntryTransfer))
+    );
+
+    let mut referred = entry(true, 2_000, 2_500);
+    referred.referrer = Pubkey::new_from_array([7; 32]);
+    assert_eq!(
+        move_position(&mut pool(), &mut source, &mut referred).err(),
+        Some(Error::from(BettingError::InvalidEntryTransfer))
+    );
+    assert_eq!(source.token_balance, 1_500);
+}
+
+#[test]
+fn an_empty_entry_has_nothing_to_transfer() {
+    let mut source = EntryState::default();
+    assert_eq!(
+        move_position(&mut pool(), &mut source, &mut EntryState::default()).err(),
+        Some(Error::from(BettingError::InvalidBet))
+    );
+}
+
+#[test]
+fn a_merge_cannot_push_the_destination_past_the_user_cap() {
+    let mut pool = PoolState {
+        max_user_deposit: 2_500,
+        ..pool()
+    };
+    let mut source = entry(true, 1_000, 1_500);
+    let mut destination = entry(true, 2_000, 2_500);
+    assert_eq!(
+        move_position(&mut pool, &mut source, &mut destination).err(),
+        Some(Error::from(BettingError::UserCapExceeded))
+    );
+    assert_eq!(destination.deposited_sol_amount, 2_000);
+
+    // A fresh destination only ever holds what the cap already admitted
+    move_position(&mut pool, &mut source, &mut EntryState::default()).unwrap();
+}
+
+#[test]
+fn the_creator_fee_exemption_travels_with_the_position() {
+    let mut source = entry(true, 1_000, 1_500);
+    source.creator_fee_exempt = true;
+    let mut destination = EntryState::default();
+    move_position(&mut pool(), &mut source, &mut destination).unwrap();
+    assert!(destination.creator_fee_exempt);
+
+    // A merged entry is exempt only if both halves were
+    let mut source = entry(true, 1_000, 1_500);
+    source.creator_fee_exempt = true;
+    let mut destination = entry(true, 2_000, 2_500);
+    move_position(&mut pool(), &mut source, &mut destination).unwrap();
+    assert!(!destination.creator_fee_exempt);
+}
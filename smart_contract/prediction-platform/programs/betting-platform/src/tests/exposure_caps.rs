@@ -0,0 +1,89 @@
+//! Deposits stop at the pool's reserve cap and each entry's deposit cap; the owner's caps
+//! override the creator's.
+
+use crate::error::BettingError;
+use crate::{apply_deposit, EntryState, PoolState};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+fn pool() -> PoolState {
+    PoolState {
+        yes_reserve: 600,
+        no_reserve: 300,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn uncapped_pools_take_any_deposit() {
+    assert!(pool().check_exposure_caps(u64::MAX, u64::MAX).is_ok());
+}
+
+#[test]
+fn reserve_cap_counts_both_sides() {
+    let pool = PoolState {
+        max_pool_reserve: 1_000,
+        ..pool()
+    };
+    assert!(pool.check_exposure_caps(0, 100).is_ok());
+    assert_eq!(
+        pool.check_exposure_caps(0, 101).unwrap_err(),
+        Error::from(BettingError::PoolCapExceeded)
+    );
+}
+
+#[test]
+fn user_cap_counts_earlier_deposits() {
+    let pool = PoolState {
+        max_user_deposit: 50,
+        ..pool()
+    };
+    assert!(pool.check_exposure_caps(30, 20).is_ok());
+    assert_eq!(
+        pool.check_exposure_caps(30, 21).unwrap_err(),
+        Error::from(BettingError::UserCapExceeded)
+    );
+}
+
+#[test]
+fn owner_caps_lock_out_the_creator() {
+    let mut pool = pool();
+    pool.set_exposure_caps(Some(5_000), Some(100), false)
+        .unwrap();
+    assert_eq!((pool.max_pool_reserve, pool.max_user_deposit), (5_000, 100));
+    // Changes only what it names
+    pool.set_exposure_caps(None, Some(200), false).unwrap();
+    assert_eq!((pool.max_pool_reserve, pool.max_user_deposit), (5_000, 200));
+
+    pool.set_exposure_caps(Some(2_000), None, true).unwrap();
+    assert!(pool.exposure_caps_by_owner);
+    assert_eq!(
+        pool.set_exposure_caps(Some(0), None, false).unwrap_err(),
+        Error::from(BettingError::ExposureCapsSetByOwner)
+    );
+    assert_eq!(pool.max_pool_reserve, 2_000);
+    // Updates that leave the caps alone still go through
+    assert!(pool.set_exposure_caps(None, None, false).is_ok());
+    pool.set_exposure_caps(Some(0), Some(0), true).unwrap();
+    assert!(pool.check_exposure_caps(u64::MAX, u64::MAX).is_ok());
+}
+
+#[test]
+fn every_deposit_path_checks_the_caps() {
+    // Revealed deposits book through apply_deposit too
+    let mut pool = PoolState {
+        max_user_deposit: 50,
+        history_disabled: true,
+        installment_count: 1,
+        ..pool()
+    };
+    let mut entry = EntryState::default();
+    let reserves = (pool.yes_reserve, pool.no_reserve);
+    apply_deposit(&mut pool, &mut entry, None, Pubkey::default(), 50, true, reserves).unwrap();
+    let reserves = (pool.yes_reserve, pool.no_reserve);
+    assert_eq!(
+        apply_deposit(&mut pool, &mut entry, None, Pubkey::default(), 1, true, reserves)
+            .unwrap_err(),
+        Error::from(BettingError::UserCapExceeded)
+    );
+}
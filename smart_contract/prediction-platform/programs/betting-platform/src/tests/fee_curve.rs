@@ -0,0 +1,66 @@
+//! Liquidity-sensitive platform fee curve.
+
+#[cfg(feature = "platform-fees")]
+use crate::fees::platform_fee_bps;
+use crate::FeeCurve;
+#[cfg(feature = "platform-fees")]
+use crate::MainState;
+
+const SOL: u64 = 1_000_000_000;
+
+fn curve() -> FeeCurve {
+    FeeCurve {
+        small_pool_reserve: SOL,
+        large_pool_reserve: 101 * SOL,
+        small_pool_bps: 500,
+        large_pool_bps: 100,
+    }
+}
+
+#[test]
+fn rate_falls_linearly_between_the_pool_sizes() {
+    let curve = curve();
+    assert_eq!(curve.bps_for(0), 500);
+    assert_eq!(curve.bps_for(SOL), 500);
+    assert_eq!(curve.bps_for(51 * SOL), 300);
+    assert_eq!(curve.bps_for(101 * SOL), 100);
+    assert_eq!(curve.bps_for(u64::MAX), 100);
+
+    let mut previous = u64::MAX;
+    for reserve in (0..=110).map(|sol| sol * SOL) {
+        let bps = curve.bps_for(reserve);
+        assert!(bps <= previous, "fee rose at {} lamports", reserve);
+        previous = bps;
+    }
+}
+
+#[test]
+fn rising_or_inverted_curves_are_rejected() {
+    assert!(FeeCurve::default().is_valid());
+    assert!(curve().is_valid());
+    assert!(!FeeCurve {
+        small_pool_bps: 100,
+        large_pool_bps: 500,
+        ..curve()
+    }
+    .is_valid());
+    assert!(!FeeCurve {
+        small_pool_reserve: 101 * SOL,
+        ..curve()
+    }
+    .is_valid());
+}
+
+#[cfg(feature = "platform-fees")]
+#[test]
+fn flat_rate_applies_until_a_curve_is_set() {
+    let mut main_state = MainState {
+        platform_fee_percent: 200,
+        ..Default::default()
+    };
+    assert_eq!(platform_fee_bps(&main_state, 1_000 * SOL), 200);
+
+    main_state.platform_fee_curve = curve();
+    assert_eq!(platform_fee_bps(&main_state, 1_000 * SOL), 100);
+    assert_eq!(platform_fee_bps(&main_state, SOL / 2), 500);
+}
@@ -0,0 +1,76 @@
+//! A fixed-end pool's closing line is taken once, when trading stopped, and never moves again.
+
+use crate::resolution::ResolutionSource;
+use crate::{finalize_outcome, implied_prices, lock_final_odds, MainState, PoolState};
+
+const NOW: i64 = 1_750_000_000;
+
+fn pool(end_timestamp: i64) -> PoolState {
+    PoolState {
+        yes_reserve: 3_000,
+        no_reserve: 1_000,
+        end_timestamp,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn recorded_once_the_pool_ended() {
+    let mut pool = pool(NOW);
+    assert_eq!(pool.record_final_odds(NOW - 1), None);
+    assert!(!pool.final_odds.is_recorded());
+
+    let recorded = pool.record_final_odds(NOW).unwrap();
+    let (yes_price, no_price) = implied_prices(3_000, 1_000);
+    assert_eq!(
+        (
+            recorded.yes_reserve,
+            recorded.no_reserve,
+            recorded.recorded_at
+        ),
+        (3_000, 1_000, NOW)
+    );
+    assert_eq!(
+        (recorded.yes_price, recorded.no_price),
+        (yes_price as u64, no_price as u64)
+    );
+    assert_eq!(pool.final_odds, recorded);
+}
+
+#[test]
+fn later_flows_do_not_move_the_closing_line() {
+    let mut pool = pool(NOW - 60);
+    lock_final_odds(&mut pool, NOW);
+    let closing = pool.final_odds;
+
+    // A late flow books against the pool after the snapshot
+    pool.yes_reserve += 5_000;
+    assert_eq!(pool.record_final_odds(NOW + 60), None);
+    lock_final_odds(&mut pool, NOW + 60);
+    assert_eq!(pool.final_odds, closing);
+}
+
+#[test]
+fn resolution_at_the_trading_end_locks_the_line() {
+    let mut pool = pool(NOW);
+    let mut main_state = MainState::default();
+    finalize_outcome(
+        &mut pool,
+        &mut main_state,
+        ResolutionSource::Referee,
+        true,
+        NOW,
+    )
+    .unwrap();
+    assert!(pool.final_odds.is_recorded());
+    assert_eq!(pool.final_odds.recorded_at, NOW);
+    assert_eq!(pool.final_odds.yes_reserve, 3_000);
+}
+
+#[test]
+fn open_ended_pools_have_no_closing_line() {
+    let mut pool = pool(-1);
+    assert_eq!(pool.record_final_odds(NOW), None);
+    pool.complete = true;
+    assert_eq!(pool.record_final_odds(NOW), None);
+}
@@ -0,0 +1,97 @@
+//! The probability history ring: slots wrap over the oldest point, points read back oldest
+//! first, and pre-ring accounts convert with their points intact.
+
+use crate::{convert_legacy_history, PoolHistoryState, ProbabilityPoint};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use bytemuck::Zeroable;
+
+const STARTED_AT: i64 = 1_000;
+const MAX_POINTS: usize = PoolHistoryState::MAX_POINTS;
+
+fn history() -> Box<PoolHistoryState> {
+    let mut history = Box::new(PoolHistoryState::zeroed());
+    history.reset(Pubkey::new_unique(), 42, STARTED_AT);
+    history
+}
+
+fn timestamps(history: &PoolHistoryState) -> Vec<i64> {
+    history.chronological().map(|point| history.timestamp_of(point)).collect()
+}
+
+// A history account as the Vec-backed layout stored it: Borsh (pool, bet_id, points) of
+// (timestamp, yes_reserve, no_reserve, kind), zero-padded to the size it was created with
+pub(super) fn legacy_history_data(
+    pool: Pubkey,
+    bet_id: u64,
+    points: &[(i64, u64, u64)],
+) -> Vec<u8> {
+    let mut data = PoolHistoryState::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(pool.as_ref());
+    data.extend_from_slice(&bet_id.to_le_bytes());
+    data.extend_from_slice(&(points.len() as u32).to_le_bytes());
+    for (timestamp, yes_reserve, no_reserve) in points {
+        data.extend_from_slice(&timestamp.to_le_bytes());
+        data.extend_from_slice(&yes_reserve.to_le_bytes());
+        data.extend_from_slice(&no_reserve.to_le_bytes());
+        data.push(ProbabilityPoint::KIND_SNAPSHOT);
+    }
+    data.resize(8 + PoolHistoryState::LEGACY_SIZE, 0);
+    data
+}
+
+#[test]
+fn the_ring_fits_in_the_legacy_accounts_space() {
+    assert_eq!(ProbabilityPoint::SIZE, std::mem::size_of::<ProbabilityPoint>());
+    // 120 points where the Vec-backed layout held 40 in 1_052 bytes
+    assert_eq!(8 + PoolHistoryState::MAX_SIZE, 1_024);
+    assert_eq!(8 + PoolHistoryState::LEGACY_SIZE, 1_052);
+}
+
+#[test]
+fn points_record_yes_share_of_the_reserves() {
+    let mut history = history();
+    history.push(STARTED_AT + 5, 3_000, 1_000, ProbabilityPoint::KIND_LARGE_DEPOSIT);
+    let point = history.points[0];
+    assert_eq!((point.offset_secs, point.yes_share_bps), (5, 7_500));
+    assert_eq!(point.kind, ProbabilityPoint::KIND_LARGE_DEPOSIT);
+    // An empty pool reads as even
+    assert_eq!(ProbabilityPoint::yes_share_bps(0, 0), 5_000);
+}
+
+#[test]
+fn a_full_ring_overwrites_its_oldest_point() {
+    let mut history = history();
+    for i in 0..MAX_POINTS as i64 {
+        assert!(history.push(STARTED_AT + i, 1, 1, 0).is_none());
+    }
+    let overwritten = history.push(STARTED_AT + MAX_POINTS as i64, 1, 1, 0).unwrap();
+    assert_eq!(history.timestamp_of(&overwritten), STARTED_AT);
+    assert_eq!(history.len as usize, MAX_POINTS);
+
+    let expected: Vec<i64> = (1..=MAX_POINTS as i64).map(|i| STARTED_AT + i).collect();
+    assert_eq!(timestamps(&history), expected);
+}
+
+#[test]
+fn a_partial_ring_reads_back_in_order() {
+    let mut history = history();
+    for i in 0..3 {
+        history.push(STARTED_AT + i * 10, 1, 1, 0);
+    }
+    assert_eq!(timestamps(&history), [STARTED_AT, STARTED_AT + 10, STARTED_AT + 20]);
+}
+
+#[test]
+fn legacy_histories_convert_with_their_points() {
+    let pool = Pubkey::new_unique();
+    let mut data = legacy_history_data(pool, 7, &[(500, 0, 0), (560, 2_000, 6_000)]);
+    assert_eq!(convert_legacy_history(&mut data).unwrap(), 2);
+
+    let history: &PoolHistoryState =
+        bytemuck::from_bytes(&data[8..8 + PoolHistoryState::MAX_SIZE]);
+    assert_eq!((history.pool, history.bet_id, history.started_at), (pool, 7, 500));
+    assert_eq!(timestamps(history), [500, 560]);
+    assert_eq!(history.points[1].yes_share_bps, 2_500);
+    assert!(data[8 + PoolHistoryState::MAX_SIZE..].iter().all(|byte| *byte == 0));
+}
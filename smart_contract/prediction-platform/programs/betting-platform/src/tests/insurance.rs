@@ -0,0 +1,42 @@
+//! The premium sub-pool must never pay out more than it collected.
+
+use crate::constants::{INSURANCE_COVERAGE_BPS, MIN_INSURANCE_PREMIUM_BPS};
+use crate::fees::{insurance_payout, insurance_premium, insurance_surplus};
+use crate::PoolState;
+
+const SOL: u64 = 1_000_000_000;
+
+#[test]
+fn premium_tracks_odds_with_a_floor() {
+    // Even pool: half the coverage
+    assert_eq!(insurance_premium(SOL, true, (0, 0)), SOL * INSURANCE_COVERAGE_BPS / 20_000);
+    // Overwhelming favourite: the floor applies
+    assert_eq!(
+        insurance_premium(SOL, true, (1_000_000 * SOL, 0)),
+        SOL * MIN_INSURANCE_PREMIUM_BPS / 10_000
+    );
+}
+
+#[test]
+fn underfunded_sub_pool_pays_pro_rata() {
+    let pool = PoolState {
+        insurance_premiums: SOL,
+        insured_no: 10 * SOL, // 5 SOL of coverage against 1 SOL of premiums
+        ..Default::default()
+    };
+    let paid = insurance_payout(&pool, true, 4 * SOL) + insurance_payout(&pool, true, 6 * SOL);
+    assert!(paid <= pool.insurance_premiums);
+    assert_eq!(insurance_surplus(&pool, true), 0);
+}
+
+#[test]
+fn funded_sub_pool_pays_full_coverage_and_returns_surplus() {
+    let pool = PoolState {
+        insurance_premiums: 3 * SOL,
+        insured_no: 2 * SOL,
+        insured_yes: 50 * SOL, // winning side, owed nothing
+        ..Default::default()
+    };
+    assert_eq!(insurance_payout(&pool, true, 2 * SOL), SOL);
+    assert_eq!(insurance_surplus(&pool, true), 2 * SOL);
+}
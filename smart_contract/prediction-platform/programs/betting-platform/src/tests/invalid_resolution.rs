@@ -0,0 +1,69 @@
+//! A referee declaring the question malformed voids the pool without fees.
+
+use crate::error::BettingError;
+use crate::resolution::ResolutionSource;
+use crate::{resolve_invalid, MainState, PoolState, RefereeProfile, WinnerSide};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+const NOW: i64 = 1_750_000_000;
+
+fn open_pool(referee: Pubkey) -> PoolState {
+    PoolState {
+        referee,
+        referee_accepted: true,
+        yes_reserve: 3_000,
+        no_reserve: 1_000,
+        creator_fee_accrued: 40,
+        end_timestamp: NOW + 3_600,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn invalid_refunds_without_fees_before_the_end() {
+    let referee = Pubkey::new_unique();
+    let mut pool = open_pool(referee);
+    let mut profile = RefereeProfile::default();
+    resolve_invalid(&mut pool, &MainState::default(), &mut profile, &referee, NOW).unwrap();
+
+    assert!(pool.refund_mode && !pool.complete);
+    assert_eq!(pool.winner, WinnerSide::Void);
+    assert_eq!(pool.resolution_source, ResolutionSource::Invalid);
+    assert_eq!(pool.platform_fee_amount, 0);
+    assert_eq!(profile.resolutions, 1);
+
+    assert_eq!(
+        resolve_invalid(&mut pool, &MainState::default(), &mut profile, &referee, NOW)
+            .unwrap_err(),
+        Error::from(BettingError::AlreadyResolved)
+    );
+}
+
+#[test]
+fn only_resolvers_may_declare_invalid() {
+    let referee = Pubkey::new_unique();
+    let mut pool = open_pool(referee);
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        resolve_invalid(
+            &mut pool,
+            &MainState::default(),
+            &mut RefereeProfile::default(),
+            &stranger,
+            NOW
+        )
+        .unwrap_err(),
+        Error::from(BettingError::Unauthorized)
+    );
+    assert_eq!(pool.resolution_source, ResolutionSource::Unresolved);
+
+    // SPL pools have no claim_refund path
+    let mut spl_pool = PoolState {
+        token_mint: Pubkey::new_unique(),
+        ..open_pool(referee)
+    };
+    let main_state = MainState::default();
+    let mut profile = RefereeProfile::default();
+    assert!(resolve_invalid(&mut spl_pool, &main_state, &mut profile, &referee, NOW).is_err());
+}
@@ -0,0 +1,53 @@
+//! Account lengths stay fixed across upgrades: new fields come out of the reserved block.
+
+use crate::constants::MAX_RANGE_BUCKETS;
+use crate::{EntryState, MainState, PoolState, RangeBucket};
+use anchor_lang::prelude::*;
+
+fn serialized_len<T: AccountSerialize>(account: &T) -> usize {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.len()
+}
+
+// Deployed account lengths. A field added without shrinking its type's reserve changes
+// these; resize existing accounts with layout::grow_account before moving a pin.
+const MAIN_STATE_LEN: usize = 648;
+const POOL_STATE_LEN: usize = 2_039;
+const ENTRY_STATE_LEN: usize = 304;
+
+#[test]
+fn account_lengths_are_pinned() {
+    assert_eq!(8 + MainState::MAX_SIZE, MAIN_STATE_LEN);
+    assert_eq!(8 + PoolState::MAX_SIZE, POOL_STATE_LEN);
+    assert_eq!(8 + EntryState::MAX_SIZE, ENTRY_STATE_LEN);
+}
+
+#[test]
+fn largest_states_fill_their_accounts() {
+    assert_eq!(serialized_len(&MainState::default()), MAIN_STATE_LEN);
+    assert_eq!(serialized_len(&EntryState::default()), ENTRY_STATE_LEN);
+
+    // Every string and list at its max_len fills PoolState's
+    let text = |len: usize| "x".repeat(len);
+    let full_pool = PoolState {
+        title: text(100),
+        description: text(500),
+        share_uuid: text(50),
+        unit_symbol: text(10),
+        side_a_label: text(30),
+        side_b_label: text(30),
+        range_buckets: vec![RangeBucket::default(); MAX_RANGE_BUCKETS],
+        ..Default::default()
+    };
+    assert_eq!(serialized_len(&full_pool), POOL_STATE_LEN);
+}
+
+#[test]
+fn reserves_start_zeroed() {
+    let mut data = Vec::new();
+    PoolState::default().try_serialize(&mut data).unwrap();
+    assert!(data[data.len() - PoolState::RESERVED..].iter().all(|&byte| byte == 0));
+    assert_eq!(MainState::default().reserved.0, [0; MainState::RESERVED]);
+    assert_eq!(EntryState::default().reserved.0, [0; EntryState::RESERVED]);
+}
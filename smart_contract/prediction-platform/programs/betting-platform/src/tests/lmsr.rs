@@ -0,0 +1,120 @@
+//! LMSR pricing: the fixed-point ln/exp helpers and what LMSR pools mint and quote.
+
+use crate::pricing::{exp_neg_wad, ln_wad, lmsr_prices, lmsr_token_amount, WAD};
+use crate::{apply_deposit, EntryState, PoolState, PricingModel, PRICE_SCALE};
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+
+const SOL: u64 = 1_000_000_000;
+
+fn wad(x: f64) -> i128 {
+    (x * WAD as f64) as i128
+}
+
+fn close(actual: f64, expected: f64, tolerance: f64) -> bool {
+    (actual - expected).abs() <= tolerance * expected.abs().max(1.0)
+}
+
+// LMSR cost of holding the given supplies, in lamports
+fn cost(yes_supply: u64, no_supply: u64, liquidity: u64) -> f64 {
+    let b = liquidity as f64;
+    let (x, y) = (yes_supply as f64 / b, no_supply as f64 / b);
+    let m = x.max(y);
+    b * (m + ((x - m).exp() + (y - m).exp()).ln())
+}
+
+fn lmsr_pool(liquidity: u64) -> PoolState {
+    PoolState {
+        history_disabled: true,
+        pricing_model: PricingModel::Lmsr,
+        lmsr_liquidity: liquidity,
+        ..Default::default()
+    }
+}
+
+fn deposit(pool: &mut PoolState, amount: u64, is_yes: bool) -> u64 {
+    let reserves = (pool.yes_reserve, pool.no_reserve);
+    apply_deposit(pool, &mut EntryState::default(), None, Pubkey::default(), amount, is_yes, reserves)
+        .unwrap()
+}
+
+#[test]
+fn exp_and_ln_match_floating_point() {
+    for x in [0.0, -0.5, -1.0, -std::f64::consts::LN_2, -7.25, -30.0] {
+        let actual = exp_neg_wad(wad(x)) as f64 / WAD as f64;
+        assert!(close(actual, x.exp(), 1e-12), "e^{x} = {actual}");
+    }
+    for x in [1.0, 0.5, std::f64::consts::E, 3.0, 1e-9, 1e6] {
+        let actual = ln_wad(wad(x) as u128).unwrap() as f64 / WAD as f64;
+        assert!(close(actual, x.ln(), 1e-12), "ln {x} = {actual}");
+    }
+    assert_eq!(exp_neg_wad(-50 * WAD), 0);
+    assert!(ln_wad(0).is_err());
+}
+
+#[test]
+fn a_balanced_pool_prices_both_sides_at_one_half() {
+    assert_eq!(lmsr_prices(0, 0, SOL), (PRICE_SCALE / 2, PRICE_SCALE / 2));
+    assert_eq!(lmsr_prices(7 * SOL, 7 * SOL, SOL), (PRICE_SCALE / 2, PRICE_SCALE / 2));
+}
+
+#[test]
+fn tokens_bought_raise_the_cost_by_the_deposit() {
+    for (yes_supply, no_supply, amount) in
+        [(0, 0, SOL), (5 * SOL, 0, SOL), (0, 30 * SOL, 2 * SOL), (3 * SOL, 4 * SOL, 1_000)]
+    {
+        let tokens = lmsr_token_amount(amount, true, yes_supply, no_supply, SOL).unwrap();
+        let raised = cost(yes_supply + tokens, no_supply, SOL) - cost(yes_supply, no_supply, SOL);
+        // Within the lamport lost to rounding the tokens down
+        assert!((raised - amount as f64).abs() <= 1.0, "{raised} for {amount}");
+    }
+}
+
+#[test]
+fn deeper_liquidity_moves_the_price_less() {
+    let mut shallow = lmsr_pool(SOL);
+    let mut deep = lmsr_pool(100 * SOL);
+    deposit(&mut shallow, SOL, true);
+    deposit(&mut deep, SOL, true);
+    assert!(shallow.current_prices().0 > deep.current_prices().0);
+    assert!(deep.current_prices().0 > PRICE_SCALE / 2);
+}
+
+#[test]
+fn a_late_bet_on_the_favourite_costs_more_per_token() {
+    let mut pool = lmsr_pool(10 * SOL);
+    let early = deposit(&mut pool, SOL, true);
+    deposit(&mut pool, 20 * SOL, true);
+    let late = deposit(&mut pool, SOL, true);
+    assert!(late < early);
+    assert_eq!(pool.yes_supply, pool.total_supply);
+}
+
+proptest! {
+    #[test]
+    fn tokens_never_fall_below_the_deposit_and_prices_sum_to_one(
+        yes_supply in 0u64..1_000 * SOL,
+        no_supply in 0u64..1_000 * SOL,
+        amount in 1u64..100 * SOL,
+        liquidity in crate::constants::MIN_LMSR_LIQUIDITY..1_000 * SOL,
+        is_yes in any::<bool>(),
+    ) {
+        let (yes_price, no_price) = lmsr_prices(yes_supply, no_supply, liquidity);
+        prop_assert_eq!(yes_price + no_price, PRICE_SCALE);
+
+        if let Ok(tokens) = lmsr_token_amount(amount, is_yes, yes_supply, no_supply, liquidity) {
+            prop_assert!(tokens >= amount);
+            let (yes_after, no_after) = if is_yes {
+                (yes_supply.saturating_add(tokens), no_supply)
+            } else {
+                (yes_supply, no_supply.saturating_add(tokens))
+            };
+            let (yes_price_after, _) = lmsr_prices(yes_after, no_after, liquidity);
+            if is_yes {
+                prop_assert!(yes_price_after >= yes_price);
+            } else {
+                prop_assert!(yes_price_after <= yes_price);
+            }
+        }
+    }
+}
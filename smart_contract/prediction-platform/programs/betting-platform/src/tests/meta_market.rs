@@ -0,0 +1,60 @@
+//! Meta-markets read their outcome off another pool's final state.
+
+use crate::{MetaCondition, PoolState, PriceCondition};
+
+const SOL: u64 = 1_000_000_000;
+
+fn condition(metric: u8, comparison: u8, threshold: u64) -> MetaCondition {
+    MetaCondition {
+        target_bet_id: 0,
+        metric,
+        comparison,
+        threshold,
+        enabled: true,
+    }
+}
+
+fn target() -> PoolState {
+    PoolState {
+        yes_reserve: 60 * SOL,
+        no_reserve: 40 * SOL,
+        yes_entries: 7,
+        no_entries: 5,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn reads_each_metric_off_the_target() {
+    let target = target();
+    let value = |metric| condition(metric, PriceCondition::ABOVE, 0).value_of(&target).unwrap();
+    assert_eq!(value(MetaCondition::TOTAL_RESERVE), 100 * SOL);
+    assert_eq!(value(MetaCondition::YES_RESERVE), 60 * SOL);
+    assert_eq!(value(MetaCondition::NO_RESERVE), 40 * SOL);
+    assert_eq!(value(MetaCondition::PARTICIPANTS), 12);
+}
+
+#[test]
+fn compares_at_the_threshold() {
+    for (comparison, expected) in [
+        (PriceCondition::ABOVE, [false, false, true]),
+        (PriceCondition::AT_OR_ABOVE, [false, true, true]),
+        (PriceCondition::BELOW, [true, false, false]),
+        (PriceCondition::AT_OR_BELOW, [true, true, false]),
+    ] {
+        let meta = condition(MetaCondition::TOTAL_RESERVE, comparison, 100 * SOL);
+        let outcomes = [99 * SOL, 100 * SOL, 101 * SOL].map(|value| meta.is_met(value).unwrap());
+        assert_eq!(outcomes, expected, "comparison {comparison}");
+    }
+}
+
+#[test]
+fn rejects_unknown_metrics_and_unset_conditions() {
+    let unknown = condition(MetaCondition::PARTICIPANTS + 1, PriceCondition::ABOVE, 0);
+    assert!(unknown.validate().is_err());
+    assert!(unknown.value_of(&target()).is_err());
+    assert!(condition(0, PriceCondition::AT_OR_BELOW + 1, 0).validate().is_err());
+
+    assert!(MetaCondition::default().validate().is_err());
+    assert!(!PoolState::default().meta_condition.is_set());
+}
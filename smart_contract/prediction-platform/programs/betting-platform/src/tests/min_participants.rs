@@ -0,0 +1,94 @@
+//! Pools created with min_participants refund instead of paying profits when too few entries
+//! took part or one side drew none.
+
+use crate::error::BettingError;
+use crate::resolution::ResolutionSource;
+use crate::{resolve_short_of_participants, MainState, PoolState, RefereeProfile, WinnerSide};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+const NOW: i64 = 1_750_000_000;
+
+fn ended_pool(referee: Pubkey, yes_entries: u32, no_entries: u32) -> PoolState {
+    PoolState {
+        referee,
+        referee_accepted: true,
+        yes_reserve: 3_000,
+        no_reserve: 1_000,
+        yes_entries,
+        no_entries,
+        min_participants: 3,
+        end_timestamp: NOW - 60,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn degenerate_pools_fall_short() {
+    let referee = Pubkey::new_unique();
+    // Enough entries on both sides
+    assert!(!ended_pool(referee, 2, 1).short_of_participants());
+    // Too few entries overall
+    assert!(ended_pool(referee, 1, 1).short_of_participants());
+    // Nobody on the other side to win from
+    assert!(ended_pool(referee, 5, 0).short_of_participants());
+    assert!(ended_pool(referee, 0, 5).short_of_participants());
+    // An empty pool
+    assert!(ended_pool(referee, 0, 0).short_of_participants());
+}
+
+#[test]
+fn without_a_minimum_every_pool_pays_out() {
+    let pool = PoolState {
+        min_participants: 0,
+        ..ended_pool(Pubkey::new_unique(), 1, 0)
+    };
+    assert!(!pool.short_of_participants());
+}
+
+#[test]
+fn short_pools_refund_without_fees() {
+    let referee = Pubkey::new_unique();
+    let mut pool = ended_pool(referee, 4, 0);
+    let mut profile = RefereeProfile::default();
+    resolve_short_of_participants(
+        &mut pool,
+        &MainState::default(),
+        &mut profile,
+        &referee,
+        NOW,
+    )
+    .unwrap();
+
+    assert!(pool.refund_mode && !pool.complete);
+    assert_eq!(pool.winner, WinnerSide::Void);
+    assert_eq!(pool.resolution_source, ResolutionSource::TooFewParticipants);
+    assert!(pool.resolution_source.is_refund());
+    assert_eq!(pool.platform_fee_amount, 0);
+    assert_eq!(profile.resolutions, 1);
+}
+
+#[test]
+fn short_pools_still_wait_for_the_end_and_the_referee() {
+    let referee = Pubkey::new_unique();
+    let mut open = PoolState {
+        end_timestamp: NOW + 3_600,
+        ..ended_pool(referee, 1, 0)
+    };
+    let main_state = MainState::default();
+    let mut profile = RefereeProfile::default();
+    assert_eq!(
+        resolve_short_of_participants(&mut open, &main_state, &mut profile, &referee, NOW)
+            .unwrap_err(),
+        Error::from(BettingError::BetNotEnded)
+    );
+
+    let mut pool = ended_pool(referee, 1, 0);
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        resolve_short_of_participants(&mut pool, &main_state, &mut profile, &stranger, NOW)
+            .unwrap_err(),
+        Error::from(BettingError::Unauthorized)
+    );
+    assert_eq!(pool.resolution_source, ResolutionSource::Unresolved);
+}
@@ -0,0 +1,51 @@
+//! In-crate tests for logic that can run without a validator.
+
+mod accounting;
+mod claim_deadline;
+mod commit_reveal;
+mod committee;
+mod creator_bond;
+mod entry_transfer;
+mod exposure_caps;
+mod fee_curve;
+mod history;
+mod insurance;
+#[cfg(feature = "referee-registry")]
+mod invalid_resolution;
+mod final_odds;
+mod layout;
+mod lmsr;
+mod meta_market;
+#[cfg(feature = "referee-registry")]
+mod min_participants;
+#[cfg(feature = "oracle")]
+mod oracle_resolution;
+mod outcome_market;
+mod outcome_tokens;
+mod payout_projection;
+mod pool_update;
+mod quote;
+mod range;
+mod rate_limit;
+mod recovery;
+mod referee_queue;
+mod referee_fee;
+mod referral;
+mod replay;
+mod rent_reclaim;
+mod resolution_guard;
+mod revenue_share;
+mod risk;
+mod scalar;
+mod schema;
+mod seed_liquidity;
+mod self_referee;
+mod settlement_audit;
+mod settlement_report;
+mod split_deadlines;
+mod test_authz;
+mod token_gate;
+mod validation;
+mod vault_health;
+mod waitlist;
+mod winner_migration;
@@ -0,0 +1,106 @@
+//! Price-feed resolution reads a Pyth price account by hand and compares its aggregate price
+//! to the pool's threshold.
+
+use crate::pyth::{self, PythPrice, PYTH_PROGRAM_ID};
+use crate::PriceCondition;
+use anchor_lang::prelude::*;
+
+// A trading Pyth v2 price account reporting `price` * 10^`expo` at `publish_time`
+pub(super) fn price_account(price: i64, expo: i32, publish_time: i64) -> Vec<u8> {
+    let mut data = vec![0u8; 3_312];
+    data[0..4].copy_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+    data[4..8].copy_from_slice(&2u32.to_le_bytes());
+    data[8..12].copy_from_slice(&3u32.to_le_bytes());
+    data[20..24].copy_from_slice(&expo.to_le_bytes());
+    data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+    data[208..216].copy_from_slice(&price.to_le_bytes());
+    data[216..224].copy_from_slice(&25u64.to_le_bytes());
+    data[224..228].copy_from_slice(&1u32.to_le_bytes());
+    data
+}
+
+fn condition(comparison: u8, threshold: i64) -> PriceCondition {
+    PriceCondition {
+        feed: Pubkey::new_unique(),
+        comparison,
+        threshold,
+        expo: -2,
+    }
+}
+
+#[test]
+fn parses_the_aggregate_price() {
+    let data = price_account(6_512_345, -2, 1_750_000_000);
+    assert_eq!(
+        pyth::parse_price(&data).unwrap(),
+        PythPrice {
+            price: 6_512_345,
+            conf: 25,
+            expo: -2,
+            publish_time: 1_750_000_000,
+        }
+    );
+    // Negative prices survive the round trip
+    assert_eq!(pyth::parse_price(&price_account(-7, -2, 0)).unwrap().price, -7);
+}
+
+#[test]
+fn rejects_other_accounts() {
+    let valid = price_account(100, -2, 0);
+    assert!(pyth::parse_price(&valid[..pyth::MIN_ACCOUNT_LEN]).is_ok());
+    assert!(pyth::parse_price(&valid[..pyth::MIN_ACCOUNT_LEN - 1]).is_err());
+
+    // Wrong magic, version, account type (product) and a halted aggregate
+    for (at, value) in [(0, 0u32), (4, 1), (8, 2), (224, 2)] {
+        let mut data = valid.clone();
+        data[at..at + 4].copy_from_slice(&value.to_le_bytes());
+        assert!(pyth::parse_price(&data).is_err());
+    }
+
+    // Right layout, wrong owner
+    let key = Pubkey::new_unique();
+    let mut lamports = 1;
+    let mut data = valid.clone();
+    let owner = Pubkey::new_unique();
+    let feed = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    assert!(pyth::read_price(&feed).is_err());
+
+    let mut lamports = 1;
+    let mut data = valid;
+    let feed = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &PYTH_PROGRAM_ID,
+        false,
+        0,
+    );
+    assert_eq!(pyth::read_price(&feed).unwrap().price, 100);
+}
+
+#[test]
+fn condition_compares_against_the_threshold() {
+    let cases = [
+        (PriceCondition::ABOVE, [false, false, true]),
+        (PriceCondition::AT_OR_ABOVE, [false, true, true]),
+        (PriceCondition::BELOW, [true, false, false]),
+        (PriceCondition::AT_OR_BELOW, [true, true, false]),
+    ];
+    for (comparison, expected) in cases {
+        let condition = condition(comparison, 100);
+        assert!(condition.validate().is_ok());
+        for (price, is_yes) in [99, 100, 101].into_iter().zip(expected) {
+            assert_eq!(condition.is_met(price).unwrap(), is_yes);
+        }
+    }
+}
+
+#[test]
+fn condition_needs_a_feed_and_a_known_comparison() {
+    assert!(condition(4, 100).validate().is_err());
+    assert!(condition(4, 100).is_met(100).is_err());
+    assert!(PriceCondition::default().validate().is_err());
+    assert!(!PriceCondition::default().is_set());
+}
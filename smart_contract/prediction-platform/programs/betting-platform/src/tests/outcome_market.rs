@@ -0,0 +1,45 @@
+//! Multi-outcome markets price and settle like yes/no pools, with outcomes indexed by u8.
+
+use crate::{implied_outcome_price, implied_prices, OutcomeMarket, PRICE_SCALE};
+
+const SOL: u64 = 1_000_000_000;
+
+#[test]
+fn two_outcomes_price_like_a_yes_no_pool() {
+    let (yes_price, no_price) = implied_prices(3 * SOL, SOL);
+    assert_eq!(implied_outcome_price(&[3 * SOL, SOL], 0), yes_price);
+    assert_eq!(implied_outcome_price(&[3 * SOL, SOL], 1), no_price);
+}
+
+#[test]
+fn prices_sum_to_one_across_outcomes() {
+    let reserves = [0, 2 * SOL, 5 * SOL, SOL];
+    let prices: Vec<u128> = (0..reserves.len())
+        .map(|index| implied_outcome_price(&reserves, index))
+        .collect();
+    let sum: u128 = prices.iter().sum();
+    assert!(PRICE_SCALE - sum < reserves.len() as u128);
+    // More money on an outcome makes it more expensive
+    assert!(prices[2] > prices[1] && prices[1] > prices[3] && prices[3] > prices[0]);
+}
+
+#[test]
+fn winners_split_every_losing_reserve_after_fees() {
+    let market = OutcomeMarket {
+        outcomes: vec!["A".into(), "B".into(), "C".into()],
+        reserves: vec![2 * SOL, 3 * SOL, 5 * SOL],
+        supplies: vec![4 * SOL, 3 * SOL, 2 * SOL],
+        creator_fee_accrued: SOL / 10,
+        platform_fee_amount: SOL / 5,
+        complete: true,
+        winner: 1,
+        ..Default::default()
+    };
+    assert_eq!(market.total_reserve(), 10 * SOL);
+    assert_eq!(
+        market.winner_profit_pot(),
+        (7 * SOL - SOL / 10 - SOL / 5) as u128
+    );
+    assert_eq!(market.check_outcome(2).unwrap(), 2);
+    assert!(market.check_outcome(3).is_err());
+}
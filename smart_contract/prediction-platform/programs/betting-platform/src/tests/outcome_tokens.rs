@@ -0,0 +1,86 @@
+//! Tokenized positions must settle against the same totals as entry claims.
+
+use crate::fees::settle_resolution_fees;
+use crate::{apply_deposit, settle_claim, settle_token_claim, EntryState, PoolState, WinnerSide};
+use anchor_lang::prelude::Pubkey;
+
+const SOL: u64 = 1_000_000_000;
+
+// Three YES entries and one NO entry on a pool that YES wins
+fn resolved_pool() -> (PoolState, Vec<EntryState>) {
+    let mut pool = PoolState {
+        creator_fee_bps: 100,
+        installment_count: 1,
+        history_disabled: true,
+        outcome_tokens: true,
+        ..Default::default()
+    };
+    let mut entries: Vec<EntryState> = (0..4).map(|_| EntryState::default()).collect();
+    for (i, (amount, is_yes)) in [(SOL, true), (3 * SOL, true), (2 * SOL, false), (5 * SOL, true)]
+        .into_iter()
+        .enumerate()
+    {
+        let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(
+            &mut pool,
+            &mut entries[i],
+            None,
+            Pubkey::default(),
+            amount,
+            is_yes,
+            pricing_reserves,
+        )
+        .unwrap();
+    }
+    pool.complete = true;
+    pool.winner = WinnerSide::Yes;
+    settle_resolution_fees(&mut pool, true, 100);
+    (pool, entries)
+}
+
+// What tokenize_entry does to the pool and entry
+fn tokenize(pool: &mut PoolState, entry: &mut EntryState) -> u64 {
+    let amount = entry.token_balance;
+    pool.tokenized_yes_supply += amount;
+    pool.tokenized_yes_principal += entry.deposited_sol_amount;
+    entry.token_balance = 0;
+    entry.deposited_sol_amount = 0;
+    amount
+}
+
+#[test]
+fn token_claims_pay_what_entry_claims_would() {
+    let (mut pool, mut entries) = resolved_pool();
+    let mut baseline = pool.clone();
+    let entry_total: u64 = [0, 1, 3]
+        .iter()
+        .map(|&i| settle_claim(&baseline, &mut entries[i].clone(), 0).unwrap())
+        .sum();
+
+    let first = tokenize(&mut pool, &mut entries[1]);
+    let second = tokenize(&mut pool, &mut entries[3]);
+    let mut paid = settle_claim(&pool, &mut entries[0], 0).unwrap();
+    // The tokens changed hands and are redeemed in uneven batches
+    paid += settle_token_claim(&mut pool, true, first / 3).unwrap();
+    paid += settle_token_claim(&mut pool, true, second).unwrap();
+    paid += settle_token_claim(&mut pool, true, first - first / 3).unwrap();
+
+    assert!(paid <= entry_total && entry_total - paid <= 3, "{paid} vs {entry_total}");
+    assert_eq!(pool.tokenized_yes_supply, 0);
+    assert_eq!(pool.tokenized_yes_principal, 0);
+
+    // Losing tokens and over-redemptions are rejected
+    assert!(settle_token_claim(&mut baseline, false, 1).is_err());
+    assert!(settle_token_claim(&mut pool, true, 1).is_err());
+}
+
+#[test]
+fn refund_mode_returns_tokenized_principal() {
+    let (mut pool, mut entries) = resolved_pool();
+    pool.complete = false;
+    pool.winner = WinnerSide::Unresolved;
+    pool.refund_mode = true;
+
+    let amount = tokenize(&mut pool, &mut entries[3]);
+    assert_eq!(settle_token_claim(&mut pool, true, amount).unwrap(), 5 * SOL);
+}
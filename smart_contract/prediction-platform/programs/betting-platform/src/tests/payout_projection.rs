@@ -0,0 +1,68 @@
+//! The payout preview must match what settle_claim pays after resolution.
+
+use crate::constants::PAYOUT_PER_TOKEN_SCALE;
+use crate::fees::{refresh_payout_projection, settle_resolution_fees};
+use crate::{apply_deposit, settle_claim, EntryState, PoolState, WinnerSide};
+use anchor_lang::prelude::Pubkey;
+
+const SOL: u64 = 1_000_000_000;
+const PLATFORM_BPS: u64 = 150;
+
+fn check_projection(winner: bool) {
+    let mut pool = PoolState {
+        creator_fee_bps: 100,
+        creator_donation_bps: 2_000,
+        installment_count: 1,
+        history_disabled: true,
+        ..Default::default()
+    };
+    let deposits = [(SOL, true), (4 * SOL, false), (2 * SOL, true), (SOL / 3, false)];
+    let mut entries: Vec<EntryState> = deposits.iter().map(|_| EntryState::default()).collect();
+    for (i, &(amount, is_yes)) in deposits.iter().enumerate() {
+        let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(
+            &mut pool,
+            &mut entries[i],
+            None,
+            Pubkey::default(),
+            amount,
+            is_yes,
+            pricing_reserves,
+        )
+        .unwrap();
+        refresh_payout_projection(&mut pool, PLATFORM_BPS);
+    }
+
+    let per_token = if winner {
+        pool.projected_payout_per_token_yes
+    } else {
+        pool.projected_payout_per_token_no
+    };
+    assert!(per_token > 0);
+
+    pool.complete = true;
+    pool.winner = WinnerSide::from_is_yes(winner);
+    settle_resolution_fees(&mut pool, winner, PLATFORM_BPS);
+    for entry in entries.iter_mut().filter(|entry| entry.is_yes == winner) {
+        let projected = entry.deposited_sol_amount as u128
+            + entry.token_balance as u128 * per_token as u128 / PAYOUT_PER_TOKEN_SCALE as u128;
+        let paid = settle_claim(&pool, entry, 0).unwrap() as u128;
+        // The per-token figure is floored, so the preview may trail by one lamport per
+        // PAYOUT_PER_TOKEN_SCALE tokens, plus the claim's own rounding
+        let tolerance = 1 + entry.token_balance as u128 / PAYOUT_PER_TOKEN_SCALE as u128;
+        assert!(
+            paid.abs_diff(projected) <= tolerance,
+            "paid {paid}, projected {projected}"
+        );
+    }
+}
+
+#[test]
+fn projection_matches_yes_payouts() {
+    check_projection(true);
+}
+
+#[test]
+fn projection_matches_no_payouts() {
+    check_projection(false);
+}
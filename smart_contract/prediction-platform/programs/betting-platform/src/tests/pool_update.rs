@@ -0,0 +1,54 @@
+//! update_pool's audit trail: what UpdatePoolEvent reports and the counters it bumps.
+
+use crate::{record_pool_update, PoolState, UpdatableFields};
+use anchor_lang::prelude::Pubkey;
+
+fn pool() -> PoolState {
+    PoolState {
+        bet_id: 9,
+        title: "Will it rain?".to_string(),
+        description: "Tomorrow, in Lisbon".to_string(),
+        end_timestamp: 1_000,
+        referee: Pubkey::new_from_array([1; 32]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn only_changed_fields_are_reported() {
+    let mut pool = pool();
+    let before = UpdatableFields::of(&pool);
+    pool.end_timestamp = 2_000;
+    pool.referee = Pubkey::new_from_array([2; 32]);
+
+    let updater = Pubkey::new_from_array([3; 32]);
+    let event = record_pool_update(&before, &mut pool, updater, 500).unwrap();
+    assert_eq!((event.bet_id, event.updater), (9, updater));
+    assert_eq!((event.previous_end_timestamp, event.end_timestamp), (Some(1_000), Some(2_000)));
+    assert_eq!(event.previous_referee, Some(Pubkey::new_from_array([1; 32])));
+    assert_eq!(event.referee, Some(Pubkey::new_from_array([2; 32])));
+    assert!(event.title.is_none() && event.previous_title.is_none());
+    assert!(event.description.is_none() && event.max_pool_reserve.is_none());
+    assert_eq!((pool.update_count, pool.last_updated_timestamp), (1, 500));
+}
+
+#[test]
+fn every_update_that_changes_something_counts() {
+    let mut pool = pool();
+    for (count, now) in [(1, 100), (2, 200)] {
+        let before = UpdatableFields::of(&pool);
+        pool.title.push('!');
+        let event = record_pool_update(&before, &mut pool, Pubkey::default(), now).unwrap();
+        assert_eq!(event.title, Some(pool.title.clone()));
+        assert_eq!(event.update_count, count);
+    }
+    assert_eq!(pool.last_updated_timestamp, 200);
+}
+
+#[test]
+fn restating_current_values_leaves_no_trace() {
+    let mut pool = pool();
+    let before = UpdatableFields::of(&pool);
+    assert!(record_pool_update(&before, &mut pool, Pubkey::default(), 100).is_none());
+    assert_eq!((pool.update_count, pool.last_updated_timestamp), (0, 0));
+}
@@ -0,0 +1,81 @@
+//! quote_deposit must report what a real deposit would mint and pay.
+
+use crate::constants::PAYOUT_PER_TOKEN_SCALE;
+use crate::fees::{platform_fee_bps, refresh_payout_projection};
+use crate::{apply_deposit, deposit_quote, EntryState, MainState, PoolState, ScalarBounds};
+use anchor_lang::prelude::Pubkey;
+
+const SOL: u64 = 1_000_000_000;
+
+fn pool() -> PoolState {
+    let mut pool = PoolState {
+        creator_fee_bps: 100,
+        history_disabled: true,
+        ..Default::default()
+    };
+    for (amount, is_yes) in [(3 * SOL, true), (2 * SOL, false)] {
+        let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(
+            &mut pool,
+            &mut EntryState::default(),
+            None,
+            Pubkey::default(),
+            amount,
+            is_yes,
+            pricing_reserves,
+        )
+        .unwrap();
+    }
+    pool
+}
+
+#[test]
+fn quote_matches_deposit() {
+    let main_state = MainState {
+        platform_fee_percent: 150,
+        ..Default::default()
+    };
+    let before = pool();
+    let quoted = deposit_quote(&main_state, &before, false, SOL).unwrap();
+
+    let mut after = before.clone();
+    let mut entry = EntryState::default();
+    let pricing_reserves = (after.yes_reserve, after.no_reserve);
+    let minted = apply_deposit(
+        &mut after,
+        &mut entry,
+        None,
+        Pubkey::default(),
+        SOL,
+        false,
+        pricing_reserves,
+    )
+    .unwrap();
+    assert_eq!(quoted.token_amount, minted);
+
+    // The pool is now even, so the post-trade odds are too
+    assert_eq!(quoted.yes_price, quoted.no_price);
+
+    // The fee deposit() projects with; nothing in builds without platform fees
+    let total_reserve = after.yes_reserve.saturating_add(after.no_reserve);
+    refresh_payout_projection(&mut after, platform_fee_bps(&main_state, total_reserve));
+    let profit = minted as u128 * after.projected_payout_per_token_no as u128
+        / PAYOUT_PER_TOKEN_SCALE as u128;
+    assert!(profit > 0);
+    assert_eq!(quoted.estimated_payout as u128, SOL as u128 + profit);
+
+    // Quoting leaves the pool untouched
+    assert_eq!(before.no_reserve, 2 * SOL);
+}
+
+#[test]
+fn quote_rejects_scalar_pools_and_empty_amounts() {
+    let main_state = MainState::default();
+    assert!(deposit_quote(&main_state, &pool(), true, 0).is_err());
+
+    let scalar = PoolState {
+        scalar_bounds: ScalarBounds { min: 0, max: 100 },
+        ..pool()
+    };
+    assert!(deposit_quote(&main_state, &scalar, true, SOL).is_err());
+}
@@ -0,0 +1,39 @@
+//! Range markets map a submitted value to exactly one bucket outcome.
+
+use crate::{PoolState, RangeBucket};
+
+fn bucket(lower: i64, upper: i64, is_yes: bool) -> RangeBucket {
+    RangeBucket {
+        lower,
+        upper,
+        is_yes,
+    }
+}
+
+#[test]
+fn buckets_must_be_sorted_disjoint_and_two_sided() {
+    assert!(RangeBucket::validate_all(&[bucket(0, 3, false), bucket(3, 100, true)]).is_ok());
+    // Gaps are fine
+    assert!(RangeBucket::validate_all(&[bucket(-10, 0, true), bucket(5, 10, false)]).is_ok());
+
+    assert!(RangeBucket::validate_all(&[bucket(0, 3, false)]).is_err());
+    assert!(RangeBucket::validate_all(&[bucket(0, 3, true), bucket(3, 5, true)]).is_err());
+    assert!(RangeBucket::validate_all(&[bucket(0, 4, false), bucket(3, 5, true)]).is_err());
+    assert!(RangeBucket::validate_all(&[bucket(3, 5, true), bucket(0, 3, false)]).is_err());
+    assert!(RangeBucket::validate_all(&[bucket(3, 3, true), bucket(3, 5, false)]).is_err());
+}
+
+#[test]
+fn value_maps_to_its_bucket() {
+    // "How many goals?": 0-2 is NO, 3 or more is YES
+    let pool = PoolState {
+        range_buckets: vec![bucket(0, 3, false), bucket(3, i64::MAX, true)],
+        ..Default::default()
+    };
+    assert!(!pool.range_outcome(0).unwrap());
+    assert!(!pool.range_outcome(2).unwrap());
+    assert!(pool.range_outcome(3).unwrap());
+    assert!(pool.range_outcome(-1).is_err());
+
+    assert!(PoolState::default().range_outcome(3).is_err());
+}
@@ -0,0 +1,33 @@
+//! Per-creator pool creation limit over a rolling 24h window.
+
+use crate::constants::CREATION_BUCKET_SECS;
+use crate::CreatorRateLimit;
+
+const DAY: i64 = 24 * 60 * 60;
+const START: i64 = 1_750_000_000;
+
+#[test]
+fn limit_blocks_the_extra_pool_until_the_window_rolls() {
+    let mut limit = CreatorRateLimit::default();
+    limit.record_creation(2, START).unwrap();
+    limit.record_creation(2, START + CREATION_BUCKET_SECS).unwrap();
+    assert!(limit.record_creation(2, START + DAY - CREATION_BUCKET_SECS).is_err());
+
+    // The first pool's slot leaves the window, the second's is still in it
+    let next_day = START + DAY;
+    assert_eq!(limit.created_in_window(next_day), 1);
+    limit.record_creation(2, next_day).unwrap();
+    assert!(limit.record_creation(2, next_day).is_err());
+}
+
+#[test]
+fn zero_means_unlimited_but_still_counts() {
+    let mut limit = CreatorRateLimit::default();
+    for _ in 0..5 {
+        limit.record_creation(0, START).unwrap();
+    }
+    assert_eq!(limit.created_in_window(START), 5);
+    // Turning a limit on takes the pools already created into account
+    assert!(limit.record_creation(5, START).is_err());
+    assert_eq!(limit.created_in_window(START + 2 * DAY), 0);
+}
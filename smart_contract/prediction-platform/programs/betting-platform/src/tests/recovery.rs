@@ -0,0 +1,57 @@
+//! Social recovery: when a recovery key may take over an entry's claims.
+
+use crate::constants::RECOVERY_CHALLENGE_SECS;
+use crate::error::BettingError;
+use crate::EntryState;
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+const INITIATED_AT: i64 = 1_000;
+
+fn recovery_key() -> Pubkey {
+    Pubkey::new_from_array([3; 32])
+}
+
+fn recovering_entry() -> EntryState {
+    EntryState {
+        user: Pubkey::new_from_array([1; 32]),
+        recovery_key: recovery_key(),
+        recovery_initiated_at: INITIATED_AT,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn the_recovery_key_claims_once_the_challenge_window_passed() {
+    let entry = recovering_entry();
+    let unlocks_at = INITIATED_AT + RECOVERY_CHALLENGE_SECS;
+    assert_eq!(entry.recovery_unlocks_at(), unlocks_at);
+    assert_eq!(
+        entry.check_recovery(&recovery_key(), unlocks_at - 1).err(),
+        Some(Error::from(BettingError::RecoveryChallengeActive))
+    );
+    assert!(entry.check_recovery(&recovery_key(), unlocks_at).is_ok());
+}
+
+#[test]
+fn only_the_registered_key_recovers() {
+    let entry = recovering_entry();
+    let not_recovery_key = Some(Error::from(BettingError::NotRecoveryKey));
+    let now = INITIATED_AT + RECOVERY_CHALLENGE_SECS;
+    assert_eq!(entry.check_recovery(&entry.user, now).err(), not_recovery_key);
+    // An entry without a recovery key is not recoverable by the default key either
+    let unprotected = EntryState::default();
+    assert_eq!(unprotected.check_recovery(&Pubkey::default(), now).err(), not_recovery_key);
+}
+
+#[test]
+fn a_cancelled_recovery_pays_nothing() {
+    let entry = EntryState {
+        recovery_initiated_at: 0,
+        ..recovering_entry()
+    };
+    assert_eq!(
+        entry.check_recovery(&recovery_key(), i64::MAX).err(),
+        Some(Error::from(BettingError::NoRecoveryPending))
+    );
+}
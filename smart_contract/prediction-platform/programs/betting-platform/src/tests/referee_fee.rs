@@ -0,0 +1,91 @@
+//! Referee fees: the resolving referee's cut of the platform fee, held until they claim it.
+
+use crate::fees::{referee_fee, referral_fee_escrow, settle_resolution_fees};
+use crate::resolution::ResolutionSource;
+use crate::{apply_deposit, finalize_outcome, EntryState, MainState, PoolState, WinnerSide};
+use anchor_lang::prelude::Pubkey;
+
+const PLATFORM_FEE_BPS: u64 = 500;
+
+// 10_000 vs 10_000 of deposits in a pool that ended at 0
+fn funded_pool(referee_fee_bps: u64) -> PoolState {
+    let mut pool = PoolState {
+        history_disabled: true,
+        installment_count: 1,
+        referee_fee_bps,
+        referee: Pubkey::new_from_array([3; 32]),
+        ..Default::default()
+    };
+    for (is_yes, referrer) in [(true, Pubkey::new_from_array([7; 32])), (false, Pubkey::default())] {
+        let mut entry = EntryState {
+            referrer,
+            ..Default::default()
+        };
+        let reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(&mut pool, &mut entry, None, Pubkey::default(), 10_000, is_yes, reserves)
+            .unwrap();
+    }
+    pool
+}
+
+// A settled 10_000 vs 10_000 pool won by YES: a 1_000 platform fee
+fn settled_pool(referee_fee_bps: u64, referral_bps: u64) -> PoolState {
+    let mut pool = funded_pool(referee_fee_bps);
+    pool.complete = true;
+    pool.winner = WinnerSide::from_is_yes(true);
+    settle_resolution_fees(&mut pool, true, PLATFORM_FEE_BPS);
+    pool.referral_fee_escrow = referral_fee_escrow(&pool, true, referral_bps);
+    pool
+}
+
+#[test]
+fn the_referee_takes_their_bps_of_the_platform_fee() {
+    let pool = settled_pool(2_000, 0);
+    assert_eq!(pool.platform_fee_amount, 1_000);
+    assert_eq!(referee_fee(&pool), 200);
+    assert_eq!(referee_fee(&settled_pool(0, 0)), 0);
+}
+
+#[test]
+fn the_referee_fee_never_eats_into_the_referral_escrow() {
+    let pool = settled_pool(10_000, 10_000);
+    assert!(pool.referral_fee_escrow > 0);
+    assert_eq!(
+        referee_fee(&pool),
+        pool.platform_fee_amount - pool.referral_fee_escrow
+    );
+}
+
+#[test]
+fn an_unclaimed_referee_fee_is_neither_swept_nor_settled() {
+    let mut pool = settled_pool(2_000, 0);
+    pool.referee_fee_amount = referee_fee(&pool);
+    pool.platform_fee_claimed = true;
+    pool.creator_fee_claimed = true;
+    pool.vault_liabilities = 5_000;
+    assert_eq!(pool.unclaimed_winnings(), 4_800);
+    assert!(!pool.is_fully_settled());
+
+    pool.referee_fee_claimed = true;
+    assert_eq!(pool.unclaimed_winnings(), 5_000);
+}
+
+#[test]
+fn only_the_referees_own_resolution_earns_them_the_fee() {
+    let mut main_state = MainState {
+        platform_fee_percent: PLATFORM_FEE_BPS,
+        ..Default::default()
+    };
+    let mut pool = funded_pool(2_000);
+    finalize_outcome(&mut pool, &mut main_state, ResolutionSource::Referee, true, 1).unwrap();
+    assert_eq!(pool.referee_fee_amount, referee_fee(&pool));
+    assert_eq!(pool.referee_fee_recipient, pool.referee);
+
+    // A provisional outcome is the referee's proposal, settled by whoever finalized it
+    for source in [ResolutionSource::Provisional, ResolutionSource::Committee] {
+        let mut pool = funded_pool(2_000);
+        finalize_outcome(&mut pool, &mut main_state, source, true, 1).unwrap();
+        assert_eq!(pool.referee_fee_amount, 0);
+        assert_eq!(pool.referee_fee_recipient, Pubkey::default());
+    }
+}
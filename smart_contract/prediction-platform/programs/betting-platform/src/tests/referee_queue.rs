@@ -0,0 +1,28 @@
+//! A referee's queue lists the pools they still owe a resolution, oldest first.
+
+use crate::RefereeQueue;
+
+#[test]
+fn resolved_pools_leave_the_queue_in_order() {
+    let mut queue = RefereeQueue {
+        bet_ids: vec![4, 7, 9],
+        ..Default::default()
+    };
+    assert!(queue.remove(7));
+    assert_eq!(queue.bet_ids, [4, 9]);
+    assert!(!queue.is_queued(7));
+    // Resolving a pool that was never queued leaves the queue alone
+    assert!(!queue.remove(7));
+    assert_eq!(queue.bet_ids, [4, 9]);
+}
+
+#[test]
+fn a_full_queue_fits_its_account() {
+    let queue = RefereeQueue {
+        bet_ids: vec![u64::MAX; RefereeQueue::MAX_PENDING],
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    anchor_lang::AnchorSerialize::serialize(&queue, &mut data).unwrap();
+    assert_eq!(data.len(), RefereeQueue::MAX_SIZE);
+}
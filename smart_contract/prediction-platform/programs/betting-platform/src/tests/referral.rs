@@ -0,0 +1,103 @@
+//! Referral fees: the escrow held back from the platform fee and each referred entry's slice.
+
+use crate::fees::{referral_fee_escrow, referral_slice, settle_resolution_fees};
+use crate::{apply_deposit, EntryState, PoolState, WinnerSide};
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+
+const PLATFORM_FEE_BPS: u64 = 500;
+
+fn referrer() -> Pubkey {
+    Pubkey::new_from_array([7; 32])
+}
+
+// A pool that took one entry per (is_yes, amount, referred) deposit
+fn pool_with(deposits: &[(bool, u64, bool)]) -> (PoolState, Vec<EntryState>) {
+    let mut pool = PoolState {
+        history_disabled: true,
+        installment_count: 1,
+        ..Default::default()
+    };
+    let entries = deposits
+        .iter()
+        .map(|&(is_yes, amount, referred)| {
+            let mut entry = EntryState::default();
+            if referred {
+                entry.referrer = referrer();
+            }
+            let reserves = (pool.yes_reserve, pool.no_reserve);
+            apply_deposit(&mut pool, &mut entry, None, Pubkey::default(), amount, is_yes, reserves)
+                .unwrap();
+            entry
+        })
+        .collect();
+    (pool, entries)
+}
+
+fn resolve(pool: &mut PoolState, is_yes: bool, referral_bps: u64) {
+    pool.complete = true;
+    pool.winner = WinnerSide::from_is_yes(is_yes);
+    settle_resolution_fees(pool, is_yes, PLATFORM_FEE_BPS);
+    pool.referral_fee_escrow = referral_fee_escrow(pool, is_yes, referral_bps);
+}
+
+#[test]
+fn deposits_count_towards_their_sides_referred_total() {
+    let (pool, _) = pool_with(&[(true, 3_000, true), (true, 1_000, false), (false, 2_000, true)]);
+    assert_eq!((pool.referred_yes, pool.referred_no), (3_000, 2_000));
+}
+
+#[test]
+fn the_escrow_is_the_referred_share_of_the_referral_cut() {
+    let (mut pool, _) =
+        pool_with(&[(true, 5_000, true), (true, 5_000, false), (false, 10_000, true)]);
+    resolve(&mut pool, true, 2_000);
+    // 5% of 20_000 is 1_000; referrers get 20% of it, a quarter of the fee base was referred YES
+    assert_eq!(pool.platform_fee_amount, 1_000);
+    assert_eq!(pool.referral_fee_escrow, 50);
+}
+
+#[test]
+fn only_referred_winning_entries_get_a_slice() {
+    let (mut pool, entries) =
+        pool_with(&[(true, 4_000, true), (true, 4_000, false), (false, 12_000, true)]);
+    resolve(&mut pool, true, 5_000);
+    assert_eq!(referral_slice(&pool, &entries[0]), pool.referral_fee_escrow);
+    assert_eq!(referral_slice(&pool, &entries[1]), 0);
+    assert_eq!(referral_slice(&pool, &entries[2]), 0);
+}
+
+#[test]
+fn a_credited_entry_has_no_slice_left() {
+    let (mut pool, mut entries) = pool_with(&[(true, 4_000, true), (false, 4_000, false)]);
+    resolve(&mut pool, true, 5_000);
+    assert!(referral_slice(&pool, &entries[0]) > 0);
+    entries[0].referral_credited = true;
+    assert_eq!(referral_slice(&pool, &entries[0]), 0);
+}
+
+#[test]
+fn no_referral_cut_keeps_the_whole_platform_fee() {
+    let (mut pool, entries) = pool_with(&[(true, 4_000, true), (false, 4_000, true)]);
+    resolve(&mut pool, true, 0);
+    assert_eq!(pool.referral_fee_escrow, 0);
+    assert_eq!(referral_slice(&pool, &entries[0]), 0);
+}
+
+proptest! {
+    #[test]
+    fn slices_never_exceed_the_escrow_nor_the_platform_fee(
+        deposits in prop::collection::vec(
+            (any::<bool>(), 1u64..1_000_000_000, any::<bool>()),
+            1..12,
+        ),
+        winner_is_yes in any::<bool>(),
+        referral_bps in 0u64..=10_000,
+    ) {
+        let (mut pool, entries) = pool_with(&deposits);
+        resolve(&mut pool, winner_is_yes, referral_bps);
+        prop_assert!(pool.referral_fee_escrow <= pool.platform_fee_amount);
+        let slices: u64 = entries.iter().map(|entry| referral_slice(&pool, entry)).sum();
+        prop_assert!(slices <= pool.referral_fee_escrow);
+    }
+}
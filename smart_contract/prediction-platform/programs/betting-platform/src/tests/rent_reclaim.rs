@@ -0,0 +1,88 @@
+//! Entries and pools may only be closed once nothing is left to pay out of them.
+
+use crate::{EntryState, PoolState, WinnerSide};
+
+fn settled_pool() -> PoolState {
+    PoolState {
+        complete: true,
+        winner: WinnerSide::Yes,
+        platform_fee_claimed: true,
+        creator_fee_claimed: true,
+        history_closed: true,
+        ..Default::default()
+    }
+}
+
+fn entry(is_yes: bool) -> EntryState {
+    EntryState {
+        deposited_sol_amount: 1_000,
+        token_balance: 1_000,
+        is_yes,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn entries_settle_once_their_payout_is_taken() {
+    let pool = settled_pool();
+    let mut winner = entry(true);
+    assert!(!winner.is_settled(&pool));
+    winner.is_claimed = true;
+    assert!(winner.is_settled(&pool));
+
+    let mut loser = entry(false);
+    assert!(loser.is_settled(&pool));
+    loser.insured_amount = 500;
+    assert!(!loser.is_settled(&pool));
+    loser.insurance_claimed = true;
+    assert!(loser.is_settled(&pool));
+
+    // Nothing settles before resolution; refunds settle on their own flag
+    assert!(!entry(false).is_settled(&PoolState::default()));
+    let refunded = PoolState {
+        refund_mode: true,
+        ..Default::default()
+    };
+    assert!(!entry(true).is_settled(&refunded));
+    assert!(EntryState::default().is_settled(&refunded));
+}
+
+#[test]
+fn pools_settle_after_entries_fees_history_and_guarantee() {
+    assert!(settled_pool().is_fully_settled());
+    assert!(!PoolState::default().is_fully_settled());
+
+    for unsettled in [
+        PoolState {
+            open_entries: 1,
+            ..settled_pool()
+        },
+        PoolState {
+            creator_fee_claimed: false,
+            creator_fee_accrued: 10,
+            ..settled_pool()
+        },
+        PoolState {
+            platform_fee_claimed: false,
+            ..settled_pool()
+        },
+        PoolState {
+            history_closed: false,
+            ..settled_pool()
+        },
+        PoolState {
+            guaranteed: true,
+            ..settled_pool()
+        },
+    ] {
+        assert!(!unsettled.is_fully_settled());
+    }
+
+    // No fees in a refunded pool, and no history to close when it was disabled
+    let refunded = PoolState {
+        refund_mode: true,
+        history_disabled: true,
+        ..Default::default()
+    };
+    assert!(refunded.is_fully_settled());
+}
@@ -0,0 +1,151 @@
+//! Event replay: folding a pool's events rebuilds exactly the state verify_replay hashes.
+
+use crate::error::BettingError;
+use crate::replay::{replay, PoolEvent, PoolReplay};
+use crate::{
+    apply_deposit, CompleteEvent, CreateEvent, DepositEvent, EntryState, PoolState,
+    RefundModeEvent, WinnerSide,
+};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorSerialize, Discriminator};
+
+const BET_ID: u64 = 7;
+
+fn create_event(bet_id: u64, seed_yes: u64, seed_no: u64) -> CreateEvent {
+    CreateEvent {
+        creator: Pubkey::new_unique(),
+        bet_id,
+        title: "Replayed".to_string(),
+        description: String::new(),
+        end_timestamp: -1,
+        referee: Pubkey::new_unique(),
+        share_uuid: String::new(),
+        unit_symbol: String::new(),
+        decimals: 0,
+        side_a_label: String::new(),
+        side_b_label: String::new(),
+        creator_donation_bps: 0,
+        range_buckets: vec![],
+        claim_window_secs: 0,
+        rent_payer: Pubkey::new_unique(),
+        pool_rent_lamports: 0,
+        history_rent_lamports: 0,
+        initial_yes_liquidity: seed_yes,
+        initial_no_liquidity: seed_no,
+        opens_at: 0,
+        timestamp: 0,
+    }
+}
+
+fn deposit_event(bet_id: u64, is_yes: bool, sol_amount: u64, token_amount: u64) -> DepositEvent {
+    DepositEvent {
+        user: Pubkey::new_unique(),
+        bet_id,
+        sol_amount,
+        token_amount,
+        is_yes,
+        insurance_premium: 0,
+        timestamp: 0,
+    }
+}
+
+fn complete_event(bet_id: u64, winner: WinnerSide) -> CompleteEvent {
+    CompleteEvent {
+        referee: Pubkey::new_unique(),
+        bet_id,
+        winner,
+        creator_fee_donated: 0,
+        timestamp: 0,
+    }
+}
+
+fn logged<T: AnchorSerialize + Discriminator>(event: &T) -> Vec<u8> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    event.serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn replaying_a_pools_events_reproduces_its_state() {
+    let (seed_yes, seed_no) = (2_000_000, 1_000_000);
+    let mut pool_state = PoolState {
+        bet_id: BET_ID,
+        yes_reserve: seed_yes,
+        no_reserve: seed_no,
+        total_reserve: seed_yes + seed_no,
+        history_disabled: true,
+        ..Default::default()
+    };
+    let create = create_event(BET_ID, seed_yes, seed_no);
+    let mut events = vec![PoolEvent::Create(Box::new(create))];
+    for (is_yes, amount) in [(true, 500_000), (false, 3_000_000), (true, 1_250_000)] {
+        let reserves = (pool_state.yes_reserve, pool_state.no_reserve);
+        let mut entry_state = EntryState::default();
+        let token_amount = apply_deposit(
+            &mut pool_state,
+            &mut entry_state,
+            None,
+            Pubkey::default(),
+            amount,
+            is_yes,
+            reserves,
+        )
+        .unwrap();
+        events.push(PoolEvent::Deposit(deposit_event(BET_ID, is_yes, amount, token_amount)));
+    }
+    pool_state.complete = true;
+    pool_state.winner = WinnerSide::No;
+    events.push(PoolEvent::Complete(complete_event(BET_ID, WinnerSide::No)));
+
+    let replayed = replay(&events).unwrap();
+    assert_eq!(replayed, PoolReplay::of(&pool_state));
+    assert_eq!(replayed.replay_hash(), PoolReplay::of(&pool_state).replay_hash());
+
+    // A missed deposit shows up in the hash
+    events.remove(2);
+    assert_ne!(replay(&events).unwrap().replay_hash(), replayed.replay_hash());
+}
+
+#[test]
+fn logged_events_decode_into_the_replay_stream() {
+    let create = logged(&create_event(BET_ID, 0, 0));
+    let deposit = logged(&deposit_event(BET_ID, true, 100, 90));
+    let refund = logged(&RefundModeEvent {
+        bet_id: BET_ID,
+        triggered_by: Pubkey::new_unique(),
+        timestamp: 0,
+    });
+    let events: Vec<PoolEvent> = [create, deposit, refund]
+        .iter()
+        .map(|data| PoolEvent::decode(data).unwrap().unwrap())
+        .collect();
+    let replayed = replay(&events).unwrap();
+    assert_eq!((replayed.yes_reserve, replayed.yes_supply), (100, 90));
+    assert_eq!((replayed.refund_mode, replayed.winner), (true, WinnerSide::Void));
+
+    // Events replay does not consume are skipped; a truncated body is rejected
+    assert!(PoolEvent::decode(&[0u8; 16]).unwrap().is_none());
+    let deposit = logged(&deposit_event(BET_ID, true, 100, 90));
+    assert_eq!(
+        PoolEvent::decode(&deposit[..deposit.len() - 1]).err(),
+        Some(Error::from(BettingError::InvalidReplayEvent))
+    );
+}
+
+#[test]
+fn streams_the_program_could_not_have_emitted_are_rejected() {
+    let out_of_order = Some(Error::from(BettingError::ReplayOutOfOrder));
+    let create = || PoolEvent::Create(Box::new(create_event(BET_ID, 0, 0)));
+    let deposit = |bet_id| PoolEvent::Deposit(deposit_event(bet_id, true, 100, 90));
+    let complete = || PoolEvent::Complete(complete_event(BET_ID, WinnerSide::Yes));
+
+    assert_eq!(replay(&[deposit(BET_ID)]).err(), out_of_order);
+    assert_eq!(replay(&[create(), create()]).err(), out_of_order);
+    assert_eq!(replay(&[create(), complete(), deposit(BET_ID)]).err(), out_of_order);
+    assert_eq!(replay(&[create(), complete(), complete()]).err(), out_of_order);
+    assert_eq!(
+        replay(&[create(), deposit(BET_ID + 1)]).err(),
+        Some(Error::from(BettingError::ReplayWrongPool))
+    );
+}
@@ -0,0 +1,105 @@
+//! Every resolution path claims the pool through resolution.rs, so whichever lands first wins
+//! and any later attempt, by the same path or another, fails with AlreadyResolved.
+
+use crate::error::BettingError;
+use crate::resolution::{self, ResolutionSource};
+use crate::{finalize_outcome, MainState, PoolState, WinnerSide};
+use anchor_lang::error::Error;
+
+const NOW: i64 = 1_750_000_000;
+
+const PATHS: [ResolutionSource; 12] = [
+    ResolutionSource::Referee,
+    ResolutionSource::Fallback,
+    ResolutionSource::Oracle,
+    ResolutionSource::Provisional,
+    ResolutionSource::DisputeRuling,
+    ResolutionSource::Assertion,
+    ResolutionSource::MetaMarket,
+    ResolutionSource::RefundDeadline,
+    ResolutionSource::Voided,
+    ResolutionSource::Committee,
+    ResolutionSource::Invalid,
+    ResolutionSource::TooFewParticipants,
+];
+
+fn ended_pool() -> PoolState {
+    PoolState {
+        yes_reserve: 3_000,
+        no_reserve: 1_000,
+        end_timestamp: NOW - 60,
+        ..Default::default()
+    }
+}
+
+// What `source`'s instruction does to the pool once its own checks passed: winner paths go
+// through finalize_outcome, refund paths claim the pool and switch it to refund mode
+fn attempt(
+    pool: &mut PoolState,
+    main_state: &mut MainState,
+    source: ResolutionSource,
+) -> Result<(), Error> {
+    if source.is_refund() {
+        resolution::claim(pool, source)?;
+        pool.refund_mode = true;
+        pool.winner = WinnerSide::Void;
+        return Ok(());
+    }
+    finalize_outcome(pool, main_state, source, source != ResolutionSource::Oracle, NOW)
+        .map(|_| ())
+}
+
+#[test]
+fn every_pair_of_paths_resolves_once() {
+    for first in PATHS {
+        for second in PATHS {
+            let mut main_state = MainState::default();
+            let mut pool = ended_pool();
+            attempt(&mut pool, &mut main_state, first).unwrap();
+            let (winner, platform_fee) = (pool.winner, pool.platform_fee_amount);
+            assert_eq!(pool.resolution_source, first);
+
+            assert_eq!(
+                attempt(&mut pool, &mut main_state, second).unwrap_err(),
+                Error::from(BettingError::AlreadyResolved),
+                "{first:?} then {second:?}"
+            );
+            assert_eq!(pool.resolution_source, first);
+            assert_eq!((pool.winner, pool.platform_fee_amount), (winner, platform_fee));
+            assert_eq!(pool.complete, !first.is_refund());
+            assert_eq!(pool.refund_mode, first.is_refund());
+            assert_eq!(main_state.resolved_pools, u64::from(!first.is_refund()));
+        }
+    }
+}
+
+#[test]
+fn no_resolution_can_start_on_a_decided_pool() {
+    assert!(resolution::ensure_unresolved(&ended_pool()).is_ok());
+    // set_provisional_winner and assert_outcome check this before starting their own path
+    for source in PATHS {
+        let mut pool = ended_pool();
+        attempt(&mut pool, &mut MainState::default(), source).unwrap();
+        assert!(resolution::ensure_unresolved(&pool).is_err());
+    }
+}
+
+#[test]
+fn pools_decided_before_the_source_was_recorded_stay_decided() {
+    let complete = PoolState {
+        complete: true,
+        winner: WinnerSide::Yes,
+        ..ended_pool()
+    };
+    let refunded = PoolState {
+        refund_mode: true,
+        winner: WinnerSide::Void,
+        ..ended_pool()
+    };
+    for mut pool in [complete, refunded] {
+        for source in PATHS {
+            assert!(attempt(&mut pool, &mut MainState::default(), source).is_err());
+            assert_eq!(pool.resolution_source, ResolutionSource::Unresolved);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Stakers split each closed epoch's revenue by the stake that earned through all of it.
+
+use crate::{MainState, RevenueEpoch, RevenueShare, StakeState};
+use anchor_lang::prelude::*;
+
+#[test]
+fn epochs_split_revenue_pro_rata_rounding_down() {
+    let epoch = RevenueEpoch {
+        revenue: 1_000,
+        total_staked: 3,
+        ..Default::default()
+    };
+    assert_eq!(epoch.share_of(1), 333);
+    assert_eq!(epoch.share_of(2), 666);
+    assert_eq!(epoch.share_of(0), 0);
+    assert_eq!(RevenueEpoch::default().share_of(1), 0);
+}
+
+#[test]
+fn stake_earns_from_the_epoch_after_it_is_added() {
+    let mut stake = StakeState {
+        next_claim_epoch: 4,
+        ..Default::default()
+    };
+    // Nothing earns epoch 4, so claims skip it
+    stake.add(100, 4);
+    assert_eq!((stake.amount, stake.activating), (0, 100));
+    assert_eq!(stake.next_claim_epoch, 5);
+
+    stake.activate(4);
+    assert_eq!(stake.amount, 0);
+    stake.activate(5);
+    assert_eq!((stake.amount, stake.activating), (100, 0));
+
+    // Adding more while earning keeps the open epoch claimable
+    stake.add(50, 6);
+    assert_eq!((stake.amount, stake.activating), (100, 50));
+    assert_eq!(stake.next_claim_epoch, 5);
+}
+
+#[test]
+fn unstaking_takes_activating_stake_first() {
+    let mut stake = StakeState {
+        amount: 100,
+        activating: 30,
+        activating_epoch: 6,
+        ..Default::default()
+    };
+    assert_eq!(stake.remove(50, 6), Some((30, 20)));
+    assert_eq!((stake.amount, stake.activating), (80, 0));
+    assert_eq!(stake.remove(81, 6), None);
+
+    // Matured stake is earning stake
+    let mut matured = StakeState {
+        amount: 100,
+        activating: 30,
+        activating_epoch: 5,
+        ..Default::default()
+    };
+    assert_eq!(matured.remove(50, 6), Some((0, 50)));
+    assert_eq!(matured.amount, 80);
+}
+
+#[test]
+fn stakers_are_caught_up_once_every_closed_epoch_is_claimed() {
+    let revenue_share = RevenueShare {
+        epoch: 3,
+        ..Default::default()
+    };
+    let mut stake = StakeState {
+        next_claim_epoch: 2,
+        ..Default::default()
+    };
+    assert!(!stake.is_caught_up(&revenue_share));
+    stake.next_claim_epoch = 3;
+    assert!(stake.is_caught_up(&revenue_share));
+}
+
+#[test]
+fn only_treasury_fees_accrue_to_stakers() {
+    let mut main_state = MainState {
+        revenue_share_bps: 1_000,
+        ..Default::default()
+    };
+    main_state.accrue_revenue_share(5_000);
+    assert_eq!(main_state.revenue_share_pending, 0);
+
+    main_state.treasury_cold_wallet = Pubkey::new_unique();
+    main_state.accrue_revenue_share(5_000);
+    main_state.accrue_revenue_share(5_000);
+    assert_eq!(main_state.revenue_share_pending, 1_000);
+}
@@ -0,0 +1,57 @@
+//! PoolSummary risk indicators.
+
+use crate::PoolSummary;
+use anchor_lang::prelude::Pubkey;
+
+#[test]
+fn evenly_spread_pool_is_not_flagged() {
+    let mut summary = PoolSummary::default();
+    for _ in 0..10 {
+        summary.record_deposit(Pubkey::new_unique(), 0, 1_000);
+    }
+    assert_eq!(summary.depositor_count, 10);
+    assert_eq!(summary.herfindahl_bps, 1_000); // 10 equal shares of 10%
+    assert_eq!(summary.top_depositor_bps, 1_000);
+    assert_eq!(summary.risk_flags, 0);
+}
+
+#[test]
+fn whale_dominating_the_pool_is_flagged() {
+    let mut summary = PoolSummary::default();
+    let whale = Pubkey::new_unique();
+    summary.record_deposit(Pubkey::new_unique(), 0, 1_000);
+    summary.record_deposit(Pubkey::new_unique(), 0, 1_000);
+    // Counts towards the same entry, in two deposits
+    summary.record_deposit(whale, 0, 3_000);
+    summary.record_deposit(whale, 3_000, 3_000);
+
+    assert_eq!(summary.depositor_count, 3);
+    assert_eq!(summary.top_depositor, whale);
+    assert_eq!(summary.top_depositor_bps, 7_500);
+    // 0.75² + 2 × 0.125²
+    assert_eq!(summary.herfindahl_bps, 5_937);
+    assert_eq!(
+        summary.risk_flags,
+        PoolSummary::FLAG_WHALE_DEPOSIT | PoolSummary::FLAG_DOMINANT_HOLDER
+    );
+}
+
+#[test]
+fn volume_rolls_off_after_24_hours() {
+    const HOUR: i64 = 60 * 60;
+    let start = 1_700_000_000 / HOUR * HOUR;
+    let mut summary = PoolSummary::default();
+    summary.record_volume(1_000, start);
+    summary.record_volume(500, start + 10 * HOUR);
+    assert_eq!(summary.volume_24h, 1_500);
+
+    // The first deposit's hour leaves the window, the second one is still in it
+    assert_eq!(summary.volume_24h_at(start + 24 * HOUR), 500);
+    summary.record_volume(200, start + 30 * HOUR);
+    assert_eq!(summary.volume_24h, 700);
+
+    // A long quiet stretch clears every bucket
+    summary.record_volume(50, start + 100 * HOUR);
+    assert_eq!(summary.volume_24h, 50);
+    assert_eq!(summary.volume_24h_at(start + 124 * HOUR), 0);
+}
@@ -0,0 +1,98 @@
+//! Scalar markets split the pot between both sides by where the resolved value lands.
+
+use crate::fees::settle_resolution_fees;
+use crate::{apply_deposit, settle_claim, EntryState, PoolState, ScalarBounds, WinnerSide};
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+
+const SOL: u64 = 1_000_000_000;
+
+fn bounds(min: i64, max: i64) -> ScalarBounds {
+    ScalarBounds { min, max }
+}
+
+// A scalar pool over [0, 100] with the given (is_yes, amount) deposits, one entry each,
+// resolved to `value` with the given fees
+fn resolved(
+    deposits: &[(bool, u64)],
+    value: i64,
+    fee_bps: (u64, u64),
+) -> (PoolState, Vec<EntryState>) {
+    let mut pool = PoolState {
+        scalar_bounds: bounds(0, 100),
+        creator_fee_bps: fee_bps.0,
+        history_disabled: true,
+        installment_count: 1,
+        ..Default::default()
+    };
+    let entries = deposits
+        .iter()
+        .map(|&(is_yes, amount)| {
+            let mut entry = EntryState::default();
+            let reserves = (pool.yes_reserve, pool.no_reserve);
+            apply_deposit(&mut pool, &mut entry, None, Pubkey::default(), amount, is_yes, reserves)
+                .unwrap();
+            entry
+        })
+        .collect();
+
+    pool.scalar_long_bps = pool.scalar_bounds.long_bps(value);
+    let is_yes = pool.scalar_long_bps >= 5_000;
+    pool.complete = true;
+    pool.winner = WinnerSide::from_is_yes(is_yes);
+    settle_resolution_fees(&mut pool, is_yes, fee_bps.1);
+    (pool, entries)
+}
+
+#[test]
+fn long_share_is_linear_and_clamped() {
+    let scalar = bounds(-50, 150);
+    assert_eq!(scalar.long_bps(-50), 0);
+    assert_eq!(scalar.long_bps(50), 5_000);
+    assert_eq!(scalar.long_bps(100), 7_500);
+    assert_eq!(scalar.long_bps(1_000), 10_000);
+    assert_eq!(scalar.long_bps(i64::MIN), 0);
+    assert_eq!(bounds(i64::MIN, i64::MAX).long_bps(i64::MAX), 10_000);
+
+    assert!(!bounds(5, 5).is_set());
+    assert!(!ScalarBounds::default().is_set());
+}
+
+#[test]
+fn both_sides_are_paid_by_distance_to_their_bound() {
+    let (pool, mut entries) = resolved(&[(true, SOL), (false, SOL)], 75, (0, 0));
+    // The short side lost the resolution but still has a share to claim
+    assert!(!entries[1].is_settled(&pool));
+    let long = settle_claim(&pool, &mut entries[0], 0).unwrap();
+    let short = settle_claim(&pool, &mut entries[1], 0).unwrap();
+    assert_eq!((long, short), (3 * SOL / 2, SOL / 2));
+    assert!(entries[1].is_settled(&pool));
+}
+
+#[test]
+fn an_unbacked_side_leaves_its_share_to_the_other() {
+    let (pool, mut entries) = resolved(&[(false, SOL)], 100, (0, 0));
+    assert_eq!(settle_claim(&pool, &mut entries[0], 0).unwrap(), SOL);
+}
+
+proptest! {
+    #[test]
+    fn claims_never_exceed_the_pot(
+        deposits in proptest::collection::vec((any::<bool>(), 1_000u64..=20 * SOL), 1..12),
+        value in -20i64..=120,
+        creator_fee_bps in 0u64..=500,
+        platform_fee_bps in 0u64..=500,
+    ) {
+        let (pool, mut entries) =
+            resolved(&deposits, value, (creator_fee_bps, platform_fee_bps));
+        let paid: u64 = entries
+            .iter_mut()
+            .map(|entry| settle_claim(&pool, entry, 0).unwrap())
+            .sum();
+        let fees = pool.creator_fee_accrued + pool.platform_fee_amount;
+        let total: u64 = deposits.iter().map(|&(_, amount)| amount).sum();
+        prop_assert!(paid + fees <= total);
+        // Rounding leaves at most a lamport per entry behind
+        prop_assert!(total - paid - fees <= entries.len() as u64);
+    }
+}
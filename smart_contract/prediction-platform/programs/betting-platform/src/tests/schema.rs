@@ -0,0 +1,93 @@
+//! The schema registry's offsets match what Borsh actually writes.
+
+use crate::layout::Reserved;
+use crate::schema::{entry_state, main_state, pool_state};
+use crate::{EntryState, MainState, PoolState};
+use anchor_lang::prelude::*;
+
+fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn key_at(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::try_from(&data[offset..offset + 32]).unwrap()
+}
+
+#[test]
+fn main_state_offsets() {
+    let state = MainState {
+        initialized: true,
+        owner: Pubkey::new_unique(),
+        current_bet_id: 42,
+        platform_fee_percent: 250,
+        retired: Reserved([7; 8]),
+        fee_admin: Pubkey::new_unique(),
+        ops_admin: Pubkey::new_unique(),
+        paused: true,
+        ..Default::default()
+    };
+    let data = serialize(&state);
+    assert_eq!(&data[..8], main_state::DISCRIMINATOR);
+    assert_eq!(data[main_state::INITIALIZED], 1);
+    assert_eq!(key_at(&data, main_state::OWNER), state.owner);
+    assert_eq!(u64_at(&data, main_state::CURRENT_BET_ID), 42);
+    assert_eq!(u64_at(&data, main_state::PLATFORM_FEE_PERCENT), 250);
+    assert_eq!(data[main_state::RETIRED..main_state::FEE_ADMIN], [7; 8]);
+    assert_eq!(key_at(&data, main_state::FEE_ADMIN), state.fee_admin);
+    assert_eq!(key_at(&data, main_state::OPS_ADMIN), state.ops_admin);
+    assert_eq!(data[main_state::PAUSED], 1);
+}
+
+#[test]
+fn pool_state_offsets() {
+    let state = PoolState {
+        creator: Pubkey::new_unique(),
+        bet_id: 9,
+        yes_reserve: 11,
+        no_reserve: 13,
+        end_timestamp: 1_750_000_000,
+        referee: Pubkey::new_unique(),
+        title: "Schema".to_string(),
+        ..Default::default()
+    };
+    let data = serialize(&state);
+    assert_eq!(&data[..8], pool_state::DISCRIMINATOR);
+    assert_eq!(key_at(&data, pool_state::CREATOR), state.creator);
+    assert_eq!(u64_at(&data, pool_state::BET_ID), 9);
+    assert_eq!(u64_at(&data, pool_state::YES_RESERVE), 11);
+    assert_eq!(u64_at(&data, pool_state::NO_RESERVE), 13);
+    assert_eq!(u64_at(&data, pool_state::END_TIMESTAMP), 1_750_000_000);
+    assert_eq!(key_at(&data, pool_state::REFEREE), state.referee);
+    assert_eq!(&data[pool_state::TITLE..pool_state::TITLE + 4], &6u32.to_le_bytes());
+}
+
+#[test]
+fn entry_state_offsets() {
+    let mut state = EntryState {
+        user: Pubkey::new_unique(),
+        bet_id: 3,
+        token_balance: 17,
+        is_claimed: true,
+        creator_fee_paid: 19,
+        deposit_count: 5,
+        claimed_at: 23,
+        ..Default::default()
+    };
+    state.recent_nonces[0] = 29;
+    let data = serialize(&state);
+    assert_eq!(&data[..8], entry_state::DISCRIMINATOR);
+    assert_eq!(key_at(&data, entry_state::USER), state.user);
+    assert_eq!(u64_at(&data, entry_state::BET_ID), 3);
+    assert_eq!(u64_at(&data, entry_state::TOKEN_BALANCE), 17);
+    assert_eq!(data[entry_state::IS_CLAIMED], 1);
+    assert_eq!(u64_at(&data, entry_state::RECENT_NONCES), 29);
+    assert_eq!(u64_at(&data, entry_state::CREATOR_FEE_PAID), 19);
+    assert_eq!(&data[entry_state::DEPOSIT_COUNT..][..4], &5u32.to_le_bytes());
+    assert_eq!(u64_at(&data, entry_state::CLAIMED_AT), 23);
+}
@@ -0,0 +1,61 @@
+//! Creator seed liquidity: priced like any reserve, handed back only where no winner is owed it.
+
+use crate::fees::resolution_fees;
+use crate::{PoolState, WinnerSide};
+
+// A pool seeded with 1 SOL per side that took `yes_bet` on YES (minting `yes_bet` tokens)
+fn seeded(yes_bet: u64) -> PoolState {
+    let seed = 1_000_000_000;
+    PoolState {
+        seed_yes_liquidity: seed,
+        seed_no_liquidity: seed,
+        yes_reserve: seed + yes_bet,
+        no_reserve: seed,
+        yes_supply: yes_bet,
+        ..Default::default()
+    }
+}
+
+fn resolve(mut pool: PoolState, winner: WinnerSide) -> PoolState {
+    pool.complete = true;
+    pool.winner = winner;
+    pool
+}
+
+#[test]
+fn nothing_is_reclaimable_before_settlement() {
+    assert_eq!(seeded(500).reclaimable_seed(), 0);
+}
+
+#[test]
+fn losing_seed_funds_winners() {
+    // YES backers won: the NO seed is their profit, the YES seed nobody claims
+    let pool = resolve(seeded(500), WinnerSide::Yes);
+    assert_eq!(pool.reclaimable_seed(), 1_000_000_000);
+}
+
+#[test]
+fn seed_comes_back_when_nobody_backed_the_winner() {
+    let mut pool = resolve(seeded(500), WinnerSide::No);
+    assert_eq!(pool.reclaimable_seed(), 2_000_000_000);
+
+    // Fees taken out of the losing side first come out of what is left of its seed
+    pool.platform_fee_amount = 1_000_000_200;
+    assert_eq!(pool.reclaimable_seed(), 1_000_000_000 + 300);
+}
+
+#[test]
+fn refunded_pools_return_the_whole_seed_once() {
+    let mut pool = seeded(500);
+    pool.refund_mode = true;
+    assert_eq!(pool.reclaimable_seed(), 2_000_000_000);
+
+    pool.seed_reclaimed = true;
+    assert_eq!(pool.reclaimable_seed(), 0);
+}
+
+#[test]
+fn platform_fee_skips_the_seed() {
+    let pool = seeded(10_000);
+    assert_eq!(resolution_fees(&pool, true, 100).platform_fee, 100);
+}
@@ -0,0 +1,31 @@
+//! Pools refereed by their own creator stop growing at the platform's self-referee cap.
+
+use crate::{MainState, PoolState};
+use anchor_lang::prelude::Pubkey;
+
+fn pool(creator: Pubkey, referee: Pubkey, yes_reserve: u64, no_reserve: u64) -> PoolState {
+    PoolState {
+        creator,
+        referee,
+        yes_reserve,
+        no_reserve,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn cap_applies_only_to_self_refereed_pools() {
+    let main_state = MainState {
+        self_referee_reserve_cap: 1_000,
+        ..Default::default()
+    };
+    let (creator, referee) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    assert!(pool(creator, creator, 600, 400).check_self_referee_cap(&main_state).is_ok());
+    assert!(pool(creator, creator, 600, 401).check_self_referee_cap(&main_state).is_err());
+    assert!(pool(creator, referee, 600, 401).check_self_referee_cap(&main_state).is_ok());
+
+    // 0 = no cap
+    let uncapped = MainState::default();
+    assert!(pool(creator, creator, u64::MAX, 1).check_self_referee_cap(&uncapped).is_ok());
+}
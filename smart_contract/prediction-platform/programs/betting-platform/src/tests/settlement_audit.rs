@@ -0,0 +1,91 @@
+//! Resolution refuses to go through unless every lamport of the reserves has a destination.
+
+use crate::error::BettingError;
+use crate::fees::settle_resolution_fees;
+use crate::{apply_deposit, audit_settlement, settle_claim, EntryState, PoolState, WinnerSide};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+
+// A pool seeded with `seed` per side that took one entry per (is_yes, amount) deposit
+fn pool_with(
+    seed: u64,
+    creator_fee_bps: u64,
+    deposits: &[(bool, u64)],
+) -> (PoolState, Vec<EntryState>) {
+    let mut pool = PoolState {
+        seed_yes_liquidity: seed,
+        seed_no_liquidity: seed,
+        yes_reserve: seed,
+        no_reserve: seed,
+        total_reserve: 2 * seed,
+        creator_fee_bps,
+        history_disabled: true,
+        installment_count: 1,
+        ..Default::default()
+    };
+    let entries = deposits
+        .iter()
+        .map(|&(is_yes, amount)| {
+            let mut entry = EntryState::default();
+            let reserves = (pool.yes_reserve, pool.no_reserve);
+            apply_deposit(&mut pool, &mut entry, None, Pubkey::default(), amount, is_yes, reserves)
+                .unwrap();
+            entry
+        })
+        .collect();
+    (pool, entries)
+}
+
+fn resolve(pool: &mut PoolState, is_yes: bool, platform_fee_bps: u64) {
+    pool.complete = true;
+    pool.winner = WinnerSide::from_is_yes(is_yes);
+    settle_resolution_fees(pool, is_yes, platform_fee_bps);
+}
+
+#[test]
+fn an_unbacked_winner_leaves_the_profit_unallocated() {
+    let (mut pool, _) = pool_with(1_000, 0, &[(false, 5_000)]);
+    resolve(&mut pool, true, 0);
+    let audit = audit_settlement(&pool, 0).unwrap();
+    // The creator takes both seeds back; the NO bets have nobody to pay
+    assert_eq!((audit.winners_pot, audit.seed_returned), (0, 2_000));
+    assert_eq!(audit.unallocated, 5_000);
+    assert_eq!(audit.total_reserve, 7_000);
+}
+
+#[test]
+fn fees_beyond_the_reserves_block_resolution() {
+    let (mut pool, _) = pool_with(0, 0, &[(true, 5_000), (false, 1_000)]);
+    resolve(&mut pool, true, 0);
+    pool.platform_fee_amount = 2_000;
+    assert_eq!(
+        audit_settlement(&pool, 0).err(),
+        Some(Error::from(BettingError::SettlementNotConserved))
+    );
+}
+
+proptest! {
+    #[test]
+    fn destinations_cover_the_reserves_and_winners_get_their_pot(
+        seed in prop_oneof![Just(0u64), 1_000u64..=1_000_000_000],
+        deposits in proptest::collection::vec((any::<bool>(), 1_000u64..=5_000_000_000), 0..10),
+        is_yes in any::<bool>(),
+        creator_fee_bps in 0u64..=500,
+        platform_fee_bps in 0u64..=500,
+    ) {
+        let (mut pool, mut entries) = pool_with(seed, creator_fee_bps, &deposits);
+        resolve(&mut pool, is_yes, platform_fee_bps);
+        let audit = audit_settlement(&pool, 0).unwrap();
+        prop_assert_eq!(audit.total_reserve, pool.yes_reserve + pool.no_reserve);
+
+        let paid: u64 = entries
+            .iter_mut()
+            .filter(|entry| entry.is_yes == is_yes)
+            .map(|entry| settle_claim(&pool, entry, 0).unwrap())
+            .sum();
+        // Winners split their pot to within a lamport each
+        prop_assert!(paid <= audit.winners_pot);
+        prop_assert!(audit.winners_pot - paid <= entries.len() as u64);
+    }
+}
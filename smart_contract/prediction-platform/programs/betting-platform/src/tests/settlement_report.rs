@@ -0,0 +1,88 @@
+//! The settlement report records the figures claims are paid from at resolution.
+
+use crate::constants::PAYOUT_PER_TOKEN_SCALE;
+use crate::fees::{refresh_payout_projection, settle_resolution_fees};
+use crate::{
+    apply_deposit, check_settlement_report, settle_claim, write_settlement_report, EntryState,
+    PoolState, SettlementReport, WinnerSide,
+};
+use anchor_lang::prelude::Pubkey;
+
+const SOL: u64 = 1_000_000_000;
+const PLATFORM_BPS: u64 = 200;
+
+fn resolved_pool() -> (PoolState, Vec<EntryState>) {
+    let mut pool = PoolState {
+        creator_fee_bps: 100,
+        installment_count: 1,
+        history_disabled: true,
+        ..Default::default()
+    };
+    // The second YES deposit tops up an existing entry and must not count it twice
+    let deposits = [(0, SOL, true), (1, 3 * SOL, false), (2, 2 * SOL, true), (0, SOL, true)];
+    let mut entries: Vec<EntryState> = (0..3).map(|_| EntryState::default()).collect();
+    for &(entry, amount, is_yes) in deposits.iter() {
+        let pricing_reserves = (pool.yes_reserve, pool.no_reserve);
+        apply_deposit(
+            &mut pool,
+            &mut entries[entry],
+            None,
+            Pubkey::default(),
+            amount,
+            is_yes,
+            pricing_reserves,
+        )
+        .unwrap();
+    }
+    refresh_payout_projection(&mut pool, PLATFORM_BPS);
+
+    pool.complete = true;
+    pool.winner = WinnerSide::Yes;
+    settle_resolution_fees(&mut pool, true, PLATFORM_BPS);
+    (pool, entries)
+}
+
+#[test]
+fn report_records_final_figures() {
+    let (mut pool, mut entries) = resolved_pool();
+    let mut report = SettlementReport::default();
+    write_settlement_report(&mut report, &mut pool, Pubkey::default(), Pubkey::default());
+
+    assert!(report.winner_is_yes);
+    assert_eq!((report.yes_entries, report.no_entries), (2, 1));
+    assert_eq!(report.yes_reserve, 4 * SOL);
+    assert_eq!(report.platform_fee, pool.platform_fee_amount);
+    // Same fees as the live preview, so the same per-token figure
+    assert_eq!(report.payout_per_token, pool.projected_payout_per_token_yes);
+    check_settlement_report(&pool, &report).unwrap();
+
+    for entry in entries.iter_mut().filter(|entry| entry.is_yes) {
+        let expected = entry.deposited_sol_amount as u128
+            + entry.token_balance as u128 * report.payout_per_token as u128
+                / PAYOUT_PER_TOKEN_SCALE as u128;
+        let paid = settle_claim(&pool, entry, 0).unwrap() as u128;
+        assert!(paid.abs_diff(expected) <= 1, "paid {paid}, expected {expected}");
+    }
+}
+
+#[test]
+fn claims_reject_drifted_pool_state() {
+    let (mut pool, _) = resolved_pool();
+    let mut report = SettlementReport::default();
+    write_settlement_report(&mut report, &mut pool, Pubkey::default(), Pubkey::default());
+
+    pool.platform_fee_amount -= 1;
+    assert!(check_settlement_report(&pool, &report).is_err());
+}
+
+#[test]
+fn claims_require_the_report_once_written() {
+    let (mut pool, _) = resolved_pool();
+    // Resolved before reports existed
+    pool.check_settlement_report_passed(false).unwrap();
+
+    let mut report = SettlementReport::default();
+    write_settlement_report(&mut report, &mut pool, Pubkey::default(), Pubkey::default());
+    assert!(pool.check_settlement_report_passed(false).is_err());
+    pool.check_settlement_report_passed(true).unwrap();
+}
@@ -0,0 +1,81 @@
+//! Split deadlines: deposits stop at the trading deadline, resolution opens there, and the
+//! resolution deadline hands the pool over to refund mode.
+
+use crate::error::BettingError;
+use crate::{MainState, PoolState};
+use anchor_lang::prelude::*;
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+fn pool(trading_end_timestamp: i64, resolution_deadline: i64) -> PoolState {
+    PoolState {
+        end_timestamp: 1_000,
+        trading_end_timestamp,
+        resolution_deadline,
+        referee: key(1),
+        referee_accepted: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn trading_stops_at_the_trading_deadline_and_resolution_opens_there() {
+    let pool = pool(600, 0);
+    assert_eq!(pool.trading_end(), 600);
+    assert!(!pool.trading_closed(599));
+    assert!(pool.trading_closed(600));
+    assert!(pool.has_ended(600));
+    assert!(!pool.has_ended(599));
+}
+
+#[test]
+fn without_a_trading_deadline_the_end_time_still_rules() {
+    let pool = pool(0, 0);
+    assert_eq!(pool.trading_end(), 1_000);
+    assert!(!pool.trading_closed(999));
+    assert!(pool.trading_closed(1_000));
+
+    let open_ended = PoolState {
+        end_timestamp: -1,
+        ..Default::default()
+    };
+    assert!(!open_ended.trading_closed(i64::MAX));
+}
+
+#[test]
+fn deadlines_must_come_in_order() {
+    assert!(pool(600, 2_000).check_split_deadlines().is_ok());
+    assert!(pool(0, 0).check_split_deadlines().is_ok());
+    for (trading_end, resolution_deadline) in [(1_001, 0), (-5, 0), (600, 1_000), (0, 900)] {
+        assert_eq!(
+            pool(trading_end, resolution_deadline).check_split_deadlines().err(),
+            Some(Error::from(BettingError::InvalidResolutionDeadlines))
+        );
+    }
+    let past_referee_deadline = PoolState {
+        referee_deadline: 3_000,
+        ..pool(600, 2_000)
+    };
+    assert!(past_referee_deadline.check_split_deadlines().is_err());
+}
+
+#[test]
+fn past_the_resolution_deadline_nobody_resolves() {
+    let pool = pool(600, 2_000);
+    let main_state = MainState {
+        owner: key(9),
+        ..Default::default()
+    };
+    assert!(!pool.resolution_window_closed(1_999));
+    assert!(pool.check_resolver(&key(1), &main_state, 1_999).is_ok());
+
+    assert!(pool.resolution_window_closed(2_000));
+    for resolver in [key(1), key(9)] {
+        assert_eq!(
+            pool.check_resolver(&resolver, &main_state, 2_000).err(),
+            Some(Error::from(BettingError::ResolutionWindowClosed))
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Claims on a resolved pool: winnings, refunds, insurance, gasless permits, outcome tokens,
+//! the creator's fee, unclaimed sweeps, social recovery and the read-only claim, vault and
+//! replay views.
+
+use super::harness::{matrix, Matrix};
+use super::world::{
+    ata, entry, guarantee, main_state, outcome_mint, pool, pool_vault, settlement, sol_vault,
+    treasury, World, OTHER_SETTLED_POOL, PERMIT_EXPIRES_AT, POOL, SETTLED_POOL,
+};
+use crate::error::BettingError;
+use crate::{accounts, instruction};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+pub(super) fn matrices(w: &World) -> Vec<Matrix> {
+    let claimable = |entry_state: Pubkey| {
+        vec![
+            AccountMeta::new_readonly(pool(SETTLED_POOL), false),
+            AccountMeta::new_readonly(entry_state, false),
+        ]
+    };
+
+    vec![
+        matrix::<instruction::Claim, _, _>(w, SETTLED_POOL, || accounts::AClaim {
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(SETTLED_POOL),
+            entry_state: entry(SETTLED_POOL, &w.alice),
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(SETTLED_POOL),
+            settlement_report: Some(settlement(SETTLED_POOL)),
+            guarantee: Some(guarantee(SETTLED_POOL)),
+            referral_state: None,
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "without the pool's settlement report",
+            |a| a.settlement_report = None,
+            BettingError::SettlementReportRequired,
+        )
+        .foreign_fails_with(guarantee(SETTLED_POOL), BettingError::PoolNotGuaranteed)
+        .build(),
+        matrix::<instruction::EmergencyClaim, _, _>(w, SETTLED_POOL, || {
+            accounts::AEmergencyClaim {
+                user: w.alice,
+                pool_state: pool(SETTLED_POOL),
+                entry_state: entry(SETTLED_POOL, &w.alice),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::ClaimRefund, _, _>(w, SETTLED_POOL, || accounts::AClaimRefund {
+            user: w.alice,
+            pool_state: pool(SETTLED_POOL),
+            entry_state: entry(SETTLED_POOL, &w.alice),
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(SETTLED_POOL),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::ClaimInsurance, _, _>(w, SETTLED_POOL, || {
+            accounts::AClaimInsurance {
+                user: w.alice,
+                pool_state: pool(SETTLED_POOL),
+                entry_state: entry(SETTLED_POOL, &w.alice),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::ClaimWithPermit, _, _>(
+            w,
+            (SETTLED_POOL, PERMIT_EXPIRES_AT),
+            || accounts::AClaimWithPermit {
+                relayer: w.mallory,
+                user: w.alice,
+                recipient: w.alice,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                entry_state: entry(SETTLED_POOL, &w.alice),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            },
+        )
+        .deny(
+            "the relayer redirects alice's permit to herself",
+            |a| a.recipient = w.mallory,
+            BettingError::InvalidPermit,
+        )
+        .deny(
+            "alice's permit replayed for bob's entry",
+            |a| {
+                a.user = w.bob;
+                a.recipient = w.bob;
+                a.entry_state = entry(SETTLED_POOL, &w.bob);
+            },
+            BettingError::InvalidPermit,
+        )
+        .deny_args::<instruction::ClaimWithPermit>(
+            "a later expiry than alice signed",
+            (SETTLED_POOL, PERMIT_EXPIRES_AT + 1),
+            |_| {},
+            BettingError::InvalidPermit,
+        )
+        .deny(
+            "a forged instructions sysvar",
+            |a| a.instructions = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .build(),
+        matrix::<instruction::ClaimCreatorFee, _, _>(w, SETTLED_POOL, || {
+            accounts::AClaimCreatorFee {
+                creator: w.creator,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "another pool's creator",
+            |a| a.creator = w.other_creator,
+            ErrorCode::ConstraintHasOne,
+        )
+        .build(),
+        matrix::<instruction::ClaimRefereeFee, _, _>(w, SETTLED_POOL, || {
+            accounts::AClaimRefereeFee {
+                referee: w.referee,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as referee", |a| a.referee = w.mallory, ErrorCode::ConstraintHasOne)
+        .build(),
+        matrix::<instruction::ReclaimSeedLiquidity, _, _>(w, SETTLED_POOL, || {
+            accounts::AReclaimSeedLiquidity {
+                creator: w.creator,
+                pool_state: pool(SETTLED_POOL),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "another pool's creator",
+            |a| a.creator = w.other_creator,
+            ErrorCode::ConstraintHasOne,
+        )
+        .build(),
+        matrix::<instruction::SweepUnclaimed, _, _>(w, SETTLED_POOL, || {
+            accounts::ASweepUnclaimed {
+                owner: w.owner,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                treasury: treasury(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "mallory's wallet as the treasury",
+            |a| a.treasury = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(OTHER_SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::SettleCreatorBond, _, _>(w, SETTLED_POOL, || {
+            accounts::ASettleCreatorBond {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                rent_payer: w.creator,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                treasury: treasury(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory's wallet as the rent payer",
+            |a| a.rent_payer = w.mallory,
+            ErrorCode::ConstraintHasOne,
+        )
+        .deny(
+            "mallory's wallet as the treasury",
+            |a| a.treasury = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::CheckClaim, _, _>(w, SETTLED_POOL, || accounts::ACheckClaim {
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(SETTLED_POOL),
+            entry_state: entry(SETTLED_POOL, &w.alice),
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::GetVaultHealth, _, _>(w, SETTLED_POOL, || {
+            accounts::AGetVaultHealth {
+                pool_state: pool(SETTLED_POOL),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+            }
+        })
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::VerifyReplay, _, _>(w, (SETTLED_POOL, None::<[u8; 32]>), || {
+            accounts::AVerifyReplay {
+                pool_state: pool(SETTLED_POOL),
+            }
+        })
+        .deny_args::<instruction::VerifyReplay>(
+            "a hash the pool does not match",
+            (SETTLED_POOL, Some([0u8; 32])),
+            |_| {},
+            BettingError::ReplayMismatch,
+        )
+        .deny(
+            "another pool",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::GetClaimableTotal, _, _>(w, (), || {
+            accounts::AGetClaimableTotal {
+                user: w.alice,
+                main_state: main_state(),
+            }
+        })
+        .remaining(claimable(entry(SETTLED_POOL, &w.alice)))
+        .deny_remaining(
+            "bob's entry counted for alice",
+            claimable(entry(SETTLED_POOL, &w.bob)),
+            BettingError::InvalidBatch,
+        )
+        .deny_remaining(
+            "a pool without its entry",
+            vec![AccountMeta::new_readonly(pool(SETTLED_POOL), false)],
+            BettingError::InvalidBatch,
+        )
+        .foreign_fails_with(pool(SETTLED_POOL), BettingError::InvalidBatch)
+        .foreign_fails_with(entry(SETTLED_POOL, &w.alice), BettingError::InvalidBatch)
+        .build(),
+        matrix::<instruction::ClaimWithTokens, _, _>(w, (SETTLED_POOL, true, 1_000u64), || {
+            accounts::AClaimWithTokens {
+                holder: w.alice,
+                pool_state: pool(SETTLED_POOL),
+                outcome_mint: outcome_mint(SETTLED_POOL, true),
+                holder_token_account: ata(&w.alice, &outcome_mint(SETTLED_POOL, true)),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory burns alice's tokens",
+            |a| a.holder = w.mallory,
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .deny(
+            "alice burns bob's tokens",
+            |a| a.holder_token_account = ata(&w.bob, &outcome_mint(SETTLED_POOL, true)),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .deny(
+            "another pool's mint",
+            |a| a.outcome_mint = outcome_mint(POOL, true),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::SetRecoveryKey, _, _>(w, (POOL, w.alice_recovery), || {
+            accounts::ASetRecoveryKey {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                entry_state: entry(POOL, &w.alice),
+            }
+        })
+        .deny(
+            "mallory names herself alice's recovery key",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::InitiateRecovery, _, _>(w, POOL, || accounts::AInitiateRecovery {
+            recovery_key: w.alice_recovery,
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(POOL),
+            entry_state: entry(POOL, &w.alice),
+        })
+        .deny(
+            "mallory recovers alice's entry",
+            |a| a.recovery_key = w.mallory,
+            BettingError::NotRecoveryKey,
+        )
+        .deny(
+            "alice's recovery key on bob's entry",
+            |a| {
+                a.user = w.bob;
+                a.entry_state = entry(POOL, &w.bob);
+            },
+            BettingError::NotRecoveryKey,
+        )
+        .build(),
+        matrix::<instruction::CancelRecovery, _, _>(w, SETTLED_POOL, || {
+            accounts::ACancelRecovery {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                entry_state: entry(SETTLED_POOL, &w.alice),
+            }
+        })
+        .deny(
+            "the recovery key cancels its own challenge",
+            |a| a.user = w.alice_recovery,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::RecoverClaim, _, _>(w, SETTLED_POOL, || accounts::ARecoverClaim {
+            recovery_key: w.alice_recovery,
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(SETTLED_POOL),
+            entry_state: entry(SETTLED_POOL, &w.alice),
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(SETTLED_POOL),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory claims alice's recovered winnings",
+            |a| a.recovery_key = w.mallory,
+            BettingError::NotRecoveryKey,
+        )
+        .deny(
+            "alice's recovery key claims bob's winnings",
+            |a| {
+                a.user = w.bob;
+                a.entry_state = entry(SETTLED_POOL, &w.bob);
+            },
+            BettingError::NotRecoveryKey,
+        )
+        .build(),
+    ]
+}
@@ -0,0 +1,365 @@
+//! Deposits: entries, direct and commit-reveal deposits, the prepaid bet balance and
+//! tokenized entries, plus the read-only deposit quote. Each is alice's own; mallory must not
+//! be able to act on her accounts.
+
+use super::harness::{matrix, Matrix};
+use super::world::{
+    ata, bet_balance, commitment, entry, history, main_state, outcome_mint, pending_deposit, pool,
+    pool_vault, sol_vault, summary, treasury, World, POOL, SETTLED_POOL, SPL_POOL,
+};
+use crate::error::BettingError;
+use crate::{accounts, instruction, DepositInput};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token;
+use anchor_spl::token::spl_token;
+
+const AMOUNT: u64 = 100_000_000;
+
+fn deposit_input() -> DepositInput {
+    DepositInput {
+        bet_id: POOL,
+        is_yes: true,
+        amount: AMOUNT,
+        client_nonce: None,
+        expected_state_hash: None,
+        insure: None,
+        min_token_amount: 0,
+        referrer: None,
+    }
+}
+
+pub(super) fn matrices(w: &World) -> Vec<Matrix> {
+    vec![
+        matrix::<instruction::CreateEntry, _, _>(w, POOL, || accounts::ACreateEntry {
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(POOL),
+            entry_state: entry(POOL, &w.alice),
+            gate_token_account: None,
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::TransferEntry, _, _>(w, POOL, || accounts::ATransferEntry {
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(POOL),
+            source_entry: entry(POOL, &w.alice),
+            recipient: w.other_creator,
+            destination_entry: entry(POOL, &w.other_creator),
+            gate_token_account: None,
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory moves alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "into bob's entry under another recipient",
+            |a| a.destination_entry = entry(POOL, &w.bob),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::Deposit, _, _>(w, deposit_input(), || accounts::ADeposit {
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(POOL),
+            entry_state: entry(POOL, &w.alice),
+            history_state: Some(history(POOL)),
+            pool_summary: Some(summary(POOL)),
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(POOL),
+            gate_token_account: None,
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool's summary",
+            |a| a.pool_summary = Some(summary(SPL_POOL)),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "without the pool's summary",
+            |a| a.pool_summary = None,
+            BettingError::PoolSummaryRequired,
+        )
+        .build(),
+        matrix::<instruction::CommitDeposit, _, _>(w, (POOL, [7u8; 32], AMOUNT), || {
+            accounts::ACommitDeposit {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                commitment_state: commitment(POOL, &w.alice),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(POOL),
+                gate_token_account: None,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory with alice's commitment",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::QueueDeposit, _, _>(w, (POOL, true, AMOUNT), || {
+            accounts::AQueueDeposit {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                entry_state: entry(POOL, &w.alice),
+                pending_deposit: pending_deposit(POOL, 2),
+                gate_token_account: None,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory without an entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "a queue position already taken",
+            |a| a.pending_deposit = pending_deposit(POOL, 0),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        // Alice heads the waitlist; anyone may crank it, but only into her entry and wallet
+        matrix::<instruction::ExecutePendingDeposit, _, _>(w, (POOL, 0u64), || {
+            accounts::AExecutePendingDeposit {
+                cranker: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                pending_deposit: pending_deposit(POOL, 0),
+                user: w.alice,
+                entry_state: entry(POOL, &w.alice),
+                history_state: Some(history(POOL)),
+                pool_summary: Some(summary(POOL)),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(POOL),
+                gate_token_account: None,
+                system_program: system_program::ID,
+            }
+        })
+        .deny("refund to mallory", |a| a.user = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "bob's entry",
+            |a| a.entry_state = entry(POOL, &w.bob),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "without the pool's summary",
+            |a| a.pool_summary = None,
+            BettingError::PoolSummaryRequired,
+        )
+        .build(),
+        matrix::<instruction::RevealDeposit, _, _>(w, (POOL, true, [0u8; 32], 0u64), || {
+            accounts::ARevealDeposit {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                entry_state: entry(POOL, &w.alice),
+                history_state: Some(history(POOL)),
+                pool_summary: Some(summary(POOL)),
+                commitment_state: commitment(POOL, &w.alice),
+                gate_token_account: None,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory with alice's entry and commitment",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "without the pool's summary",
+            |a| a.pool_summary = None,
+            BettingError::PoolSummaryRequired,
+        )
+        .build(),
+        matrix::<instruction::RefundCommitment, _, _>(w, POOL, || {
+            accounts::ARefundCommitment {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                commitment_state: commitment(POOL, &w.alice),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(POOL),
+                platform_owner: treasury(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .deny(
+            "mallory with alice's commitment",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        // Alice's commitment outlived the settled pool; anyone may send it back to her
+        matrix::<instruction::RefundUnrevealed, _, _>(w, SETTLED_POOL, || {
+            accounts::ARefundUnrevealed {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                user: w.alice,
+                commitment_state: commitment(SETTLED_POOL, &w.alice),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(SETTLED_POOL),
+                platform_owner: treasury(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .deny(
+            "mallory refunds alice's commitment to herself",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::FundBetBalance, _, _>(w, AMOUNT, || accounts::AFundBetBalance {
+            user: w.alice,
+            main_state: main_state(),
+            bet_balance: bet_balance(&w.alice),
+            system_program: system_program::ID,
+        })
+        .build(),
+        matrix::<instruction::WithdrawBetBalance, _, _>(w, AMOUNT, || {
+            accounts::AWithdrawBetBalance {
+                user: w.alice,
+                bet_balance: bet_balance(&w.alice),
+            }
+        })
+        .deny(
+            "mallory with alice's balance",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::DepositFromBalance, _, _>(w, deposit_input(), || {
+            accounts::ADepositFromBalance {
+                user: w.alice,
+                main_state: main_state(),
+                bet_balance: bet_balance(&w.alice),
+                pool_state: pool(POOL),
+                entry_state: entry(POOL, &w.alice),
+                history_state: Some(history(POOL)),
+                pool_summary: Some(summary(POOL)),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(POOL),
+                gate_token_account: None,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory with alice's balance and entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::TokenizeEntry, _, _>(w, POOL, || accounts::ATokenizeEntry {
+            user: w.alice,
+            pool_state: pool(POOL),
+            entry_state: entry(POOL, &w.alice),
+            outcome_mint: outcome_mint(POOL, true),
+            user_token_account: ata(&w.alice, &outcome_mint(POOL, true)),
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory with alice's entry and tokens",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .deny(
+            "mallory with alice's entry",
+            |a| {
+                a.user = w.mallory;
+                a.user_token_account = ata(&w.mallory, &outcome_mint(POOL, true));
+            },
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "the side alice did not bet on",
+            |a| a.outcome_mint = outcome_mint(POOL, false),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "bob's token account",
+            |a| a.user_token_account = ata(&w.bob, &outcome_mint(POOL, true)),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        // The pool is the outcome mint's authority, checked before the entry's seeds
+        .foreign_fails_with(pool(POOL), ErrorCode::ConstraintMintMintAuthority)
+        .build(),
+        matrix::<instruction::QuoteDeposit, _, _>(w, (POOL, true, AMOUNT), || {
+            accounts::AQuoteDeposit {
+                main_state: main_state(),
+                pool_state: pool(POOL),
+            }
+        })
+        .deny_args::<instruction::QuoteDeposit>(
+            "an empty amount",
+            (POOL, true, 0u64),
+            |_| {},
+            BettingError::InvalidBet,
+        )
+        .deny(
+            "another pool",
+            |a| a.pool_state = pool(SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+    ]
+}
+
+// Every way into a gated pool turns away a wallet that doesn't show the gate token; the crank
+// refunds a queued deposit instead (see waitlist_accepts)
+#[test]
+fn gated_pools_refuse_ungated_wallets_on_every_path() {
+    let mut w = World::new();
+    w.gate(&[POOL, SPL_POOL]);
+    let paths = [
+        "create_entry",
+        "transfer_entry",
+        "deposit",
+        "deposit_from_balance",
+        "deposit_spl",
+        "commit_deposit",
+        "reveal_deposit",
+        "queue_deposit",
+    ];
+    let matrices: Vec<_> = super::registry(&w)
+        .into_iter()
+        .filter(|matrix| paths.contains(&matrix.instruction.as_str()))
+        .collect();
+    assert_eq!(matrices.len(), paths.len());
+    let expected = Err(Error::from(BettingError::GateTokenRequired).into());
+    for matrix in &matrices {
+        assert_eq!(matrix.control(&w), expected, "{}", matrix.instruction);
+    }
+}
@@ -0,0 +1,470 @@
+//! Runs instructions through the program's `entry` without a validator, and the matrix builder
+//! every instruction registers its denied attempts with.
+//!
+//! Syscalls go to `Runtime`: a fixed clock, default rent, and just enough of the system,
+//! token and associated token programs for `init` constraints to create accounts. Token
+//! balances are not tracked; cases only need instructions to get as far as their checks.
+
+use super::world::{World, NOW};
+use anchor_lang::error::{Error, ErrorCode};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::{
+    ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS,
+};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::{Discriminator, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::instruction::TokenInstruction;
+use std::marker::PhantomData;
+use std::sync::Once;
+
+struct Runtime;
+
+impl SyscallStubs for Runtime {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: NOW,
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let account = |index: usize| {
+            let key = instruction.accounts[index].pubkey;
+            account_infos
+                .iter()
+                .find(|info| info.key == &key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        if instruction.program_id == anchor_lang::system_program::ID {
+            system_instruction(&instruction.data, account)
+        } else if instruction.program_id == spl_token::ID {
+            token_instruction(&instruction.data, account)
+        } else if instruction.program_id == anchor_spl::associated_token::ID {
+            // Create and CreateIdempotent: [payer, ata, wallet, mint, system, token]
+            let ata = account(1)?;
+            allocate(ata, spl_token::state::Account::LEN);
+            move_lamports(account(0)?, ata, Rent::default().minimum_balance(ata.data_len()))?;
+            ata.assign(&spl_token::ID);
+            pack_token_account(ata, account(3)?.key, account(2)?.key)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn system_instruction<'a, 'info>(
+    data: &[u8],
+    account: impl Fn(usize) -> std::result::Result<&'a AccountInfo<'info>, ProgramError>,
+) -> ProgramResult
+where
+    'info: 'a,
+{
+    let u64_at = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+    let key_at = |at: usize| Pubkey::try_from(&data[at..at + 32]).unwrap();
+    match u32::from_le_bytes(data[..4].try_into().unwrap()) {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            move_lamports(account(0)?, account(1)?, u64_at(4))?;
+            allocate(account(1)?, u64_at(12) as usize);
+            account(1)?.assign(&key_at(20));
+        }
+        // Assign { owner }
+        1 => account(0)?.assign(&key_at(4)),
+        // Transfer { lamports }
+        2 => move_lamports(account(0)?, account(1)?, u64_at(4))?,
+        // Allocate { space }
+        8 => allocate(account(0)?, u64_at(4) as usize),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn token_instruction<'a, 'info>(
+    data: &[u8],
+    account: impl Fn(usize) -> std::result::Result<&'a AccountInfo<'info>, ProgramError>,
+) -> ProgramResult
+where
+    'info: 'a,
+{
+    match TokenInstruction::unpack(data)? {
+        TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority,
+            freeze_authority,
+        } => {
+            let mint = spl_token::state::Mint {
+                mint_authority: COption::Some(mint_authority),
+                supply: 0,
+                decimals,
+                is_initialized: true,
+                freeze_authority,
+            };
+            spl_token::state::Mint::pack(mint, &mut account(0)?.try_borrow_mut_data()?)
+        }
+        TokenInstruction::InitializeAccount3 { owner } => {
+            pack_token_account(account(0)?, account(1)?.key, &owner)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn pack_token_account(info: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> ProgramResult {
+    let token_account = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    spl_token::state::Account::pack(token_account, &mut info.try_borrow_mut_data()?)
+}
+
+fn allocate(info: &AccountInfo, space: usize) {
+    *info.data.borrow_mut() = serialized_data(vec![0u8; space]);
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> ProgramResult {
+    let remaining = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+// Lays a key and its data out the way the runtime serializes them: the original data length
+// sits just before the key and the current length just before the data, where `realloc` and
+// Anchor's `close` write it
+fn serialized(key: Pubkey, data: Vec<u8>) -> (&'static Pubkey, &'static mut [u8]) {
+    let key_buffer = Box::leak(vec![0u8; 4 + 32].into_boxed_slice());
+    key_buffer[..4].copy_from_slice(&(data.len() as u32).to_le_bytes());
+    key_buffer[4..].copy_from_slice(key.as_ref());
+    let key = unsafe { &*(key_buffer[4..].as_ptr() as *const Pubkey) };
+    (key, serialized_data(data))
+}
+
+// Like the runtime, leaves MAX_PERMITTED_DATA_INCREASE spare bytes after the data for reallocs
+fn serialized_data(data: Vec<u8>) -> &'static mut [u8] {
+    let len = data.len();
+    let buffer = Box::leak(vec![0u8; 8 + len + MAX_PERMITTED_DATA_INCREASE].into_boxed_slice());
+    buffer[..8].copy_from_slice(&(len as u64).to_le_bytes());
+    buffer[8..8 + len].copy_from_slice(&data);
+    &mut buffer[8..8 + len]
+}
+
+// Runs one instruction against a fresh copy of the world's accounts
+pub(super) fn execute(world: &World, metas: &[AccountMeta], data: &[u8]) -> ProgramResult {
+    static RUNTIME: Once = Once::new();
+    RUNTIME.call_once(|| {
+        set_syscall_stubs(Box::new(Runtime));
+    });
+
+    // Like the runtime, repeated keys share one account with the union of their privileges
+    let mut unique: Vec<AccountInfo<'static>> = Vec::new();
+    let infos: Vec<AccountInfo<'static>> = metas
+        .iter()
+        .map(|meta| {
+            if let Some(info) = unique.iter().find(|info| info.key == &meta.pubkey) {
+                return info.clone();
+            }
+            let repeats = || metas.iter().filter(|other| other.pubkey == meta.pubkey);
+            let stored = world.account(&meta.pubkey);
+            let (key, data) = serialized(meta.pubkey, stored.data);
+            let info = AccountInfo::new(
+                key,
+                repeats().any(|other| other.is_signer),
+                repeats().any(|other| other.is_writable),
+                Box::leak(Box::new(stored.lamports)),
+                data,
+                Box::leak(Box::new(stored.owner)),
+                stored.executable,
+                u64::MAX,
+            );
+            unique.push(info.clone());
+            info
+        })
+        .collect();
+    crate::entry(&crate::ID, Box::leak(infos.into_boxed_slice()), data)
+}
+
+// The error an attempt must fail with
+pub(super) struct Expected {
+    name: String,
+    error: ProgramError,
+}
+
+impl Expected {
+    fn new(error: impl Into<Error>) -> Self {
+        let error = error.into();
+        let name = match &error {
+            Error::AnchorError(anchor_error) => anchor_error.error_name.clone(),
+            Error::ProgramError(program_error) => program_error.to_string(),
+        };
+        Self {
+            name,
+            error: error.into(),
+        }
+    }
+}
+
+struct Case {
+    label: String,
+    metas: Vec<AccountMeta>,
+    data: Vec<u8>,
+    expected: Expected,
+}
+
+// Denied attempts at one instruction, next to a control attempt by the rightful caller
+pub(super) struct Matrix {
+    pub instruction: String,
+    control_metas: Vec<AccountMeta>,
+    control_data: Vec<u8>,
+    cases: Vec<Case>,
+}
+
+impl Matrix {
+    // Runs every case; returns a line per case that did not fail as expected
+    pub fn check(&self, world: &World) -> Vec<String> {
+        let control = execute(world, &self.control_metas, &self.control_data);
+        let mut failures = Vec::new();
+        for case in &self.cases {
+            let expected = Err(case.expected.error.clone());
+            let outcome = execute(world, &case.metas, &case.data);
+            if outcome != expected {
+                failures.push(format!(
+                    "{}: {}: expected {}, got {:?}",
+                    self.instruction, case.label, case.expected.name, outcome
+                ));
+            } else if control == expected {
+                // Otherwise the case proves nothing about who may call the instruction
+                failures.push(format!(
+                    "{}: {}: the control attempt fails with {} too",
+                    self.instruction, case.label, case.expected.name
+                ));
+            }
+        }
+        failures
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    // Runs the control attempt alone, against a world that may differ from the one its
+    // accounts were picked in
+    pub fn control(&self, world: &World) -> ProgramResult {
+        execute(world, &self.control_metas, &self.control_data)
+    }
+}
+
+pub(super) struct Builder<'w, A, F> {
+    world: &'w World,
+    instruction: String,
+    data: Vec<u8>,
+    accounts: F,
+    remaining: Vec<AccountMeta>,
+    cases: Vec<Case>,
+    foreign_errors: Vec<(Pubkey, Expected)>,
+    metas: PhantomData<A>,
+}
+
+// Starts the matrix of instruction `I`. `accounts` builds the control attempt's accounts; each
+// denied case starts from a fresh copy. Every signer that drops out and every account the
+// world has a foreign twin for (another pool's, another user's) is tried automatically.
+pub(super) fn matrix<'w, I, A, F>(
+    world: &'w World,
+    args: impl AnchorSerialize,
+    accounts: F,
+) -> Builder<'w, A, F>
+where
+    I: Discriminator,
+    A: ToAccountMetas,
+    F: Fn() -> A,
+{
+    let type_name = std::any::type_name::<I>();
+    let name = type_name.rsplit("::").next().unwrap_or(type_name);
+    Builder {
+        world,
+        instruction: snake_case(name),
+        data: instruction_data::<I>(args),
+        accounts,
+        remaining: Vec::new(),
+        cases: Vec::new(),
+        foreign_errors: Vec::new(),
+        metas: PhantomData,
+    }
+}
+
+impl<A: ToAccountMetas, F: Fn() -> A> Builder<'_, A, F> {
+    // Accounts after the instruction's own, passed by the control attempt and every case
+    pub fn remaining(mut self, remaining: Vec<AccountMeta>) -> Self {
+        self.remaining = remaining;
+        self
+    }
+
+    // Same arguments, accounts changed by `mutate`
+    pub fn deny(self, label: &str, mutate: impl FnOnce(&mut A), error: impl Into<Error>) -> Self {
+        let data = self.data.clone();
+        self.deny_data(label, data, mutate, error)
+    }
+
+    // Arguments replaced, accounts changed by `mutate`
+    pub fn deny_args<I: Discriminator>(
+        self,
+        label: &str,
+        args: impl AnchorSerialize,
+        mutate: impl FnOnce(&mut A),
+        error: impl Into<Error>,
+    ) -> Self {
+        self.deny_data(label, instruction_data::<I>(args), mutate, error)
+    }
+
+    // Same arguments and accounts, the remaining accounts replaced
+    pub fn deny_remaining(
+        mut self,
+        label: &str,
+        remaining: Vec<AccountMeta>,
+        error: impl Into<Error>,
+    ) -> Self {
+        let mut metas = (self.accounts)().to_account_metas(None);
+        metas.extend(remaining);
+        self.cases.push(Case {
+            label: label.to_string(),
+            metas,
+            data: self.data.clone(),
+            expected: Expected::new(error),
+        });
+        self
+    }
+
+    // What swapping `original` for one of its foreign twins fails with, when the account is not
+    // caught by its own seeds (ConstraintSeeds is assumed otherwise)
+    pub fn foreign_fails_with(mut self, original: Pubkey, error: impl Into<Error>) -> Self {
+        self.foreign_errors.push((original, Expected::new(error)));
+        self
+    }
+
+    fn deny_data(
+        mut self,
+        label: &str,
+        data: Vec<u8>,
+        mutate: impl FnOnce(&mut A),
+        error: impl Into<Error>,
+    ) -> Self {
+        let mut accounts = (self.accounts)();
+        mutate(&mut accounts);
+        let mut metas = accounts.to_account_metas(None);
+        metas.extend(self.remaining.iter().cloned());
+        self.cases.push(Case {
+            label: label.to_string(),
+            metas,
+            data,
+            expected: Expected::new(error),
+        });
+        self
+    }
+
+    pub fn build(mut self) -> Matrix {
+        let mut control_metas = (self.accounts)().to_account_metas(None);
+        control_metas.extend(self.remaining.iter().cloned());
+        let world = self.world;
+
+        let mut signers: Vec<Pubkey> = control_metas
+            .iter()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        signers.dedup();
+        for signer in signers {
+            let mut metas = control_metas.clone();
+            for meta in metas.iter_mut().filter(|meta| meta.pubkey == signer) {
+                meta.is_signer = false;
+            }
+            self.cases.push(Case {
+                label: format!("{} does not sign", world.name(&signer)),
+                metas,
+                data: self.data.clone(),
+                expected: Expected::new(ErrorCode::AccountNotSigner),
+            });
+        }
+
+        let mut originals: Vec<Pubkey> = control_metas
+            .iter()
+            .filter(|meta| !meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        originals.dedup();
+        for original in originals {
+            for twin in world.foreign(&original) {
+                let metas = control_metas
+                    .iter()
+                    .map(|meta| AccountMeta {
+                        pubkey: if meta.pubkey == original { *twin } else { meta.pubkey },
+                        ..meta.clone()
+                    })
+                    .collect();
+                let expected = match self.foreign_errors.iter().find(|(key, _)| key == &original) {
+                    Some((_, expected)) => Expected {
+                        name: expected.name.clone(),
+                        error: expected.error.clone(),
+                    },
+                    None => Expected::new(ErrorCode::ConstraintSeeds),
+                };
+                self.cases.push(Case {
+                    label: format!("{} instead of {}", world.name(twin), world.name(&original)),
+                    metas,
+                    data: self.data.clone(),
+                    expected,
+                });
+            }
+        }
+
+        Matrix {
+            instruction: self.instruction,
+            control_metas,
+            control_data: self.data,
+            cases: self.cases,
+        }
+    }
+}
+
+fn instruction_data<I: Discriminator>(args: impl AnchorSerialize) -> Vec<u8> {
+    let mut data = I::DISCRIMINATOR.to_vec();
+    args.serialize(&mut data).unwrap();
+    data
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
@@ -0,0 +1,336 @@
+//! Platform configuration: owner-only and admin-role instructions, the referee bonus vault,
+//! treasury sweeps, revenue share staking and per-user notification preferences.
+
+use super::harness::{matrix, Matrix};
+use super::world::{
+    ata, main_state, outcome_mint, prefs, referral, revenue_epoch, revenue_share, sol_vault,
+    stake, treasury, World, POOL, REVENUE_EPOCH,
+};
+#[cfg(feature = "referee-registry")]
+use super::world::{bonus_vault, referee_profile};
+use crate::constants::{MAX_RESERVE_MILESTONES, MIN_REVENUE_EPOCH_SECS};
+use crate::error::BettingError;
+use crate::{accounts, instruction, ClaimWindowBounds, FeeCurve};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::associated_token;
+use anchor_spl::token::spl_token;
+
+pub(super) fn matrices(w: &World) -> Vec<Matrix> {
+    vec![
+        matrix::<instruction::InitMainState, _, _>(w, (), || accounts::AInitMainState {
+            owner: w.owner,
+            main_state: main_state(),
+            sol_vault: sol_vault(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        })
+        .deny(
+            "treasury instead of the sol vault",
+            |a| a.sol_vault = treasury(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::UpdateMainState, _, _>(
+            w,
+            (
+                w.owner,
+                1_000u64,
+                1_000u64,
+                100u64,
+                200u64,
+                None::<ClaimWindowBounds>,
+                None::<u64>,
+                None::<[u64; MAX_RESERVE_MILESTONES]>,
+                None::<u64>,
+                None::<u64>,
+            ),
+            || accounts::AUpdateMainState {
+                owner: w.owner,
+                main_state: main_state(),
+            },
+        )
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny("fee admin as owner", |a| a.owner = w.fee_admin, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::SetAdmins, _, _>(w, (w.alice, w.bob), || accounts::ASetAdmins {
+            owner: w.owner,
+            main_state: main_state(),
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny("ops admin as owner", |a| a.owner = w.ops_admin, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::SetFees, _, _>(
+            w,
+            (100u64, 200u64, None::<FeeCurve>, None::<u64>, None::<u64>),
+            || accounts::ASetFees {
+                admin: w.fee_admin,
+                main_state: main_state(),
+            },
+        )
+        .deny("mallory as admin", |a| a.admin = w.mallory, BettingError::Unauthorized)
+        .deny("ops admin as admin", |a| a.admin = w.ops_admin, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::SetPaused, _, _>(w, true, || accounts::ASetPaused {
+            admin: w.ops_admin,
+            main_state: main_state(),
+        })
+        .deny("mallory as admin", |a| a.admin = w.mallory, BettingError::Unauthorized)
+        .deny("fee admin as admin", |a| a.admin = w.fee_admin, BettingError::Unauthorized)
+        .build(),
+        #[cfg(feature = "referee-registry")]
+        matrix::<instruction::FundRefereeBonus, _, _>(w, (1_000u64, None::<u64>), || {
+            accounts::AFundRefereeBonus {
+                owner: w.owner,
+                main_state: main_state(),
+                bonus_vault: bonus_vault(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "treasury instead of the bonus vault",
+            |a| a.bonus_vault = treasury(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        #[cfg(feature = "referee-registry")]
+        matrix::<instruction::ClaimRefereeBonus, _, _>(w, (), || {
+            accounts::AClaimRefereeBonus {
+                referee: w.referee,
+                main_state: main_state(),
+                referee_profile: referee_profile(&w.referee),
+                bonus_vault: bonus_vault(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory claims the referee's bonus",
+            |a| a.referee = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "treasury instead of the bonus vault",
+            |a| a.bonus_vault = treasury(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::RegisterReferrer, _, _>(w, (), || accounts::ARegisterReferrer {
+            referrer: w.mallory,
+            referral_state: referral(&w.mallory),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory registers as alice",
+            |a| a.referral_state = referral(&w.alice),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::ClaimReferralFee, _, _>(w, (), || accounts::AClaimReferralFee {
+            referrer: w.alice,
+            referral_state: referral(&w.alice),
+        })
+        .deny(
+            "mallory claims alice's referral fees",
+            |a| a.referrer = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "alice with bob's referral state",
+            |a| a.referral_state = referral(&w.bob),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::SetTreasurySweep, _, _>(w, (w.cold_wallet, 0u64), || {
+            accounts::ASetTreasurySweep {
+                owner: w.owner,
+                main_state: main_state(),
+                treasury: treasury(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "sol vault instead of the treasury",
+            |a| a.treasury = sol_vault(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::SweepTreasuryIfAbove, _, _>(w, (), || {
+            accounts::ASweepTreasuryIfAbove {
+                caller: w.mallory,
+                main_state: main_state(),
+                treasury: treasury(),
+                cold_wallet: w.cold_wallet,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory instead of the cold wallet",
+            |a| a.cold_wallet = w.mallory,
+            BettingError::Unauthorized,
+        )
+        .deny(
+            "sol vault instead of the treasury",
+            |a| a.treasury = sol_vault(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::SetNotificationPrefs, _, _>(
+            w,
+            ([7u8; 32], "https://example.com".to_string(), true, true),
+            || accounts::ASetNotificationPrefs {
+                user: w.alice,
+                notification_prefs: prefs(&w.alice),
+                system_program: system_program::ID,
+            },
+        )
+        .build(),
+        matrix::<instruction::CloseNotificationPrefs, _, _>(w, (), || {
+            accounts::ACloseNotificationPrefs {
+                user: w.alice,
+                notification_prefs: prefs(&w.alice),
+            }
+        })
+        .build(),
+        matrix::<instruction::SetRevenueShare, _, _>(
+            w,
+            (1_000u64, MIN_REVENUE_EPOCH_SECS),
+            || accounts::ASetRevenueShare {
+                owner: w.owner,
+                main_state: main_state(),
+                revenue_share: revenue_share(),
+                stake_mint: w.mint,
+                stake_vault: ata(&revenue_share(), &w.mint),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+        )
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "staking switched to another mint",
+            |a| {
+                a.stake_mint = outcome_mint(POOL, true);
+                a.stake_vault = ata(&revenue_share(), &outcome_mint(POOL, true));
+            },
+            BettingError::StakeMintMismatch,
+        )
+        .build(),
+        matrix::<instruction::StakePlatformToken, _, _>(w, 1_000u64, || {
+            accounts::AStakePlatformToken {
+                user: w.bob,
+                revenue_share: revenue_share(),
+                stake_state: stake(&w.bob),
+                user_token_account: ata(&w.bob, &w.mint),
+                stake_vault: ata(&revenue_share(), &w.mint),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "bob stakes mallory's tokens",
+            |a| a.user_token_account = ata(&w.mallory, &w.mint),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .deny(
+            "mallory with bob's stake",
+            |a| {
+                a.user = w.mallory;
+                a.user_token_account = ata(&w.mallory, &w.mint);
+            },
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "alice's tokens as the stake vault",
+            |a| a.stake_vault = ata(&w.alice, &w.mint),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .build(),
+        matrix::<instruction::UnstakePlatformToken, _, _>(w, 1_000u64, || {
+            accounts::AUnstakePlatformToken {
+                user: w.bob,
+                revenue_share: revenue_share(),
+                stake_state: stake(&w.bob),
+                user_token_account: ata(&w.bob, &w.mint),
+                stake_vault: ata(&revenue_share(), &w.mint),
+                token_program: spl_token::ID,
+            }
+        })
+        .deny(
+            "bob's stake paid to mallory",
+            |a| a.user_token_account = ata(&w.mallory, &w.mint),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .deny(
+            "mallory with bob's stake",
+            |a| {
+                a.user = w.mallory;
+                a.user_token_account = ata(&w.mallory, &w.mint);
+            },
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::CheckpointEpoch, _, _>(w, (), || accounts::ACheckpointEpoch {
+            caller: w.mallory,
+            main_state: main_state(),
+            revenue_share: revenue_share(),
+            revenue_epoch: revenue_epoch(REVENUE_EPOCH),
+            treasury: treasury(),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "sol vault instead of the treasury",
+            |a| a.treasury = sol_vault(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "the next epoch's record",
+            |a| a.revenue_epoch = revenue_epoch(REVENUE_EPOCH + 1),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::ClaimRevenueShare, _, _>(w, (), || {
+            accounts::AClaimRevenueShare {
+                user: w.alice,
+                revenue_share: revenue_share(),
+                stake_state: stake(&w.alice),
+                revenue_epoch: revenue_epoch(REVENUE_EPOCH - 1),
+            }
+        })
+        .deny(
+            "mallory with alice's stake",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::GetSchemaVersion, _, _>(w, (), || accounts::AGetSchemaVersion {
+            main_state: main_state(),
+        })
+        .deny(
+            "the revenue share as main state",
+            |a| a.main_state = revenue_share(),
+            ErrorCode::AccountDiscriminatorMismatch,
+        )
+        .build(),
+        #[cfg(feature = "referee-registry")]
+        matrix::<instruction::SetDefaultReferees, _, _>(w, vec![w.referee], || {
+            accounts::ASetDefaultReferees {
+                owner: w.owner,
+                main_state: main_state(),
+                default_referees: super::world::default_referees(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .build(),
+        #[cfg(feature = "test-utils")]
+        matrix::<instruction::SetMockClock, _, _>(w, 60i64, || accounts::ASetMockClock {
+            owner: w.owner,
+            main_state: main_state(),
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .build(),
+    ]
+}
@@ -0,0 +1,200 @@
+//! Multi-outcome markets and SPL-denominated pools, which keep their own deposit, resolution
+//! and claim instructions.
+
+use super::harness::{matrix, Matrix};
+use super::world::{
+    ata, entry, history, main_state, outcome_entry, outcome_market, pool, sol_vault, summary,
+    treasury, World, MARKET, NEXT_BET_ID, SPL_POOL,
+};
+use crate::error::BettingError;
+use crate::{accounts, instruction, CreateOutcomeMarketInput, DepositInput};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+pub(super) fn matrices(w: &World) -> Vec<Matrix> {
+    let token_vault = ata(&pool(SPL_POOL), &w.mint);
+    let tokens = |wallet: &Pubkey| ata(wallet, &w.mint);
+
+    vec![
+        matrix::<instruction::CreateOutcomeMarket, _, _>(
+            w,
+            CreateOutcomeMarketInput {
+                title: "Authz market".to_string(),
+                end_timestamp: -1,
+                referee: w.referee,
+                outcomes: vec!["red".to_string(), "green".to_string()],
+            },
+            || accounts::ACreateOutcomeMarket {
+                creator: w.creator,
+                main_state: main_state(),
+                outcome_market: outcome_market(NEXT_BET_ID),
+                sol_vault: sol_vault(),
+                system_program: system_program::ID,
+            },
+        )
+        .deny(
+            "an existing market's address",
+            |a| a.outcome_market = outcome_market(MARKET),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "treasury instead of the sol vault",
+            |a| a.sol_vault = treasury(),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::DepositOutcome, _, _>(w, (MARKET, 0u8, 1_000u64), || {
+            accounts::ADepositOutcome {
+                user: w.alice,
+                main_state: main_state(),
+                outcome_market: outcome_market(MARKET),
+                outcome_entry: outcome_entry(MARKET, &w.alice),
+                sol_vault: sol_vault(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::SetOutcomeWinner, _, _>(w, (MARKET, 0u8), || {
+            accounts::ASetOutcomeWinner {
+                referee: w.referee,
+                main_state: main_state(),
+                outcome_market: outcome_market(MARKET),
+                creator: w.creator,
+                sol_vault: sol_vault(),
+                platform_owner: treasury(),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as referee", |a| a.referee = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "another market's referee",
+            |a| a.referee = w.other_referee,
+            BettingError::Unauthorized,
+        )
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .build(),
+        matrix::<instruction::ClaimOutcome, _, _>(w, MARKET, || accounts::AClaimOutcome {
+            user: w.alice,
+            outcome_market: outcome_market(MARKET),
+            outcome_entry: outcome_entry(MARKET, &w.alice),
+            sol_vault: sol_vault(),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::DepositSpl, _, _>(
+            w,
+            DepositInput {
+                bet_id: SPL_POOL,
+                is_yes: true,
+                amount: 1_000,
+                client_nonce: None,
+                expected_state_hash: None,
+                insure: None,
+                min_token_amount: 0,
+                referrer: None,
+            },
+            || accounts::ADepositSpl {
+                user: w.alice,
+                main_state: main_state(),
+                pool_state: pool(SPL_POOL),
+                entry_state: entry(SPL_POOL, &w.alice),
+                history_state: Some(history(SPL_POOL)),
+                pool_summary: Some(summary(SPL_POOL)),
+                user_token_account: tokens(&w.alice),
+                token_vault,
+                token_program: spl_token::ID,
+                gate_token_account: None,
+                system_program: system_program::ID,
+            },
+        )
+        .deny(
+            "alice spends mallory's tokens",
+            |a| a.user_token_account = tokens(&w.mallory),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .deny(
+            "mallory with alice's entry",
+            |a| {
+                a.user = w.mallory;
+                a.user_token_account = tokens(&w.mallory);
+            },
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "alice's tokens as the pool's vault",
+            |a| a.token_vault = tokens(&w.alice),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .build(),
+        matrix::<instruction::ClaimSpl, _, _>(w, SPL_POOL, || accounts::AClaimSpl {
+            user: w.alice,
+            main_state: main_state(),
+            pool_state: pool(SPL_POOL),
+            entry_state: entry(SPL_POOL, &w.alice),
+            token_vault,
+            user_token_account: tokens(&w.alice),
+            settlement_report: None,
+            token_program: spl_token::ID,
+        })
+        .deny(
+            "mallory with alice's entry",
+            |a| {
+                a.user = w.mallory;
+                a.user_token_account = tokens(&w.mallory);
+            },
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "alice's tokens as the pool's vault",
+            |a| a.token_vault = tokens(&w.alice),
+            ErrorCode::ConstraintTokenOwner,
+        )
+        .build(),
+        matrix::<instruction::ClaimCreatorFeeSpl, _, _>(w, SPL_POOL, || {
+            accounts::AClaimFeeSpl {
+                claimer: w.creator,
+                main_state: main_state(),
+                pool_state: pool(SPL_POOL),
+                token_vault,
+                destination: tokens(&w.creator),
+                token_program: spl_token::ID,
+            }
+        })
+        .deny("mallory as creator", |a| a.claimer = w.mallory, BettingError::Unauthorized)
+        .deny("the owner as creator", |a| a.claimer = w.owner, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::ClaimPlatformFeeSpl, _, _>(w, SPL_POOL, || {
+            accounts::AClaimFeeSpl {
+                claimer: w.owner,
+                main_state: main_state(),
+                pool_state: pool(SPL_POOL),
+                token_vault,
+                destination: tokens(&w.owner),
+                token_program: spl_token::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.claimer = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "the pool's creator as owner",
+            |a| a.claimer = w.creator,
+            BettingError::Unauthorized,
+        )
+        .build(),
+    ]
+}
@@ -0,0 +1,89 @@
+//! Permission boundaries: every instruction is attempted with missing signers, the wrong
+//! wallet, the wrong PDA and accounts of a foreign pool or user, and must fail with the exact
+//! error its constraints or handler define. Each instruction registers its matrix in
+//! `registry`; an instruction in lib.rs without one fails `every_instruction_is_registered`.
+
+mod claims;
+mod deposits;
+mod harness;
+mod main_state;
+mod markets;
+mod pools;
+mod resolution;
+mod world;
+
+use harness::Matrix;
+use std::collections::BTreeSet;
+use world::World;
+
+fn registry(w: &World) -> Vec<Matrix> {
+    [
+        main_state::matrices(w),
+        pools::matrices(w),
+        deposits::matrices(w),
+        resolution::matrices(w),
+        claims::matrices(w),
+        markets::matrices(w),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+// `pub fn` names in the program module, minus those gated behind a disabled feature
+fn program_instructions() -> BTreeSet<String> {
+    let source = include_str!("../../lib.rs");
+    let mut instructions = BTreeSet::new();
+    let mut enabled = true;
+    for line in source.lines() {
+        let line = line.trim();
+        if let Some(feature) = line
+            .strip_prefix("#[cfg(feature = \"")
+            .and_then(|rest| rest.strip_suffix("\")]"))
+        {
+            enabled = match feature {
+                "platform-fees" => cfg!(feature = "platform-fees"),
+                "referee-registry" => cfg!(feature = "referee-registry"),
+                "oracle" => cfg!(feature = "oracle"),
+                "test-utils" => cfg!(feature = "test-utils"),
+                other => panic!("unknown feature {other} gates an instruction"),
+            };
+        } else if let Some(rest) = line.strip_prefix("pub fn ") {
+            let name = rest.split(['(', '<']).next().unwrap_or_default();
+            if enabled {
+                instructions.insert(name.to_string());
+            }
+            enabled = true;
+        } else if !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//") {
+            // A gate on anything else, e.g. a module, ends at that item
+            enabled = true;
+        }
+    }
+    instructions
+}
+
+#[test]
+fn every_instruction_is_registered() {
+    let w = World::new();
+    let matrices = registry(&w);
+    let registered: BTreeSet<String> = matrices.iter().map(|m| m.instruction.clone()).collect();
+    let instructions = program_instructions();
+
+    let missing: Vec<_> = instructions.difference(&registered).collect();
+    assert!(missing.is_empty(), "instructions without an authz matrix: {missing:?}");
+    let unknown: Vec<_> = registered.difference(&instructions).collect();
+    assert!(unknown.is_empty(), "matrices for unknown instructions: {unknown:?}");
+    for matrix in &matrices {
+        assert!(!matrix.is_empty(), "{} has no denied cases", matrix.instruction);
+    }
+}
+
+#[test]
+fn authorization_matrix() {
+    let w = World::new();
+    let failures: Vec<String> = registry(&w)
+        .iter()
+        .flat_map(|matrix| matrix.check(&w))
+        .collect();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,559 @@
+//! Pool lifecycle outside of deposits, resolution and claims: creation, updates, linking,
+//! referee hand-over, subscriptions, escalation, guarantees, event-start locks, final odds,
+//! layout migrations and closing settled accounts.
+
+use super::harness::{matrix, Matrix};
+use super::world::{
+    ata, creator_rate_limit, entry, guarantee, history, link, main_state, metadata, pool,
+    pool_vault, prefs, sol_vault, subscribers, summary, title_index, World, LEGACY_POOL,
+    NEXT_BET_ID, NOW, OTHER_POOL, OTHER_SETTLED_POOL, POOL, PROVISIONAL_POOL, RANGE_POOL,
+    SETTLED_POOL, TITLE,
+};
+#[cfg(feature = "oracle")]
+use super::world::{start_feed, FEED_ID};
+use crate::error::BettingError;
+use crate::{
+    accounts, instruction, CreateMetaPoolInput, CreatePoolInput, LocalizedText, MetaCondition,
+    PriceCondition,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token;
+use anchor_spl::token::spl_token;
+
+fn create_pool_input(title: &str, referee: Pubkey) -> CreatePoolInput {
+    CreatePoolInput {
+        title: title.to_string(),
+        description: "Opened by the authorization matrix".to_string(),
+        end_timestamp: -1,
+        referee: Some(referee),
+        referee_index: None,
+        commit_reveal: None,
+        installments: None,
+        installment_period_secs: None,
+        unit_symbol: None,
+        decimals: None,
+        side_a_label: None,
+        side_b_label: None,
+        disable_history: None,
+        waive_creator_self_fee: None,
+        referee_deadline: None,
+        refund_deadline: None,
+        trading_end_timestamp: None,
+        resolution_deadline: None,
+        creator_donation_bps: None,
+        max_deposits_per_entry: None,
+        optimistic_resolution: None,
+        outcome_tokens: None,
+        range_buckets: None,
+        claim_deadline_override: None,
+        provisional_window_secs: None,
+        start_feed: None,
+        price_condition: None,
+        initial_yes_liquidity: None,
+        initial_no_liquidity: None,
+        scalar_bounds: None,
+        opens_at: None,
+        referee_committee: None,
+        referee_threshold: None,
+        gate_mint: None,
+        gate_min_amount: None,
+        min_participants: None,
+        max_pool_reserve: None,
+        max_user_deposit: None,
+        referee_fee_bps: None,
+        pricing_model: None,
+        lmsr_liquidity: None,
+    }
+}
+
+pub(super) fn matrices(w: &World) -> Vec<Matrix> {
+    let new_title = "New pool";
+    vec![
+        matrix::<instruction::CreatePool, _, _>(
+            w,
+            create_pool_input(new_title, w.referee),
+            || accounts::ACreatePool {
+                creator: w.creator,
+                main_state: main_state(),
+                pool_state: pool(NEXT_BET_ID),
+                history_state: Some(history(NEXT_BET_ID)),
+                pool_summary: summary(NEXT_BET_ID),
+                #[cfg(feature = "referee-registry")]
+                default_referees: None,
+                title_index: title_index(&w.creator, new_title),
+                creator_rate_limit: creator_rate_limit(&w.creator),
+                pool_vault: pool_vault(NEXT_BET_ID),
+                system_program: system_program::ID,
+            },
+        )
+        .deny(
+            "an existing pool as the new pool",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another creator's title index",
+            |a| a.title_index = title_index(&w.other_creator, new_title),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another creator's rate limit",
+            |a| a.creator_rate_limit = creator_rate_limit(&w.other_creator),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "an existing pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "an existing pool's summary",
+            |a| a.pool_summary = summary(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::CreateMetaPool, _, _>(
+            w,
+            CreateMetaPoolInput {
+                pool: CreatePoolInput {
+                    referee: None,
+                    end_timestamp: NOW + 60,
+                    ..create_pool_input(new_title, w.referee)
+                },
+                condition: MetaCondition {
+                    target_bet_id: POOL,
+                    metric: MetaCondition::TOTAL_RESERVE,
+                    comparison: PriceCondition::AT_OR_ABOVE,
+                    threshold: 100_000_000_000,
+                    enabled: true,
+                },
+            },
+            || accounts::ACreateMetaPool {
+                creator: w.creator,
+                main_state: main_state(),
+                pool_state: pool(NEXT_BET_ID),
+                history_state: Some(history(NEXT_BET_ID)),
+                title_index: title_index(&w.creator, new_title),
+                creator_rate_limit: creator_rate_limit(&w.creator),
+                pool_vault: pool_vault(NEXT_BET_ID),
+                target_pool_state: pool(POOL),
+                system_program: system_program::ID,
+            },
+        )
+        .deny(
+            "an existing pool as the new pool",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool as the target",
+            |a| a.target_pool_state = pool(OTHER_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another creator's title index",
+            |a| a.title_index = title_index(&w.other_creator, new_title),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::CreatePoolSpl, _, _>(
+            w,
+            create_pool_input(new_title, w.referee),
+            || accounts::ACreatePoolSpl {
+                creator: w.creator,
+                main_state: main_state(),
+                pool_state: pool(NEXT_BET_ID),
+                history_state: Some(history(NEXT_BET_ID)),
+                title_index: title_index(&w.creator, new_title),
+                creator_rate_limit: creator_rate_limit(&w.creator),
+                token_mint: w.mint,
+                token_vault: ata(&pool(NEXT_BET_ID), &w.mint),
+                sol_vault: sol_vault(),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+        )
+        .deny(
+            "a pool vault instead of the sol vault",
+            |a| a.sol_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "an existing pool as the new pool",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another creator's title index",
+            |a| a.title_index = title_index(&w.other_creator, new_title),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::UpdatePool, _, _>(
+            w,
+            (
+                POOL,
+                None::<String>,
+                Some("Updated".to_string()),
+                None::<i64>,
+                None::<Pubkey>,
+                None::<u64>,
+                None::<u64>,
+            ),
+            || accounts::AUpdatePool {
+                updater: w.creator,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                title_index: Some(title_index(&w.creator, TITLE)),
+                new_title_index: None,
+                system_program: system_program::ID,
+            },
+        )
+        .deny("mallory as updater", |a| a.updater = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "another pool's creator as updater",
+            |a| a.updater = w.other_creator,
+            BettingError::Unauthorized,
+        )
+        .deny("the referee as updater", |a| a.updater = w.referee, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::SetPoolMetadata, _, _>(w, (POOL, Vec::<LocalizedText>::new()), || {
+            accounts::ASetPoolMetadata {
+                updater: w.creator,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                pool_metadata: metadata(POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as updater", |a| a.updater = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "another pool's creator as updater",
+            |a| a.updater = w.other_creator,
+            BettingError::Unauthorized,
+        )
+        .deny(
+            "another pool's metadata",
+            |a| a.pool_metadata = metadata(SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::RefreshShareUuid, _, _>(w, POOL, || accounts::ARefreshShareUuid {
+            payer: w.mallory,
+            pool_state: pool(POOL),
+        })
+        .build(),
+        matrix::<instruction::SetLiabilityCap, _, _>(w, (POOL, 5_000u64), || {
+            accounts::ASetLiabilityCap {
+                owner: w.owner,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny("the pool's creator as owner", |a| a.owner = w.creator, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::LinkPools, _, _>(
+            w,
+            (POOL, false, "Rematch".to_string(), "Second leg".to_string(), -1i64),
+            || accounts::ALinkPools {
+                creator: w.creator,
+                parent_pool_state: pool(POOL),
+                pool_link: link(POOL, false),
+                system_program: system_program::ID,
+            },
+        )
+        .deny("mallory as creator", |a| a.creator = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "another pool's creator",
+            |a| a.creator = w.other_creator,
+            BettingError::Unauthorized,
+        )
+        .deny(
+            "a link of the other outcome",
+            |a| a.pool_link = link(POOL, true),
+            ErrorCode::ConstraintSeeds,
+        )
+        // The creator is checked against the parent before the parent's own seeds
+        .foreign_fails_with(pool(POOL), BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::OpenLinkedPool, _, _>(w, (SETTLED_POOL, true), || {
+            accounts::AOpenLinkedPool {
+                payer: w.mallory,
+                main_state: main_state(),
+                parent_pool_state: pool(SETTLED_POOL),
+                pool_link: link(SETTLED_POOL, true),
+                pool_state: pool(NEXT_BET_ID),
+                history_state: Some(history(NEXT_BET_ID)),
+                pool_vault: pool_vault(NEXT_BET_ID),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "an existing pool as the new pool",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::AcceptRefereeRole, _, _>(w, POOL, || {
+            accounts::AAcceptRefereeRole {
+                referee: w.referee,
+                pool_state: pool(POOL),
+            }
+        })
+        .deny("mallory as referee", |a| a.referee = w.mallory, BettingError::NotPendingReferee)
+        .deny(
+            "another pool's referee",
+            |a| a.referee = w.other_referee,
+            BettingError::NotPendingReferee,
+        )
+        .deny("the pool's creator", |a| a.referee = w.creator, BettingError::NotPendingReferee)
+        .build(),
+        matrix::<instruction::SubscribePool, _, _>(w, POOL, || accounts::ASubscribePool {
+            user: w.bob,
+            pool_state: pool(POOL),
+            notification_prefs: prefs(&w.bob),
+            pool_subscribers: subscribers(POOL),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "alice's notification prefs",
+            |a| a.notification_prefs = prefs(&w.alice),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::UnsubscribePool, _, _>(w, POOL, || {
+            accounts::AUnsubscribePool {
+                user: w.alice,
+                pool_subscribers: subscribers(POOL),
+            }
+        })
+        .deny("mallory unsubscribes", |a| a.user = w.mallory, BettingError::NotSubscribed)
+        .build(),
+        matrix::<instruction::NotifyPoolLocked, _, _>(w, PROVISIONAL_POOL, || {
+            accounts::ANotifyPoolLocked {
+                main_state: main_state(),
+                pool_state: pool(PROVISIONAL_POOL),
+                pool_subscribers: subscribers(PROVISIONAL_POOL),
+            }
+        })
+        .deny(
+            "another pool's subscribers",
+            |a| a.pool_subscribers = subscribers(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool's state",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::CloseHistory, _, _>(w, SETTLED_POOL, || accounts::ACloseHistory {
+            pool_state: pool(SETTLED_POOL),
+            history_state: history(SETTLED_POOL),
+            rent_payer: w.creator,
+        })
+        .deny(
+            "mallory as rent payer",
+            |a| a.rent_payer = w.mallory,
+            ErrorCode::ConstraintHasOne,
+        )
+        .build(),
+        // bob lost the settled pool, so his entry has nothing left to claim
+        matrix::<instruction::CloseEntry, _, _>(w, SETTLED_POOL, || accounts::ACloseEntry {
+            pool_state: pool(SETTLED_POOL),
+            entry_state: entry(SETTLED_POOL, &w.bob),
+            user: w.bob,
+        })
+        .deny(
+            "mallory takes bob's entry rent",
+            |a| a.user = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "bob's entry in another pool",
+            |a| a.pool_state = pool(OTHER_SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::ClosePool, _, _>(w, SETTLED_POOL, || accounts::AClosePool {
+            pool_state: pool(SETTLED_POOL),
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(SETTLED_POOL),
+            rent_payer: w.creator,
+            system_program: system_program::ID,
+        })
+        .deny(
+            "mallory as rent payer",
+            |a| a.rent_payer = w.mallory,
+            ErrorCode::ConstraintHasOne,
+        )
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::MigrateVault, _, _>(w, RANGE_POOL, || accounts::AMigrateVault {
+            owner: w.owner,
+            main_state: main_state(),
+            pool_state: pool(RANGE_POOL),
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(RANGE_POOL),
+            system_program: system_program::ID,
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny("the pool's creator as owner", |a| a.owner = w.creator, BettingError::Unauthorized)
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::MigratePoolWinner, _, _>(w, LEGACY_POOL, || {
+            accounts::AMigratePoolWinner {
+                owner: w.owner,
+                main_state: main_state(),
+                pool_state: pool(LEGACY_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny_args::<instruction::MigratePoolWinner>(
+            "a pool already in the current layout",
+            SETTLED_POOL,
+            |a| a.pool_state = pool(SETTLED_POOL),
+            BettingError::PoolAlreadyMigrated,
+        )
+        .deny(
+            "another pool's address",
+            |a| a.pool_state = pool(SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::MigrateHistory, _, _>(w, LEGACY_POOL, || {
+            accounts::AMigrateHistory {
+                history_state: history(LEGACY_POOL),
+            }
+        })
+        .deny_args::<instruction::MigrateHistory>(
+            "a history already in the ring layout",
+            SETTLED_POOL,
+            |a| a.history_state = history(SETTLED_POOL),
+            BettingError::HistoryAlreadyMigrated,
+        )
+        .deny(
+            "another pool's history",
+            |a| a.history_state = history(SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::VoidPool, _, _>(w, POOL, || accounts::AVoidPool {
+            caller: w.owner,
+            main_state: main_state(),
+            pool_state: pool(POOL),
+            history_state: Some(history(POOL)),
+        })
+        .deny(
+            "mallory voids an open-ended pool",
+            |a| a.caller = w.mallory,
+            BettingError::VoidGraceNotElapsed,
+        )
+        .deny(
+            "the pool's creator voids it",
+            |a| a.caller = w.creator,
+            BettingError::VoidGraceNotElapsed,
+        )
+        .build(),
+        matrix::<instruction::TriggerRefundMode, _, _>(w, POOL, || {
+            accounts::ATriggerRefundMode {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                history_state: Some(history(POOL)),
+            }
+        })
+        .build(),
+        matrix::<instruction::GuaranteePool, _, _>(w, (POOL, 1_000u64), || {
+            accounts::AGuaranteePool {
+                owner: w.owner,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                guarantee: guarantee(POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .deny("the pool's creator as owner", |a| a.owner = w.creator, BettingError::Unauthorized)
+        .build(),
+        matrix::<instruction::ReleaseGuarantee, _, _>(w, SETTLED_POOL, || {
+            accounts::AReleaseGuarantee {
+                owner: w.owner,
+                main_state: main_state(),
+                pool_state: pool(SETTLED_POOL),
+                guarantee: guarantee(SETTLED_POOL),
+            }
+        })
+        .deny("mallory as owner", |a| a.owner = w.mallory, BettingError::Unauthorized)
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::PublishEventStart, _, _>(w, (FEED_ID, 0i64), || {
+            accounts::APublishEventStart {
+                authority: w.referee,
+                start_feed: start_feed(&w.referee, FEED_ID),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory publishes to the referee's feed",
+            |a| a.authority = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::CrankLockOnStart, _, _>(w, POOL, || {
+            accounts::ACrankLockOnStart {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                start_feed: start_feed(&w.referee, FEED_ID),
+                history_state: Some(history(POOL)),
+            }
+        })
+        .foreign_fails_with(start_feed(&w.referee, FEED_ID), BettingError::StartFeedMismatch)
+        .build(),
+        matrix::<instruction::RecordFinalOdds, _, _>(w, PROVISIONAL_POOL, || {
+            accounts::ARecordFinalOdds {
+                main_state: main_state(),
+                pool_state: pool(PROVISIONAL_POOL),
+            }
+        })
+        .deny_args::<instruction::RecordFinalOdds>(
+            "an open-ended pool",
+            POOL,
+            |a| a.pool_state = pool(POOL),
+            BettingError::BetNotEnded,
+        )
+        .deny(
+            "another pool",
+            |a| a.pool_state = pool(SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::PublishOddsFeed, _, _>(w, POOL, || accounts::APublishOddsFeed {
+            payer: w.mallory,
+            pool_state: pool(POOL),
+            odds_feed: super::world::odds_feed(POOL),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "another pool's odds feed",
+            |a| a.odds_feed = super::world::odds_feed(SETTLED_POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+    ]
+}
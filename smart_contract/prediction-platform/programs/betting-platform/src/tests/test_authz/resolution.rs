@@ -0,0 +1,489 @@
+//! Resolution: referee and council calls, batch and range resolution, the provisional
+//! confirmation window, optimistic assertions, price-feed resolution and referee queues.
+
+use super::harness::{matrix, Matrix};
+use super::world::{
+    entry, history, main_state, pool, pool_vault, referee_queue, resolution_dispute,
+    resolution_state, settlement, sol_vault, title_index, treasury, World, COMMITTEE_POOL,
+    DISPUTED_POOL, META_POOL, ORACLE_POOL, POOL, PROVISIONAL_POOL, RANGE_POOL, SETTLED_POOL,
+    TITLE,
+};
+#[cfg(feature = "oracle")]
+use super::world::{assertion, oracle};
+#[cfg(feature = "referee-registry")]
+use super::world::referee_profile;
+use crate::error::BettingError;
+use crate::{accounts, instruction, PoolResolution, SetWinnerManyInput};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::system_program;
+
+pub(super) fn matrices(w: &World) -> Vec<Matrix> {
+    // What set_winner_many reads per pool: POOL is title-indexed and has its own vault
+    let batch_accounts = |creator: Pubkey| {
+        [
+            pool(POOL),
+            #[cfg(feature = "oracle")]
+            oracle(POOL),
+            settlement(POOL),
+            #[cfg(feature = "referee-registry")]
+            referee_profile(&w.referee),
+            title_index(&w.creator, TITLE),
+            creator,
+            pool_vault(POOL),
+        ]
+        .into_iter()
+        .map(|key| AccountMeta::new(key, false))
+        .collect::<Vec<_>>()
+    };
+
+    let set_winner_many = matrix::<instruction::SetWinnerMany, _, _>(
+        w,
+        SetWinnerManyInput {
+            resolutions: vec![PoolResolution {
+                bet_id: POOL,
+                is_yes: true,
+            }],
+        },
+        || accounts::ASetWinnerMany {
+            referee: w.referee,
+            main_state: main_state(),
+            sol_vault: sol_vault(),
+            platform_owner: treasury(),
+            system_program: system_program::ID,
+        },
+    )
+    .remaining(batch_accounts(w.creator))
+    .deny("mallory as referee", |a| a.referee = w.mallory, BettingError::Unauthorized)
+    .deny(
+        "mallory as platform owner",
+        |a| a.platform_owner = w.mallory,
+        ErrorCode::ConstraintAddress,
+    )
+    .deny_remaining("no accounts for the pool", Vec::new(), BettingError::InvalidBatch)
+    .deny_remaining(
+        "mallory receives the title index rent",
+        batch_accounts(w.mallory),
+        BettingError::TitleIndexMismatch,
+    )
+    .foreign_fails_with(pool(POOL), BettingError::InvalidBatch)
+    .foreign_fails_with(pool_vault(POOL), BettingError::InvalidBatch)
+    .foreign_fails_with(title_index(&w.creator, TITLE), BettingError::TitleIndexMismatch);
+    #[cfg(feature = "referee-registry")]
+    let set_winner_many =
+        set_winner_many.foreign_fails_with(referee_profile(&w.referee), BettingError::InvalidBatch);
+
+    vec![
+        matrix::<instruction::SetWinner, _, _>(w, (POOL, true, None::<bool>), || {
+            accounts::ASetWinner {
+                referee: w.referee,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                creator: w.creator,
+                title_index: Some(title_index(&w.creator, TITLE)),
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(POOL),
+                platform_owner: treasury(),
+                #[cfg(feature = "oracle")]
+                outcome_oracle: oracle(POOL),
+                settlement_report: settlement(POOL),
+                #[cfg(feature = "referee-registry")]
+                referee_profile: referee_profile(&w.referee),
+                history_state: Some(history(POOL)),
+                referee_queue: Some(referee_queue(&w.referee)),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as referee", |a| a.referee = w.mallory, BettingError::Unauthorized)
+        .deny_args::<instruction::SetWinner>(
+            "mallory declaring the pool invalid",
+            (POOL, true, Some(true)),
+            |a| a.referee = w.mallory,
+            BettingError::Unauthorized,
+        )
+        .deny(
+            "another pool's referee",
+            |a| a.referee = w.other_referee,
+            BettingError::Unauthorized,
+        )
+        .deny(
+            "ops admin without a referee deadline",
+            |a| a.referee = w.ops_admin,
+            BettingError::Unauthorized,
+        )
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .build(),
+        matrix::<instruction::SetWinnerByValue, _, _>(w, (RANGE_POOL, 50i64), || {
+            accounts::ASetWinnerByValue {
+                referee: w.referee,
+                main_state: main_state(),
+                pool_state: pool(RANGE_POOL),
+                creator: w.creator,
+                title_index: None,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(RANGE_POOL),
+                platform_owner: treasury(),
+                #[cfg(feature = "oracle")]
+                outcome_oracle: oracle(RANGE_POOL),
+                settlement_report: settlement(RANGE_POOL),
+                #[cfg(feature = "referee-registry")]
+                referee_profile: referee_profile(&w.referee),
+                history_state: Some(history(RANGE_POOL)),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as referee", |a| a.referee = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "another pool's referee",
+            |a| a.referee = w.other_referee,
+            BettingError::Unauthorized,
+        )
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .deny(
+            "another pool's state",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool's history",
+            |a| a.history_state = Some(history(POOL)),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        set_winner_many.build(),
+        matrix::<instruction::SetProvisionalWinner, _, _>(
+            w,
+            (PROVISIONAL_POOL, true, None::<i64>),
+            || accounts::ASetProvisionalWinner {
+                referee: w.referee,
+                main_state: main_state(),
+                pool_state: pool(PROVISIONAL_POOL),
+                #[cfg(feature = "referee-registry")]
+                referee_profile: referee_profile(&w.referee),
+                system_program: system_program::ID,
+            },
+        )
+        .deny("mallory as referee", |a| a.referee = w.mallory, BettingError::Unauthorized)
+        .deny(
+            "another pool's referee",
+            |a| a.referee = w.other_referee,
+            BettingError::Unauthorized,
+        )
+        .deny(
+            "another pool's state",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::DisputeProvisionalWinner, _, _>(w, PROVISIONAL_POOL, || {
+            accounts::ADisputeProvisionalWinner {
+                disputer: w.alice,
+                main_state: main_state(),
+                pool_state: pool(PROVISIONAL_POOL),
+                entry_state: entry(PROVISIONAL_POOL, &w.alice),
+                resolution_dispute: resolution_dispute(PROVISIONAL_POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory disputes with alice's entry",
+            |a| a.disputer = w.mallory,
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "bob's entry",
+            |a| a.entry_state = entry(PROVISIONAL_POOL, &w.bob),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "alice's entry in another pool",
+            |a| a.entry_state = entry(POOL, &w.alice),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::FinalizeResolution, _, _>(w, (PROVISIONAL_POOL, None::<bool>), || {
+            accounts::AFinalizeResolution {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(PROVISIONAL_POOL),
+                creator: w.creator,
+                title_index: None,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(PROVISIONAL_POOL),
+                platform_owner: treasury(),
+                #[cfg(feature = "oracle")]
+                outcome_oracle: oracle(PROVISIONAL_POOL),
+                settlement_report: settlement(PROVISIONAL_POOL),
+                history_state: Some(history(PROVISIONAL_POOL)),
+                resolution_dispute: None,
+                disputer: None,
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .deny(
+            "another pool's state",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        // The referee casts the committee's first vote, which resolves nothing yet
+        matrix::<instruction::ProposeWinner, _, _>(w, (COMMITTEE_POOL, true), || {
+            accounts::AProposeWinner {
+                member: w.referee,
+                main_state: main_state(),
+                pool_state: pool(COMMITTEE_POOL),
+                creator: w.creator,
+                resolution_state: resolution_state(COMMITTEE_POOL),
+                title_index: None,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(COMMITTEE_POOL),
+                platform_owner: treasury(),
+                #[cfg(feature = "oracle")]
+                outcome_oracle: None,
+                settlement_report: None,
+                history_state: Some(history(COMMITTEE_POOL)),
+                system_program: system_program::ID,
+            }
+        })
+        .deny("mallory votes", |a| a.member = w.mallory, BettingError::NotCommitteeMember)
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "another pool's votes",
+            |a| a.resolution_state = resolution_state(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .build(),
+        // The owner overturns the YES alice disputed
+        matrix::<instruction::FinalizeResolution, _, _>(w, (DISPUTED_POOL, Some(false)), || {
+            accounts::AFinalizeResolution {
+                caller: w.owner,
+                main_state: main_state(),
+                pool_state: pool(DISPUTED_POOL),
+                creator: w.creator,
+                title_index: None,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(DISPUTED_POOL),
+                platform_owner: treasury(),
+                #[cfg(feature = "oracle")]
+                outcome_oracle: oracle(DISPUTED_POOL),
+                settlement_report: settlement(DISPUTED_POOL),
+                history_state: Some(history(DISPUTED_POOL)),
+                resolution_dispute: Some(resolution_dispute(DISPUTED_POOL)),
+                disputer: Some(w.alice),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory rules on the dispute",
+            |a| a.caller = w.mallory,
+            BettingError::ProvisionalOutcomeDisputed,
+        )
+        .deny(
+            "the referee rules on the dispute",
+            |a| a.caller = w.referee,
+            BettingError::ProvisionalOutcomeDisputed,
+        )
+        .deny(
+            "bob takes alice's bond",
+            |a| a.disputer = Some(w.bob),
+            BettingError::InvalidDisputer,
+        )
+        .deny(
+            "without the dispute",
+            |a| a.resolution_dispute = None,
+            BettingError::InvalidDisputer,
+        )
+        .deny_args::<instruction::FinalizeResolution>(
+            "no ruling",
+            (DISPUTED_POOL, None::<bool>),
+            |_| {},
+            BettingError::DisputeVerdictRequired,
+        )
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::AssertOutcome, _, _>(w, (POOL, true), || {
+            accounts::AAssertOutcome {
+                asserter: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(POOL),
+                assertion: assertion(POOL),
+                system_program: system_program::ID,
+            }
+        })
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::DisputeAssertion, _, _>(w, POOL, || accounts::ADisputeAssertion {
+            disputer: w.bob,
+            main_state: main_state(),
+            assertion: assertion(POOL),
+            system_program: system_program::ID,
+        })
+        .deny(
+            "the asserter disputes her own assertion",
+            |a| a.disputer = w.alice,
+            BettingError::InvalidDisputer,
+        )
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::SettleAssertion, _, _>(w, POOL, || accounts::ASettleAssertion {
+            caller: w.mallory,
+            main_state: main_state(),
+            pool_state: pool(POOL),
+            creator: w.creator,
+            title_index: Some(title_index(&w.creator, TITLE)),
+            assertion: assertion(POOL),
+            asserter: w.alice,
+            disputer: None,
+            sol_vault: sol_vault(),
+            pool_vault: pool_vault(POOL),
+            platform_owner: treasury(),
+            outcome_oracle: oracle(POOL),
+            settlement_report: settlement(POOL),
+            system_program: system_program::ID,
+        })
+        .deny("bob as asserter", |a| a.asserter = w.bob, ErrorCode::ConstraintHasOne)
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .build(),
+        #[cfg(feature = "oracle")]
+        matrix::<instruction::ResolveWithOracle, _, _>(w, ORACLE_POOL, || {
+            accounts::AResolveWithOracle {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(ORACLE_POOL),
+                price_feed: w.price_feed,
+                creator: w.creator,
+                title_index: None,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(ORACLE_POOL),
+                platform_owner: treasury(),
+                outcome_oracle: oracle(ORACLE_POOL),
+                settlement_report: settlement(ORACLE_POOL),
+                history_state: Some(history(ORACLE_POOL)),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "mallory's account as the price feed",
+            |a| a.price_feed = w.mallory,
+            BettingError::PriceFeedMismatch,
+        )
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .deny(
+            "another pool's state",
+            |a| a.pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .foreign_fails_with(w.price_feed, BettingError::PriceFeedMismatch)
+        .build(),
+        matrix::<instruction::ResolveMetaMarket, _, _>(w, META_POOL, || {
+            accounts::AResolveMetaMarket {
+                caller: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(META_POOL),
+                target_pool_state: pool(SETTLED_POOL),
+                creator: w.creator,
+                title_index: None,
+                sol_vault: sol_vault(),
+                pool_vault: pool_vault(META_POOL),
+                platform_owner: treasury(),
+                #[cfg(feature = "oracle")]
+                outcome_oracle: oracle(META_POOL),
+                settlement_report: settlement(META_POOL),
+                history_state: Some(history(META_POOL)),
+                system_program: system_program::ID,
+            }
+        })
+        .deny(
+            "another pool as the target",
+            |a| a.target_pool_state = pool(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .deny("mallory as creator", |a| a.creator = w.mallory, ErrorCode::ConstraintHasOne)
+        .deny(
+            "mallory as platform owner",
+            |a| a.platform_owner = w.mallory,
+            ErrorCode::ConstraintAddress,
+        )
+        .deny(
+            "another pool's vault",
+            |a| a.pool_vault = pool_vault(POOL),
+            ErrorCode::ConstraintSeeds,
+        )
+        .build(),
+        matrix::<instruction::EnqueuePendingResolution, _, _>(w, ORACLE_POOL, || {
+            accounts::AEnqueuePendingResolution {
+                payer: w.mallory,
+                main_state: main_state(),
+                pool_state: pool(ORACLE_POOL),
+                referee_queue: referee_queue(&w.referee),
+                system_program: system_program::ID,
+            }
+        })
+        .deny_args::<instruction::EnqueuePendingResolution>(
+            "a pool that has not ended",
+            POOL,
+            |a| a.pool_state = pool(POOL),
+            BettingError::BetNotEnded,
+        )
+        .deny_args::<instruction::EnqueuePendingResolution>(
+            "a decided pool",
+            SETTLED_POOL,
+            |a| a.pool_state = pool(SETTLED_POOL),
+            BettingError::AlreadyResolved,
+        )
+        .deny("another pool", |a| a.pool_state = pool(POOL), ErrorCode::ConstraintSeeds)
+        .build(),
+        matrix::<instruction::PruneRefereeQueue, _, _>(w, SETTLED_POOL, || {
+            accounts::APruneRefereeQueue {
+                pool_state: pool(SETTLED_POOL),
+                referee_queue: referee_queue(&w.referee),
+            }
+        })
+        .deny_args::<instruction::PruneRefereeQueue>(
+            "a pool still pending",
+            POOL,
+            |a| a.pool_state = pool(POOL),
+            BettingError::ResolutionPending,
+        )
+        .deny("another pool", |a| a.pool_state = pool(POOL), ErrorCode::ConstraintSeeds)
+        // Seeded by its own referee; SETTLED_POOL is not in the other referee's queue
+        .foreign_fails_with(referee_queue(&w.referee), BettingError::NotQueued)
+        .build(),
+    ]
+}
@@ -0,0 +1,1309 @@
+//! The accounts every matrix runs against: an initialized platform with two SOL pools owned by
+//! different creators, an SPL pool, two outcome markets and platform-token staking, plus the
+//! PDAs around them.
+//! `foreign` lists, per account, the same kind of account belonging to another pool or user.
+
+use crate::constants::{
+    CREATOR_FEE_PERCENT, DEFAULT_CLAIM_WINDOW_SECS, INITIAL_PRICE, MAX_CLAIM_WINDOW_SECS,
+    MIN_CLAIM_WINDOW_SECS, MIN_REVENUE_EPOCH_SECS, OUTCOME_MINT_SEED, PLATFORM_FEE_PERCENT,
+    POOL_VAULT_SEED, PROVISIONAL_DISPUTE_BOND, RECOVERY_CHALLENGE_SECS, SCALE_FACTOR,
+    TREASURY_SEED, VAULT_SEED,
+};
+use crate::permit::permit_message;
+#[cfg(feature = "oracle")]
+use crate::pyth::PYTH_PROGRAM_ID;
+#[cfg(feature = "oracle")]
+use crate::tests::oracle_resolution::price_account;
+use crate::tests::history::legacy_history_data;
+use crate::tests::winner_migration::legacy_pool_data;
+use crate::{
+    write_settlement_report, BetBalance, ClaimWindowBounds, CommitmentState, CreatorRateLimit,
+    EntryState, MainState, MetaCondition, NotificationPrefs, OutcomeEntry, OutcomeMarket,
+    PendingDeposit, PoolGuarantee, PoolHistoryState, PoolLink, PoolState, PoolSubscribers,
+    PoolSummary, PriceCondition, ProbabilityPoint, RangeBucket, RefereeQueue, ReferralState,
+    ResolutionDispute, ResolutionState, RevenueEpoch, RevenueShare, SettlementReport, StakeState,
+    TitleIndex, WinnerSide,
+};
+#[cfg(feature = "oracle")]
+use crate::{Assertion, StartFeed};
+#[cfg(feature = "referee-registry")]
+use crate::{constants::REFEREE_BONUS_VAULT_SEED, DefaultReferees, RefereeProfile};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::sysvar::instructions::{
+    construct_instructions_data, BorrowedInstruction,
+};
+use anchor_lang::solana_program::{bpf_loader_upgradeable, ed25519_program};
+use anchor_lang::solana_program::{program_option::COption, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use std::collections::HashMap;
+
+pub(super) const NOW: i64 = 1_750_000_000;
+
+pub(super) const POOL: u64 = 1;
+pub(super) const OTHER_POOL: u64 = 2;
+pub(super) const SPL_POOL: u64 = 3;
+pub(super) const MARKET: u64 = 4;
+pub(super) const OTHER_MARKET: u64 = 5;
+pub(super) const SETTLED_POOL: u64 = 6;
+pub(super) const OTHER_SETTLED_POOL: u64 = 7;
+pub(super) const RANGE_POOL: u64 = 8;
+pub(super) const PROVISIONAL_POOL: u64 = 9;
+pub(super) const ORACLE_POOL: u64 = 10;
+pub(super) const DISPUTED_POOL: u64 = 11;
+pub(super) const LEGACY_POOL: u64 = 12;
+pub(super) const META_POOL: u64 = 13;
+pub(super) const COMMITTEE_POOL: u64 = 14;
+pub(super) const NEXT_BET_ID: u64 = 15;
+
+pub(super) const TITLE: &str = "Authz pool";
+pub(super) const OTHER_TITLE: &str = "Foreign pool";
+#[cfg(feature = "oracle")]
+pub(super) const FEED_ID: u64 = 7;
+// The open revenue epoch, which has run its length; alice still has its predecessor to claim
+pub(super) const REVENUE_EPOCH: u64 = 3;
+// alice's permit for a gasless claim of SETTLED_POOL into her own wallet
+pub(super) const PERMIT_EXPIRES_AT: i64 = NOW + 3_600;
+
+const SOL: u64 = 1_000_000_000;
+
+#[derive(Clone)]
+pub(super) struct Stored {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+    pub executable: bool,
+}
+
+impl Default for Stored {
+    fn default() -> Self {
+        Self {
+            lamports: 0,
+            owner: anchor_lang::system_program::ID,
+            data: Vec::new(),
+            executable: false,
+        }
+    }
+}
+
+pub(super) struct World {
+    accounts: HashMap<Pubkey, Stored>,
+    names: HashMap<Pubkey, String>,
+    foreign: HashMap<Pubkey, Vec<Pubkey>>,
+    pub owner: Pubkey,
+    pub fee_admin: Pubkey,
+    pub ops_admin: Pubkey,
+    pub creator: Pubkey,
+    pub referee: Pubkey,
+    pub other_creator: Pubkey,
+    pub other_referee: Pubkey,
+    pub alice: Pubkey,
+    pub bob: Pubkey,
+    pub mallory: Pubkey,
+    pub cold_wallet: Pubkey,
+    pub alice_recovery: Pubkey,
+    pub mint: Pubkey,
+    pub price_feed: Pubkey,
+    #[cfg(feature = "oracle")]
+    pub other_price_feed: Pubkey,
+}
+
+pub(super) fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+pub(super) fn main_state() -> Pubkey {
+    pda(&[MainState::PREFIX_SEED])
+}
+
+pub(super) fn sol_vault() -> Pubkey {
+    pda(&[VAULT_SEED.as_bytes()])
+}
+
+pub(super) fn treasury() -> Pubkey {
+    pda(&[TREASURY_SEED.as_bytes()])
+}
+
+#[cfg(feature = "referee-registry")]
+pub(super) fn bonus_vault() -> Pubkey {
+    pda(&[REFEREE_BONUS_VAULT_SEED.as_bytes()])
+}
+
+#[cfg(feature = "referee-registry")]
+pub(super) fn default_referees() -> Pubkey {
+    pda(&[DefaultReferees::PREFIX_SEED])
+}
+
+pub(super) fn pool(bet_id: u64) -> Pubkey {
+    pda(&[PoolState::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn pool_vault(bet_id: u64) -> Pubkey {
+    pda(&[POOL_VAULT_SEED.as_bytes(), &bet_id.to_le_bytes()])
+}
+
+pub(super) fn entry(bet_id: u64, user: &Pubkey) -> Pubkey {
+    pda(&[EntryState::PREFIX_SEED, pool(bet_id).as_ref(), user.as_ref()])
+}
+
+pub(super) fn commitment(bet_id: u64, user: &Pubkey) -> Pubkey {
+    pda(&[CommitmentState::PREFIX_SEED, pool(bet_id).as_ref(), user.as_ref()])
+}
+
+pub(super) fn resolution_state(bet_id: u64) -> Pubkey {
+    pda(&[ResolutionState::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn pending_deposit(bet_id: u64, position: u64) -> Pubkey {
+    pda(&[PendingDeposit::PREFIX_SEED, pool(bet_id).as_ref(), &position.to_le_bytes()])
+}
+
+pub(super) fn history(bet_id: u64) -> Pubkey {
+    pda(&[PoolHistoryState::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn summary(bet_id: u64) -> Pubkey {
+    pda(&[PoolSummary::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn settlement(bet_id: u64) -> Pubkey {
+    pda(&[SettlementReport::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+#[cfg(feature = "oracle")]
+pub(super) fn oracle(bet_id: u64) -> Pubkey {
+    pda(&[crate::OutcomeOracle::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn guarantee(bet_id: u64) -> Pubkey {
+    pda(&[PoolGuarantee::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn subscribers(bet_id: u64) -> Pubkey {
+    pda(&[PoolSubscribers::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+#[cfg(feature = "oracle")]
+pub(super) fn assertion(bet_id: u64) -> Pubkey {
+    pda(&[Assertion::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn resolution_dispute(bet_id: u64) -> Pubkey {
+    pda(&[ResolutionDispute::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+#[cfg(feature = "oracle")]
+pub(super) fn odds_feed(bet_id: u64) -> Pubkey {
+    pda(&[crate::OddsFeed::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn metadata(bet_id: u64) -> Pubkey {
+    pda(&[crate::PoolMetadata::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn link(parent_bet_id: u64, on_yes: bool) -> Pubkey {
+    pda(&[PoolLink::PREFIX_SEED, &parent_bet_id.to_le_bytes(), &[on_yes as u8]])
+}
+
+pub(super) fn title_index(creator: &Pubkey, title: &str) -> Pubkey {
+    pda(&[TitleIndex::PREFIX_SEED, creator.as_ref(), &TitleIndex::title_hash(title)])
+}
+
+pub(super) fn creator_rate_limit(creator: &Pubkey) -> Pubkey {
+    pda(&[CreatorRateLimit::PREFIX_SEED, creator.as_ref()])
+}
+
+#[cfg(feature = "referee-registry")]
+pub(super) fn referee_profile(referee: &Pubkey) -> Pubkey {
+    pda(&[RefereeProfile::PREFIX_SEED, referee.as_ref()])
+}
+
+pub(super) fn referee_queue(referee: &Pubkey) -> Pubkey {
+    pda(&[RefereeQueue::PREFIX_SEED, referee.as_ref()])
+}
+
+pub(super) fn bet_balance(user: &Pubkey) -> Pubkey {
+    pda(&[BetBalance::PREFIX_SEED, user.as_ref()])
+}
+
+pub(super) fn prefs(user: &Pubkey) -> Pubkey {
+    pda(&[NotificationPrefs::PREFIX_SEED, user.as_ref()])
+}
+
+pub(super) fn outcome_market(bet_id: u64) -> Pubkey {
+    pda(&[OutcomeMarket::PREFIX_SEED, &bet_id.to_le_bytes()])
+}
+
+pub(super) fn outcome_entry(bet_id: u64, user: &Pubkey) -> Pubkey {
+    pda(&[OutcomeEntry::PREFIX_SEED, outcome_market(bet_id).as_ref(), user.as_ref()])
+}
+
+#[cfg(feature = "oracle")]
+pub(super) fn start_feed(authority: &Pubkey, feed_id: u64) -> Pubkey {
+    pda(&[StartFeed::PREFIX_SEED, authority.as_ref(), &feed_id.to_le_bytes()])
+}
+
+pub(super) fn outcome_mint(bet_id: u64, is_yes: bool) -> Pubkey {
+    pda(&[OUTCOME_MINT_SEED.as_bytes(), &bet_id.to_le_bytes(), &[is_yes as u8]])
+}
+
+pub(super) fn ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(wallet, mint)
+}
+
+pub(super) fn revenue_share() -> Pubkey {
+    pda(&[RevenueShare::PREFIX_SEED])
+}
+
+pub(super) fn revenue_epoch(epoch: u64) -> Pubkey {
+    pda(&[RevenueEpoch::PREFIX_SEED, &epoch.to_le_bytes()])
+}
+
+pub(super) fn stake(user: &Pubkey) -> Pubkey {
+    pda(&[StakeState::PREFIX_SEED, user.as_ref()])
+}
+
+pub(super) fn referral(referrer: &Pubkey) -> Pubkey {
+    pda(&[ReferralState::PREFIX_SEED, referrer.as_ref()])
+}
+
+fn wallet(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+impl World {
+    pub fn new() -> Self {
+        let mut world = Self {
+            accounts: HashMap::new(),
+            names: HashMap::new(),
+            foreign: HashMap::new(),
+            owner: wallet(1),
+            fee_admin: wallet(2),
+            ops_admin: wallet(3),
+            creator: wallet(4),
+            referee: wallet(5),
+            other_creator: wallet(6),
+            other_referee: wallet(7),
+            alice: wallet(8),
+            bob: wallet(9),
+            mallory: wallet(10),
+            cold_wallet: wallet(11),
+            mint: wallet(12),
+            price_feed: wallet(13),
+            #[cfg(feature = "oracle")]
+            other_price_feed: wallet(14),
+            alice_recovery: wallet(15),
+        };
+        world.programs();
+        world.wallets();
+        world.platform();
+        world.pools();
+        world.spl_pool();
+        world.outcome_markets();
+        world.revenue_share();
+        world.referrals();
+        world
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Stored {
+        self.accounts.get(key).cloned().unwrap_or_default()
+    }
+
+    pub fn name(&self, key: &Pubkey) -> String {
+        self.names.get(key).cloned().unwrap_or_else(|| key.to_string())
+    }
+
+    pub fn foreign(&self, key: &Pubkey) -> &[Pubkey] {
+        self.foreign.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    // Restricts the pools to holders of the world's mint (see PoolState::check_gate)
+    pub fn gate(&mut self, bet_ids: &[u64]) {
+        for &bet_id in bet_ids {
+            let key = pool(bet_id);
+            let stored = self.account(&key);
+            let mut pool_state = PoolState::try_deserialize(&mut stored.data.as_slice()).unwrap();
+            pool_state.gate_mint = self.mint;
+            pool_state.gate_min_amount = 1;
+            let name = self.name(&key);
+            self.put_state(&name, key, &pool_state, PoolState::MAX_SIZE);
+        }
+    }
+
+    fn put(&mut self, name: &str, key: Pubkey, stored: Stored) {
+        self.names.insert(key, name.to_string());
+        self.accounts.insert(key, stored);
+    }
+
+    // A program account of `T`, serialized into `8 + max_size` bytes
+    fn put_state<T: AccountSerialize + Owner>(
+        &mut self,
+        name: &str,
+        key: Pubkey,
+        state: &T,
+        max_size: usize,
+    ) {
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        data.resize(data.len().max(8 + max_size), 0);
+        let lamports = Rent::default().minimum_balance(data.len());
+        self.put(
+            name,
+            key,
+            Stored {
+                lamports,
+                owner: T::owner(),
+                data,
+                executable: false,
+            },
+        );
+    }
+
+    // A pool's zero-copy history, holding the point recorded at its creation
+    fn put_history(&mut self, name: &str, bet_id: u64) {
+        let mut history_state: PoolHistoryState = bytemuck::Zeroable::zeroed();
+        history_state.reset(pool(bet_id), bet_id, NOW - 3_600);
+        history_state.push(NOW - 3_600, 0, 0, ProbabilityPoint::KIND_SNAPSHOT);
+        let mut data = PoolHistoryState::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&history_state));
+        let lamports = Rent::default().minimum_balance(data.len());
+        self.put(
+            name,
+            history(bet_id),
+            Stored {
+                lamports,
+                owner: crate::ID,
+                data,
+                executable: false,
+            },
+        );
+    }
+
+    fn put_lamports(&mut self, name: &str, key: Pubkey, lamports: u64) {
+        self.put(
+            name,
+            key,
+            Stored {
+                lamports,
+                ..Stored::default()
+            },
+        );
+    }
+
+    fn put_packed<T: Pack>(&mut self, name: &str, key: Pubkey, state: T) {
+        let mut data = vec![0u8; T::LEN];
+        T::pack(state, &mut data).unwrap();
+        self.put(
+            name,
+            key,
+            Stored {
+                lamports: Rent::default().minimum_balance(T::LEN),
+                owner: spl_token::ID,
+                data,
+                executable: false,
+            },
+        );
+    }
+
+    fn put_token_account(&mut self, name: &str, wallet: &Pubkey, mint: &Pubkey) {
+        let token_account = spl_token::state::Account {
+            mint: *mint,
+            owner: *wallet,
+            amount: 10 * SOL,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        self.put_packed(name, ata(wallet, mint), token_account);
+    }
+
+    fn twin(&mut self, original: Pubkey, foreign: &[Pubkey]) {
+        self.foreign.insert(original, foreign.to_vec());
+    }
+
+    fn programs(&mut self) {
+        for (name, key) in [
+            ("betting program", crate::ID),
+            ("system program", anchor_lang::system_program::ID),
+            ("token program", spl_token::ID),
+            ("associated token program", anchor_spl::associated_token::ID),
+        ] {
+            self.put(
+                name,
+                key,
+                Stored {
+                    lamports: 1,
+                    owner: bpf_loader_upgradeable::ID,
+                    data: Vec::new(),
+                    executable: true,
+                },
+            );
+        }
+
+        let rent = Rent::default();
+        let mut data = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+        data.extend(rent.exemption_threshold.to_le_bytes());
+        data.push(rent.burn_percent);
+        self.put(
+            "rent sysvar",
+            sysvar::rent::ID,
+            Stored {
+                lamports: 1,
+                owner: sysvar::ID,
+                data,
+                executable: false,
+            },
+        );
+
+        // An Ed25519 instruction verifying alice's permit, followed by the claim itself
+        let message = permit_message(&crate::ID, SETTLED_POOL, &self.alice, PERMIT_EXPIRES_AT);
+        let pubkey_offset = 16u16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut ed25519 = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            ed25519.extend(value.to_le_bytes());
+        }
+        ed25519.extend(self.alice.to_bytes());
+        ed25519.extend([0u8; 64]);
+        ed25519.extend(&message);
+        let mut data = construct_instructions_data(&[
+            BorrowedInstruction {
+                program_id: &ed25519_program::ID,
+                accounts: Vec::new(),
+                data: &ed25519,
+            },
+            BorrowedInstruction {
+                program_id: &crate::ID,
+                accounts: Vec::new(),
+                data: &[],
+            },
+        ]);
+        // The sysvar ends with the index of the executing instruction
+        let current = data.len() - 2;
+        data[current..].copy_from_slice(&1u16.to_le_bytes());
+        self.put(
+            "instructions sysvar",
+            sysvar::instructions::ID,
+            Stored {
+                lamports: 1,
+                owner: sysvar::ID,
+                data,
+                executable: false,
+            },
+        );
+    }
+
+    fn wallets(&mut self) {
+        for (name, key) in [
+            ("owner", self.owner),
+            ("fee admin", self.fee_admin),
+            ("ops admin", self.ops_admin),
+            ("creator", self.creator),
+            ("referee", self.referee),
+            ("other creator", self.other_creator),
+            ("other referee", self.other_referee),
+            ("alice", self.alice),
+            ("bob", self.bob),
+            ("mallory", self.mallory),
+            ("cold wallet", self.cold_wallet),
+            ("alice's recovery key", self.alice_recovery),
+        ] {
+            self.put_lamports(name, key, 100 * SOL);
+        }
+    }
+
+    fn platform(&mut self) {
+        let state = MainState {
+            initialized: true,
+            owner: self.owner,
+            scale_factor: SCALE_FACTOR,
+            initial_price: INITIAL_PRICE,
+            current_bet_id: NEXT_BET_ID,
+            creator_fee_percent: CREATOR_FEE_PERCENT,
+            platform_fee_percent: PLATFORM_FEE_PERCENT,
+            fee_admin: self.fee_admin,
+            ops_admin: self.ops_admin,
+            treasury: treasury(),
+            treasury_cold_wallet: self.cold_wallet,
+            referee_bonus_per_streak: SOL / 10,
+            referee_bonus_funded: SOL,
+            claim_window: ClaimWindowBounds {
+                min_secs: MIN_CLAIM_WINDOW_SECS,
+                default_secs: DEFAULT_CLAIM_WINDOW_SECS,
+                max_secs: MAX_CLAIM_WINDOW_SECS,
+            },
+            revenue_share_bps: 1_000,
+            revenue_share_pending: SOL / 10,
+            ..MainState::default()
+        };
+        self.put_state("main state", main_state(), &state, MainState::MAX_SIZE);
+        self.put_lamports("sol vault", sol_vault(), 10 * SOL);
+        self.put_lamports("treasury", treasury(), 10 * SOL);
+        #[cfg(feature = "referee-registry")]
+        self.put_referee_registry();
+
+        // The referee still owes POOL; SETTLED_POOL was decided but not pruned yet
+        for (name, referee, bet_ids) in [
+            ("referee queue", self.referee, vec![POOL, SETTLED_POOL]),
+            ("other referee queue", self.other_referee, vec![]),
+        ] {
+            let queue = RefereeQueue { referee, bet_ids };
+            self.put_state(name, referee_queue(&referee), &queue, RefereeQueue::MAX_SIZE);
+        }
+        self.twin(
+            referee_queue(&self.referee),
+            &[referee_queue(&self.other_referee)],
+        );
+
+        for (name, user) in [("alice", self.alice), ("bob", self.bob)] {
+            let balance = BetBalance {
+                user,
+                balance: SOL,
+                total_funded: SOL,
+                total_bet: 0,
+            };
+            self.put_state(
+                &format!("{name}'s bet balance"),
+                bet_balance(&user),
+                &balance,
+                BetBalance::MAX_SIZE,
+            );
+            // The balance is held by the account itself, on top of its rent
+            if let Some(stored) = self.accounts.get_mut(&bet_balance(&user)) {
+                stored.lamports += SOL;
+            }
+            let notification_prefs = NotificationPrefs {
+                user,
+                notify_on_lock: true,
+                ..NotificationPrefs::default()
+            };
+            self.put_state(
+                &format!("{name}'s notification prefs"),
+                prefs(&user),
+                &notification_prefs,
+                NotificationPrefs::MAX_SIZE,
+            );
+        }
+        self.twin(bet_balance(&self.alice), &[bet_balance(&self.bob)]);
+        self.twin(prefs(&self.alice), &[prefs(&self.bob)]);
+
+        #[cfg(feature = "oracle")]
+        self.put_oracle_feeds();
+    }
+
+    // The bonus vault, default referee list and both referees' profiles
+    #[cfg(feature = "referee-registry")]
+    fn put_referee_registry(&mut self) {
+        self.put_lamports("bonus vault", bonus_vault(), 10 * SOL);
+
+        let referees = DefaultReferees {
+            referees: vec![self.referee],
+        };
+        self.put_state(
+            "default referees",
+            default_referees(),
+            &referees,
+            DefaultReferees::MAX_SIZE,
+        );
+
+        for (name, referee) in [
+            ("referee profile", self.referee),
+            ("other referee profile", self.other_referee),
+        ] {
+            let profile = RefereeProfile {
+                referee,
+                bonus_streaks_earned: 1,
+                ..RefereeProfile::default()
+            };
+            self.put_state(name, referee_profile(&referee), &profile, RefereeProfile::MAX_SIZE);
+        }
+        self.twin(
+            referee_profile(&self.referee),
+            &[referee_profile(&self.other_referee)],
+        );
+    }
+
+    // Event-start feeds and the Pyth price feeds ORACLE_POOL resolves from
+    #[cfg(feature = "oracle")]
+    fn put_oracle_feeds(&mut self) {
+        for (name, authority) in [
+            ("start feed", self.referee),
+            ("mallory's start feed", self.mallory),
+        ] {
+            let feed = StartFeed {
+                authority,
+                feed_id: FEED_ID,
+                started_at: NOW - 60,
+                updated_at: NOW - 60,
+            };
+            self.put_state(name, start_feed(&authority, FEED_ID), &feed, StartFeed::MAX_SIZE);
+        }
+        self.twin(
+            start_feed(&self.referee, FEED_ID),
+            &[start_feed(&self.mallory, FEED_ID)],
+        );
+
+        // Pyth price feeds, both published just after ORACLE_POOL ended
+        for (name, key) in [
+            ("price feed", self.price_feed),
+            ("other price feed", self.other_price_feed),
+        ] {
+            let data = price_account(6_500_000, -2, NOW - 100);
+            self.put(
+                name,
+                key,
+                Stored {
+                    lamports: Rent::default().minimum_balance(data.len()),
+                    owner: PYTH_PROGRAM_ID,
+                    data,
+                    executable: false,
+                },
+            );
+        }
+        self.twin(self.price_feed, &[self.other_price_feed]);
+    }
+
+    fn pool_state(&self, bet_id: u64, creator: Pubkey, referee: Pubkey, title: &str) -> PoolState {
+        PoolState {
+            creator,
+            bet_id,
+            initial_price: INITIAL_PRICE,
+            scale_factor: SCALE_FACTOR,
+            yes_supply: SOL,
+            yes_reserve: SOL,
+            no_supply: SOL,
+            no_reserve: SOL,
+            end_timestamp: NOW + 3_600,
+            created_timestamp: NOW - 3_600,
+            referee,
+            title: title.to_string(),
+            winner: WinnerSide::Unresolved,
+            creator_fee_bps: CREATOR_FEE_PERCENT,
+            title_indexed: true,
+            title_hash: TitleIndex::title_hash(title),
+            optimistic_resolution: true,
+            rent_payer: creator,
+            referee_accepted: true,
+            claim_window_secs: DEFAULT_CLAIM_WINDOW_SECS,
+            #[cfg(feature = "oracle")]
+            start_feed: start_feed(&self.referee, FEED_ID),
+            own_vault: true,
+            yes_entries: 1,
+            no_entries: 1,
+            installment_count: 1,
+            waitlist_queued: 2,
+            has_summary: true,
+            ..PoolState::default()
+        }
+    }
+
+    fn entry_state(bet_id: u64, user: Pubkey, is_yes: bool) -> EntryState {
+        EntryState {
+            user,
+            bet_id,
+            deposited_sol_amount: SOL,
+            token_balance: SOL,
+            is_yes,
+            deposit_count: 1,
+            ..EntryState::default()
+        }
+    }
+
+    // A SOL pool with its vault and satellite accounts, its settlement report once resolved, and
+    // entries and commitments of alice (yes) and bob (no)
+    fn put_pool(&mut self, label: &str, mut pool_state: PoolState) {
+        let bet_id = pool_state.bet_id;
+        let pool_key = pool(bet_id);
+        if pool_state.complete {
+            let (mut report, referee) = (SettlementReport::default(), pool_state.referee);
+            write_settlement_report(&mut report, &mut pool_state, pool_key, referee);
+            self.put_state(
+                &format!("{label} settlement report"),
+                settlement(bet_id),
+                &report,
+                SettlementReport::MAX_SIZE,
+            );
+        }
+        self.put_state(&format!("{label} state"), pool_key, &pool_state, PoolState::MAX_SIZE);
+        self.put_lamports(&format!("{label} vault"), pool_vault(bet_id), 10 * SOL);
+
+        self.put_history(&format!("{label} history"), bet_id);
+        // Matches the entries below: alice and bob deposited SOL each
+        let pool_summary = PoolSummary {
+            pool: pool_key,
+            bet_id,
+            total_deposited: 2 * SOL,
+            depositor_count: 2,
+            sum_sq_entry_deposits: 2 * (SOL as u128) * (SOL as u128),
+            herfindahl_bps: 5_000,
+            top_depositor: self.alice,
+            top_entry_deposit: SOL,
+            top_depositor_bps: 5_000,
+            largest_deposit: SOL,
+            ..PoolSummary::default()
+        };
+        self.put_state(
+            &format!("{label} summary"),
+            summary(bet_id),
+            &pool_summary,
+            PoolSummary::MAX_SIZE,
+        );
+        if pool_state.title_indexed {
+            let index = TitleIndex {
+                creator: pool_state.creator,
+                pool: pool_key,
+                bet_id,
+            };
+            self.put_state(
+                &format!("{label} title index"),
+                title_index(&pool_state.creator, &pool_state.title),
+                &index,
+                TitleIndex::MAX_SIZE,
+            );
+        }
+        let pool_guarantee = PoolGuarantee {
+            pool: pool_key,
+            bet_id,
+            balance: SOL,
+            total_funded: SOL,
+            total_drawn: 0,
+        };
+        self.put_state(
+            &format!("{label} guarantee"),
+            guarantee(bet_id),
+            &pool_guarantee,
+            PoolGuarantee::MAX_SIZE,
+        );
+        let pool_subscribers = PoolSubscribers {
+            pool: pool_key,
+            bet_id,
+            subscribers: vec![self.alice],
+            lock_notified: false,
+        };
+        self.put_state(
+            &format!("{label} subscribers"),
+            subscribers(bet_id),
+            &pool_subscribers,
+            PoolSubscribers::MAX_SIZE,
+        );
+        #[cfg(feature = "oracle")]
+        {
+            let pool_assertion = Assertion {
+                bet_id,
+                asserter: self.alice,
+                is_yes: true,
+                bond: SOL,
+                asserted_at: NOW - 60,
+                expires_at: NOW + 3_600,
+                ..Assertion::default()
+            };
+            self.put_state(
+                &format!("{label} assertion"),
+                assertion(bet_id),
+                &pool_assertion,
+                Assertion::MAX_SIZE,
+            );
+        }
+        let pool_link = PoolLink {
+            parent_bet_id: bet_id,
+            on_yes: true,
+            creator: pool_state.creator,
+            title: format!("{} follow-up", pool_state.title),
+            description: "Follow-up market".to_string(),
+            duration_secs: -1,
+            opened: false,
+            child_bet_id: 0,
+        };
+        self.put_state(
+            &format!("{label} link"),
+            link(bet_id, true),
+            &pool_link,
+            PoolLink::MAX_SIZE,
+        );
+
+        for (name, user, is_yes) in [("alice", self.alice, true), ("bob", self.bob, false)] {
+            let mut entry_state = Self::entry_state(bet_id, user, is_yes);
+            if user == self.alice {
+                entry_state.recovery_key = self.alice_recovery;
+                // Her settled winnings are being recovered, past the challenge window
+                if bet_id == SETTLED_POOL {
+                    entry_state.recovery_initiated_at = NOW - RECOVERY_CHALLENGE_SECS - 60;
+                }
+            }
+            self.put_state(
+                &format!("{name}'s {label} entry"),
+                entry(bet_id, &user),
+                &entry_state,
+                EntryState::MAX_SIZE,
+            );
+            let commitment_state = CommitmentState {
+                user,
+                bet_id,
+                commitment_hash: [0; 32],
+                amount: SOL,
+                yes_reserve: SOL,
+                no_reserve: SOL,
+                committed_timestamp: NOW - 60,
+                reveal_deadline: NOW + 3_600,
+            };
+            self.put_state(
+                &format!("{name}'s {label} commitment"),
+                commitment(bet_id, &user),
+                &commitment_state,
+                CommitmentState::MAX_SIZE,
+            );
+        }
+        // Alice heads the waitlist, bob queued right behind her
+        for (position, name, user) in [(0, "alice", self.alice), (1, "bob", self.bob)] {
+            let pending = PendingDeposit {
+                user,
+                bet_id,
+                position,
+                is_yes: true,
+                amount: SOL,
+                queued_at: NOW - 60,
+            };
+            self.put_state(
+                &format!("{name}'s {label} pending deposit"),
+                pending_deposit(bet_id, position),
+                &pending,
+                PendingDeposit::MAX_SIZE,
+            );
+            if let Some(stored) = self.accounts.get_mut(&pending_deposit(bet_id, position)) {
+                stored.lamports += SOL;
+            }
+        }
+    }
+
+    // The yes outcome mint of a pool, with alice and bob holding tokens
+    fn put_outcome_mint(&mut self, label: &str, bet_id: u64) {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(pool(bet_id)),
+            supply: 2 * SOL,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let yes_mint = outcome_mint(bet_id, true);
+        self.put_packed(&format!("{label} yes mint"), yes_mint, mint);
+        for (name, holder) in [("alice", self.alice), ("bob", self.bob)] {
+            self.put_token_account(&format!("{name}'s {label} yes tokens"), &holder, &yes_mint);
+        }
+    }
+
+    fn pools(&mut self) {
+        let (creator, referee) = (self.creator, self.referee);
+        let (other_creator, other_referee) = (self.other_creator, self.other_referee);
+        let open = |bet_id, creator, referee, title| PoolState {
+            end_timestamp: -1,
+            outcome_tokens: true,
+            ..self.pool_state(bet_id, creator, referee, title)
+        };
+        let settled = |bet_id, creator, referee, title| PoolState {
+            end_timestamp: NOW - 3_600,
+            created_timestamp: NOW - 7_200,
+            complete: true,
+            winner: WinnerSide::Yes,
+            resolved_timestamp: NOW - 60,
+            title_indexed: false,
+            guaranteed: true,
+            outcome_tokens: true,
+            ..self.pool_state(bet_id, creator, referee, title)
+        };
+        let pools = [
+            ("pool", open(POOL, creator, referee, TITLE)),
+            ("other pool", open(OTHER_POOL, other_creator, other_referee, OTHER_TITLE)),
+            ("settled pool", settled(SETTLED_POOL, creator, referee, "Settled pool")),
+            (
+                "other settled pool",
+                settled(OTHER_SETTLED_POOL, other_creator, other_referee, "Other settled"),
+            ),
+            (
+                "range pool",
+                PoolState {
+                    range_buckets: vec![
+                        RangeBucket {
+                            lower: 0,
+                            upper: 100,
+                            is_yes: true,
+                        },
+                        RangeBucket {
+                            lower: 100,
+                            upper: 200,
+                            is_yes: false,
+                        },
+                    ],
+                    end_timestamp: NOW - 60,
+                    title_indexed: false,
+                    optimistic_resolution: false,
+                    // Still in the legacy shared vault
+                    own_vault: false,
+                    ..self.pool_state(RANGE_POOL, creator, referee, "Range pool")
+                },
+            ),
+            (
+                "provisional pool",
+                PoolState {
+                    provisional_window_secs: 3_600,
+                    end_timestamp: NOW - 60,
+                    title_indexed: false,
+                    optimistic_resolution: false,
+                    ..self.pool_state(PROVISIONAL_POOL, creator, referee, "Provisional pool")
+                },
+            ),
+            (
+                "oracle pool",
+                PoolState {
+                    price_condition: PriceCondition {
+                        feed: self.price_feed,
+                        comparison: PriceCondition::ABOVE,
+                        threshold: 6_000_000,
+                        expo: -2,
+                    },
+                    end_timestamp: NOW - 120,
+                    title_indexed: false,
+                    optimistic_resolution: false,
+                    ..self.pool_state(ORACLE_POOL, creator, referee, "Oracle pool")
+                },
+            ),
+            (
+                "disputed pool",
+                PoolState {
+                    provisional_window_secs: 3_600,
+                    provisional_at: NOW - 120,
+                    provisional_is_yes: true,
+                    provisional_by: referee,
+                    provisional_disputed: true,
+                    end_timestamp: NOW - 600,
+                    title_indexed: false,
+                    optimistic_resolution: false,
+                    ..self.pool_state(DISPUTED_POOL, creator, referee, "Disputed pool")
+                },
+            ),
+            (
+                "meta pool",
+                PoolState {
+                    meta_condition: MetaCondition {
+                        target_bet_id: SETTLED_POOL,
+                        metric: MetaCondition::TOTAL_RESERVE,
+                        comparison: PriceCondition::AT_OR_ABOVE,
+                        threshold: SOL,
+                        enabled: true,
+                    },
+                    end_timestamp: NOW - 60,
+                    title_indexed: false,
+                    optimistic_resolution: false,
+                    ..self.pool_state(META_POOL, creator, Pubkey::default(), "Meta pool")
+                },
+            ),
+            (
+                "committee pool",
+                PoolState {
+                    referee_committee: [
+                        referee,
+                        other_referee,
+                        Pubkey::default(),
+                        Pubkey::default(),
+                        Pubkey::default(),
+                    ],
+                    committee_size: 2,
+                    committee_threshold: 2,
+                    end_timestamp: NOW - 60,
+                    title_indexed: false,
+                    optimistic_resolution: false,
+                    ..self.pool_state(COMMITTEE_POOL, creator, referee, "Committee pool")
+                },
+            ),
+        ];
+        for (label, pool_state) in pools {
+            self.put_pool(label, pool_state);
+        }
+        // alice disputed the referee's provisional YES
+        let dispute = ResolutionDispute {
+            bet_id: DISPUTED_POOL,
+            disputer: self.alice,
+            is_yes: true,
+            bond: PROVISIONAL_DISPUTE_BOND,
+            disputed_at: NOW - 60,
+        };
+        self.put_state(
+            "disputed pool dispute",
+            resolution_dispute(DISPUTED_POOL),
+            &dispute,
+            ResolutionDispute::MAX_SIZE,
+        );
+        if let Some(stored) = self.accounts.get_mut(&resolution_dispute(DISPUTED_POOL)) {
+            stored.lamports += PROVISIONAL_DISPUTE_BOND;
+        }
+        // A resolved pool still in the layout with a String winner
+        let legacy_pool = PoolState {
+            complete: true,
+            winner: WinnerSide::Yes,
+            resolved_timestamp: NOW - 60,
+            title_indexed: false,
+            ..self.pool_state(LEGACY_POOL, creator, referee, "Legacy pool")
+        };
+        let data = legacy_pool_data(&legacy_pool);
+        self.put(
+            "legacy pool state",
+            pool(LEGACY_POOL),
+            Stored {
+                lamports: Rent::default().minimum_balance(data.len()),
+                owner: crate::ID,
+                data,
+                executable: false,
+            },
+        );
+        // ...and its history, still the Vec-backed one
+        let data = legacy_history_data(pool(LEGACY_POOL), LEGACY_POOL, &[(NOW - 3_600, 0, 0)]);
+        self.put(
+            "legacy pool history",
+            history(LEGACY_POOL),
+            Stored {
+                lamports: Rent::default().minimum_balance(data.len()),
+                owner: crate::ID,
+                data,
+                executable: false,
+            },
+        );
+        self.put_outcome_mint("pool", POOL);
+        self.put_outcome_mint("settled pool", SETTLED_POOL);
+
+        let (alice, bob) = (self.alice, self.bob);
+        for (own, foreign) in [(POOL, OTHER_POOL), (SETTLED_POOL, OTHER_SETTLED_POOL)] {
+            for key in [
+                pool,
+                pool_vault,
+                history,
+                summary,
+                guarantee,
+                subscribers,
+                #[cfg(feature = "oracle")]
+                assertion,
+            ] {
+                self.twin(key(own), &[key(foreign)]);
+            }
+            self.twin(link(own, true), &[link(foreign, true)]);
+            self.twin(entry(own, &alice), &[entry(own, &bob), entry(foreign, &alice)]);
+            self.twin(
+                commitment(own, &alice),
+                &[commitment(own, &bob), commitment(foreign, &alice)],
+            );
+            self.twin(
+                pending_deposit(own, 0),
+                &[pending_deposit(own, 1), pending_deposit(foreign, 0)],
+            );
+        }
+        self.twin(settlement(SETTLED_POOL), &[settlement(OTHER_SETTLED_POOL)]);
+        self.twin(
+            title_index(&creator, TITLE),
+            &[title_index(&other_creator, OTHER_TITLE)],
+        );
+    }
+
+    fn spl_pool(&mut self) {
+        let pool_key = pool(SPL_POOL);
+        let pool_state = PoolState {
+            token_mint: self.mint,
+            own_vault: false,
+            title_indexed: false,
+            title_hash: [0; 32],
+            ..self.pool_state(SPL_POOL, self.creator, self.referee, "Token pool")
+        };
+        self.put_state("token pool state", pool_key, &pool_state, PoolState::MAX_SIZE);
+
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(self.owner),
+            supply: 100 * SOL,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mint_key = self.mint;
+        self.put_packed("mint", mint_key, mint);
+        self.put_token_account("token pool vault", &pool_key, &mint_key);
+        for (name, user, is_yes) in [("alice", self.alice, true), ("bob", self.bob, false)] {
+            self.put_token_account(&format!("{name}'s tokens"), &user, &mint_key);
+            let entry_state = Self::entry_state(SPL_POOL, user, is_yes);
+            self.put_state(
+                &format!("{name}'s token pool entry"),
+                entry(SPL_POOL, &user),
+                &entry_state,
+                EntryState::MAX_SIZE,
+            );
+        }
+        for (name, wallet) in [
+            ("mallory's tokens", self.mallory),
+            ("creator's tokens", self.creator),
+            ("owner's tokens", self.owner),
+        ] {
+            self.put_token_account(name, &wallet, &mint_key);
+        }
+        self.put_history("token pool history", SPL_POOL);
+        let pool_summary = PoolSummary {
+            pool: pool_key,
+            bet_id: SPL_POOL,
+            total_deposited: 2 * SOL,
+            depositor_count: 2,
+            sum_sq_entry_deposits: 2 * (SOL as u128) * (SOL as u128),
+            herfindahl_bps: 5_000,
+            top_depositor: self.alice,
+            top_entry_deposit: SOL,
+            top_depositor_bps: 5_000,
+            largest_deposit: SOL,
+            ..PoolSummary::default()
+        };
+        self.put_state(
+            "token pool summary",
+            summary(SPL_POOL),
+            &pool_summary,
+            PoolSummary::MAX_SIZE,
+        );
+
+        self.twin(pool_key, &[pool(POOL)]);
+        self.twin(
+            entry(SPL_POOL, &self.alice),
+            &[entry(SPL_POOL, &self.bob), entry(POOL, &self.alice)],
+        );
+        self.twin(history(SPL_POOL), &[history(POOL)]);
+        self.twin(summary(SPL_POOL), &[summary(POOL)]);
+    }
+
+    fn outcome_markets(&mut self) {
+        for (label, bet_id, creator, referee) in [
+            ("market", MARKET, self.creator, self.referee),
+            ("other market", OTHER_MARKET, self.other_creator, self.other_referee),
+        ] {
+            let market = OutcomeMarket {
+                bet_id,
+                creator,
+                referee,
+                title: format!("{label} title"),
+                end_timestamp: NOW - 60,
+                created_timestamp: NOW - 3_600,
+                outcomes: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+                reserves: vec![SOL, SOL, SOL],
+                supplies: vec![SOL, SOL, SOL],
+                creator_fee_bps: CREATOR_FEE_PERCENT,
+                ..OutcomeMarket::default()
+            };
+            self.put_state(
+                &format!("{label} state"),
+                outcome_market(bet_id),
+                &market,
+                OutcomeMarket::MAX_SIZE,
+            );
+            for (name, user, outcome) in [("alice", self.alice, 0), ("bob", self.bob, 1)] {
+                let outcome_entry_state = OutcomeEntry {
+                    user,
+                    bet_id,
+                    outcome,
+                    deposited_sol_amount: SOL,
+                    token_balance: SOL,
+                    ..OutcomeEntry::default()
+                };
+                self.put_state(
+                    &format!("{name}'s {label} entry"),
+                    outcome_entry(bet_id, &user),
+                    &outcome_entry_state,
+                    OutcomeEntry::MAX_SIZE,
+                );
+            }
+        }
+        self.twin(outcome_market(MARKET), &[outcome_market(OTHER_MARKET)]);
+        self.twin(
+            outcome_entry(MARKET, &self.alice),
+            &[outcome_entry(MARKET, &self.bob), outcome_entry(OTHER_MARKET, &self.alice)],
+        );
+    }
+
+    // Platform-token staking on `mint`: alice and bob stake, bob has claimed every closed epoch
+    fn revenue_share(&mut self) {
+        let staked = 2 * SOL;
+        let share = RevenueShare {
+            stake_mint: self.mint,
+            epoch_duration_secs: MIN_REVENUE_EPOCH_SECS,
+            epoch: REVENUE_EPOCH,
+            epoch_started_at: NOW - MIN_REVENUE_EPOCH_SECS - 60,
+            total_staked: staked,
+            total_distributed: 2 * SOL / 10,
+            total_claimed: SOL / 10,
+            ..RevenueShare::default()
+        };
+        self.put_state("revenue share", revenue_share(), &share, RevenueShare::MAX_SIZE);
+        // Closed epochs' revenue not claimed yet, on top of rent
+        if let Some(stored) = self.accounts.get_mut(&revenue_share()) {
+            stored.lamports += SOL / 10;
+        }
+        let mint_key = self.mint;
+        self.put_token_account("stake vault", &revenue_share(), &mint_key);
+
+        for epoch in 1..REVENUE_EPOCH {
+            let record = RevenueEpoch {
+                epoch,
+                revenue: SOL / 10,
+                total_staked: staked,
+                claimed: SOL / 20,
+                closed_at: NOW - MIN_REVENUE_EPOCH_SECS * (REVENUE_EPOCH - epoch) as i64 - 60,
+            };
+            self.put_state(
+                &format!("revenue epoch {epoch}"),
+                revenue_epoch(epoch),
+                &record,
+                RevenueEpoch::MAX_SIZE,
+            );
+        }
+        self.twin(revenue_epoch(REVENUE_EPOCH - 1), &[revenue_epoch(REVENUE_EPOCH - 2)]);
+
+        for (name, user, next_claim_epoch) in [
+            ("alice", self.alice, REVENUE_EPOCH - 1),
+            ("bob", self.bob, REVENUE_EPOCH),
+        ] {
+            let stake_state = StakeState {
+                user,
+                amount: staked / 2,
+                next_claim_epoch,
+                ..StakeState::default()
+            };
+            self.put_state(
+                &format!("{name}'s stake"),
+                stake(&user),
+                &stake_state,
+                StakeState::MAX_SIZE,
+            );
+        }
+        self.twin(stake(&self.alice), &[stake(&self.bob)]);
+    }
+
+    // Alice refers bettors and has fees credited she hasn't claimed yet; bob registered but
+    // was never credited
+    fn referrals(&mut self) {
+        for (name, referrer, accrued) in [("alice", self.alice, SOL / 50), ("bob", self.bob, 0)] {
+            let referral_state = ReferralState {
+                referrer,
+                accrued,
+                claimed: accrued / 2,
+            };
+            self.put_state(
+                &format!("{name}'s referral state"),
+                referral(&referrer),
+                &referral_state,
+                ReferralState::MAX_SIZE,
+            );
+            // Credited lamports not claimed yet, on top of rent
+            if let Some(stored) = self.accounts.get_mut(&referral(&referrer)) {
+                stored.lamports += referral_state.unclaimed();
+            }
+        }
+        self.twin(referral(&self.alice), &[referral(&self.bob)]);
+    }
+}
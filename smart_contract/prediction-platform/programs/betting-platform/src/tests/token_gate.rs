@@ -0,0 +1,44 @@
+//! Token-gated pools only take entries and deposits from holders of the gate mint.
+
+use crate::error::BettingError;
+use crate::PoolState;
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+fn gated(min_amount: u64) -> PoolState {
+    PoolState {
+        gate_mint: Pubkey::new_unique(),
+        gate_min_amount: min_amount,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn open_pools_ignore_the_gate_account() {
+    let pool = PoolState::default();
+    assert!(!pool.is_gated());
+    assert!(pool.check_gate(None).is_ok());
+    assert!(pool.check_gate(Some(0)).is_ok());
+}
+
+#[test]
+fn holders_of_the_minimum_pass() {
+    let pool = gated(5);
+    assert!(pool.check_gate(Some(5)).is_ok());
+    assert!(pool.check_gate(Some(6)).is_ok());
+    assert_eq!(
+        pool.check_gate(Some(4)).unwrap_err(),
+        Error::from(BettingError::GateBalanceTooLow)
+    );
+}
+
+#[test]
+fn gated_pools_require_the_token_account() {
+    // An NFT collection gate: holding one token is enough, but it must be shown
+    let pool = gated(1);
+    assert_eq!(
+        pool.check_gate(None).unwrap_err(),
+        Error::from(BettingError::GateTokenRequired)
+    );
+    assert!(pool.check_gate(Some(1)).is_ok());
+}
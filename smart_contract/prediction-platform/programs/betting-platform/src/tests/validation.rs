@@ -0,0 +1,22 @@
+//! Text limits are enforced in UTF-8 bytes, not characters.
+
+use crate::constants::{MAX_DESCRIPTION_LEN, MAX_TITLE_LEN};
+use crate::validation::{validate_description, validate_title};
+
+#[test]
+fn title_limit_counts_bytes() {
+    assert!(validate_title(&"a".repeat(MAX_TITLE_LEN)).is_ok());
+    assert!(validate_title(&"a".repeat(MAX_TITLE_LEN + 1)).is_err());
+
+    // Four bytes per emoji: 25 fit in 100 bytes, 26 do not despite being "26 characters"
+    assert!(validate_title(&"🚀".repeat(MAX_TITLE_LEN / 4)).is_ok());
+    assert!(validate_title(&"🚀".repeat(MAX_TITLE_LEN / 4 + 1)).is_err());
+}
+
+#[test]
+fn description_limit_counts_bytes() {
+    // Two bytes per character
+    assert!(validate_description(&"é".repeat(MAX_DESCRIPTION_LEN / 2)).is_ok());
+    assert!(validate_description(&"é".repeat(MAX_DESCRIPTION_LEN / 2 + 1)).is_err());
+    assert!(validate_description("").is_err());
+}
@@ -0,0 +1,24 @@
+//! Vault coverage reported to monitoring bots.
+
+use crate::VaultHealth;
+
+const RENT: u64 = 890_880;
+
+#[test]
+fn coverage_counts_only_lamports_above_rent() {
+    let covered = VaultHealth::new(RENT + 1_000, 1_000, RENT);
+    assert_eq!(covered.coverage_bps, 10_000);
+
+    let short = VaultHealth::new(RENT + 750, 1_000, RENT);
+    assert_eq!(short.coverage_bps, 7_500);
+
+    // A vault below its rent minimum covers nothing
+    assert_eq!(VaultHealth::new(RENT / 2, 1_000, RENT).coverage_bps, 0);
+}
+
+#[test]
+fn nothing_owed_is_fully_covered() {
+    let settled = VaultHealth::new(RENT, 0, RENT);
+    assert_eq!(settled.liabilities, 0);
+    assert_eq!(settled.coverage_bps, u64::MAX);
+}
@@ -0,0 +1,181 @@
+//! Scheduled opens: deposits wait for the opening time and the waitlist ahead of them, and the
+//! crank refunds queued deposits the pool can no longer take.
+
+use crate::error::BettingError;
+use crate::{waitlist_accepts, EntryState, MainState, PendingDeposit, PoolState, WinnerSide};
+use anchor_lang::error::Error;
+use anchor_lang::prelude::Pubkey;
+
+const OPENS_AT: i64 = 1_000;
+
+fn scheduled_pool() -> PoolState {
+    PoolState {
+        opens_at: OPENS_AT,
+        end_timestamp: -1,
+        history_disabled: true,
+        installment_count: 1,
+        ..Default::default()
+    }
+}
+
+fn queued(is_yes: bool, amount: u64) -> PendingDeposit {
+    PendingDeposit {
+        is_yes,
+        amount,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn deposits_wait_for_the_opening_time() {
+    let pool = scheduled_pool();
+    assert_eq!(
+        pool.require_open_for_deposits(OPENS_AT - 1).err(),
+        Some(Error::from(BettingError::PoolNotOpen))
+    );
+    assert!(pool.require_open_for_deposits(OPENS_AT).is_ok());
+    // Pools created without a schedule are open from the start
+    assert!(PoolState::default().require_open_for_deposits(0).is_ok());
+}
+
+#[test]
+fn deposits_wait_for_the_waitlist_to_drain() {
+    let mut pool = PoolState {
+        waitlist_queued: 2,
+        waitlist_executed: 1,
+        ..scheduled_pool()
+    };
+    assert_eq!(
+        pool.require_open_for_deposits(OPENS_AT).err(),
+        Some(Error::from(BettingError::WaitlistPending))
+    );
+    pool.waitlist_executed = 2;
+    assert!(pool.require_open_for_deposits(OPENS_AT).is_ok());
+}
+
+#[test]
+fn an_undrained_waitlist_keeps_the_pool_open() {
+    let mut pool = PoolState {
+        refund_mode: true,
+        history_disabled: true,
+        platform_fee_claimed: true,
+        waitlist_queued: 1,
+        ..Default::default()
+    };
+    assert!(!pool.is_fully_settled());
+    pool.waitlist_executed = 1;
+    assert!(pool.is_fully_settled());
+}
+
+#[test]
+fn the_waitlist_takes_deposits_the_pool_would_take() {
+    let pool = scheduled_pool();
+    let entry = EntryState::default();
+    assert!(waitlist_accepts(
+        &MainState::default(),
+        &pool,
+        &entry,
+        &queued(true, 500),
+        None,
+        OPENS_AT,
+    ));
+}
+
+#[test]
+fn a_deposit_on_the_other_side_is_refunded() {
+    let pool = scheduled_pool();
+    let entry = EntryState {
+        is_yes: true,
+        token_balance: 10,
+        deposit_count: 1,
+        ..Default::default()
+    };
+    let main_state = MainState::default();
+    assert!(!waitlist_accepts(&main_state, &pool, &entry, &queued(false, 500), None, OPENS_AT));
+    assert!(waitlist_accepts(&main_state, &pool, &entry, &queued(true, 500), None, OPENS_AT));
+}
+
+#[test]
+fn deposits_past_a_cap_are_refunded() {
+    let entry = EntryState {
+        deposit_count: 1,
+        ..Default::default()
+    };
+    let capped = PoolState {
+        max_deposits_per_entry: 1,
+        ..scheduled_pool()
+    };
+    let main_state = MainState::default();
+    assert!(!waitlist_accepts(&main_state, &capped, &entry, &queued(true, 500), None, OPENS_AT));
+
+    let self_refereed = scheduled_pool();
+    let main_state = MainState {
+        self_referee_reserve_cap: 400,
+        ..Default::default()
+    };
+    let entry = EntryState::default();
+    assert!(!waitlist_accepts(
+        &main_state,
+        &self_refereed,
+        &entry,
+        &queued(true, 500),
+        None,
+        OPENS_AT,
+    ));
+}
+
+#[test]
+fn deposits_reaching_an_ended_or_provisionally_resolved_pool_are_refunded() {
+    let entry = EntryState::default();
+    let main_state = MainState::default();
+    let ended = PoolState {
+        end_timestamp: OPENS_AT + 10,
+        ..scheduled_pool()
+    };
+    assert!(waitlist_accepts(&main_state, &ended, &entry, &queued(true, 500), None, OPENS_AT));
+    assert!(!waitlist_accepts(
+        &main_state,
+        &ended,
+        &entry,
+        &queued(true, 500),
+        None,
+        OPENS_AT + 10,
+    ));
+
+    let provisional = PoolState {
+        provisional_window_secs: 60,
+        provisional_at: OPENS_AT,
+        winner: WinnerSide::Unresolved,
+        ..scheduled_pool()
+    };
+    assert!(!waitlist_accepts(
+        &main_state,
+        &provisional,
+        &entry,
+        &queued(true, 500),
+        None,
+        OPENS_AT,
+    ));
+}
+
+#[test]
+fn gated_pools_refund_heads_that_no_longer_hold_the_gate_token() {
+    let pool = PoolState {
+        gate_mint: Pubkey::new_unique(),
+        gate_min_amount: 1,
+        ..scheduled_pool()
+    };
+    let entry = EntryState::default();
+    let main_state = MainState::default();
+    for gate_balance in [None, Some(0)] {
+        assert!(!waitlist_accepts(
+            &main_state,
+            &pool,
+            &entry,
+            &queued(true, 500),
+            gate_balance,
+            OPENS_AT,
+        ));
+    }
+    assert!(waitlist_accepts(&main_state, &pool, &entry, &queued(true, 500), Some(1), OPENS_AT));
+}
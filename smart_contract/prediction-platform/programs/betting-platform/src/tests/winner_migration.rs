@@ -0,0 +1,83 @@
+//! Pools written while `winner` was a String migrate to the WinnerSide layout field for field.
+
+use crate::{splice_legacy_winner, PoolState, WinnerSide, LEGACY_POOL_SIZE};
+use anchor_lang::prelude::*;
+
+fn current_data(pool: &PoolState) -> Vec<u8> {
+    let mut data = Vec::new();
+    pool.try_serialize(&mut data).unwrap();
+    data.resize(8 + PoolState::MAX_SIZE, 0);
+    data
+}
+
+// `pool` as the legacy layout stored it: the winner as "yes", "no" or "" (void pools had
+// not resolved, so they stored "")
+pub(super) fn legacy_pool_data(pool: &PoolState) -> Vec<u8> {
+    let data = current_data(pool);
+    let mut winner_at = 8 + 32 + 8 * 3 + 8 * 6 + 8 * 2 + 32;
+    for _ in 0..3 {
+        let len = u32::from_le_bytes(data[winner_at..winner_at + 4].try_into().unwrap());
+        winner_at += 4 + len as usize;
+    }
+    let winner: &[u8] = match pool.winner {
+        WinnerSide::Yes => b"yes",
+        WinnerSide::No => b"no",
+        WinnerSide::Unresolved | WinnerSide::Void => b"",
+    };
+    let mut legacy = data[..winner_at].to_vec();
+    legacy.extend_from_slice(&(winner.len() as u32).to_le_bytes());
+    legacy.extend_from_slice(winner);
+    legacy.extend_from_slice(&data[winner_at + 1..]);
+    legacy.resize(LEGACY_POOL_SIZE, 0);
+    legacy
+}
+
+fn pool(winner: WinnerSide) -> PoolState {
+    PoolState {
+        bet_id: 42,
+        title: "Will it rain?".to_string(),
+        description: "Resolves on any rain".to_string(),
+        share_uuid: "abc".to_string(),
+        winner,
+        complete: winner == WinnerSide::Yes || winner == WinnerSide::No,
+        refund_mode: winner == WinnerSide::Void,
+        yes_reserve: 7,
+        rent_payer: Pubkey::new_unique(),
+        history_closed: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn splicing_recovers_the_current_layout() {
+    for winner in [WinnerSide::Unresolved, WinnerSide::Yes, WinnerSide::No] {
+        let expected = pool(winner);
+        let mut data = legacy_pool_data(&expected);
+        assert_eq!(splice_legacy_winner(&mut data).unwrap(), winner);
+        // The handler grows the account by the reserved block, which starts out zeroed
+        data.resize(8 + PoolState::MAX_SIZE, 0);
+        assert_eq!(data, current_data(&expected));
+    }
+}
+
+#[test]
+fn refunded_legacy_pools_read_as_unresolved_until_migrated() {
+    // The handler turns refund-mode pools into Void after splicing
+    let mut data = legacy_pool_data(&pool(WinnerSide::Void));
+    assert_eq!(splice_legacy_winner(&mut data).unwrap(), WinnerSide::Unresolved);
+    data.resize(8 + PoolState::MAX_SIZE, 0);
+    let migrated = PoolState::try_deserialize(&mut &data[..]).unwrap();
+    assert!(migrated.refund_mode);
+}
+
+#[test]
+fn unknown_winners_and_accounts_are_rejected() {
+    let mut data = legacy_pool_data(&pool(WinnerSide::Yes));
+    data[0] ^= 1;
+    assert!(splice_legacy_winner(&mut data).is_err());
+
+    let mut data = legacy_pool_data(&pool(WinnerSide::Yes));
+    let at = data.windows(3).position(|window| window == b"yes").unwrap();
+    data[at..at + 3].copy_from_slice(b"YES");
+    assert!(splice_legacy_winner(&mut data).is_err());
+}
@@ -0,0 +1,20 @@
+//! Time source for the program's gating checks (end times, windows, deadlines).
+
+use crate::MainState;
+use anchor_lang::prelude::*;
+
+// Current unix time as seen by gating checks. Builds with the `test-utils` feature shift the
+// cluster clock by MainState::clock_offset_secs (set through set_mock_clock) so localnet
+// suites can move past end times and windows without waiting; other builds ignore the offset.
+pub fn now(main_state: &MainState) -> Result<i64> {
+    let now = Clock::get()?.unix_timestamp;
+    #[cfg(feature = "test-utils")]
+    {
+        Ok(now.saturating_add(main_state.clock_offset_secs))
+    }
+    #[cfg(not(feature = "test-utils"))]
+    {
+        let _ = main_state;
+        Ok(now)
+    }
+}
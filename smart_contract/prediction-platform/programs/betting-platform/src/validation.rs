@@ -0,0 +1,69 @@
+//! Length checks for user-supplied text.
+//!
+//! Every limit is a count of UTF-8 bytes, because that is what account space is reserved
+//! for. Clients must measure with a byte count (`Buffer.byteLength`, `TextEncoder`), not
+//! `string.length`: a 100-byte title fits 100 ASCII characters but only 25 emoji. On
+//! failure the measured length is logged next to the limit so the error is actionable.
+
+use crate::{
+    constants::{MAX_DESCRIPTION_LEN, MAX_SIDE_LABEL_LEN, MAX_TITLE_LEN, MAX_UNIT_SYMBOL_LEN},
+    error::BettingError,
+    LocalizedText,
+};
+use anchor_lang::prelude::*;
+
+fn check_max_bytes(field: &str, text: &str, max_bytes: usize, error: BettingError) -> Result<()> {
+    if text.len() > max_bytes {
+        msg!("{} is {} bytes, limit is {} bytes", field, text.len(), max_bytes);
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+pub fn validate_title(title: &str) -> Result<()> {
+    check_max_bytes("title", title, MAX_TITLE_LEN, BettingError::TitleTooLong)?;
+    require!(!title.is_empty(), BettingError::TitleEmpty);
+    Ok(())
+}
+
+pub fn validate_description(description: &str) -> Result<()> {
+    check_max_bytes(
+        "description",
+        description,
+        MAX_DESCRIPTION_LEN,
+        BettingError::DescriptionTooLong,
+    )?;
+    require!(!description.is_empty(), BettingError::DescriptionEmpty);
+    Ok(())
+}
+
+pub fn validate_title_and_description(title: &str, description: &str) -> Result<()> {
+    validate_title(title)?;
+    validate_description(description)
+}
+
+// Empty is allowed: it means "no unit"
+pub fn validate_unit_symbol(unit_symbol: &str) -> Result<()> {
+    check_max_bytes(
+        "unit symbol",
+        unit_symbol,
+        MAX_UNIT_SYMBOL_LEN,
+        BettingError::UnitSymbolTooLong,
+    )
+}
+
+// Empty is allowed: it means "use the default Yes/No label"
+pub fn validate_side_label(label: &str) -> Result<()> {
+    check_max_bytes("side label", label, MAX_SIDE_LABEL_LEN, BettingError::SideLabelTooLong)
+}
+
+pub fn validate_localized_text(translation: &LocalizedText) -> Result<()> {
+    require!(!translation.language.is_empty(), BettingError::InvalidLanguageTag);
+    check_max_bytes(
+        "language tag",
+        &translation.language,
+        LocalizedText::MAX_LANGUAGE_LEN,
+        BettingError::InvalidLanguageTag,
+    )?;
+    validate_title_and_description(&translation.title, &translation.description)
+}
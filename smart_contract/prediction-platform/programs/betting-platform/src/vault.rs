@@ -0,0 +1,118 @@
+//! Where a pool's lamports live. Every SOL pool keeps its funds in its own pool-vault PDA
+//! ([POOL_VAULT_SEED, bet_id]), so an accounting bug in one market can never pay out another
+//! market's deposits. Pools created before per-pool vaults stay in the legacy shared sol-vault
+//! until migrate_vault moves them over; instructions that move pool funds take both vaults and
+//! route through PoolVault.
+
+use crate::constants::{POOL_VAULT_SEED, VAULT_SEED};
+use crate::PoolState;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_instruction;
+
+pub struct PoolVault<'a, 'info> {
+    pub account: &'a AccountInfo<'info>,
+    bet_id: [u8; 8],
+    bump: u8,
+    shared: bool,
+}
+
+impl<'a, 'info> PoolVault<'a, 'info> {
+    // Picks the vault holding `pool_state`'s funds out of the two an instruction receives
+    pub fn select(
+        pool_state: &PoolState,
+        sol_vault: &'a AccountInfo<'info>,
+        sol_vault_bump: u8,
+        pool_vault: &'a AccountInfo<'info>,
+        pool_vault_bump: u8,
+    ) -> Self {
+        let (account, bump) = if pool_state.own_vault {
+            (pool_vault, pool_vault_bump)
+        } else {
+            (sol_vault, sol_vault_bump)
+        };
+        Self {
+            account,
+            bet_id: pool_state.bet_id.to_le_bytes(),
+            bump,
+            shared: !pool_state.own_vault,
+        }
+    }
+
+    // Lamports the vault can pay out; its rent-exempt minimum always stays behind
+    pub fn available(&self) -> Result<u64> {
+        Ok(self
+            .account
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0)))
+    }
+
+    // Moves `amount` lamports out of the vault and off the pool's vault_liabilities
+    pub fn pay(
+        &self,
+        pool_state: &mut PoolState,
+        recipient: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        pool_state.vault_liabilities = pool_state.vault_liabilities.saturating_sub(amount);
+        let transfer_instruction =
+            system_instruction::transfer(self.account.key, recipient.key, amount);
+        let bump = [self.bump];
+        let seeds: &[&[u8]] = if self.shared {
+            &[VAULT_SEED.as_bytes(), &bump]
+        } else {
+            &[POOL_VAULT_SEED.as_bytes(), &self.bet_id, &bump]
+        };
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_instruction,
+            &[
+                self.account.clone(),
+                recipient.clone(),
+                system_program.clone(),
+            ],
+            &[seeds],
+        )?;
+        Ok(())
+    }
+}
+
+// The vault deposits into `pool_state` land in, out of the two an instruction receives
+pub fn pool_vault_account<'a, 'info>(
+    pool_state: &PoolState,
+    sol_vault: &'a AccountInfo<'info>,
+    pool_vault: &'a AccountInfo<'info>,
+) -> &'a AccountInfo<'info> {
+    if pool_state.own_vault {
+        pool_vault
+    } else {
+        sol_vault
+    }
+}
+
+// Funds a new pool's vault with its rent-exempt minimum so deposits of any size can land in it
+pub(crate) fn fund_pool_vault_rent<'info>(
+    payer: &AccountInfo<'info>,
+    pool_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    let rent_top_up = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(pool_vault.lamports());
+    if rent_top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: pool_vault.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+    Ok(rent_top_up)
+}
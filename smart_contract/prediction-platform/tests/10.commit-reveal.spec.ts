@@ -0,0 +1,209 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+import { createHash, randomBytes } from "crypto";
+
+// Commitment = sha256(is_yes as u8 || salt)
+const commitmentHash = (isYes: boolean, salt: Buffer): number[] =>
+  Array.from(
+    createHash("sha256")
+      .update(Buffer.concat([Buffer.from([isYes ? 1 : 0]), salt]))
+      .digest()
+  );
+
+describe("Commit-Reveal Deposits", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let whale: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let poolStatePDA: web3.PublicKey;
+  let betId: number;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+    whale = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, user, whale]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    betId = mainState.currentBetId.toNumber();
+    [poolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), new anchor.BN(betId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        title: "Commit-Reveal Pool",
+        description: "Deposits are sealed until revealed",
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        referee: referee.publicKey,
+        commitReveal: true,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Commit-Reveal Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const kp of [user, whale]) {
+      await program.methods
+        .createEntry({ betId: new anchor.BN(betId) })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+  });
+
+  it("rejects plain deposits on a commit-reveal pool", async () => {
+    try {
+      await program.methods
+        .deposit({
+          betId: new anchor.BN(betId),
+          isYes: true,
+          amount: new anchor.BN(1_000_000),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("deposit should require commit-reveal");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("CommitRevealRequired");
+    }
+  });
+
+  it("prices a revealed deposit at commit-time reserves", async () => {
+    const salt = randomBytes(32);
+    await program.methods
+      .commitDeposit({
+        betId: new anchor.BN(betId),
+        commitmentHash: commitmentHash(true, salt),
+        amount: new anchor.BN(100_000_000),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    let pool = await program.account.poolState.fetch(poolStatePDA);
+    expect(pool.pendingCommitments.toNumber()).to.equal(1);
+    // Funds are locked but not yet part of the reserves
+    expect(pool.yesReserve.toNumber()).to.equal(0);
+
+    // Another bettor moves the odds in between
+    const whaleSalt = randomBytes(32);
+    await program.methods
+      .commitDeposit({
+        betId: new anchor.BN(betId),
+        commitmentHash: commitmentHash(true, whaleSalt),
+        amount: new anchor.BN(2_000_000_000),
+      })
+      .accounts({ user: whale.publicKey })
+      .signers([whale])
+      .rpc();
+    await program.methods
+      .revealDeposit({
+        betId: new anchor.BN(betId),
+        isYes: true,
+        salt: Array.from(whaleSalt),
+      })
+      .accounts({ user: whale.publicKey })
+      .signers([whale])
+      .rpc();
+
+    await program.methods
+      .revealDeposit({
+        betId: new anchor.BN(betId),
+        isYes: true,
+        salt: Array.from(salt),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    const [entryPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("entry"), poolStatePDA.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const entry = await program.account.entryState.fetch(entryPDA);
+    // Empty pool at commit time: YES price is exactly 0.5, so tokens = 2x deposit
+    expect(entry.tokenBalance.toNumber()).to.equal(200_000_000);
+    expect(entry.depositedSolAmount.toNumber()).to.equal(100_000_000);
+
+    pool = await program.account.poolState.fetch(poolStatePDA);
+    expect(pool.pendingCommitments.toNumber()).to.equal(0);
+  });
+
+  it("rejects a reveal that does not match the commitment", async () => {
+    const salt = randomBytes(32);
+    await program.methods
+      .commitDeposit({
+        betId: new anchor.BN(betId),
+        commitmentHash: commitmentHash(true, salt),
+        amount: new anchor.BN(1_000_000),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    try {
+      await program.methods
+        .revealDeposit({
+          betId: new anchor.BN(betId),
+          isYes: false,
+          salt: Array.from(salt),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("reveal with the wrong side should fail");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidReveal");
+    }
+
+    // Still inside the reveal window, so no refund yet
+    try {
+      await program.methods
+        .refundCommitment({ betId: new anchor.BN(betId) })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("refund should wait for the reveal window to close");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RevealWindowOpen");
+    }
+  });
+});
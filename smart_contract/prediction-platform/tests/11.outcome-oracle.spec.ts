@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+// Third-party programs read the OutcomeOracle by raw offsets (see README), so this spec
+// decodes the account the same way instead of going through the Anchor coder.
+describe("Outcome Oracle", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("publishes the resolved outcome in a stable layout", async () => {
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    const betId = mainState.currentBetId;
+    const [poolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [outcomePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("outcome"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        title: "Oracle Pool",
+        description: "Outcome is published for other programs",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Oracle Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(1_000_000_000),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(outcomePDA);
+    expect(info).to.not.be.null;
+    expect(info!.owner.toString()).to.equal(program.programId.toString());
+    const data = info!.data;
+
+    expect(data.readUInt8(8)).to.equal(1); // version
+    expect(new web3.PublicKey(data.subarray(9, 41)).toString()).to.equal(
+      poolStatePDA.toString()
+    );
+    expect(data.readBigUInt64LE(41)).to.equal(BigInt(betId.toString()));
+    expect(data.readUInt8(49)).to.equal(1); // YES
+    expect(Number(data.readBigInt64LE(50))).to.be.greaterThan(0);
+    expect(data.readBigUInt64LE(58)).to.equal(BigInt(1_000_000_000));
+    expect(data.readBigUInt64LE(66)).to.equal(BigInt(0));
+    // 2 SOL virtual YES vs 1 SOL virtual NO => 2/3 YES
+    expect(Number(data.readBigUInt64LE(74))).to.be.approximately(666_666_666, 1);
+    expect(Number(data.readBigUInt64LE(82))).to.be.approximately(333_333_333, 1);
+  });
+});
@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+// Events-only pools skip PoolHistoryState; clients pass `null` for the optional account.
+describe("History Disabled Pools", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let historyPDA: web3.PublicKey;
+  let poolStatePDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        3 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    betId = mainState.currentBetId;
+    [poolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    [historyPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("history"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  });
+
+  it("creates a pool without a history account", async () => {
+    await program.methods
+      .createPool({
+        title: "Events Only",
+        description: "History is indexed off-chain",
+        endTimestamp: new anchor.BN(-1),
+        referee: creator.publicKey,
+        disableHistory: true,
+      } as any)
+      .accountsPartial({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Events Only"
+        ),
+        historyState: null,
+      })
+      .signers([creator])
+      .rpc();
+
+    const pool = await program.account.poolState.fetch(poolStatePDA);
+    expect(pool.historyDisabled).to.be.true;
+    expect(await provider.connection.getAccountInfo(historyPDA)).to.be.null;
+  });
+
+  it("deposits without touching history", async () => {
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(1_000_000),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accountsPartial({ user: user.publicKey, historyState: null })
+      .signers([user])
+      .rpc();
+
+    const pool = await program.account.poolState.fetch(poolStatePDA);
+    expect(pool.yesReserve.toNumber()).to.equal(1_000_000);
+    expect(await provider.connection.getAccountInfo(historyPDA)).to.be.null;
+  });
+
+  it("rejects a history account for a history-disabled pool", async () => {
+    try {
+      await program.methods
+        .deposit({
+          betId,
+          isYes: true,
+          amount: new anchor.BN(1_000_000),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accountsPartial({ user: user.publicKey, historyState: historyPDA })
+        .signers([user])
+        .rpc();
+      expect.fail("deposit should reject the history account");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("HistoryDisabled");
+    }
+  });
+});
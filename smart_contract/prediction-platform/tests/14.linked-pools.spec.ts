@@ -0,0 +1,154 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Linked Pools", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let cranker: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let parentBetId: anchor.BN;
+
+  const linkPDA = (betId: anchor.BN, onYes: boolean) =>
+    web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("link"),
+        betId.toArrayLike(Buffer, "le", 8),
+        Buffer.from([onYes ? 1 : 0]),
+      ],
+      program.programId
+    )[0];
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    cranker = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, cranker]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    parentBetId = mainState.currentBetId;
+    await program.methods
+      .createPool({
+        title: "Semifinal: A vs B",
+        description: "Winner advances to the final",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        sideALabel: "Team A",
+        sideBLabel: "Team B",
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Semifinal: A vs B"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const onYes of [true, false]) {
+      await program.methods
+        .linkPools({
+          parentBetId,
+          onYes,
+          title: onYes ? "Final: A vs C" : "Final: B vs C",
+          description: "Opened automatically from the semifinal",
+          durationSecs: new anchor.BN(3600),
+        })
+        .accounts({ creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+    }
+  });
+
+  it("refuses to open a child before the parent resolves", async () => {
+    try {
+      await program.methods
+        .openLinkedPool({ parentBetId, onYes: true })
+        .accounts({ payer: cranker.publicKey })
+        .signers([cranker])
+        .rpc();
+      expect.fail("child should not open while the parent is unresolved");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BetNotComplete");
+    }
+  });
+
+  it("opens only the child linked to the winning outcome", async () => {
+    await program.methods
+      .setWinner({ betId: parentBetId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    try {
+      await program.methods
+        .openLinkedPool({ parentBetId, onYes: false })
+        .accounts({ payer: cranker.publicKey })
+        .signers([cranker])
+        .rpc();
+      expect.fail("losing-side child should not open");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("LinkOutcomeMismatch");
+    }
+
+    const childBetId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await program.methods
+      .openLinkedPool({ parentBetId, onYes: true })
+      .accounts({ payer: cranker.publicKey })
+      .signers([cranker])
+      .rpc();
+
+    const link = await program.account.poolLink.fetch(linkPDA(parentBetId, true));
+    expect(link.opened).to.equal(true);
+    expect(link.childBetId.toNumber()).to.equal(childBetId.toNumber());
+
+    const [childPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), childBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const child = await program.account.poolState.fetch(childPDA);
+    expect(child.title).to.equal("Final: A vs C");
+    expect(child.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(child.referee.toBase58()).to.equal(referee.publicKey.toBase58());
+    expect(child.sideALabel).to.equal("Team A");
+    expect(child.endTimestamp.toNumber()).to.be.greaterThan(0);
+  });
+});
@@ -0,0 +1,162 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+// Payouts must only ever depend on PoolState, EntryState and the vault. emergency_claim
+// enforces that rule: it takes no auxiliary accounts at all.
+describe("Emergency Claim", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let winner: web3.Keypair;
+  let loser: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const DEPOSIT = 500_000_000;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    winner = web3.Keypair.generate();
+    loser = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, winner, loser]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    await program.methods
+      .createPool({
+        title: "Emergency Claim Pool",
+        description: "Winners can always be paid",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Emergency Claim Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const [kp, isYes] of [
+      [winner, true],
+      [loser, false],
+    ] as [web3.Keypair, boolean][]) {
+      await program.methods
+        .createEntry({ betId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+      await program.methods
+        .deposit({
+          betId,
+          isYes,
+          amount: new anchor.BN(DEPOSIT),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+  });
+
+  it("rejects emergency claims before resolution", async () => {
+    try {
+      await program.methods
+        .emergencyClaim({ betId })
+        .accounts({ user: winner.publicKey })
+        .signers([winner])
+        .rpc();
+      expect.fail("unresolved pool should not pay out");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MarketNotResolved");
+    }
+  });
+
+  it("pays winners with only pool, entry and vault accounts", async () => {
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    const ix = program.idl.instructions.find((i) => i.name === "emergencyClaim")!;
+    expect(ix.accounts.map((a) => a.name)).to.deep.equal([
+      "user",
+      "poolState",
+      "entryState",
+      "solVault",
+      "poolVault",
+      "systemProgram",
+    ]);
+
+    const before = await provider.connection.getBalance(winner.publicKey);
+    await program.methods
+      .emergencyClaim({ betId })
+      .accounts({ user: winner.publicKey })
+      .signers([winner])
+      .rpc();
+    const after = await provider.connection.getBalance(winner.publicKey);
+    // Principal plus a share of the losing side, minus fees
+    expect(after - before).to.be.greaterThan(DEPOSIT);
+
+    try {
+      await program.methods
+        .claim({ betId })
+        .accounts({ user: winner.publicKey })
+        .signers([winner])
+        .rpc();
+      expect.fail("entry was already paid through the emergency path");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyClaimedAt");
+    }
+
+    try {
+      await program.methods
+        .emergencyClaim({ betId })
+        .accounts({ user: loser.publicKey })
+        .signers([loser])
+        .rpc();
+      expect.fail("losing entry should not be paid");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotWinnerSide");
+    }
+  });
+});
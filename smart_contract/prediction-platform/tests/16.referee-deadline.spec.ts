@@ -0,0 +1,192 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe("Referee Deadline Escalation", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+
+  const DEPOSIT = 100_000_000;
+
+  const createEscalatingPool = async (refereeIn: number, refundIn: number) => {
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createPool({
+        title: "Escalating Pool",
+        description: "Referee, then council, then refunds",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        refereeDeadline: new anchor.BN(now + refereeIn),
+        refundDeadline: new anchor.BN(now + refundIn),
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Escalating Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    return betId;
+  };
+
+  const ownerSetWinner = (betId: anchor.BN) =>
+    program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: (provider.wallet as any).publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await sleep(1000);
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("rejects deadlines that are out of order", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods
+        .createPool({
+          title: "Bad Deadlines",
+          description: "Refund before referee deadline",
+          endTimestamp: new anchor.BN(-1),
+          referee: referee.publicKey,
+          refereeDeadline: new anchor.BN(now + 100),
+          refundDeadline: new anchor.BN(now + 50),
+        } as any)
+        .accounts({
+          creator: creator.publicKey,
+          titleIndex: titleIndexPDA(
+            program.programId,
+            creator.publicKey,
+            "Bad Deadlines"
+          ),
+        })
+        .signers([creator])
+        .rpc();
+      expect.fail("refund deadline must come after the referee deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidResolutionDeadlines");
+    }
+  });
+
+  it("lets the owner resolve only after the referee deadline", async () => {
+    const betId = await createEscalatingPool(3, 3600);
+    try {
+      await ownerSetWinner(betId);
+      expect.fail("owner should wait for the referee deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RefereeWindowActive");
+    }
+
+    await sleep(4000);
+    await ownerSetWinner(betId);
+
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.complete).to.equal(true);
+    // The referee let the deadline pass, so the council's resolution is a no-show
+    expect(pool.refereeNoShow).to.equal(true);
+  });
+
+  it("refunds every deposit once the refund deadline passes", async () => {
+    const betId = await createEscalatingPool(2, 4);
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    try {
+      await program.methods
+        .triggerRefundMode({ betId })
+        .accounts({ caller: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("refund mode should wait for the refund deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RefundDeadlineNotReached");
+    }
+
+    await sleep(5000);
+    try {
+      await ownerSetWinner(betId);
+      expect.fail("resolution window should be closed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResolutionWindowClosed");
+    }
+
+    await program.methods
+      .triggerRefundMode({ betId })
+      .accounts({ caller: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    const before = await provider.connection.getBalance(user.publicKey);
+    await program.methods
+      .claimRefund({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    const after = await provider.connection.getBalance(user.publicKey);
+    // Full deposit back, less the transaction fee
+    expect(after - before).to.be.approximately(DEPOSIT, 10_000);
+  });
+});
@@ -0,0 +1,135 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Vault Migration", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let poolStatePDA: web3.PublicKey;
+  let poolVaultPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const DEPOSIT = 200_000_000;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [poolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    [poolVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool-vault"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        title: "Migrating Pool",
+        description: "Funds live in a per-pool vault",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Migrating Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+  });
+
+  it("rejects migration by anyone but the owner", async () => {
+    try {
+      await program.methods
+        .migrateVault({ betId })
+        .accounts({ owner: creator.publicKey } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("only the owner may migrate vaults");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("keeps new pools' deposits in their own vault", async () => {
+    const pool = await program.account.poolState.fetch(poolStatePDA);
+    expect(pool.ownVault).to.equal(true);
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(0);
+    expect(await provider.connection.getBalance(poolVaultPDA)).to.equal(
+      DEPOSIT + rent
+    );
+
+    // Nothing of this pool sits in the shared vault, so there is nothing to move
+    try {
+      await program.methods.migrateVault({ betId }).rpc();
+      expect.fail("pool already has its own vault");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("VaultMigrated");
+    }
+
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(1_000_000),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    expect(await provider.connection.getBalance(poolVaultPDA)).to.equal(
+      DEPOSIT + 1_000_000 + rent
+    );
+  });
+});
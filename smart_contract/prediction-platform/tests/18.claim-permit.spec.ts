@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Claim With Permit", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let winner: web3.Keypair;
+  let loser: web3.Keypair;
+  let relayer: web3.Keypair;
+  let recipient: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  // PERMIT_DOMAIN || program_id || bet_id_le || recipient || expires_at_le
+  const permitMessage = (recipientKey: web3.PublicKey, expiresAt: anchor.BN) =>
+    Buffer.concat([
+      Buffer.from("delphi-claim-permit"),
+      program.programId.toBuffer(),
+      betId.toArrayLike(Buffer, "le", 8),
+      recipientKey.toBuffer(),
+      expiresAt.toTwos(64).toArrayLike(Buffer, "le", 8),
+    ]);
+
+  const claimWithPermit = (
+    signer: web3.Keypair,
+    message: Buffer,
+    expiresAt: anchor.BN
+  ) =>
+    program.methods
+      .claimWithPermit({ betId, expiresAt })
+      .accounts({
+        relayer: relayer.publicKey,
+        user: winner.publicKey,
+        recipient: recipient.publicKey,
+      } as any)
+      .preInstructions([
+        web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: signer.secretKey,
+          message,
+        }),
+      ])
+      .signers([relayer])
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    winner = web3.Keypair.generate();
+    loser = web3.Keypair.generate();
+    relayer = web3.Keypair.generate();
+    recipient = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, winner, loser, relayer, recipient]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    await program.methods
+      .createPool({
+        title: "Permit Pool",
+        description: "Winner claims without paying fees",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Permit Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const [kp, isYes] of [
+      [winner, true],
+      [loser, false],
+    ] as [web3.Keypair, boolean][]) {
+      await program.methods
+        .createEntry({ betId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+      await program.methods
+        .deposit({
+          betId,
+          isYes,
+          amount: new anchor.BN(500_000_000),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+  });
+
+  it("rejects a permit signed by someone other than the winner", async () => {
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 600);
+    try {
+      await claimWithPermit(
+        relayer,
+        permitMessage(recipient.publicKey, expiresAt),
+        expiresAt
+      );
+      expect.fail("permit from the wrong key should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidPermit");
+    }
+  });
+
+  it("rejects an expired permit", async () => {
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) - 600);
+    try {
+      await claimWithPermit(
+        winner,
+        permitMessage(recipient.publicKey, expiresAt),
+        expiresAt
+      );
+      expect.fail("expired permit should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PermitExpired");
+    }
+  });
+
+  it("pays the signed recipient while the relayer covers fees", async () => {
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 600);
+    const winnerBefore = await provider.connection.getBalance(winner.publicKey);
+    const recipientBefore = await provider.connection.getBalance(
+      recipient.publicKey
+    );
+
+    await claimWithPermit(
+      winner,
+      permitMessage(recipient.publicKey, expiresAt),
+      expiresAt
+    );
+
+    expect(await provider.connection.getBalance(winner.publicKey)).to.equal(
+      winnerBefore
+    );
+    const recipientAfter = await provider.connection.getBalance(
+      recipient.publicKey
+    );
+    expect(recipientAfter - recipientBefore).to.be.greaterThan(500_000_000);
+  });
+});
@@ -0,0 +1,176 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Deposit Insurance", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let winner: web3.Keypair;
+  let loser: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let poolStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const DEPOSIT = 500_000_000;
+  const COVERAGE_BPS = 5000;
+
+  const entryPDA = (user: web3.PublicKey) =>
+    web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("entry"), poolStatePDA.toBuffer(), user.toBuffer()],
+      program.programId
+    )[0];
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    winner = web3.Keypair.generate();
+    loser = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, winner, loser]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [poolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .createPool({
+        title: "Insured Pool",
+        description: "Losers can buy back half their stake",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Insured Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const kp of [winner, loser]) {
+      await program.methods
+        .createEntry({ betId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+  });
+
+  it("charges a premium on top of an insured deposit", async () => {
+    const before = await provider.connection.getBalance(solVaultPDA);
+    await program.methods
+      .deposit({
+        betId,
+        isYes: false,
+        amount: new anchor.BN(DEPOSIT),
+        insure: true,
+        minTokenAmount: new anchor.BN(0),
+      } as any)
+      .accounts({ user: loser.publicKey })
+      .signers([loser])
+      .rpc();
+
+    const entry = await program.account.entryState.fetch(
+      entryPDA(loser.publicKey)
+    );
+    // Empty pool: even odds, so the premium is half the coverage
+    const premium = (DEPOSIT * COVERAGE_BPS) / 10000 / 2;
+    expect(entry.insuredAmount.toNumber()).to.equal(DEPOSIT);
+    expect(entry.insurancePremiumPaid.toNumber()).to.equal(premium);
+    expect((await provider.connection.getBalance(solVaultPDA)) - before).to.equal(
+      DEPOSIT + premium
+    );
+  });
+
+  it("pays losing insured entries from the premium sub-pool", async () => {
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: winner.publicKey })
+      .signers([winner])
+      .rpc();
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    try {
+      await program.methods
+        .claimInsurance({ betId })
+        .accounts({ user: winner.publicKey })
+        .signers([winner])
+        .rpc();
+      expect.fail("uninsured entry should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotInsured");
+    }
+
+    const pool = await program.account.poolState.fetch(poolStatePDA);
+    const coverage = (DEPOSIT * COVERAGE_BPS) / 10000;
+    // The only insured loser shares the whole sub-pool
+    const expected = Math.min(coverage, pool.insurancePremiums.toNumber());
+
+    const before = await provider.connection.getBalance(loser.publicKey);
+    await program.methods
+      .claimInsurance({ betId })
+      .accounts({ user: loser.publicKey })
+      .signers([loser])
+      .rpc();
+    const after = await provider.connection.getBalance(loser.publicKey);
+    expect(after - before).to.be.approximately(expected, 10_000);
+
+    try {
+      await program.methods
+        .claimInsurance({ betId })
+        .accounts({ user: loser.publicKey })
+        .signers([loser])
+        .rpc();
+      expect.fail("insurance can only be claimed once");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyClaimed");
+    }
+  });
+});
@@ -2,6 +2,8 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { createHash } from "crypto";
+import { titleIndexPDA } from "./helpers";
 
 describe("Create Pool", () => {
   let program: Program<BettingProgram>;
@@ -61,6 +63,11 @@ describe("Create Pool", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -108,6 +115,7 @@ describe("Create Pool", () => {
     const firstPoint = historyState.points[0];
     expect(firstPoint.yesReserve.toNumber()).to.equal(0);
     expect(firstPoint.noReserve.toNumber()).to.equal(0);
+    expect(firstPoint.kind).to.equal(0); // snapshot
     // Timestamp should be reasonable
     expect(firstPoint.timestamp.toNumber()).to.be.at.least(createdLowerBound);
   });
@@ -133,6 +141,11 @@ describe("Create Pool", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Second Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -227,4 +240,203 @@ describe("Create Pool", () => {
         program.removeEventListener(listener);
         expect(eventEmitted).to.be.true;
     });*/
+  it("derives a deterministic share uuid from program id and bet id", async () => {
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    const currentBetId = mainState.currentBetId;
+
+    const [poolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), currentBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        title: "Share Link Pool",
+        description: "Share link can be derived off-chain",
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Share Link Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    // share_uuid = base58(sha256(program_id || bet_id_le))[..12]
+    const digest = createHash("sha256")
+      .update(
+        Buffer.concat([
+          program.programId.toBuffer(),
+          currentBetId.toArrayLike(Buffer, "le", 8),
+        ])
+      )
+      .digest();
+    const expected = anchor.utils.bytes.bs58.encode(digest).slice(0, 12);
+
+    const poolState = await program.account.poolState.fetch(poolStatePDA);
+    expect(poolState.shareUuid).to.equal(expected);
+  });
+  it("rejects a duplicate title among the creator's active pools", async () => {
+    const title = "Duplicate Title Pool";
+    const createPool = () =>
+      program.methods
+        .createPool({
+          title,
+          description: "Only one active pool per creator and title",
+          endTimestamp: new anchor.BN(-1),
+          referee: referee.publicKey,
+        } as any)
+        .accounts({
+          creator: creator.publicKey,
+          titleIndex: titleIndexPDA(
+            program.programId,
+            creator.publicKey,
+            title
+          ),
+        })
+        .signers([creator])
+        .rpc();
+
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await createPool();
+    try {
+      await createPool();
+      expect.fail("duplicate title should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DuplicateMarket");
+    }
+
+    // Resolution closes the index, so the title can be reused
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: (provider.wallet as any).publicKey,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .rpc();
+    await createPool();
+  });
+
+  it("records rent per pool and returns it when history closes", async () => {
+    const title = "Rent Telemetry Pool";
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await program.methods
+      .createPool({
+        title,
+        description: "Rent is attributed to the creator",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+
+    const seed = betId.toArrayLike(Buffer, "le", 8);
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), seed],
+      program.programId
+    );
+    const [historyPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("history"), seed],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.rentPayer.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(pool.poolRentLamports.toNumber()).to.equal(
+      await provider.connection.getBalance(poolPDA)
+    );
+    const historyRent = await provider.connection.getBalance(historyPDA);
+    expect(pool.historyRentLamports.toNumber()).to.equal(historyRent);
+
+    try {
+      await program.methods.closeHistory({ betId }).rpc();
+      expect.fail("history of an open pool must stay");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BetNotComplete");
+    }
+
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: (provider.wallet as any).publicKey,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .rpc();
+
+    // Resolution is marked on the chart before the history can be closed
+    const { points } = await program.account.poolHistoryState.fetch(
+      historyPDA
+    );
+    expect(points[points.length - 1].kind).to.equal(3); // KIND_RESOLUTION
+
+    const before = await provider.connection.getBalance(creator.publicKey);
+    await program.methods.closeHistory({ betId }).rpc();
+    const after = await provider.connection.getBalance(creator.publicKey);
+    expect(after - before).to.equal(historyRent);
+
+    const closed = await program.account.poolState.fetch(poolPDA);
+    expect(closed.rentReclaimedLamports.toNumber()).to.equal(historyRent);
+  });
+
+  it("bounds the creator's claim deadline override", async () => {
+    const { claimWindow } = await program.account.mainState.fetch(
+      mainStatePDA
+    );
+    const create = (title: string, claimDeadlineOverride: anchor.BN | null) =>
+      program.methods
+        .createPool({
+          title,
+          description: "Claim window picked by the creator",
+          endTimestamp: new anchor.BN(-1),
+          referee: referee.publicKey,
+          claimDeadlineOverride,
+        } as any)
+        .accounts({
+          creator: creator.publicKey,
+          titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+        })
+        .signers([creator])
+        .rpc();
+
+    try {
+      await create("Endless Claims", claimWindow.maxSecs.addn(1));
+      expect.fail("window above MainState's maximum");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidClaimWindow");
+    }
+
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await create("Friends Claims", claimWindow.minSecs);
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.claimWindowSecs.toNumber()).to.equal(
+      claimWindow.minSecs.toNumber()
+    );
+
+    const defaultBetId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await create("Default Claims", null);
+    const [defaultPoolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), defaultBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const defaultPool = await program.account.poolState.fetch(defaultPoolPDA);
+    expect(defaultPool.claimWindowSecs.toNumber()).to.equal(
+      claimWindow.defaultSecs.toNumber()
+    );
+  });
 });
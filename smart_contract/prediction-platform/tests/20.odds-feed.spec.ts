@@ -0,0 +1,105 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Odds Feed", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let oddsFeedPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [oddsFeedPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("odds-feed"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        title: "Feed Pool",
+        description: "Odds pushed to a feed account",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Feed Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(1_000_000_000),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+  });
+
+  it("publishes implied probabilities in oracle feed format", async () => {
+    await program.methods.publishOddsFeed({ betId }).rpc();
+
+    const feed = await program.account.oddsFeed.fetch(oddsFeedPDA);
+    expect(feed.expo).to.equal(-9);
+    expect(feed.status).to.equal(1); // trading
+    // 1 SOL on YES against 1 SOL of virtual reserve per side: 2/3 YES
+    expect(feed.yesPrice.toNumber()).to.equal(666_666_666);
+    expect(feed.noPrice.toNumber()).to.equal(333_333_333);
+    expect(feed.publishTime.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("rate-limits repeated publishes", async () => {
+    try {
+      await program.methods.publishOddsFeed({ betId }).rpc();
+      expect.fail("second publish should be rate limited");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("OddsFeedRateLimited");
+    }
+  });
+});
@@ -0,0 +1,128 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+// Requires a program built with `--features test-utils`
+describe("Mock Clock", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const setMockClock = (offsetSecs: number) =>
+    (program.methods as any)
+      .setMockClock({ offsetSecs: new anchor.BN(offsetSecs) })
+      .rpc();
+
+  before(async function () {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    if (!program.idl.instructions.some((ix) => ix.name === "setMockClock")) {
+      this.skip();
+    }
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    await program.methods
+      .createPool({
+        title: "Clock Pool",
+        description: "Ends in a day",
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 86_400),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Clock Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+  });
+
+  after(async () => {
+    if (program.idl.instructions.some((ix) => ix.name === "setMockClock")) {
+      await setMockClock(0);
+    }
+  });
+
+  it("only lets the owner move the clock", async () => {
+    try {
+      await (program.methods as any)
+        .setMockClock({ offsetSecs: new anchor.BN(1) })
+        .accounts({ owner: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect.fail("non-owner should not move the clock");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("closes deposits once the mocked time passes the end", async () => {
+    await setMockClock(2 * 86_400);
+    try {
+      await program.methods
+        .deposit({
+          betId,
+          isYes: true,
+          amount: new anchor.BN(1_000_000),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("deposit after the mocked end should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BetEnded");
+    }
+
+    await setMockClock(0);
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(1_000_000),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+  });
+});
@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Optimistic Oracle", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let asserter: web3.Keypair;
+  let disputer: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+
+  const BOND = 100_000_000; // ASSERTION_BOND
+  const LIVENESS = 2 * 60 * 60; // ASSERTION_LIVENESS_SECS
+
+  const createPool = async (title: string, optimisticResolution: boolean) => {
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await program.methods
+      .createPool({
+        title,
+        description: "Resolved by bonded assertions",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        optimisticResolution,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+    return betId;
+  };
+
+  const assertOutcome = (betId: anchor.BN, isYes: boolean) =>
+    program.methods
+      .assertOutcome({ betId, isYes })
+      .accounts({ asserter: asserter.publicKey })
+      .signers([asserter])
+      .rpc();
+
+  const settle = (betId: anchor.BN, withDisputer = false) =>
+    program.methods
+      .settleAssertion({ betId })
+      .accounts({
+        caller: (provider.wallet as any).publicKey,
+        disputer: withDisputer ? disputer.publicKey : null,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    asserter = web3.Keypair.generate();
+    disputer = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, asserter, disputer]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("rejects assertions on pools without optimistic resolution", async () => {
+    const betId = await createPool("Referee Only Pool", false);
+    try {
+      await assertOutcome(betId, true);
+      expect.fail("pool does not accept assertions");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal(
+        "OptimisticResolutionDisabled"
+      );
+    }
+  });
+
+  it("pays both bonds to the disputer when the referee disagrees", async () => {
+    const betId = await createPool("Disputed Pool", true);
+    await assertOutcome(betId, true);
+
+    try {
+      await settle(betId);
+      expect.fail("assertion is still within its liveness window");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AssertionLivenessActive");
+    }
+
+    try {
+      await program.methods
+        .disputeAssertion({ betId })
+        .accounts({ disputer: asserter.publicKey })
+        .signers([asserter])
+        .rpc();
+      expect.fail("asserter cannot dispute their own assertion");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDisputer");
+    }
+
+    await program.methods
+      .disputeAssertion({ betId })
+      .accounts({ disputer: disputer.publicKey })
+      .signers([disputer])
+      .rpc();
+
+    try {
+      await settle(betId, true);
+      expect.fail("disputed assertion waits for the referee");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AssertionAwaitingResolution");
+    }
+
+    await program.methods
+      .setWinner({ betId, isYes: false })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    const before = await provider.connection.getBalance(disputer.publicKey);
+    await settle(betId, true);
+    const after = await provider.connection.getBalance(disputer.publicKey);
+    expect(after - before).to.equal(2 * BOND);
+  });
+
+  it("resolves the pool once an undisputed assertion outlives its window", async function () {
+    if (!program.idl.instructions.some((ix) => ix.name === "setMockClock")) {
+      this.skip();
+    }
+    const betId = await createPool("Undisputed Pool", true);
+    await assertOutcome(betId, false);
+
+    await (program.methods as any)
+      .setMockClock({ offsetSecs: new anchor.BN(LIVENESS + 1) })
+      .rpc();
+    try {
+      await settle(betId);
+    } finally {
+      await (program.methods as any)
+        .setMockClock({ offsetSecs: new anchor.BN(0) })
+        .rpc();
+    }
+
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.complete).to.equal(true);
+    expect(pool.winner).to.deep.equal({ no: {} });
+  });
+});
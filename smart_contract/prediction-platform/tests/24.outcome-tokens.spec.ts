@@ -0,0 +1,197 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program, utils } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { createAtaIx, splTransferIx, titleIndexPDA } from "./helpers";
+
+describe("Outcome Tokens", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let winner: web3.Keypair;
+  let loser: web3.Keypair;
+  let buyer: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let betId: anchor.BN;
+  let yesMint: web3.PublicKey;
+
+  const DEPOSIT = 500_000_000;
+
+  const outcomeMint = (isYes: boolean) =>
+    web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("outcome-mint"),
+        betId.toArrayLike(Buffer, "le", 8),
+        Buffer.from([isYes ? 1 : 0]),
+      ],
+      program.programId
+    )[0];
+
+  const claimWithTokens = (holder: web3.Keypair, amount: anchor.BN) =>
+    program.methods
+      .claimWithTokens({ betId, isYes: true, amount })
+      .accounts({
+        holder: holder.publicKey,
+        holderTokenAccount: utils.token.associatedAddress({
+          mint: yesMint,
+          owner: holder.publicKey,
+        }),
+      } as any)
+      .signers([holder])
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    winner = web3.Keypair.generate();
+    loser = web3.Keypair.generate();
+    buyer = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, winner, loser, buyer]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        5 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    await program.methods
+      .createPool({
+        title: "Outcome Token Pool",
+        description: "Positions can change hands",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        outcomeTokens: true,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Outcome Token Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    yesMint = outcomeMint(true);
+
+    for (const [kp, isYes] of [
+      [winner, true],
+      [loser, false],
+    ] as [web3.Keypair, boolean][]) {
+      await program.methods
+        .createEntry({ betId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+      await program.methods
+        .deposit({
+          betId,
+          isYes,
+          amount: new anchor.BN(DEPOSIT),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+  });
+
+  it("mints the entry's position to the holder and empties the entry", async () => {
+    await program.methods
+      .tokenizeEntry({ betId })
+      .accounts({ user: winner.publicKey })
+      .signers([winner])
+      .rpc();
+
+    const ata = utils.token.associatedAddress({
+      mint: yesMint,
+      owner: winner.publicKey,
+    });
+    const balance = await provider.connection.getTokenAccountBalance(ata);
+    expect(Number(balance.value.amount)).to.be.greaterThan(0);
+
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.tokenizedYesSupply.toString()).to.equal(balance.value.amount);
+    expect(pool.tokenizedYesPrincipal.toNumber()).to.equal(DEPOSIT);
+  });
+
+  it("pays whoever burns the winning tokens", async () => {
+    const winnerAta = utils.token.associatedAddress({
+      mint: yesMint,
+      owner: winner.publicKey,
+    });
+    const buyerAta = utils.token.associatedAddress({
+      mint: yesMint,
+      owner: buyer.publicKey,
+    });
+    const amount = Number(
+      (await provider.connection.getTokenAccountBalance(winnerAta)).value
+        .amount
+    );
+    await provider.sendAndConfirm(
+      new web3.Transaction().add(
+        createAtaIx(buyer.publicKey, buyer.publicKey, yesMint),
+        splTransferIx(winnerAta, buyerAta, winner.publicKey, amount)
+      ),
+      [buyer, winner]
+    );
+
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    // The entry no longer holds the position
+    try {
+      await program.methods
+        .claim({ betId })
+        .accounts({ user: winner.publicKey })
+        .signers([winner])
+        .rpc();
+      expect.fail("tokenized entry has nothing left to claim");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("WrongBet");
+    }
+
+    const before = await provider.connection.getBalance(buyer.publicKey);
+    await claimWithTokens(buyer, new anchor.BN(amount));
+    const after = await provider.connection.getBalance(buyer.publicKey);
+    // Principal plus a share of the losing side, minus fees
+    expect(after - before).to.be.greaterThan(DEPOSIT);
+
+    const supply = await provider.connection.getTokenSupply(yesMint);
+    expect(supply.value.amount).to.equal("0");
+  });
+});
@@ -0,0 +1,183 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe("Notification Hooks", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let stranger: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let betId: anchor.BN;
+  let subscribersPDA: web3.PublicKey;
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+    stranger = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, user, stranger]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await sleep(1000);
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [subscribersPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("subscribers"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .createPool({
+        title: "Notified Pool",
+        description: "Subscribers hear when it locks and resolves",
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 3),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Notified Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("only lets users with notification prefs subscribe", async () => {
+    try {
+      await program.methods
+        .subscribePool({ betId })
+        .accounts({ user: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      expect.fail("subscribing requires notification prefs");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+
+    await program.methods
+      .setNotificationPrefs({
+        endpointHash: Array.from(Buffer.alloc(32, 7)),
+        endpointUri: "ipfs://notification-settings",
+        notifyOnLock: true,
+        notifyOnResolution: true,
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .subscribePool({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    const list = await program.account.poolSubscribers.fetch(subscribersPDA);
+    expect(list.subscribers.map((key) => key.toBase58())).to.deep.equal([
+      user.publicKey.toBase58(),
+    ]);
+
+    try {
+      await program.methods
+        .subscribePool({ betId })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("user is already subscribed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadySubscribed");
+    }
+  });
+
+  it("announces the lock once and the resolution", async () => {
+    const notifyLocked = () =>
+      program.methods.notifyPoolLocked({ betId }).rpc();
+
+    try {
+      await notifyLocked();
+      expect.fail("pool has not locked yet");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BetNotEnded");
+    }
+
+    await sleep(4000);
+    const notified: [number, string][] = [];
+    const listener = program.addEventListener(
+      "poolNotificationEvent",
+      (event) => {
+        if (event.betId.eq(betId)) {
+          notified.push([event.kind, event.subscribers.toBase58()]);
+        }
+      }
+    );
+
+    await notifyLocked();
+    try {
+      await notifyLocked();
+      expect.fail("lock was already announced");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("LockAlreadyNotified");
+    }
+
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    await sleep(1000);
+    await program.removeEventListener(listener);
+    expect(notified).to.deep.equal([
+      [1, subscribersPDA.toBase58()],
+      [2, subscribersPDA.toBase58()],
+    ]);
+  });
+
+  it("refunds the prefs rent on opt-out", async () => {
+    const [prefsPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("notification-prefs"), user.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .closeNotificationPrefs()
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(prefsPDA)).to.be.null;
+  });
+});
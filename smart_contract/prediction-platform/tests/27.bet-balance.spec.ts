@@ -0,0 +1,133 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Bet Balance", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let betBalancePDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const FUNDED = 300_000_000;
+  const BET = 100_000_000;
+
+  const depositFromBalance = (amount: number) =>
+    program.methods
+      .depositFromBalance({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(amount),
+        minTokenAmount: new anchor.BN(0),
+      } as any)
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [betBalancePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bet-balance"), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    await program.methods
+      .createPool({
+        title: "Balance Pool",
+        description: "Bets paid from a pre-funded balance",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Balance Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+  });
+
+  it("bets from the escrowed balance", async () => {
+    await program.methods
+      .fundBetBalance({ amount: new anchor.BN(FUNDED) })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    await depositFromBalance(BET);
+    await depositFromBalance(BET);
+
+    const balance = await program.account.betBalance.fetch(betBalancePDA);
+    expect(balance.balance.toNumber()).to.equal(FUNDED - 2 * BET);
+    expect(balance.totalBet.toNumber()).to.equal(2 * BET);
+
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.yesReserve.toNumber()).to.equal(2 * BET);
+  });
+
+  it("rejects bets above the balance", async () => {
+    try {
+      await depositFromBalance(2 * BET);
+      expect.fail("balance only holds one more bet");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InsufficientBetBalance");
+    }
+  });
+
+  it("withdraws what is left", async () => {
+    const before = await provider.connection.getBalance(user.publicKey);
+    await program.methods
+      .withdrawBetBalance({ amount: new anchor.BN(FUNDED - 2 * BET) })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    const after = await provider.connection.getBalance(user.publicKey);
+    expect(after - before).to.be.approximately(FUNDED - 2 * BET, 10_000);
+
+    const balance = await program.account.betBalance.fetch(betBalancePDA);
+    expect(balance.balance.toNumber()).to.equal(0);
+  });
+});
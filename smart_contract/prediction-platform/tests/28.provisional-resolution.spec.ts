@@ -0,0 +1,230 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe("Provisional Resolution", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+
+  const WINDOW = 2;
+  const DEPOSIT = 100_000_000;
+
+  const createPool = async (title: string) => {
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await program.methods
+      .createPool({
+        title,
+        description: "Resolved in two phases",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        provisionalWindowSecs: new anchor.BN(WINDOW),
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    return betId;
+  };
+
+  const setProvisional = (betId: anchor.BN) =>
+    program.methods
+      .setProvisionalWinner({ betId, isYes: true, value: null })
+      .accounts({ referee: referee.publicKey })
+      .signers([referee])
+      .rpc();
+
+  const finalize = (betId: anchor.BN, title: string) =>
+    program.methods
+      .finalizeResolution({ betId, isYes: null })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await sleep(1000);
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("locks trading and finalizes after the window", async () => {
+    const title = "Provisional Pool";
+    const betId = await createPool(title);
+
+    try {
+      await program.methods
+        .setWinner({ betId, isYes: true })
+        .accounts({
+          referee: referee.publicKey,
+          solVault: solVaultPDA,
+          platformOwner: (provider.wallet as any).publicKey,
+        } as any)
+        .signers([referee])
+        .rpc();
+      expect.fail("pool resolves in two phases");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal(
+        "ProvisionalResolutionRequired"
+      );
+    }
+
+    await setProvisional(betId);
+    try {
+      await program.methods
+        .deposit({
+          betId,
+          isYes: false,
+          amount: new anchor.BN(DEPOSIT),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("trading is locked once an outcome is proposed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProvisionalOutcomePending");
+    }
+
+    try {
+      await finalize(betId, title);
+      expect.fail("confirmation window is still open");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConfirmationWindowActive");
+    }
+
+    await sleep((WINDOW + 1) * 1000);
+    await finalize(betId, title);
+
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.complete).to.equal(true);
+    expect(pool.winner).to.deep.equal({ yes: {} });
+  });
+
+  it("leaves disputed outcomes to the owner", async () => {
+    const title = "Disputed Pool";
+    const betId = await createPool(title);
+    await setProvisional(betId);
+
+    const [disputePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("resolution-dispute"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const balanceBeforeDispute = await provider.connection.getBalance(
+      user.publicKey
+    );
+    await program.methods
+      .disputeProvisionalWinner({ betId })
+      .accounts({ disputer: user.publicKey })
+      .signers([user])
+      .rpc();
+    const dispute = await program.account.resolutionDispute.fetch(disputePDA);
+    expect(dispute.disputer.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(dispute.isYes).to.equal(true);
+
+    await sleep((WINDOW + 1) * 1000);
+    try {
+      await program.methods
+        .finalizeResolution({ betId, isYes: true })
+        .accounts({
+          caller: user.publicKey,
+          creator: creator.publicKey,
+          titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+          platformOwner: (provider.wallet as any).publicKey,
+        } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("disputed outcomes are not finalized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProvisionalOutcomeDisputed");
+    }
+    try {
+      await finalize(betId, title);
+      expect.fail("the owner has to rule");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DisputeVerdictRequired");
+    }
+
+    // The owner overturns the provisional YES, so the bond goes back to the disputer
+    await program.methods
+      .finalizeResolution({ betId, isYes: false })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+        platformOwner: (provider.wallet as any).publicKey,
+        resolutionDispute: disputePDA,
+        disputer: user.publicKey,
+      } as any)
+      .rpc();
+
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.winner).to.deep.equal({ no: {} });
+    expect(await provider.connection.getAccountInfo(disputePDA)).to.equal(null);
+    // Only the dispute's transaction fee is gone
+    const balanceAfter = await provider.connection.getBalance(user.publicKey);
+    expect(balanceBeforeDispute - balanceAfter).to.be.lessThan(10_000);
+  });
+});
@@ -0,0 +1,150 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+
+describe("Outcome Market", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let users: web3.Keypair[];
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+  let marketPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const DEPOSIT = 200_000_000;
+
+  const depositOutcome = (user: web3.Keypair, outcome: number) =>
+    program.methods
+      .depositOutcome({ betId, outcome, amount: new anchor.BN(DEPOSIT) })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    users = [0, 1, 2].map(() => web3.Keypair.generate());
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, ...users]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("rejects markets with a single outcome", async () => {
+    try {
+      await program.methods
+        .createOutcomeMarket({
+          title: "One Horse Race",
+          endTimestamp: new anchor.BN(-1),
+          referee: referee.publicKey,
+          outcomes: ["Alpha"],
+        })
+        .accounts({ creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect.fail("a market needs at least two outcomes");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidOutcomeCount");
+    }
+  });
+
+  it("pays the winning outcome from every losing reserve", async () => {
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [marketPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("outcome-market"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .createOutcomeMarket({
+        title: "Three Horse Race",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        outcomes: ["Alpha", "Bravo", "Charlie"],
+      })
+      .accounts({ creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    for (let i = 0; i < users.length; i++) {
+      await depositOutcome(users[i], i);
+    }
+    const market = await program.account.outcomeMarket.fetch(marketPDA);
+    expect(market.reserves.map((r) => r.toNumber())).to.deep.equal([
+      DEPOSIT,
+      DEPOSIT,
+      DEPOSIT,
+    ]);
+
+    try {
+      await program.methods
+        .setOutcomeWinner({ betId, winner: 3 })
+        .accounts({
+          referee: referee.publicKey,
+          creator: creator.publicKey,
+          platformOwner: (provider.wallet as any).publicKey,
+        } as any)
+        .signers([referee])
+        .rpc();
+      expect.fail("there is no fourth outcome");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidOutcomeIndex");
+    }
+
+    await program.methods
+      .setOutcomeWinner({ betId, winner: 1 })
+      .accounts({
+        referee: referee.publicKey,
+        creator: creator.publicKey,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    const before = await provider.connection.getBalance(users[1].publicKey);
+    await program.methods
+      .claimOutcome({ betId })
+      .accounts({ user: users[1].publicKey })
+      .signers([users[1]])
+      .rpc();
+    const after = await provider.connection.getBalance(users[1].publicKey);
+    // Principal plus most of the two losing reserves
+    expect(after - before).to.be.greaterThan(2.5 * DEPOSIT);
+
+    try {
+      await program.methods
+        .claimOutcome({ betId })
+        .accounts({ user: users[0].publicKey })
+        .signers([users[0]])
+        .rpc();
+      expect.fail("losing outcome should not be paid");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotWinnerSide");
+    }
+  });
+});
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
 
 describe("Create Entry", () => {
   let program: Program<BettingProgram>;
@@ -67,6 +68,11 @@ describe("Create Entry", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -167,6 +173,11 @@ describe("Create Entry", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Past Pool"
+        ),
       })
       .signers([creator])
       .rpc();
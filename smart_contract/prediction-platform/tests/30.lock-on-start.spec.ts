@@ -0,0 +1,162 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Lock On Start", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let oracle: web3.Keypair;
+  let user: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let poolPDA: web3.PublicKey;
+  let startFeedPDA: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const FEED_ID = new anchor.BN(7);
+
+  const publishStart = (startedAt: number) =>
+    program.methods
+      .publishEventStart({
+        feedId: FEED_ID,
+        startedAt: new anchor.BN(startedAt),
+      })
+      .accounts({ authority: oracle.publicKey })
+      .signers([oracle])
+      .rpc();
+
+  const crank = () =>
+    program.methods
+      .crankLockOnStart({ betId })
+      .accounts({ caller: user.publicKey, startFeed: startFeedPDA })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    oracle = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [startFeedPDA] = web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("start-feed"),
+        oracle.publicKey.toBuffer(),
+        FEED_ID.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, oracle, user]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    await publishStart(0);
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .createPool({
+        title: "Kickoff Pool",
+        description: "Locks when the match starts",
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        referee: referee.publicKey,
+        startFeed: startFeedPDA,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Kickoff Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+  });
+
+  it("waits for the feed to report the start", async () => {
+    try {
+      await crank();
+      expect.fail("event has not started");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("EventNotStarted");
+    }
+  });
+
+  it("locks trading once the event starts", async () => {
+    await publishStart(Math.floor(Date.now() / 1000) - 5);
+    await crank();
+
+    const pool = await program.account.poolState.fetch(poolPDA);
+    expect(pool.lockedOnStart).to.equal(true);
+
+    try {
+      await program.methods
+        .deposit({
+          betId,
+          isYes: true,
+          amount: new anchor.BN(100_000_000),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("trading is locked");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BetEnded");
+    }
+
+    try {
+      await program.methods
+        .updatePool({
+          betId,
+          endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          referee: null,
+          title: null,
+          description: null,
+        })
+        .accounts({ updater: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect.fail("the end time is fixed once locked");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PoolLockedOnStart");
+    }
+
+    try {
+      await crank();
+      expect.fail("pool is already locked");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PoolAlreadyLocked");
+    }
+  });
+});
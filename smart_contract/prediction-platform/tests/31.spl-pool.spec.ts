@@ -0,0 +1,196 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program, utils } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import {
+  createAtaIx,
+  createMintIxs,
+  mintToIx,
+  titleIndexPDA,
+} from "./helpers";
+
+describe("SPL Pool", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let winner: web3.Keypair;
+  let loser: web3.Keypair;
+  let mint: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let poolPDA: web3.PublicKey;
+  let vault: web3.PublicKey;
+  let betId: anchor.BN;
+
+  const DEPOSIT = 50_000_000; // 50 USDC
+  const ata = (owner: web3.PublicKey) =>
+    utils.token.associatedAddress({ mint: mint.publicKey, owner });
+  const tokenBalance = async (account: web3.PublicKey) =>
+    Number(
+      (await provider.connection.getTokenAccountBalance(account)).value.amount
+    );
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+    const payer = (provider.wallet as any).publicKey;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    winner = web3.Keypair.generate();
+    loser = web3.Keypair.generate();
+    mint = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, winner, loser]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+
+    const rent =
+      await provider.connection.getMinimumBalanceForRentExemption(82);
+    const setup = new web3.Transaction().add(
+      ...createMintIxs(payer, mint.publicKey, payer, 6, rent)
+    );
+    for (const kp of [winner, loser, creator]) {
+      setup.add(createAtaIx(payer, kp.publicKey, mint.publicKey));
+    }
+    setup.add(createAtaIx(payer, payer, mint.publicKey));
+    for (const kp of [winner, loser]) {
+      setup.add(mintToIx(mint.publicKey, ata(kp.publicKey), payer, DEPOSIT));
+    }
+    await provider.sendAndConfirm(setup, [mint]);
+
+    betId = (await program.account.mainState.fetch(mainStatePDA)).currentBetId;
+    [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    vault = ata(poolPDA);
+    await program.methods
+      .createPoolSpl({
+        title: "USDC Pool",
+        description: "Denominated in an SPL mint",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        tokenMint: mint.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "USDC Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("takes deposits in the pool's mint only", async () => {
+    for (const [kp, isYes] of [
+      [winner, true],
+      [loser, false],
+    ] as [web3.Keypair, boolean][]) {
+      await program.methods
+        .createEntry({ betId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+      await program.methods
+        .depositSpl({
+          betId,
+          isYes,
+          amount: new anchor.BN(DEPOSIT),
+          minTokenAmount: new anchor.BN(0),
+        } as any)
+        .accounts({
+          user: kp.publicKey,
+          userTokenAccount: ata(kp.publicKey),
+        })
+        .signers([kp])
+        .rpc();
+    }
+    expect(await tokenBalance(vault)).to.equal(2 * DEPOSIT);
+
+    try {
+      await program.methods
+        .deposit({
+          betId,
+          isYes: true,
+          amount: new anchor.BN(DEPOSIT),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: winner.publicKey })
+        .signers([winner])
+        .rpc();
+      expect.fail("SOL deposits into an SPL pool must fail");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("SplPoolRequiresTokenPath");
+    }
+  });
+
+  it("pays winners and fees from the token vault", async () => {
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("sol-vault")],
+          program.programId
+        )[0],
+        platformOwner: (provider.wallet as any).publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "USDC Pool"
+        ),
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    await program.methods
+      .claimSpl({ betId })
+      .accounts({
+        user: winner.publicKey,
+        userTokenAccount: ata(winner.publicKey),
+      })
+      .signers([winner])
+      .rpc();
+    const payout = await tokenBalance(ata(winner.publicKey));
+    expect(payout).to.be.greaterThan(DEPOSIT);
+
+    await program.methods
+      .claimCreatorFeeSpl({ betId })
+      .accounts({
+        claimer: creator.publicKey,
+        destination: ata(creator.publicKey),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .claimPlatformFeeSpl({ betId })
+      .accounts({
+        claimer: (provider.wallet as any).publicKey,
+        destination: ata((provider.wallet as any).publicKey),
+      })
+      .rpc();
+
+    // Everything but rounding dust has left the vault
+    expect(await tokenBalance(vault)).to.be.lessThan(10);
+  });
+});
@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Void Pool", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let user: web3.Keypair;
+  let stranger: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+
+  const DEPOSIT = 200_000_000;
+  const VOID_GRACE = 14 * 86_400; // VOID_GRACE_SECS
+
+  const createPool = async (title: string, endTimestamp: number) => {
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await program.methods
+      .createPool({
+        title,
+        description: "Nobody resolves this one",
+        endTimestamp: new anchor.BN(endTimestamp),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+    return betId;
+  };
+
+  const voidPool = (betId: anchor.BN, caller?: web3.Keypair) =>
+    caller
+      ? program.methods
+          .voidPool({ betId })
+          .accounts({ caller: caller.publicKey })
+          .signers([caller])
+          .rpc()
+      : program.methods.voidPool({ betId }).rpc();
+
+  const poolPDA = (betId: anchor.BN) =>
+    web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), betId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    user = web3.Keypair.generate();
+    stranger = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, user, stranger]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("lets the owner void a pool and refunds each deposit", async () => {
+    const betId = await createPool(
+      "Owner Voided Pool",
+      Math.floor(Date.now() / 1000) + 3600
+    );
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    try {
+      await voidPool(betId, stranger);
+      expect.fail("only the owner may void before the grace period");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("VoidGraceNotElapsed");
+    }
+
+    await voidPool(betId);
+    const pool = await program.account.poolState.fetch(poolPDA(betId));
+    expect(pool.refundMode).to.equal(true);
+    expect(pool.voidedAt.toNumber()).to.be.greaterThan(0);
+    expect(pool.resolvedTimestamp.toNumber()).to.equal(pool.voidedAt.toNumber());
+
+    try {
+      await voidPool(betId);
+      expect.fail("pool is already voided");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyResolved");
+    }
+
+    const before = await provider.connection.getBalance(user.publicKey);
+    await program.methods
+      .claimRefund({ betId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+    const after = await provider.connection.getBalance(user.publicKey);
+    // Full deposit back, less the transaction fee
+    expect(after - before).to.be.approximately(DEPOSIT, 10_000);
+  });
+
+  it("keeps open-ended pools owner-only", async () => {
+    const betId = await createPool("Open Ended Void Pool", -1);
+    try {
+      await voidPool(betId, stranger);
+      expect.fail("open-ended pools have no end to count the grace from");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("VoidGraceNotElapsed");
+    }
+  });
+
+  it("lets anyone void once the grace period has passed", async function () {
+    if (!program.idl.instructions.some((ix) => ix.name === "setMockClock")) {
+      this.skip();
+    }
+    const betId = await createPool(
+      "Abandoned Pool",
+      Math.floor(Date.now() / 1000) + 60
+    );
+
+    await (program.methods as any)
+      .setMockClock({ offsetSecs: new anchor.BN(VOID_GRACE + 120) })
+      .rpc();
+    try {
+      await voidPool(betId, stranger);
+    } finally {
+      await (program.methods as any)
+        .setMockClock({ offsetSecs: new anchor.BN(0) })
+        .rpc();
+    }
+
+    const pool = await program.account.poolState.fetch(poolPDA(betId));
+    expect(pool.refundMode).to.equal(true);
+  });
+});
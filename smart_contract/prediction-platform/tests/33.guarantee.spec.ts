@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, Program } from "@coral-xyz/anchor";
+import { BettingProgram } from "../target/types/betting_program";
+import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+
+describe("Platform Guarantee", () => {
+  let program: Program<BettingProgram>;
+  let provider: anchor.AnchorProvider;
+  let creator: web3.Keypair;
+  let referee: web3.Keypair;
+  let winner: web3.Keypair;
+  let mainStatePDA: web3.PublicKey;
+  let solVaultPDA: web3.PublicKey;
+
+  const GUARANTEE = 300_000_000;
+  const DEPOSIT = 100_000_000;
+
+  const createPool = async (title: string) => {
+    const betId = (await program.account.mainState.fetch(mainStatePDA))
+      .currentBetId;
+    await program.methods
+      .createPool({
+        title,
+        description: "Flagship market backed by the platform",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+    const seed = betId.toArrayLike(Buffer, "le", 8);
+    const [poolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), seed],
+      program.programId
+    );
+    const [guaranteePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("guarantee"), seed],
+      program.programId
+    );
+    return { betId, poolPDA, guaranteePDA };
+  };
+
+  before(async () => {
+    provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    program = anchor.workspace.BettingProgram as Program<BettingProgram>;
+
+    creator = web3.Keypair.generate();
+    referee = web3.Keypair.generate();
+    winner = web3.Keypair.generate();
+
+    [mainStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("main")],
+      program.programId
+    );
+    [solVaultPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol-vault")],
+      program.programId
+    );
+
+    for (const kp of [creator, referee, winner]) {
+      await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await program.methods.initMainState().rpc();
+    } catch (error) {
+      // Main state might already be initialized
+    }
+  });
+
+  it("flags a pool once the owner escrows a guarantee", async () => {
+    const { betId, poolPDA, guaranteePDA } = await createPool(
+      "Guaranteed Pool"
+    );
+
+    try {
+      await program.methods
+        .guaranteePool({ betId, amount: new anchor.BN(GUARANTEE) })
+        .accounts({ owner: creator.publicKey } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("only the owner may guarantee a pool");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await program.methods
+      .guaranteePool({ betId, amount: new anchor.BN(GUARANTEE) })
+      .rpc();
+    expect((await program.account.poolState.fetch(poolPDA)).guaranteed).to.equal(
+      true
+    );
+    const guarantee = await program.account.poolGuarantee.fetch(guaranteePDA);
+    expect(guarantee.balance.toNumber()).to.equal(GUARANTEE);
+
+    try {
+      await program.methods.releaseGuarantee({ betId }).rpc();
+      expect.fail("guarantee backs the pool until claims close");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("GuaranteeLocked");
+    }
+
+    // Winners still claim through the vault while it can pay them
+    await program.methods
+      .createEntry({ betId })
+      .accounts({ user: winner.publicKey })
+      .signers([winner])
+      .rpc();
+    await program.methods
+      .deposit({
+        betId,
+        isYes: true,
+        amount: new anchor.BN(DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
+      })
+      .accounts({ user: winner.publicKey })
+      .signers([winner])
+      .rpc();
+    await program.methods
+      .setWinner({ betId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+    await program.methods
+      .claim({ betId })
+      .accountsPartial({ user: winner.publicKey, guarantee: guaranteePDA })
+      .signers([winner])
+      .rpc();
+    const after = await program.account.poolGuarantee.fetch(guaranteePDA);
+    expect(after.balance.toNumber()).to.equal(GUARANTEE);
+    expect(after.totalDrawn.toNumber()).to.equal(0);
+  });
+
+  it("returns the guarantee of a voided pool", async () => {
+    const { betId, poolPDA, guaranteePDA } = await createPool(
+      "Voided Guaranteed Pool"
+    );
+    await program.methods
+      .guaranteePool({ betId, amount: new anchor.BN(GUARANTEE) })
+      .rpc();
+    await program.methods.voidPool({ betId }).rpc();
+
+    const owner = (provider.wallet as any).publicKey;
+    const before = await provider.connection.getBalance(owner);
+    await program.methods.releaseGuarantee({ betId }).rpc();
+    const after = await provider.connection.getBalance(owner);
+    expect(after - before).to.be.approximately(GUARANTEE, 10_000);
+
+    expect((await program.account.poolState.fetch(poolPDA)).guaranteed).to.equal(
+      false
+    );
+    const guarantee = await program.account.poolGuarantee.fetch(guaranteePDA);
+    expect(guarantee.balance.toNumber()).to.equal(0);
+  });
+});
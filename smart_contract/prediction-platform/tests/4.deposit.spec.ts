@@ -2,6 +2,8 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
+import { createHash } from "crypto";
 
 describe("Deposit", () => {
   let program: Program<BettingProgram>;
@@ -82,6 +84,11 @@ describe("Deposit", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -125,6 +132,7 @@ describe("Deposit", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(depositAmount),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: user.publicKey,
@@ -204,6 +212,7 @@ describe("Deposit", () => {
         betId: new anchor.BN(betId),
         isYes: false,
         amount: new anchor.BN(depositAmount),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: user2.publicKey,
@@ -268,6 +277,7 @@ describe("Deposit", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(secondDeposit),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: user.publicKey,
@@ -337,6 +347,7 @@ describe("Deposit", () => {
           betId: new anchor.BN(betId),
           isYes: true,
           amount: new anchor.BN(zeroAmount),
+          minTokenAmount: new anchor.BN(0),
         })
         .accounts({
           user: user3.publicKey,
@@ -358,6 +369,7 @@ describe("Deposit", () => {
           betId: new anchor.BN(betId),
           isYes: false, // Trying to switch from YES to NO
           amount: new anchor.BN(2000000),
+          minTokenAmount: new anchor.BN(0),
         })
         .accounts({
           user: user.publicKey,
@@ -387,6 +399,11 @@ describe("Deposit", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Past Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -444,6 +461,7 @@ describe("Deposit", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(depositAmount),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: user4.publicKey,
@@ -457,4 +475,157 @@ describe("Deposit", () => {
     program.removeEventListener(listener);
     expect(eventEmitted).to.be.true;
   });
+
+  it("should enforce a quoted odds snapshot when one is given", async () => {
+    // sha256(yes || no) over reserves rounded down to STATE_HASH_PRECISION lamports
+    const STATE_HASH_PRECISION = 1_000_000;
+    const stateHash = (yesReserve: anchor.BN, noReserve: anchor.BN) =>
+      Array.from(
+        createHash("sha256")
+          .update(
+            Buffer.concat([
+              yesReserve
+                .divn(STATE_HASH_PRECISION)
+                .toArrayLike(Buffer, "le", 8),
+              noReserve.divn(STATE_HASH_PRECISION).toArrayLike(Buffer, "le", 8),
+            ])
+          )
+          .digest()
+      );
+
+    const pool = await program.account.poolState.fetch(poolStatePDA);
+    const quoted = stateHash(pool.yesReserve, pool.noReserve);
+
+    await program.methods
+      .deposit({
+        betId: new anchor.BN(betId),
+        isYes: true,
+        amount: new anchor.BN(1_000_000),
+        expectedStateHash: quoted,
+        minTokenAmount: new anchor.BN(0),
+      } as any)
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    // Odds moved by a full 0.001 SOL since the quote
+    try {
+      await program.methods
+        .deposit({
+          betId: new anchor.BN(betId),
+          isYes: true,
+          amount: new anchor.BN(1_000_000),
+          expectedStateHash: quoted,
+          minTokenAmount: new anchor.BN(0),
+        } as any)
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("stale snapshot should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("StateHashMismatch");
+    }
+  });
+
+  it("should reject deposits minting fewer tokens than the minimum", async () => {
+    try {
+      await program.methods
+        .deposit({
+          betId: new anchor.BN(betId),
+          isYes: true,
+          amount: new anchor.BN(1_000_000),
+          // No 0.001 SOL deposit mints u64::MAX tokens
+          minTokenAmount: new anchor.BN("18446744073709551615"),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+      expect.fail("deposit below the minimum token amount should be rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("SlippageExceeded");
+    }
+  });
+
+  it("should cap the number of deposits per entry", async () => {
+    const cappedBetId = (
+      await program.account.mainState.fetch(mainStatePDA)
+    ).currentBetId;
+    const [cappedPoolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), cappedBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [cappedEntryPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("entry"), cappedPoolPDA.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        title: "Capped Pool",
+        description: "Two deposits per entry",
+        maxDepositsPerEntry: 2,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Capped Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createEntry({ betId: cappedBetId })
+      .accounts({ user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    const deposit = () =>
+      program.methods
+        .deposit({
+          betId: cappedBetId,
+          isYes: true,
+          amount: new anchor.BN(1_000_000),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: user.publicKey })
+        .signers([user])
+        .rpc();
+
+    await deposit();
+    await deposit();
+    const entry = await program.account.entryState.fetch(cappedEntryPDA);
+    expect(entry.depositCount).to.equal(2);
+    expect(entry.depositedSolAmount.toNumber()).to.equal(2_000_000);
+
+    try {
+      await deposit();
+      expect.fail("third deposit should exceed the cap");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TooManyDeposits");
+    }
+  });
+
+  it("should keep risk indicators on the pool summary", async () => {
+    const [summaryPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("summary"), new anchor.BN(betId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const summary = await program.account.poolSummary.fetch(summaryPDA);
+    const pool = await program.account.poolState.fetch(poolStatePDA);
+
+    // Every deposit into this pool went through the summary
+    expect(summary.totalDeposited.toNumber()).to.equal(
+      pool.yesReserve.add(pool.noReserve).toNumber()
+    );
+    expect(summary.depositorCount).to.be.greaterThan(1);
+    expect(summary.herfindahlBps.toNumber()).to.be.within(1, 10000);
+    expect(summary.topDepositorBps.toNumber()).to.be.within(
+      summary.herfindahlBps.toNumber(),
+      10000
+    );
+  });
 });
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
 
 describe("Set Winner", () => {
   let program: Program<BettingProgram>;
@@ -72,6 +73,11 @@ describe("Set Winner", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -97,7 +103,23 @@ describe("Set Winner", () => {
     // Verify the winner was set
     const poolState = await program.account.poolState.fetch(poolStatePDA);
     expect(poolState.complete).to.be.true;
-    expect(poolState.winner).to.equal("yes");
+    expect(poolState.winner).to.deep.equal({ yes: {} });
+
+    // The settlement report freezes the final figures next to the pool
+    const [reportPDA] = web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("settlement"),
+        new anchor.BN(betId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const report = await program.account.settlementReport.fetch(reportPDA);
+    expect(report.pool.toBase58()).to.equal(poolStatePDA.toBase58());
+    expect(report.winnerIsYes).to.be.true;
+    expect(report.resolvedBy.toBase58()).to.equal(referee.publicKey.toBase58());
+    expect(report.resolvedAt.toNumber()).to.equal(
+      poolState.resolvedTimestamp.toNumber()
+    );
   });
 
   it("should create another pool and set winner to NO", async () => {
@@ -124,6 +146,11 @@ describe("Set Winner", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool NO"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -147,7 +174,7 @@ describe("Set Winner", () => {
     // Verify the winner was set to NO
     const poolState = await program.account.poolState.fetch(newPoolStatePDA);
     expect(poolState.complete).to.be.true;
-    expect(poolState.winner).to.equal("no");
+    expect(poolState.winner).to.deep.equal({ no: {} });
   });
 
   it("should allow owner to set winner", async () => {
@@ -174,6 +201,11 @@ describe("Set Winner", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool Owner"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -196,7 +228,7 @@ describe("Set Winner", () => {
 
     const poolState = await program.account.poolState.fetch(newPoolStatePDA);
     expect(poolState.complete).to.be.true;
-    expect(poolState.winner).to.equal("yes");
+    expect(poolState.winner).to.deep.equal({ yes: {} });
   });
 
   it("should fail when called by unauthorized user", async () => {
@@ -215,6 +247,11 @@ describe("Set Winner", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool Unauthorized"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -258,6 +295,11 @@ describe("Set Winner", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Future Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -306,7 +348,7 @@ describe("Set Winner", () => {
 
       expect.fail("Should have failed because bet is already complete");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("BetComplete");
+      expect(error.error.errorCode.code).to.include("AlreadyResolved");
     }
   });
 
@@ -326,6 +368,11 @@ describe("Set Winner", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Event Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -334,7 +381,7 @@ describe("Set Winner", () => {
     const listener = program.addEventListener("completeEvent", (event) => {
       expect(event.referee.toString()).to.equal(referee.publicKey.toString());
       expect(event.betId.toNumber()).to.equal(newBetId);
-      expect(event.winner).to.equal("no");
+      expect(event.winner).to.deep.equal({ no: {} });
       eventEmitted = true;
     });
 
@@ -387,4 +434,294 @@ describe("Set Winner", () => {
       expect(error).to.exist;
     }
   });
+
+  it("should resolve several pools in one batch", async () => {
+    const betIds: anchor.BN[] = [];
+    const titles = ["Bracket Match 1", "Bracket Match 2"];
+    for (const title of titles) {
+      const mainState = await program.account.mainState.fetch(mainStatePDA);
+      betIds.push(mainState.currentBetId);
+      await program.methods
+        .createPool({
+          endTimestamp: new anchor.BN(-1),
+          referee: referee.publicKey,
+          title,
+          description: "Resolved together with the rest of the round",
+        })
+        .accounts({
+          creator: creator.publicKey,
+          titleIndex: titleIndexPDA(
+            program.programId,
+            creator.publicKey,
+            title
+          ),
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    const [refereeProfilePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("referee-profile"), referee.publicKey.toBuffer()],
+      program.programId
+    );
+    const pda = (seed: string, id: anchor.BN) =>
+      web3.PublicKey.findProgramAddressSync(
+        [Buffer.from(seed), id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    await program.methods
+      .setWinnerMany({
+        resolutions: [
+          { betId: betIds[0], isYes: true },
+          { betId: betIds[1], isYes: false },
+        ],
+      })
+      .accounts({
+        referee: referee.publicKey,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .remainingAccounts(
+        betIds.flatMap((id, i) => [
+          { pubkey: pda("pool", id), isWritable: true, isSigner: false },
+          { pubkey: pda("outcome", id), isWritable: true, isSigner: false },
+          { pubkey: pda("settlement", id), isWritable: true, isSigner: false },
+          { pubkey: refereeProfilePDA, isWritable: true, isSigner: false },
+          {
+            pubkey: titleIndexPDA(
+              program.programId,
+              creator.publicKey,
+              titles[i]
+            ),
+            isWritable: true,
+            isSigner: false,
+          },
+          { pubkey: creator.publicKey, isWritable: true, isSigner: false },
+          { pubkey: pda("pool-vault", id), isWritable: true, isSigner: false },
+        ])
+      )
+      .signers([referee])
+      .rpc();
+
+    const first = await program.account.poolState.fetch(pda("pool", betIds[0]));
+    const second = await program.account.poolState.fetch(pda("pool", betIds[1]));
+    expect(first.complete).to.be.true;
+    expect(first.winner).to.deep.equal({ yes: {} });
+    expect(second.complete).to.be.true;
+    expect(second.winner).to.deep.equal({ no: {} });
+
+    const oracle = await program.account.outcomeOracle.fetch(
+      pda("outcome", betIds[1])
+    );
+    expect(oracle.betId.toNumber()).to.equal(betIds[1].toNumber());
+    const report = await program.account.settlementReport.fetch(
+      pda("settlement", betIds[1])
+    );
+    expect(report.winnerIsYes).to.equal(false);
+
+    // Both pools share the referee, so one profile records both resolutions
+    const profile = await program.account.refereeProfile.fetch(
+      refereeProfilePDA
+    );
+    expect(profile.referee.toBase58()).to.equal(referee.publicKey.toBase58());
+    expect(profile.resolutions.toNumber()).to.be.greaterThanOrEqual(2);
+  });
+
+  it("should record resolution latency statistics", async () => {
+    const before = await program.account.mainState.fetch(mainStatePDA);
+    const newBetId = before.currentBetId;
+    const [newPoolStatePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), newBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Ended 10 minutes ago
+    await program.methods
+      .createPool({
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) - 600),
+        referee: referee.publicKey,
+        title: "Latency Pool",
+        description: "Resolved well after its end",
+      })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Latency Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .setWinner({ betId: newBetId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("sol-vault")],
+          program.programId
+        )[0],
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    const pool = await program.account.poolState.fetch(newPoolStatePDA);
+    expect(pool.resolutionLatencySecs.toNumber()).to.be.at.least(590);
+
+    const after = await program.account.mainState.fetch(mainStatePDA);
+    expect(after.resolvedPools.toNumber()).to.equal(
+      before.resolvedPools.toNumber() + 1
+    );
+    expect(after.timedResolutions.toNumber()).to.equal(
+      before.timedResolutions.toNumber() + 1
+    );
+    expect(after.avgResolutionLatencySecs.toNumber()).to.equal(
+      Math.floor(
+        after.totalResolutionLatencySecs.toNumber() /
+          after.timedResolutions.toNumber()
+      )
+    );
+  });
+
+  it("resolves a range market from the submitted value", async () => {
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    const rangeBetId = mainState.currentBetId;
+    const title = "How many goals?";
+    // 0-2 goals resolves NO, 3 to 99 resolves YES
+    const rangeBuckets = [
+      { lower: new anchor.BN(0), upper: new anchor.BN(3), isYes: false },
+      { lower: new anchor.BN(3), upper: new anchor.BN(100), isYes: true },
+    ];
+    await program.methods
+      .createPool({
+        title,
+        description: "Total goals scored in the final",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        rangeBuckets,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+
+    const accounts = {
+      referee: referee.publicKey,
+      platformOwner: (provider.wallet as any).publicKey,
+    } as any;
+    try {
+      await program.methods
+        .setWinner({ betId: rangeBetId, isYes: true })
+        .accounts(accounts)
+        .signers([referee])
+        .rpc();
+      expect.fail("range markets resolve by value");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RangeValueRequired");
+    }
+
+    try {
+      await program.methods
+        .setWinnerByValue({ betId: rangeBetId, value: new anchor.BN(150) })
+        .accounts(accounts)
+        .signers([referee])
+        .rpc();
+      expect.fail("value outside every bucket");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ValueOutsideRange");
+    }
+
+    await program.methods
+      .setWinnerByValue({ betId: rangeBetId, value: new anchor.BN(2) })
+      .accounts(accounts)
+      .signers([referee])
+      .rpc();
+
+    const [rangePoolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), rangeBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(rangePoolPDA);
+    expect(pool.winner).to.deep.equal({ no: {} });
+    expect(pool.rangeValue.toNumber()).to.equal(2);
+  });
+
+  it("requires a handed-over referee to accept before resolving", async () => {
+    const newReferee = web3.Keypair.generate();
+    await provider.connection.requestAirdrop(
+      newReferee.publicKey,
+      web3.LAMPORTS_PER_SOL
+    );
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    const handoffBetId = mainState.currentBetId;
+    const title = "Referee Handoff Pool";
+    await program.methods
+      .createPool({
+        title,
+        description: "Referee changes before resolution",
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(program.programId, creator.publicKey, title),
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .updatePool({
+        betId: handoffBetId,
+        endTimestamp: null,
+        referee: newReferee.publicKey,
+        title: null,
+        description: null,
+      })
+      .accounts({ updater: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    const resolve = (signer: web3.Keypair) =>
+      program.methods
+        .setWinner({ betId: handoffBetId, isYes: true })
+        .accounts({
+          referee: signer.publicKey,
+          platformOwner: (provider.wallet as any).publicKey,
+        } as any)
+        .signers([signer])
+        .rpc();
+
+    try {
+      await resolve(newReferee);
+      expect.fail("new referee has not accepted yet");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RefereeNotAccepted");
+    }
+    try {
+      await resolve(referee);
+      expect.fail("previous referee lost resolution power");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await program.methods
+      .acceptRefereeRole({ betId: handoffBetId })
+      .accounts({ referee: newReferee.publicKey })
+      .signers([newReferee])
+      .rpc();
+    await resolve(newReferee);
+
+    const [handoffPoolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), handoffBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pool = await program.account.poolState.fetch(handoffPoolPDA);
+    expect(pool.refereeAccepted).to.equal(true);
+    expect(pool.complete).to.equal(true);
+  });
 });
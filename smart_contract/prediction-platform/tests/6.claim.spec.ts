@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
 
 describe("Claim", () => {
   let program: Program<BettingProgram>;
@@ -85,6 +86,11 @@ describe("Claim", () => {
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Test Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -138,6 +144,7 @@ describe("Claim", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(winnerDeposit),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: winnerUser.publicKey,
@@ -150,6 +157,7 @@ describe("Claim", () => {
         betId: new anchor.BN(betId),
         isYes: false,
         amount: new anchor.BN(loserDeposit),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: loserUser.publicKey,
@@ -188,6 +196,30 @@ describe("Claim", () => {
       .rpc();
   });
 
+  it("should sum claimable amounts across entries", async () => {
+    const claimableTotal = (user: web3.Keypair, entry: web3.PublicKey) =>
+      program.methods
+        .getClaimableTotal()
+        .accounts({ user: user.publicKey })
+        .remainingAccounts([
+          { pubkey: poolStatePDA, isSigner: false, isWritable: false },
+          { pubkey: entry, isSigner: false, isWritable: false },
+        ])
+        .view();
+
+    const check = await program.methods
+      .checkClaim({ betId: new anchor.BN(betId) })
+      .accounts({ user: winnerUser.publicKey })
+      .view();
+    const total = await claimableTotal(winnerUser, winnerEntryStatePDA);
+    expect(total.toNumber()).to.equal(check.amount.toNumber());
+    expect(total.toNumber()).to.be.greaterThan(0);
+
+    // Losing entries count as nothing
+    const loserTotal = await claimableTotal(loserUser, loserEntryStatePDA);
+    expect(loserTotal.toNumber()).to.equal(0);
+  });
+
   it("should allow winner to claim rewards successfully", async () => {
     const winnerBalanceBefore = await provider.connection.getBalance(
       winnerUser.publicKey
@@ -262,7 +294,7 @@ describe("Claim", () => {
 
       expect.fail("Should have failed because user is on losing side");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("WrongBet");
+      expect(error.error.errorCode.code).to.include("NotWinnerSide");
     }
   });
 
@@ -280,10 +312,29 @@ describe("Claim", () => {
 
       expect.fail("Should have failed because already claimed");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("AlreadyClaimed");
+      expect(error.error.errorCode.code).to.include("AlreadyClaimedAt");
     }
   });
 
+  it("should report claim denial reasons without failing", async () => {
+    const errorCode = (name: string) =>
+      program.idl.errors.find((e) => e.name === name)!.code;
+    const check = (user: web3.Keypair) =>
+      program.methods
+        .checkClaim({ betId: new anchor.BN(betId) })
+        .accounts({ user: user.publicKey })
+        .view();
+
+    const claimed = await check(winnerUser);
+    expect(claimed.claimable).to.equal(false);
+    expect(claimed.reasonCode).to.equal(errorCode("AlreadyClaimedAt"));
+    expect(claimed.claimedAt.toNumber()).to.be.greaterThan(0);
+
+    const loser = await check(loserUser);
+    expect(loser.claimable).to.equal(false);
+    expect(loser.reasonCode).to.equal(errorCode("NotWinnerSide"));
+  });
+
   it("should fail when trying to claim from incomplete bet", async () => {
     // Create a new pool that's ended but not completed (no winner set)
     const mainState = await program.account.mainState.fetch(mainStatePDA);
@@ -295,11 +346,16 @@ describe("Claim", () => {
       .createPool({
         endTimestamp: new anchor.BN(futureEndTimestamp),
         referee: referee.publicKey,
-        title: "Test Pool",
+        title: "Incomplete Pool",
         description: "This is a test betting pool",
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Incomplete Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -320,6 +376,7 @@ describe("Claim", () => {
         betId: new anchor.BN(newBetId),
         isYes: true,
         amount: new anchor.BN(2000000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: winnerUser.publicKey,
@@ -356,7 +413,7 @@ describe("Claim", () => {
 
       expect.fail("Should have failed because bet is not complete");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("BetNotComplete");
+      expect(error.error.errorCode.code).to.include("MarketNotResolved");
     }
   });
 
@@ -371,11 +428,16 @@ describe("Claim", () => {
       .createPool({
         endTimestamp: new anchor.BN(futureEndTimestamp),
         referee: referee.publicKey,
-        title: "Test Pool",
+        title: "Active Pool",
         description: "This is a test betting pool",
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Active Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -396,6 +458,7 @@ describe("Claim", () => {
         betId: new anchor.BN(newBetId),
         isYes: true,
         amount: new anchor.BN(2000000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: winnerUser.publicKey,
@@ -416,7 +479,7 @@ describe("Claim", () => {
 
       expect.fail("Should have failed because bet has not ended");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("BetNotEnded");
+      expect(error.error.errorCode.code).to.include("EndTimeNotReached");
     }
   });
 
@@ -439,11 +502,16 @@ describe("Claim", () => {
       .createPool({
         endTimestamp: new anchor.BN(futureEndTimestamp),
         referee: referee.publicKey,
-        title: "Test Pool",
+        title: "NO Winner Pool",
         description: "This is a test betting pool",
       })
       .accounts({
         creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "NO Winner Pool"
+        ),
       })
       .signers([creator])
       .rpc();
@@ -488,6 +556,7 @@ describe("Claim", () => {
         betId: new anchor.BN(newBetId),
         isYes: false, // NO bet
         amount: new anchor.BN(2500000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: noWinner.publicKey,
@@ -500,6 +569,7 @@ describe("Claim", () => {
         betId: new anchor.BN(newBetId),
         isYes: true, // YES bet
         amount: new anchor.BN(1500000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({
         user: yesLoser.publicKey,
@@ -598,7 +668,7 @@ describe("Claim", () => {
 
       expect.fail("YES loser should not be able to claim");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("WrongBet");
+      expect(error.error.errorCode.code).to.include("NotWinnerSide");
     }
   });
 
@@ -644,7 +714,14 @@ describe("Claim", () => {
         title: "Multi Winner Pool",
         description: "Profit split test",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Multi Winner Pool"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -703,6 +780,7 @@ describe("Claim", () => {
         betId: new anchor.BN(multiBetId),
         isYes: true,
         amount: new anchor.BN(earlyYesDeposit),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: earlyYes.publicKey })
       .signers([earlyYes])
@@ -715,6 +793,7 @@ describe("Claim", () => {
         betId: new anchor.BN(multiBetId),
         isYes: false,
         amount: new anchor.BN(noLoserDeposit),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: noLoser.publicKey })
       .signers([noLoser])
@@ -727,6 +806,7 @@ describe("Claim", () => {
         betId: new anchor.BN(multiBetId),
         isYes: true,
         amount: new anchor.BN(lateYesDeposit),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: lateYes.publicKey })
       .signers([lateYes])
@@ -845,7 +925,7 @@ describe("Claim", () => {
         .rpc();
       expect.fail("Loser should not be able to claim in multi-winner test");
     } catch (error: any) {
-      expect(error.error.errorCode.code).to.include("WrongBet");
+      expect(error.error.errorCode.code).to.include("NotWinnerSide");
     }
   });
 });
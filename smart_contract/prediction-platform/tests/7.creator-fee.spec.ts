@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
 
 describe("Creator Fee Rewards", () => {
   let program: Program<BettingProgram>;
@@ -86,7 +87,14 @@ describe("Creator Fee Rewards", () => {
         title: "Creator Fee Test Pool",
         description: "Testing creator fee functionality",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Creator Fee Test Pool"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -109,6 +117,7 @@ describe("Creator Fee Rewards", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(WINNER_DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: winner.publicKey })
       .signers([winner])
@@ -119,6 +128,7 @@ describe("Creator Fee Rewards", () => {
         betId: new anchor.BN(betId),
         isYes: false,
         amount: new anchor.BN(LOSER_DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: loser.publicKey })
       .signers([loser])
@@ -266,7 +276,14 @@ describe("Creator Fee Rewards", () => {
         title: "Incomplete Pool",
         description: "Testing incomplete pool",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Incomplete Pool"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -296,7 +313,14 @@ describe("Creator Fee Rewards", () => {
         title: "Active Pool",
         description: "Testing active pool",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Active Pool"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -346,7 +370,14 @@ describe("Creator Fee Rewards", () => {
         title: "One Sided Pool",
         description: "Testing one sided pool",
       })
-      .accounts({ creator: oneSidedCreator.publicKey })
+      .accounts({
+        creator: oneSidedCreator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          oneSidedCreator.publicKey,
+          "One Sided Pool"
+        ),
+      })
       .signers([oneSidedCreator])
       .rpc();
 
@@ -361,6 +392,7 @@ describe("Creator Fee Rewards", () => {
         betId: new anchor.BN(newBetId),
         isYes: true,
         amount: new anchor.BN(2000000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: oneSidedUser.publicKey })
       .signers([oneSidedUser])
@@ -464,7 +496,14 @@ describe("Creator Fee Rewards", () => {
         title: "Empty Pool",
         description: "Testing empty pool",
       })
-      .accounts({ creator: zeroCreator.publicKey })
+      .accounts({
+        creator: zeroCreator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          zeroCreator.publicKey,
+          "Empty Pool"
+        ),
+      })
       .signers([zeroCreator])
       .rpc();
 
@@ -565,4 +604,138 @@ describe("Creator Fee Rewards", () => {
     const totalPaidOut = actualWinnerPayout + creatorFee + platformFee;
     expect(totalPaidOut).to.be.approximately(totalDeposits, 100000);
   });
+
+  it("should waive the creator fee on the creator's own entry when opted in", async () => {
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    const selfBetId = mainState.currentBetId;
+    const [selfPoolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), selfBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        endTimestamp: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        referee: referee.publicKey,
+        title: "Creator Self-Bet Pool",
+        description: "Creator's own stake is fee-free",
+        waiveCreatorSelfFee: true,
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Creator Self-Bet Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const kp of [creator, winner]) {
+      await program.methods
+        .createEntry({ betId: selfBetId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+      await program.methods
+        .deposit({
+          betId: selfBetId,
+          isYes: true,
+          amount: new anchor.BN(WINNER_DEPOSIT),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+
+    const entryPDA = (user: web3.PublicKey) =>
+      web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("entry"), selfPoolPDA.toBuffer(), user.toBuffer()],
+        program.programId
+      )[0];
+    const creatorEntry = await program.account.entryState.fetch(entryPDA(creator.publicKey));
+    const winnerEntry = await program.account.entryState.fetch(entryPDA(winner.publicKey));
+    expect(creatorEntry.creatorFeeExempt).to.equal(true);
+    expect(creatorEntry.creatorFeePaid.toNumber()).to.equal(0);
+    expect(winnerEntry.creatorFeeExempt).to.equal(false);
+
+    // Only the other bettor's deposit accrues creator fee
+    const pool = await program.account.poolState.fetch(selfPoolPDA);
+    expect(pool.creatorFeeAccrued.toNumber()).to.equal(
+      winnerEntry.creatorFeePaid.toNumber()
+    );
+    expect(pool.creatorFeeAccrued.toNumber()).to.equal(
+      Math.floor((WINNER_DEPOSIT * CREATOR_FEE_PERCENT) / 10000)
+    );
+  });
+
+  it("should donate part of the creator fee to winners at resolution", async () => {
+    const mainState = await program.account.mainState.fetch(mainStatePDA);
+    const donateBetId = mainState.currentBetId;
+    const [donatePoolPDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), donateBetId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createPool({
+        endTimestamp: new anchor.BN(-1),
+        referee: referee.publicKey,
+        title: "Community Pool",
+        description: "Half the creator fee goes to winners",
+        creatorDonationBps: new anchor.BN(5000),
+      } as any)
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Community Pool"
+        ),
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const [kp, isYes, amount] of [
+      [winner, true, WINNER_DEPOSIT],
+      [loser, false, LOSER_DEPOSIT],
+    ] as [web3.Keypair, boolean, number][]) {
+      await program.methods
+        .createEntry({ betId: donateBetId })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+      await program.methods
+        .deposit({
+          betId: donateBetId,
+          isYes,
+          amount: new anchor.BN(amount),
+          minTokenAmount: new anchor.BN(0),
+        })
+        .accounts({ user: kp.publicKey })
+        .signers([kp])
+        .rpc();
+    }
+
+    const accrued = (
+      await program.account.poolState.fetch(donatePoolPDA)
+    ).creatorFeeAccrued.toNumber();
+
+    await program.methods
+      .setWinner({ betId: donateBetId, isYes: true })
+      .accounts({
+        referee: referee.publicKey,
+        solVault: solVaultPDA,
+        platformOwner: (provider.wallet as any).publicKey,
+      } as any)
+      .signers([referee])
+      .rpc();
+
+    const pool = await program.account.poolState.fetch(donatePoolPDA);
+    const donated = Math.floor(accrued / 2);
+    expect(pool.creatorFeeDonated.toNumber()).to.equal(donated);
+    expect(pool.creatorFeeAccrued.toNumber()).to.equal(accrued - donated);
+  });
 });
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
 
 // Tests for open-ended markets (no fixed end time). We use endTimestamp = -1 to denote open-ended.
 describe("Open-Ended Markets", () => {
@@ -71,7 +72,14 @@ describe("Open-Ended Markets", () => {
         title: "Open-Ended Pool",
         description: "Resolves when referee decides",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Open-Ended Pool"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -116,6 +124,7 @@ describe("Open-Ended Markets", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(2_000_000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: yesUser.publicKey })
       .signers([yesUser])
@@ -126,6 +135,7 @@ describe("Open-Ended Markets", () => {
         betId: new anchor.BN(betId),
         isYes: false,
         amount: new anchor.BN(3_000_000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: noUser.publicKey })
       .signers([noUser])
@@ -144,7 +154,7 @@ describe("Open-Ended Markets", () => {
 
     const poolAfter = await program.account.poolState.fetch(poolStatePDA);
     expect(poolAfter.complete).to.be.true;
-    expect(poolAfter.winner).to.equal("no");
+    expect(poolAfter.winner).to.deep.equal({ no: {} });
 
     // After completion, further deposits should fail with BetComplete
     try {
@@ -153,6 +163,7 @@ describe("Open-Ended Markets", () => {
           betId: new anchor.BN(betId),
           isYes: true,
           amount: new anchor.BN(1_000_000),
+          minTokenAmount: new anchor.BN(0),
         })
         .accounts({ user: yesUser.publicKey })
         .signers([yesUser])
@@ -202,7 +213,14 @@ describe("Open-Ended Markets", () => {
         title: "Open-Ended Pool 2",
         description: "No fixed end",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Open-Ended Pool 2"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -236,6 +254,7 @@ describe("Open-Ended Markets", () => {
         betId: new anchor.BN(newBetId),
         isYes: true,
         amount: new anchor.BN(3_000_000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: winUser.publicKey })
       .signers([winUser])
@@ -246,6 +265,7 @@ describe("Open-Ended Markets", () => {
         betId: new anchor.BN(newBetId),
         isYes: false,
         amount: new anchor.BN(2_000_000),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: loseUser.publicKey })
       .signers([loseUser])
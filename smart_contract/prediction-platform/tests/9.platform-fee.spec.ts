@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { web3, Program } from "@coral-xyz/anchor";
 import { BettingProgram } from "../target/types/betting_program";
 import { expect } from "chai";
+import { titleIndexPDA } from "./helpers";
 
 describe("Platform Fee (auto-claimed on resolution)", () => {
   let program: Program<BettingProgram>;
@@ -72,7 +73,14 @@ describe("Platform Fee (auto-claimed on resolution)", () => {
         title: "Platform Fee Test",
         description: "Platform fee claim flow",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Platform Fee Test"
+        ),
+      })
       .signers([creator])
       .rpc();
 
@@ -94,6 +102,7 @@ describe("Platform Fee (auto-claimed on resolution)", () => {
         betId: new anchor.BN(betId),
         isYes: true,
         amount: new anchor.BN(WINNER_DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: yesUser.publicKey })
       .signers([yesUser])
@@ -103,6 +112,7 @@ describe("Platform Fee (auto-claimed on resolution)", () => {
         betId: new anchor.BN(betId),
         isYes: false,
         amount: new anchor.BN(LOSER_DEPOSIT),
+        minTokenAmount: new anchor.BN(0),
       })
       .accounts({ user: noUser.publicKey })
       .signers([noUser])
@@ -230,7 +240,14 @@ describe("Platform Fee (auto-claimed on resolution)", () => {
         title: "Active",
         description: "Active pool",
       })
-      .accounts({ creator: creator.publicKey })
+      .accounts({
+        creator: creator.publicKey,
+        titleIndex: titleIndexPDA(
+          program.programId,
+          creator.publicKey,
+          "Active"
+        ),
+      })
       .signers([creator])
       .rpc();
 
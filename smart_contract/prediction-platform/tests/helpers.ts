@@ -0,0 +1,118 @@
+import { BN, utils, web3 } from "@coral-xyz/anchor";
+import { createHash } from "crypto";
+
+// TitleIndex PDA: ["title-index", creator, sha256(title)]. The hash can't be expressed as an
+// IDL seed, so create_pool callers pass this account explicitly.
+export const titleIndexPDA = (
+  programId: web3.PublicKey,
+  creator: web3.PublicKey,
+  title: string
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("title-index"),
+      creator.toBuffer(),
+      createHash("sha256").update(title).digest(),
+    ],
+    programId
+  )[0];
+
+// SPL Token transfer of `amount` between two token accounts, built by hand so
+// the specs don't need @solana/spl-token
+export const splTransferIx = (
+  source: web3.PublicKey,
+  destination: web3.PublicKey,
+  owner: web3.PublicKey,
+  amount: number
+) => {
+  const data = Buffer.concat([
+    Buffer.from([3]), // Transfer
+    new BN(amount).toArrayLike(Buffer, "le", 8),
+  ]);
+  return new web3.TransactionInstruction({
+    programId: utils.token.TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: source, isSigner: false, isWritable: true },
+      { pubkey: destination, isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: true, isWritable: false },
+    ],
+    data,
+  });
+};
+
+// Idempotent creation of `owner`'s associated token account for `mint`
+export const createAtaIx = (
+  payer: web3.PublicKey,
+  owner: web3.PublicKey,
+  mint: web3.PublicKey
+) =>
+  new web3.TransactionInstruction({
+    programId: utils.token.ASSOCIATED_PROGRAM_ID,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      {
+        pubkey: utils.token.associatedAddress({ mint, owner }),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      {
+        pubkey: web3.SystemProgram.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      {
+        pubkey: utils.token.TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+    ],
+    data: Buffer.from([1]), // CreateIdempotent
+  });
+
+// Instructions creating an SPL mint with `authority` as mint authority (no freeze authority)
+export const createMintIxs = (
+  payer: web3.PublicKey,
+  mint: web3.PublicKey,
+  authority: web3.PublicKey,
+  decimals: number,
+  rentLamports: number
+) => [
+  web3.SystemProgram.createAccount({
+    fromPubkey: payer,
+    newAccountPubkey: mint,
+    lamports: rentLamports,
+    space: 82,
+    programId: utils.token.TOKEN_PROGRAM_ID,
+  }),
+  new web3.TransactionInstruction({
+    programId: utils.token.TOKEN_PROGRAM_ID,
+    keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+    data: Buffer.concat([
+      Buffer.from([20, decimals]), // InitializeMint2
+      authority.toBuffer(),
+      Buffer.from([0]), // no freeze authority
+    ]),
+  }),
+];
+
+// SPL Token MintTo of `amount` into `destination`
+export const mintToIx = (
+  mint: web3.PublicKey,
+  destination: web3.PublicKey,
+  authority: web3.PublicKey,
+  amount: number
+) =>
+  new web3.TransactionInstruction({
+    programId: utils.token.TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: destination, isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.concat([
+      Buffer.from([7]), // MintTo
+      new BN(amount).toArrayLike(Buffer, "le", 8),
+    ]),
+  });